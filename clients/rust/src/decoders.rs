@@ -0,0 +1,47 @@
+use crate::{
+    accounts::{Mining, RewardPool},
+    errors::MplxRewardsError,
+};
+use solana_program::pubkey::Pubkey;
+use std::io::{Error, ErrorKind};
+
+/// The mint is the first field of the token account in both the Token and Token-2022 programs
+const TOKEN_ACCOUNT_MINT_LEN: usize = 32;
+
+/// Decodes the reward pool and the mining taking part in the claim and checks that
+/// the mining, the vault and the destination token account are all bound to the reward mint
+/// of the pool. These are the checks the program makes, so such a claim would be rejected anyway.
+pub fn decode_claim_accounts(
+    reward_pool: &[u8],
+    mining: &[u8],
+    vault: &[u8],
+    destination: &[u8],
+) -> Result<(RewardPool, Mining), Error> {
+    let reward_pool = RewardPool::from_bytes(reward_pool)?;
+    let mining = Mining::from_bytes(mining)?;
+
+    assert_reward_mint(&mining.reward_mint, &reward_pool.reward_mint)?;
+    assert_reward_mint(&token_account_mint(vault)?, &reward_pool.reward_mint)?;
+    assert_reward_mint(&token_account_mint(destination)?, &reward_pool.reward_mint)?;
+
+    Ok((reward_pool, mining))
+}
+
+fn token_account_mint(data: &[u8]) -> Result<Pubkey, Error> {
+    let mint = data
+        .get(..TOKEN_ACCOUNT_MINT_LEN)
+        .ok_or_else(|| Error::from(ErrorKind::UnexpectedEof))?;
+
+    Ok(Pubkey::try_from(mint).expect("slice has the pubkey length"))
+}
+
+fn assert_reward_mint(mint: &Pubkey, reward_mint: &Pubkey) -> Result<(), Error> {
+    if mint != reward_mint {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            MplxRewardsError::InvalidRewardMint,
+        ));
+    }
+
+    Ok(())
+}
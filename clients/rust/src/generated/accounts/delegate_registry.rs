@@ -0,0 +1,57 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DelegateRegistry {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub mining: Pubkey,
+    pub max_stake_from_others: u64,
+    pub bump: u8,
+    pub data: [u8; 7],
+    pub allow_list_len: u8,
+    pub deny_list_len: u8,
+    pub padding: [u8; 6],
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<[serde_with::DisplayFromStr; 16]>")
+    )]
+    pub allow_list: [Pubkey; 16],
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<[serde_with::DisplayFromStr; 16]>")
+    )]
+    pub deny_list: [Pubkey; 16],
+}
+
+impl DelegateRegistry {
+    pub const LEN: usize = 1080;
+
+    #[inline(always)]
+    pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
+        let mut data = data;
+        Self::deserialize(&mut data)
+    }
+}
+
+impl<'a> TryFrom<&solana_program::account_info::AccountInfo<'a>> for DelegateRegistry {
+    type Error = std::io::Error;
+
+    fn try_from(
+        account_info: &solana_program::account_info::AccountInfo<'a>,
+    ) -> Result<Self, Self::Error> {
+        let mut data: &[u8] = &(*account_info.data).borrow();
+        Self::deserialize(&mut data)
+    }
+}
@@ -33,10 +33,12 @@ pub struct Mining {
     pub stake_from_others: u64,
     pub bump: u8,
     pub data: [u8; 7],
+    pub boost: u64,
+    pub boost_expires_at: u64,
 }
 
 impl Mining {
-    pub const LEN: usize = 144;
+    pub const LEN: usize = 160;
 
     #[inline(always)]
     pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
@@ -5,8 +5,14 @@
 //! [https://github.com/metaplex-foundation/kinobi]
 //!
 
+pub(crate) mod r#boost_record;
+pub(crate) mod r#delegate_registry;
 pub(crate) mod r#mining;
+pub(crate) mod r#penalty_history;
 pub(crate) mod r#reward_pool;
 
+pub use self::r#boost_record::*;
+pub use self::r#delegate_registry::*;
 pub use self::r#mining::*;
+pub use self::r#penalty_history::*;
 pub use self::r#reward_pool::*;
@@ -38,10 +38,30 @@ pub struct RewardPool {
     pub tokens_available_for_distribution: u64,
     pub token_account_bump: u8,
     pub data: [u8; 7],
+    pub min_deposit: u64,
+    pub max_weighted_share_per_mining: u64,
+    pub max_total_share: u64,
+    pub epoch_length_secs: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub boost_collection: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub boost_attester: Pubkey,
+    pub sunset_at: u64,
+    pub sunset_grace_period_secs: u64,
+    pub total_rewards_indexed: u64,
+    pub total_rewards_claimed: u64,
+    pub unbonding_period_secs: u64,
+    pub unbonding_multiplier_bps: u64,
 }
 
 impl RewardPool {
-    pub const LEN: usize = 176;
+    pub const LEN: usize = 320;
 
     #[inline(always)]
     pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
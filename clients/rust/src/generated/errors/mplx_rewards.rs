@@ -60,6 +60,144 @@ pub enum MplxRewardsError {
         "Rewards: Penalty is not apliable becase it's bigger than the mining's weighted stake"
     )]
     DecreaseRewardsTooBig,
+    /// 16 (0x10) - Rewards: Invalid penalty kind
+    #[error("Rewards: Invalid penalty kind")]
+    InvalidPenaltyKind,
+    /// 17 (0x11) - Rewards: Penalty record not found
+    #[error("Rewards: Penalty record not found")]
+    PenaltyNotFound,
+    /// 18 (0x12) - Rewards: Penalty has already been restored
+    #[error("Rewards: Penalty has already been restored")]
+    PenaltyAlreadyRestored,
+    /// 19 (0x13) - Rewards: Penalty cannot be restored
+    #[error("Rewards: Penalty cannot be restored")]
+    PenaltyNotRestorable,
+    /// 20 (0x14) - Rewards: No space left for weighted stake modifiers
+    #[error("Rewards: No space left for weighted stake modifiers")]
+    WeightedStakeDiffsAreFull,
+    /// 21 (0x15) - Rewards: Deposit amount is lower than the minimal deposit
+    #[error("Rewards: Deposit amount is lower than the minimal deposit")]
+    DepositIsTooSmall,
+    /// 22 (0x16) - Rewards: Weighted stake limit per mining is exceeded
+    #[error("Rewards: Weighted stake limit per mining is exceeded")]
+    MiningShareLimitExceeded,
+    /// 23 (0x17) - Rewards: Pool's total share limit is exceeded
+    #[error("Rewards: Pool's total share limit is exceeded")]
+    PoolShareLimitExceeded,
+    /// 24 (0x18) - Rewards: Invalid boost
+    #[error("Rewards: Invalid boost")]
+    InvalidBoost,
+    /// 25 (0x19) - Rewards: Invalid boost proof
+    #[error("Rewards: Invalid boost proof")]
+    InvalidBoostProof,
+    /// 26 (0x1A) - Rewards: Boost is already active
+    #[error("Rewards: Boost is already active")]
+    BoostIsAlreadyActive,
+    /// 27 (0x1B) - Rewards: Epoch length cannot be changed
+    #[error("Rewards: Epoch length cannot be changed")]
+    EpochLengthCannotBeChanged,
+    /// 28 (0x1C) - Rewards: Invalid epoch length
+    #[error("Rewards: Invalid epoch length")]
+    InvalidEpochLength,
+    /// 29 (0x1D) - Rewards: Pool still has stakes
+    #[error("Rewards: Pool still has stakes")]
+    PoolHasStakes,
+    /// 30 (0x1E) - Rewards: Pool is sunset
+    #[error("Rewards: Pool is sunset")]
+    PoolIsSunset,
+    /// 31 (0x1F) - Rewards: Sunset grace period has elapsed
+    #[error("Rewards: Sunset grace period has elapsed")]
+    SunsetGracePeriodElapsed,
+    /// 32 (0x20) - Rewards: Account must be migrated to the current layout
+    #[error("Rewards: Account must be migrated to the current layout")]
+    AccountNeedsMigration,
+    /// 33 (0x21) - Rewards: Unsupported account version
+    #[error("Rewards: Unsupported account version")]
+    UnsupportedAccountVersion,
+    /// 34 (0x22) - Rewards: Source and destination minings are the same
+    #[error("Rewards: Source and destination minings are the same")]
+    MiningsAreTheSame,
+    /// 35 (0x23) - Rewards: Delegate is not registered
+    #[error("Rewards: Delegate is not registered")]
+    DelegateIsNotRegistered,
+    /// 36 (0x24) - Rewards: Delegation is not allowed by the delegate
+    #[error("Rewards: Delegation is not allowed by the delegate")]
+    DelegationIsNotAllowed,
+    /// 37 (0x25) - Rewards: Delegate's stake from others limit is exceeded
+    #[error("Rewards: Delegate's stake from others limit is exceeded")]
+    DelegateStakeLimitExceeded,
+    /// 38 (0x26) - Rewards: Delegate list is too long
+    #[error("Rewards: Delegate list is too long")]
+    DelegateListIsTooLong,
+    /// 39 (0x27) - Rewards: Invalid delegate weights
+    #[error("Rewards: Invalid delegate weights")]
+    InvalidDelegateWeights,
+    /// 40 (0x28) - Rewards: Delegate is duplicated
+    #[error("Rewards: Delegate is duplicated")]
+    DelegateIsDuplicated,
+    /// 41 (0x29) - Rewards: Unsupported token program
+    #[error("Rewards: Unsupported token program")]
+    UnsupportedTokenProgram,
+    /// 42 (0x2A) - Rewards: Vault doesn't cover pool's liabilities
+    #[error("Rewards: Vault doesn't cover pool's liabilities")]
+    VaultIsInsolvent,
+    /// 43 (0x2B) - Rewards: Invalid account type
+    #[error("Rewards: Invalid account type")]
+    InvalidAccountType,
+    /// 44 (0x2C) - Rewards: Invalid account length
+    #[error("Rewards: Invalid account length")]
+    InvalidAccountLength,
+    /// 45 (0x2D) - Rewards: Account is not initialized
+    #[error("Rewards: Account is not initialized")]
+    AccountIsNotInitialized,
+    /// 46 (0x2E) - Rewards: Reward mint doesn't match the pool
+    #[error("Rewards: Reward mint doesn't match the pool")]
+    InvalidRewardMint,
+    /// 47 (0x2F) - Rewards: Deposit is already applied
+    #[error("Rewards: Deposit is already applied")]
+    DepositIsAlreadyApplied,
+    /// 48 (0x30) - Rewards: Deposit is not found
+    #[error("Rewards: Deposit is not found")]
+    DepositIsNotFound,
+    /// 49 (0x31) - Rewards: No space left for positions
+    #[error("Rewards: No space left for positions")]
+    PositionsAreFull,
+    /// 50 (0x32) - Rewards: Amount doesn't match the deposit
+    #[error("Rewards: Amount doesn't match the deposit")]
+    InvalidDepositAmount,
+    /// 51 (0x33) - Rewards: Invalid deposit ids
+    #[error("Rewards: Invalid deposit ids")]
+    InvalidDepositIds,
+    /// 52 (0x34) - Rewards: Withdrawal hasn't been requested
+    #[error("Rewards: Withdrawal hasn't been requested")]
+    WithdrawalIsNotRequested,
+    /// 53 (0x35) - Rewards: Unbonding period isn't over yet
+    #[error("Rewards: Unbonding period isn't over yet")]
+    UnbondingIsNotOver,
+    /// 54 (0x36) - Rewards: Invalid unbonding multiplier
+    #[error("Rewards: Invalid unbonding multiplier")]
+    InvalidUnbondingMultiplier,
+    /// 55 (0x37) - Rewards: No space left in the index history
+    #[error("Rewards: No space left in the index history")]
+    IndexHistoryIsFull,
+    /// 56 (0x38) - Rewards: Pool still has unclaimed rewards
+    #[error("Rewards: Pool still has unclaimed rewards")]
+    PoolHasUnclaimedRewards,
+    /// 57 (0x39) - Rewards: Mining has penalty history or delegate registry
+    #[error("Rewards: Mining has penalty history or delegate registry")]
+    MiningHasLinkedAccounts,
+    /// 58 (0x3A) - Rewards: Unsupported mint extension
+    #[error("Rewards: Unsupported mint extension")]
+    UnsupportedMintExtension,
+    /// 59 (0x3B) - Rewards: NFT is already used for an active boost
+    #[error("Rewards: NFT is already used for an active boost")]
+    NftIsAlreadyUsedForBoost,
+    /// 60 (0x3C) - Rewards: Delegates don't match the deposit
+    #[error("Rewards: Delegates don't match the deposit")]
+    DelegatesDontMatchDeposit,
+    /// 61 (0x3D) - Rewards: No space left for delegated stakes
+    #[error("Rewards: No space left for delegated stakes")]
+    DelegatedStakesAreFull,
 }
 
 impl solana_program::program_error::PrintProgramError for MplxRewardsError {
@@ -0,0 +1,743 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use crate::generated::types::BoostProof;
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+
+/// Accounts.
+pub struct ApplyBoost {
+    /// The address of the reward pool
+    pub reward_pool: solana_program::pubkey::Pubkey,
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: solana_program::pubkey::Pubkey,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: solana_program::pubkey::Pubkey,
+    /// NFT token account owned by the mining owner or the attester who signs the boost
+    pub proof: solana_program::pubkey::Pubkey,
+    /// Metadata account of the NFT, required for the collection proof
+    pub proof_metadata: Option<solana_program::pubkey::Pubkey>,
+    /// The address of the boost record of the NFT mint, required for the collection proof
+    pub boost_record: Option<solana_program::pubkey::Pubkey>,
+    /// The address of the payer for the boost record creation, required for the collection proof
+    pub payer: Option<solana_program::pubkey::Pubkey>,
+    /// The system program, required for the collection proof
+    pub system_program: Option<solana_program::pubkey::Pubkey>,
+}
+
+impl ApplyBoost {
+    pub fn instruction(
+        &self,
+        args: ApplyBoostInstructionArgs,
+    ) -> solana_program::instruction::Instruction {
+        self.instruction_with_remaining_accounts(args, &[])
+    }
+    #[allow(clippy::vec_init_then_push)]
+    pub fn instruction_with_remaining_accounts(
+        &self,
+        args: ApplyBoostInstructionArgs,
+        remaining_accounts: &[solana_program::instruction::AccountMeta],
+    ) -> solana_program::instruction::Instruction {
+        let mut accounts = Vec::with_capacity(8 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.reward_pool,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.mining,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.deposit_authority,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.proof, false,
+        ));
+        if let Some(proof_metadata) = self.proof_metadata {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                proof_metadata,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
+        if let Some(boost_record) = self.boost_record {
+            accounts.push(solana_program::instruction::AccountMeta::new(
+                boost_record,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
+        if let Some(payer) = self.payer {
+            accounts.push(solana_program::instruction::AccountMeta::new(payer, true));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
+        if let Some(system_program) = self.system_program {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                system_program,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
+        accounts.extend_from_slice(remaining_accounts);
+        let mut data = ApplyBoostInstructionData::new().try_to_vec().unwrap();
+        let mut args = args.try_to_vec().unwrap();
+        data.append(&mut args);
+
+        solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ApplyBoostInstructionData {
+    discriminator: u8,
+}
+
+impl ApplyBoostInstructionData {
+    pub fn new() -> Self {
+        Self { discriminator: 16 }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApplyBoostInstructionArgs {
+    pub mining_owner: Pubkey,
+    pub boost_bps: u16,
+    pub expires_at: u64,
+    pub proof_kind: BoostProof,
+}
+
+/// Instruction builder for `ApplyBoost`.
+///
+/// ### Accounts:
+///
+///   0. `[writable]` reward_pool
+///   1. `[writable]` mining
+///   2. `[signer]` deposit_authority
+///   3. `[]` proof
+///   4. `[optional]` proof_metadata
+///   5. `[writable, optional]` boost_record
+///   6. `[writable, signer, optional]` payer
+///   7. `[optional]` system_program
+#[derive(Default)]
+pub struct ApplyBoostBuilder {
+    reward_pool: Option<solana_program::pubkey::Pubkey>,
+    mining: Option<solana_program::pubkey::Pubkey>,
+    deposit_authority: Option<solana_program::pubkey::Pubkey>,
+    proof: Option<solana_program::pubkey::Pubkey>,
+    proof_metadata: Option<solana_program::pubkey::Pubkey>,
+    boost_record: Option<solana_program::pubkey::Pubkey>,
+    payer: Option<solana_program::pubkey::Pubkey>,
+    system_program: Option<solana_program::pubkey::Pubkey>,
+    mining_owner: Option<Pubkey>,
+    boost_bps: Option<u16>,
+    expires_at: Option<u64>,
+    proof_kind: Option<BoostProof>,
+    __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
+}
+
+impl ApplyBoostBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The address of the reward pool
+    #[inline(always)]
+    pub fn reward_pool(&mut self, reward_pool: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.reward_pool = Some(reward_pool);
+        self
+    }
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    #[inline(always)]
+    pub fn mining(&mut self, mining: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.mining = Some(mining);
+        self
+    }
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    #[inline(always)]
+    pub fn deposit_authority(
+        &mut self,
+        deposit_authority: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.deposit_authority = Some(deposit_authority);
+        self
+    }
+    /// NFT token account owned by the mining owner or the attester who signs the boost
+    #[inline(always)]
+    pub fn proof(&mut self, proof: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.proof = Some(proof);
+        self
+    }
+    /// `[optional account]`
+    /// Metadata account of the NFT, required for the collection proof
+    #[inline(always)]
+    pub fn proof_metadata(
+        &mut self,
+        proof_metadata: Option<solana_program::pubkey::Pubkey>,
+    ) -> &mut Self {
+        self.proof_metadata = proof_metadata;
+        self
+    }
+    /// `[optional account]`
+    /// The address of the boost record of the NFT mint, required for the collection proof
+    #[inline(always)]
+    pub fn boost_record(
+        &mut self,
+        boost_record: Option<solana_program::pubkey::Pubkey>,
+    ) -> &mut Self {
+        self.boost_record = boost_record;
+        self
+    }
+    /// `[optional account]`
+    /// The address of the payer for the boost record creation, required for the collection proof
+    #[inline(always)]
+    pub fn payer(&mut self, payer: Option<solana_program::pubkey::Pubkey>) -> &mut Self {
+        self.payer = payer;
+        self
+    }
+    /// `[optional account]`
+    /// The system program, required for the collection proof
+    #[inline(always)]
+    pub fn system_program(
+        &mut self,
+        system_program: Option<solana_program::pubkey::Pubkey>,
+    ) -> &mut Self {
+        self.system_program = system_program;
+        self
+    }
+    #[inline(always)]
+    pub fn mining_owner(&mut self, mining_owner: Pubkey) -> &mut Self {
+        self.mining_owner = Some(mining_owner);
+        self
+    }
+    #[inline(always)]
+    pub fn boost_bps(&mut self, boost_bps: u16) -> &mut Self {
+        self.boost_bps = Some(boost_bps);
+        self
+    }
+    #[inline(always)]
+    pub fn expires_at(&mut self, expires_at: u64) -> &mut Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+    #[inline(always)]
+    pub fn proof_kind(&mut self, proof_kind: BoostProof) -> &mut Self {
+        self.proof_kind = Some(proof_kind);
+        self
+    }
+    /// Add an aditional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: solana_program::instruction::AccountMeta,
+    ) -> &mut Self {
+        self.__remaining_accounts.push(account);
+        self
+    }
+    /// Add additional accounts to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[solana_program::instruction::AccountMeta],
+    ) -> &mut Self {
+        self.__remaining_accounts.extend_from_slice(accounts);
+        self
+    }
+    #[allow(clippy::clone_on_copy)]
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = ApplyBoost {
+            reward_pool: self.reward_pool.expect("reward_pool is not set"),
+            mining: self.mining.expect("mining is not set"),
+            deposit_authority: self
+                .deposit_authority
+                .expect("deposit_authority is not set"),
+            proof: self.proof.expect("proof is not set"),
+            proof_metadata: self.proof_metadata,
+            boost_record: self.boost_record,
+            payer: self.payer,
+            system_program: self.system_program,
+        };
+        let args = ApplyBoostInstructionArgs {
+            mining_owner: self.mining_owner.clone().expect("mining_owner is not set"),
+            boost_bps: self.boost_bps.clone().expect("boost_bps is not set"),
+            expires_at: self.expires_at.clone().expect("expires_at is not set"),
+            proof_kind: self.proof_kind.clone().expect("proof_kind is not set"),
+        };
+
+        accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
+    }
+}
+
+/// `apply_boost` CPI accounts.
+pub struct ApplyBoostCpiAccounts<'a, 'b> {
+    /// The address of the reward pool
+    pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// NFT token account owned by the mining owner or the attester who signs the boost
+    pub proof: &'b solana_program::account_info::AccountInfo<'a>,
+    /// Metadata account of the NFT, required for the collection proof
+    pub proof_metadata: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// The address of the boost record of the NFT mint, required for the collection proof
+    pub boost_record: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// The address of the payer for the boost record creation, required for the collection proof
+    pub payer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// The system program, required for the collection proof
+    pub system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+}
+
+/// `apply_boost` CPI instruction.
+pub struct ApplyBoostCpi<'a, 'b> {
+    /// The program to invoke.
+    pub __program: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward pool
+    pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// NFT token account owned by the mining owner or the attester who signs the boost
+    pub proof: &'b solana_program::account_info::AccountInfo<'a>,
+    /// Metadata account of the NFT, required for the collection proof
+    pub proof_metadata: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// The address of the boost record of the NFT mint, required for the collection proof
+    pub boost_record: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// The address of the payer for the boost record creation, required for the collection proof
+    pub payer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// The system program, required for the collection proof
+    pub system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// The arguments for the instruction.
+    pub __args: ApplyBoostInstructionArgs,
+}
+
+impl<'a, 'b> ApplyBoostCpi<'a, 'b> {
+    pub fn new(
+        program: &'b solana_program::account_info::AccountInfo<'a>,
+        accounts: ApplyBoostCpiAccounts<'a, 'b>,
+        args: ApplyBoostInstructionArgs,
+    ) -> Self {
+        Self {
+            __program: program,
+            reward_pool: accounts.reward_pool,
+            mining: accounts.mining,
+            deposit_authority: accounts.deposit_authority,
+            proof: accounts.proof,
+            proof_metadata: accounts.proof_metadata,
+            boost_record: accounts.boost_record,
+            payer: accounts.payer,
+            system_program: accounts.system_program,
+            __args: args,
+        }
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], &[])
+    }
+    #[inline(always)]
+    pub fn invoke_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], remaining_accounts)
+    }
+    #[inline(always)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(signers_seeds, &[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed_with_remaining_accounts(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let mut accounts = Vec::with_capacity(8 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.reward_pool.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.mining.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.deposit_authority.key,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.proof.key,
+            false,
+        ));
+        if let Some(proof_metadata) = self.proof_metadata {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *proof_metadata.key,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
+        if let Some(boost_record) = self.boost_record {
+            accounts.push(solana_program::instruction::AccountMeta::new(
+                *boost_record.key,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
+        if let Some(payer) = self.payer {
+            accounts.push(solana_program::instruction::AccountMeta::new(
+                *payer.key, true,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
+        if let Some(system_program) = self.system_program {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *system_program.key,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
+        remaining_accounts.iter().for_each(|remaining_account| {
+            accounts.push(solana_program::instruction::AccountMeta {
+                pubkey: *remaining_account.0.key,
+                is_signer: remaining_account.1,
+                is_writable: remaining_account.2,
+            })
+        });
+        let mut data = ApplyBoostInstructionData::new().try_to_vec().unwrap();
+        let mut args = self.__args.try_to_vec().unwrap();
+        data.append(&mut args);
+
+        let instruction = solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        };
+        let mut account_infos = Vec::with_capacity(8 + 1 + remaining_accounts.len());
+        account_infos.push(self.__program.clone());
+        account_infos.push(self.reward_pool.clone());
+        account_infos.push(self.mining.clone());
+        account_infos.push(self.deposit_authority.clone());
+        account_infos.push(self.proof.clone());
+        if let Some(proof_metadata) = self.proof_metadata {
+            account_infos.push(proof_metadata.clone());
+        }
+        if let Some(boost_record) = self.boost_record {
+            account_infos.push(boost_record.clone());
+        }
+        if let Some(payer) = self.payer {
+            account_infos.push(payer.clone());
+        }
+        if let Some(system_program) = self.system_program {
+            account_infos.push(system_program.clone());
+        }
+        remaining_accounts
+            .iter()
+            .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
+
+        if signers_seeds.is_empty() {
+            solana_program::program::invoke(&instruction, &account_infos)
+        } else {
+            solana_program::program::invoke_signed(&instruction, &account_infos, signers_seeds)
+        }
+    }
+}
+
+/// Instruction builder for `ApplyBoost` via CPI.
+///
+/// ### Accounts:
+///
+///   0. `[writable]` reward_pool
+///   1. `[writable]` mining
+///   2. `[signer]` deposit_authority
+///   3. `[]` proof
+///   4. `[optional]` proof_metadata
+///   5. `[writable, optional]` boost_record
+///   6. `[writable, signer, optional]` payer
+///   7. `[optional]` system_program
+pub struct ApplyBoostCpiBuilder<'a, 'b> {
+    instruction: Box<ApplyBoostCpiBuilderInstruction<'a, 'b>>,
+}
+
+impl<'a, 'b> ApplyBoostCpiBuilder<'a, 'b> {
+    pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
+        let instruction = Box::new(ApplyBoostCpiBuilderInstruction {
+            __program: program,
+            reward_pool: None,
+            mining: None,
+            deposit_authority: None,
+            proof: None,
+            proof_metadata: None,
+            boost_record: None,
+            payer: None,
+            system_program: None,
+            mining_owner: None,
+            boost_bps: None,
+            expires_at: None,
+            proof_kind: None,
+            __remaining_accounts: Vec::new(),
+        });
+        Self { instruction }
+    }
+    /// The address of the reward pool
+    #[inline(always)]
+    pub fn reward_pool(
+        &mut self,
+        reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.reward_pool = Some(reward_pool);
+        self
+    }
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    #[inline(always)]
+    pub fn mining(
+        &mut self,
+        mining: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.mining = Some(mining);
+        self
+    }
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    #[inline(always)]
+    pub fn deposit_authority(
+        &mut self,
+        deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.deposit_authority = Some(deposit_authority);
+        self
+    }
+    /// NFT token account owned by the mining owner or the attester who signs the boost
+    #[inline(always)]
+    pub fn proof(&mut self, proof: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+        self.instruction.proof = Some(proof);
+        self
+    }
+    /// `[optional account]`
+    /// Metadata account of the NFT, required for the collection proof
+    #[inline(always)]
+    pub fn proof_metadata(
+        &mut self,
+        proof_metadata: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.proof_metadata = proof_metadata;
+        self
+    }
+    /// `[optional account]`
+    /// The address of the boost record of the NFT mint, required for the collection proof
+    #[inline(always)]
+    pub fn boost_record(
+        &mut self,
+        boost_record: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.boost_record = boost_record;
+        self
+    }
+    /// `[optional account]`
+    /// The address of the payer for the boost record creation, required for the collection proof
+    #[inline(always)]
+    pub fn payer(
+        &mut self,
+        payer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.payer = payer;
+        self
+    }
+    /// `[optional account]`
+    /// The system program, required for the collection proof
+    #[inline(always)]
+    pub fn system_program(
+        &mut self,
+        system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.system_program = system_program;
+        self
+    }
+    #[inline(always)]
+    pub fn mining_owner(&mut self, mining_owner: Pubkey) -> &mut Self {
+        self.instruction.mining_owner = Some(mining_owner);
+        self
+    }
+    #[inline(always)]
+    pub fn boost_bps(&mut self, boost_bps: u16) -> &mut Self {
+        self.instruction.boost_bps = Some(boost_bps);
+        self
+    }
+    #[inline(always)]
+    pub fn expires_at(&mut self, expires_at: u64) -> &mut Self {
+        self.instruction.expires_at = Some(expires_at);
+        self
+    }
+    #[inline(always)]
+    pub fn proof_kind(&mut self, proof_kind: BoostProof) -> &mut Self {
+        self.instruction.proof_kind = Some(proof_kind);
+        self
+    }
+    /// Add an additional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: &'b solana_program::account_info::AccountInfo<'a>,
+        is_writable: bool,
+        is_signer: bool,
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .push((account, is_writable, is_signer));
+        self
+    }
+    /// Add additional accounts to the instruction.
+    ///
+    /// Each account is represented by a tuple of the `AccountInfo`, a `bool` indicating whether the account is writable or not,
+    /// and a `bool` indicating whether the account is a signer or not.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .extend_from_slice(accounts);
+        self
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed(&[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let args = ApplyBoostInstructionArgs {
+            mining_owner: self
+                .instruction
+                .mining_owner
+                .clone()
+                .expect("mining_owner is not set"),
+            boost_bps: self
+                .instruction
+                .boost_bps
+                .clone()
+                .expect("boost_bps is not set"),
+            expires_at: self
+                .instruction
+                .expires_at
+                .clone()
+                .expect("expires_at is not set"),
+            proof_kind: self
+                .instruction
+                .proof_kind
+                .clone()
+                .expect("proof_kind is not set"),
+        };
+        let instruction = ApplyBoostCpi {
+            __program: self.instruction.__program,
+
+            reward_pool: self
+                .instruction
+                .reward_pool
+                .expect("reward_pool is not set"),
+
+            mining: self.instruction.mining.expect("mining is not set"),
+
+            deposit_authority: self
+                .instruction
+                .deposit_authority
+                .expect("deposit_authority is not set"),
+
+            proof: self.instruction.proof.expect("proof is not set"),
+
+            proof_metadata: self.instruction.proof_metadata,
+
+            boost_record: self.instruction.boost_record,
+
+            payer: self.instruction.payer,
+
+            system_program: self.instruction.system_program,
+            __args: args,
+        };
+        instruction.invoke_signed_with_remaining_accounts(
+            signers_seeds,
+            &self.instruction.__remaining_accounts,
+        )
+    }
+}
+
+struct ApplyBoostCpiBuilderInstruction<'a, 'b> {
+    __program: &'b solana_program::account_info::AccountInfo<'a>,
+    reward_pool: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    proof: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    proof_metadata: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    boost_record: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    payer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    mining_owner: Option<Pubkey>,
+    boost_bps: Option<u16>,
+    expires_at: Option<u64>,
+    proof_kind: Option<BoostProof>,
+    /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+    __remaining_accounts: Vec<(
+        &'b solana_program::account_info::AccountInfo<'a>,
+        bool,
+        bool,
+    )>,
+}
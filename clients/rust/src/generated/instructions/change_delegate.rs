@@ -23,6 +23,8 @@ pub struct ChangeDelegate {
     pub old_delegate_mining: solana_program::pubkey::Pubkey,
     /// The address of the new delegate mining account
     pub new_delegate_mining: solana_program::pubkey::Pubkey,
+    /// Registry of the new delegate mining, required when the new delegate mining differs from the mining
+    pub new_delegate_registry: Option<solana_program::pubkey::Pubkey>,
 }
 
 impl ChangeDelegate {
@@ -38,7 +40,7 @@ impl ChangeDelegate {
         args: ChangeDelegateInstructionArgs,
         remaining_accounts: &[solana_program::instruction::AccountMeta],
     ) -> solana_program::instruction::Instruction {
-        let mut accounts = Vec::with_capacity(6 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(7 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             self.reward_pool,
             false,
@@ -63,6 +65,17 @@ impl ChangeDelegate {
             self.new_delegate_mining,
             false,
         ));
+        if let Some(new_delegate_registry) = self.new_delegate_registry {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                new_delegate_registry,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         accounts.extend_from_slice(remaining_accounts);
         let mut data = ChangeDelegateInstructionData::new().try_to_vec().unwrap();
         let mut args = args.try_to_vec().unwrap();
@@ -90,8 +103,11 @@ impl ChangeDelegateInstructionData {
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChangeDelegateInstructionArgs {
+    pub deposit_id: u64,
     pub staked_amount: u64,
     pub new_delegate: Pubkey,
+    pub old_delegate_weights: Vec<u16>,
+    pub new_delegate_weights: Vec<u16>,
 }
 
 /// Instruction builder for `ChangeDelegate`.
@@ -104,6 +120,7 @@ pub struct ChangeDelegateInstructionArgs {
 ///   3. `[signer]` mining_owner
 ///   4. `[writable]` old_delegate_mining
 ///   5. `[writable]` new_delegate_mining
+///   6. `[optional]` new_delegate_registry
 #[derive(Default)]
 pub struct ChangeDelegateBuilder {
     reward_pool: Option<solana_program::pubkey::Pubkey>,
@@ -112,8 +129,12 @@ pub struct ChangeDelegateBuilder {
     mining_owner: Option<solana_program::pubkey::Pubkey>,
     old_delegate_mining: Option<solana_program::pubkey::Pubkey>,
     new_delegate_mining: Option<solana_program::pubkey::Pubkey>,
+    new_delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    deposit_id: Option<u64>,
     staked_amount: Option<u64>,
     new_delegate: Option<Pubkey>,
+    old_delegate_weights: Option<Vec<u16>>,
+    new_delegate_weights: Option<Vec<u16>>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -166,6 +187,21 @@ impl ChangeDelegateBuilder {
         self.new_delegate_mining = Some(new_delegate_mining);
         self
     }
+    /// `[optional account]`
+    /// Registry of the new delegate mining, required when the new delegate mining differs from the mining
+    #[inline(always)]
+    pub fn new_delegate_registry(
+        &mut self,
+        new_delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    ) -> &mut Self {
+        self.new_delegate_registry = new_delegate_registry;
+        self
+    }
+    #[inline(always)]
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.deposit_id = Some(deposit_id);
+        self
+    }
     #[inline(always)]
     pub fn staked_amount(&mut self, staked_amount: u64) -> &mut Self {
         self.staked_amount = Some(staked_amount);
@@ -176,6 +212,16 @@ impl ChangeDelegateBuilder {
         self.new_delegate = Some(new_delegate);
         self
     }
+    #[inline(always)]
+    pub fn old_delegate_weights(&mut self, old_delegate_weights: Vec<u16>) -> &mut Self {
+        self.old_delegate_weights = Some(old_delegate_weights);
+        self
+    }
+    #[inline(always)]
+    pub fn new_delegate_weights(&mut self, new_delegate_weights: Vec<u16>) -> &mut Self {
+        self.new_delegate_weights = Some(new_delegate_weights);
+        self
+    }
     /// Add an aditional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -209,13 +255,23 @@ impl ChangeDelegateBuilder {
             new_delegate_mining: self
                 .new_delegate_mining
                 .expect("new_delegate_mining is not set"),
+            new_delegate_registry: self.new_delegate_registry,
         };
         let args = ChangeDelegateInstructionArgs {
+            deposit_id: self.deposit_id.clone().expect("deposit_id is not set"),
             staked_amount: self
                 .staked_amount
                 .clone()
                 .expect("staked_amount is not set"),
             new_delegate: self.new_delegate.clone().expect("new_delegate is not set"),
+            old_delegate_weights: self
+                .old_delegate_weights
+                .clone()
+                .expect("old_delegate_weights is not set"),
+            new_delegate_weights: self
+                .new_delegate_weights
+                .clone()
+                .expect("new_delegate_weights is not set"),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -236,6 +292,8 @@ pub struct ChangeDelegateCpiAccounts<'a, 'b> {
     pub old_delegate_mining: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the new delegate mining account
     pub new_delegate_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// Registry of the new delegate mining, required when the new delegate mining differs from the mining
+    pub new_delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
 }
 
 /// `change_delegate` CPI instruction.
@@ -254,6 +312,8 @@ pub struct ChangeDelegateCpi<'a, 'b> {
     pub old_delegate_mining: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the new delegate mining account
     pub new_delegate_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// Registry of the new delegate mining, required when the new delegate mining differs from the mining
+    pub new_delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     /// The arguments for the instruction.
     pub __args: ChangeDelegateInstructionArgs,
 }
@@ -272,6 +332,7 @@ impl<'a, 'b> ChangeDelegateCpi<'a, 'b> {
             mining_owner: accounts.mining_owner,
             old_delegate_mining: accounts.old_delegate_mining,
             new_delegate_mining: accounts.new_delegate_mining,
+            new_delegate_registry: accounts.new_delegate_registry,
             __args: args,
         }
     }
@@ -308,7 +369,7 @@ impl<'a, 'b> ChangeDelegateCpi<'a, 'b> {
             bool,
         )],
     ) -> solana_program::entrypoint::ProgramResult {
-        let mut accounts = Vec::with_capacity(6 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(7 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             *self.reward_pool.key,
             false,
@@ -333,6 +394,17 @@ impl<'a, 'b> ChangeDelegateCpi<'a, 'b> {
             *self.new_delegate_mining.key,
             false,
         ));
+        if let Some(new_delegate_registry) = self.new_delegate_registry {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *new_delegate_registry.key,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         remaining_accounts.iter().for_each(|remaining_account| {
             accounts.push(solana_program::instruction::AccountMeta {
                 pubkey: *remaining_account.0.key,
@@ -349,7 +421,7 @@ impl<'a, 'b> ChangeDelegateCpi<'a, 'b> {
             accounts,
             data,
         };
-        let mut account_infos = Vec::with_capacity(6 + 1 + remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(7 + 1 + remaining_accounts.len());
         account_infos.push(self.__program.clone());
         account_infos.push(self.reward_pool.clone());
         account_infos.push(self.mining.clone());
@@ -357,6 +429,9 @@ impl<'a, 'b> ChangeDelegateCpi<'a, 'b> {
         account_infos.push(self.mining_owner.clone());
         account_infos.push(self.old_delegate_mining.clone());
         account_infos.push(self.new_delegate_mining.clone());
+        if let Some(new_delegate_registry) = self.new_delegate_registry {
+            account_infos.push(new_delegate_registry.clone());
+        }
         remaining_accounts
             .iter()
             .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
@@ -379,6 +454,7 @@ impl<'a, 'b> ChangeDelegateCpi<'a, 'b> {
 ///   3. `[signer]` mining_owner
 ///   4. `[writable]` old_delegate_mining
 ///   5. `[writable]` new_delegate_mining
+///   6. `[optional]` new_delegate_registry
 pub struct ChangeDelegateCpiBuilder<'a, 'b> {
     instruction: Box<ChangeDelegateCpiBuilderInstruction<'a, 'b>>,
 }
@@ -393,8 +469,12 @@ impl<'a, 'b> ChangeDelegateCpiBuilder<'a, 'b> {
             mining_owner: None,
             old_delegate_mining: None,
             new_delegate_mining: None,
+            new_delegate_registry: None,
+            deposit_id: None,
             staked_amount: None,
             new_delegate: None,
+            old_delegate_weights: None,
+            new_delegate_weights: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -453,6 +533,21 @@ impl<'a, 'b> ChangeDelegateCpiBuilder<'a, 'b> {
         self.instruction.new_delegate_mining = Some(new_delegate_mining);
         self
     }
+    /// `[optional account]`
+    /// Registry of the new delegate mining, required when the new delegate mining differs from the mining
+    #[inline(always)]
+    pub fn new_delegate_registry(
+        &mut self,
+        new_delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.new_delegate_registry = new_delegate_registry;
+        self
+    }
+    #[inline(always)]
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.instruction.deposit_id = Some(deposit_id);
+        self
+    }
     #[inline(always)]
     pub fn staked_amount(&mut self, staked_amount: u64) -> &mut Self {
         self.instruction.staked_amount = Some(staked_amount);
@@ -463,6 +558,16 @@ impl<'a, 'b> ChangeDelegateCpiBuilder<'a, 'b> {
         self.instruction.new_delegate = Some(new_delegate);
         self
     }
+    #[inline(always)]
+    pub fn old_delegate_weights(&mut self, old_delegate_weights: Vec<u16>) -> &mut Self {
+        self.instruction.old_delegate_weights = Some(old_delegate_weights);
+        self
+    }
+    #[inline(always)]
+    pub fn new_delegate_weights(&mut self, new_delegate_weights: Vec<u16>) -> &mut Self {
+        self.instruction.new_delegate_weights = Some(new_delegate_weights);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -505,6 +610,11 @@ impl<'a, 'b> ChangeDelegateCpiBuilder<'a, 'b> {
         signers_seeds: &[&[&[u8]]],
     ) -> solana_program::entrypoint::ProgramResult {
         let args = ChangeDelegateInstructionArgs {
+            deposit_id: self
+                .instruction
+                .deposit_id
+                .clone()
+                .expect("deposit_id is not set"),
             staked_amount: self
                 .instruction
                 .staked_amount
@@ -515,6 +625,16 @@ impl<'a, 'b> ChangeDelegateCpiBuilder<'a, 'b> {
                 .new_delegate
                 .clone()
                 .expect("new_delegate is not set"),
+            old_delegate_weights: self
+                .instruction
+                .old_delegate_weights
+                .clone()
+                .expect("old_delegate_weights is not set"),
+            new_delegate_weights: self
+                .instruction
+                .new_delegate_weights
+                .clone()
+                .expect("new_delegate_weights is not set"),
         };
         let instruction = ChangeDelegateCpi {
             __program: self.instruction.__program,
@@ -545,6 +665,8 @@ impl<'a, 'b> ChangeDelegateCpiBuilder<'a, 'b> {
                 .instruction
                 .new_delegate_mining
                 .expect("new_delegate_mining is not set"),
+
+            new_delegate_registry: self.instruction.new_delegate_registry,
             __args: args,
         };
         instruction.invoke_signed_with_remaining_accounts(
@@ -562,8 +684,12 @@ struct ChangeDelegateCpiBuilderInstruction<'a, 'b> {
     mining_owner: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     old_delegate_mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     new_delegate_mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    new_delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    deposit_id: Option<u64>,
     staked_amount: Option<u64>,
     new_delegate: Option<Pubkey>,
+    old_delegate_weights: Option<Vec<u16>>,
+    new_delegate_weights: Option<Vec<u16>>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
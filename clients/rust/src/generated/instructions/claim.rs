@@ -38,7 +38,7 @@ impl Claim {
         remaining_accounts: &[solana_program::instruction::AccountMeta],
     ) -> solana_program::instruction::Instruction {
         let mut accounts = Vec::with_capacity(8 + remaining_accounts.len());
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+        accounts.push(solana_program::instruction::AccountMeta::new(
             self.reward_pool,
             false,
         ));
@@ -95,7 +95,7 @@ impl ClaimInstructionData {
 ///
 /// ### Accounts:
 ///
-///   0. `[]` reward_pool
+///   0. `[writable]` reward_pool
 ///   1. `[]` reward_mint
 ///   2. `[writable]` vault
 ///   3. `[writable]` mining
@@ -309,7 +309,7 @@ impl<'a, 'b> ClaimCpi<'a, 'b> {
         )],
     ) -> solana_program::entrypoint::ProgramResult {
         let mut accounts = Vec::with_capacity(8 + remaining_accounts.len());
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+        accounts.push(solana_program::instruction::AccountMeta::new(
             *self.reward_pool.key,
             false,
         ));
@@ -381,7 +381,7 @@ impl<'a, 'b> ClaimCpi<'a, 'b> {
 ///
 /// ### Accounts:
 ///
-///   0. `[]` reward_pool
+///   0. `[writable]` reward_pool
 ///   1. `[]` reward_mint
 ///   2. `[writable]` vault
 ///   3. `[writable]` mining
@@ -0,0 +1,543 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+
+/// Accounts.
+pub struct ClosePool {
+    /// The address of the reward pool
+    pub reward_pool: solana_program::pubkey::Pubkey,
+    /// The address of the reward mint
+    pub reward_mint: solana_program::pubkey::Pubkey,
+    /// The address of the reward vault
+    pub vault: solana_program::pubkey::Pubkey,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: solana_program::pubkey::Pubkey,
+    /// The token account of the fill authority remaining rewards are swept to
+    pub recipient_token_account: solana_program::pubkey::Pubkey,
+    /// The address where lamports from accounts closing will be transferred
+    pub target_account: solana_program::pubkey::Pubkey,
+    /// The address of the Token program where rewards are minted
+    pub token_program: solana_program::pubkey::Pubkey,
+}
+
+impl ClosePool {
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        self.instruction_with_remaining_accounts(&[])
+    }
+    #[allow(clippy::vec_init_then_push)]
+    pub fn instruction_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[solana_program::instruction::AccountMeta],
+    ) -> solana_program::instruction::Instruction {
+        let mut accounts = Vec::with_capacity(7 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.reward_pool,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.reward_mint,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.vault, false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.deposit_authority,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.recipient_token_account,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.target_account,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.token_program,
+            false,
+        ));
+        accounts.extend_from_slice(remaining_accounts);
+        let data = ClosePoolInstructionData::new().try_to_vec().unwrap();
+
+        solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ClosePoolInstructionData {
+    discriminator: u8,
+}
+
+impl ClosePoolInstructionData {
+    pub fn new() -> Self {
+        Self { discriminator: 18 }
+    }
+}
+
+/// Instruction builder for `ClosePool`.
+///
+/// ### Accounts:
+///
+///   0. `[writable]` reward_pool
+///   1. `[writable]` reward_mint
+///   2. `[writable]` vault
+///   3. `[signer]` deposit_authority
+///   4. `[writable]` recipient_token_account
+///   5. `[writable]` target_account
+///   6. `[optional]` token_program (default to `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`)
+#[derive(Default)]
+pub struct ClosePoolBuilder {
+    reward_pool: Option<solana_program::pubkey::Pubkey>,
+    reward_mint: Option<solana_program::pubkey::Pubkey>,
+    vault: Option<solana_program::pubkey::Pubkey>,
+    deposit_authority: Option<solana_program::pubkey::Pubkey>,
+    recipient_token_account: Option<solana_program::pubkey::Pubkey>,
+    target_account: Option<solana_program::pubkey::Pubkey>,
+    token_program: Option<solana_program::pubkey::Pubkey>,
+    __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
+}
+
+impl ClosePoolBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The address of the reward pool
+    #[inline(always)]
+    pub fn reward_pool(&mut self, reward_pool: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.reward_pool = Some(reward_pool);
+        self
+    }
+    /// The address of the reward mint
+    #[inline(always)]
+    pub fn reward_mint(&mut self, reward_mint: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.reward_mint = Some(reward_mint);
+        self
+    }
+    /// The address of the reward vault
+    #[inline(always)]
+    pub fn vault(&mut self, vault: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.vault = Some(vault);
+        self
+    }
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    #[inline(always)]
+    pub fn deposit_authority(
+        &mut self,
+        deposit_authority: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.deposit_authority = Some(deposit_authority);
+        self
+    }
+    /// The token account of the fill authority remaining rewards are swept to
+    #[inline(always)]
+    pub fn recipient_token_account(
+        &mut self,
+        recipient_token_account: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.recipient_token_account = Some(recipient_token_account);
+        self
+    }
+    /// The address where lamports from accounts closing will be transferred
+    #[inline(always)]
+    pub fn target_account(&mut self, target_account: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.target_account = Some(target_account);
+        self
+    }
+    /// `[optional account, default to 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA']`
+    /// The address of the Token program where rewards are minted
+    #[inline(always)]
+    pub fn token_program(&mut self, token_program: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.token_program = Some(token_program);
+        self
+    }
+    /// Add an aditional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: solana_program::instruction::AccountMeta,
+    ) -> &mut Self {
+        self.__remaining_accounts.push(account);
+        self
+    }
+    /// Add additional accounts to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[solana_program::instruction::AccountMeta],
+    ) -> &mut Self {
+        self.__remaining_accounts.extend_from_slice(accounts);
+        self
+    }
+    #[allow(clippy::clone_on_copy)]
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = ClosePool {
+            reward_pool: self.reward_pool.expect("reward_pool is not set"),
+            reward_mint: self.reward_mint.expect("reward_mint is not set"),
+            vault: self.vault.expect("vault is not set"),
+            deposit_authority: self
+                .deposit_authority
+                .expect("deposit_authority is not set"),
+            recipient_token_account: self
+                .recipient_token_account
+                .expect("recipient_token_account is not set"),
+            target_account: self.target_account.expect("target_account is not set"),
+            token_program: self.token_program.unwrap_or(solana_program::pubkey!(
+                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+            )),
+        };
+
+        accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
+    }
+}
+
+/// `close_pool` CPI accounts.
+pub struct ClosePoolCpiAccounts<'a, 'b> {
+    /// The address of the reward pool
+    pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward mint
+    pub reward_mint: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward vault
+    pub vault: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The token account of the fill authority remaining rewards are swept to
+    pub recipient_token_account: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address where lamports from accounts closing will be transferred
+    pub target_account: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Token program where rewards are minted
+    pub token_program: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+/// `close_pool` CPI instruction.
+pub struct ClosePoolCpi<'a, 'b> {
+    /// The program to invoke.
+    pub __program: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward pool
+    pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward mint
+    pub reward_mint: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward vault
+    pub vault: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The token account of the fill authority remaining rewards are swept to
+    pub recipient_token_account: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address where lamports from accounts closing will be transferred
+    pub target_account: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Token program where rewards are minted
+    pub token_program: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+impl<'a, 'b> ClosePoolCpi<'a, 'b> {
+    pub fn new(
+        program: &'b solana_program::account_info::AccountInfo<'a>,
+        accounts: ClosePoolCpiAccounts<'a, 'b>,
+    ) -> Self {
+        Self {
+            __program: program,
+            reward_pool: accounts.reward_pool,
+            reward_mint: accounts.reward_mint,
+            vault: accounts.vault,
+            deposit_authority: accounts.deposit_authority,
+            recipient_token_account: accounts.recipient_token_account,
+            target_account: accounts.target_account,
+            token_program: accounts.token_program,
+        }
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], &[])
+    }
+    #[inline(always)]
+    pub fn invoke_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], remaining_accounts)
+    }
+    #[inline(always)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(signers_seeds, &[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed_with_remaining_accounts(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let mut accounts = Vec::with_capacity(7 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.reward_pool.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.reward_mint.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.vault.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.deposit_authority.key,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.recipient_token_account.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.target_account.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.token_program.key,
+            false,
+        ));
+        remaining_accounts.iter().for_each(|remaining_account| {
+            accounts.push(solana_program::instruction::AccountMeta {
+                pubkey: *remaining_account.0.key,
+                is_signer: remaining_account.1,
+                is_writable: remaining_account.2,
+            })
+        });
+        let data = ClosePoolInstructionData::new().try_to_vec().unwrap();
+
+        let instruction = solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        };
+        let mut account_infos = Vec::with_capacity(7 + 1 + remaining_accounts.len());
+        account_infos.push(self.__program.clone());
+        account_infos.push(self.reward_pool.clone());
+        account_infos.push(self.reward_mint.clone());
+        account_infos.push(self.vault.clone());
+        account_infos.push(self.deposit_authority.clone());
+        account_infos.push(self.recipient_token_account.clone());
+        account_infos.push(self.target_account.clone());
+        account_infos.push(self.token_program.clone());
+        remaining_accounts
+            .iter()
+            .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
+
+        if signers_seeds.is_empty() {
+            solana_program::program::invoke(&instruction, &account_infos)
+        } else {
+            solana_program::program::invoke_signed(&instruction, &account_infos, signers_seeds)
+        }
+    }
+}
+
+/// Instruction builder for `ClosePool` via CPI.
+///
+/// ### Accounts:
+///
+///   0. `[writable]` reward_pool
+///   1. `[writable]` reward_mint
+///   2. `[writable]` vault
+///   3. `[signer]` deposit_authority
+///   4. `[writable]` recipient_token_account
+///   5. `[writable]` target_account
+///   6. `[]` token_program
+pub struct ClosePoolCpiBuilder<'a, 'b> {
+    instruction: Box<ClosePoolCpiBuilderInstruction<'a, 'b>>,
+}
+
+impl<'a, 'b> ClosePoolCpiBuilder<'a, 'b> {
+    pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
+        let instruction = Box::new(ClosePoolCpiBuilderInstruction {
+            __program: program,
+            reward_pool: None,
+            reward_mint: None,
+            vault: None,
+            deposit_authority: None,
+            recipient_token_account: None,
+            target_account: None,
+            token_program: None,
+            __remaining_accounts: Vec::new(),
+        });
+        Self { instruction }
+    }
+    /// The address of the reward pool
+    #[inline(always)]
+    pub fn reward_pool(
+        &mut self,
+        reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.reward_pool = Some(reward_pool);
+        self
+    }
+    /// The address of the reward mint
+    #[inline(always)]
+    pub fn reward_mint(
+        &mut self,
+        reward_mint: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.reward_mint = Some(reward_mint);
+        self
+    }
+    /// The address of the reward vault
+    #[inline(always)]
+    pub fn vault(&mut self, vault: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+        self.instruction.vault = Some(vault);
+        self
+    }
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    #[inline(always)]
+    pub fn deposit_authority(
+        &mut self,
+        deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.deposit_authority = Some(deposit_authority);
+        self
+    }
+    /// The token account of the fill authority remaining rewards are swept to
+    #[inline(always)]
+    pub fn recipient_token_account(
+        &mut self,
+        recipient_token_account: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.recipient_token_account = Some(recipient_token_account);
+        self
+    }
+    /// The address where lamports from accounts closing will be transferred
+    #[inline(always)]
+    pub fn target_account(
+        &mut self,
+        target_account: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.target_account = Some(target_account);
+        self
+    }
+    /// The address of the Token program where rewards are minted
+    #[inline(always)]
+    pub fn token_program(
+        &mut self,
+        token_program: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.token_program = Some(token_program);
+        self
+    }
+    /// Add an additional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: &'b solana_program::account_info::AccountInfo<'a>,
+        is_writable: bool,
+        is_signer: bool,
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .push((account, is_writable, is_signer));
+        self
+    }
+    /// Add additional accounts to the instruction.
+    ///
+    /// Each account is represented by a tuple of the `AccountInfo`, a `bool` indicating whether the account is writable or not,
+    /// and a `bool` indicating whether the account is a signer or not.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .extend_from_slice(accounts);
+        self
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed(&[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let instruction = ClosePoolCpi {
+            __program: self.instruction.__program,
+
+            reward_pool: self
+                .instruction
+                .reward_pool
+                .expect("reward_pool is not set"),
+
+            reward_mint: self
+                .instruction
+                .reward_mint
+                .expect("reward_mint is not set"),
+
+            vault: self.instruction.vault.expect("vault is not set"),
+
+            deposit_authority: self
+                .instruction
+                .deposit_authority
+                .expect("deposit_authority is not set"),
+
+            recipient_token_account: self
+                .instruction
+                .recipient_token_account
+                .expect("recipient_token_account is not set"),
+
+            target_account: self
+                .instruction
+                .target_account
+                .expect("target_account is not set"),
+
+            token_program: self
+                .instruction
+                .token_program
+                .expect("token_program is not set"),
+        };
+        instruction.invoke_signed_with_remaining_accounts(
+            signers_seeds,
+            &self.instruction.__remaining_accounts,
+        )
+    }
+}
+
+struct ClosePoolCpiBuilderInstruction<'a, 'b> {
+    __program: &'b solana_program::account_info::AccountInfo<'a>,
+    reward_pool: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    reward_mint: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    vault: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    recipient_token_account: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    target_account: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    token_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+    __remaining_accounts: Vec<(
+        &'b solana_program::account_info::AccountInfo<'a>,
+        bool,
+        bool,
+    )>,
+}
@@ -17,6 +17,8 @@ pub struct DecreaseRewards {
     pub reward_pool: solana_program::pubkey::Pubkey,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: solana_program::pubkey::Pubkey,
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    pub penalty_history: Option<solana_program::pubkey::Pubkey>,
 }
 
 impl DecreaseRewards {
@@ -32,7 +34,7 @@ impl DecreaseRewards {
         args: DecreaseRewardsInstructionArgs,
         remaining_accounts: &[solana_program::instruction::AccountMeta],
     ) -> solana_program::instruction::Instruction {
-        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             self.deposit_authority,
             true,
@@ -45,6 +47,17 @@ impl DecreaseRewards {
             self.mining,
             false,
         ));
+        if let Some(penalty_history) = self.penalty_history {
+            accounts.push(solana_program::instruction::AccountMeta::new(
+                penalty_history,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         accounts.extend_from_slice(remaining_accounts);
         let mut data = DecreaseRewardsInstructionData::new().try_to_vec().unwrap();
         let mut args = args.try_to_vec().unwrap();
@@ -74,6 +87,7 @@ impl DecreaseRewardsInstructionData {
 pub struct DecreaseRewardsInstructionArgs {
     pub mining_owner: Pubkey,
     pub decreased_weighted_stake_number: u64,
+    pub reason_code: u32,
 }
 
 /// Instruction builder for `DecreaseRewards`.
@@ -83,13 +97,16 @@ pub struct DecreaseRewardsInstructionArgs {
 ///   0. `[signer]` deposit_authority
 ///   1. `[writable]` reward_pool
 ///   2. `[writable]` mining
+///   3. `[writable, optional]` penalty_history
 #[derive(Default)]
 pub struct DecreaseRewardsBuilder {
     deposit_authority: Option<solana_program::pubkey::Pubkey>,
     reward_pool: Option<solana_program::pubkey::Pubkey>,
     mining: Option<solana_program::pubkey::Pubkey>,
+    penalty_history: Option<solana_program::pubkey::Pubkey>,
     mining_owner: Option<Pubkey>,
     decreased_weighted_stake_number: Option<u64>,
+    reason_code: Option<u32>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -118,6 +135,16 @@ impl DecreaseRewardsBuilder {
         self.mining = Some(mining);
         self
     }
+    /// `[optional account]`
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    #[inline(always)]
+    pub fn penalty_history(
+        &mut self,
+        penalty_history: Option<solana_program::pubkey::Pubkey>,
+    ) -> &mut Self {
+        self.penalty_history = penalty_history;
+        self
+    }
     #[inline(always)]
     pub fn mining_owner(&mut self, mining_owner: Pubkey) -> &mut Self {
         self.mining_owner = Some(mining_owner);
@@ -131,6 +158,11 @@ impl DecreaseRewardsBuilder {
         self.decreased_weighted_stake_number = Some(decreased_weighted_stake_number);
         self
     }
+    #[inline(always)]
+    pub fn reason_code(&mut self, reason_code: u32) -> &mut Self {
+        self.reason_code = Some(reason_code);
+        self
+    }
     /// Add an aditional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -157,6 +189,7 @@ impl DecreaseRewardsBuilder {
                 .expect("deposit_authority is not set"),
             reward_pool: self.reward_pool.expect("reward_pool is not set"),
             mining: self.mining.expect("mining is not set"),
+            penalty_history: self.penalty_history,
         };
         let args = DecreaseRewardsInstructionArgs {
             mining_owner: self.mining_owner.clone().expect("mining_owner is not set"),
@@ -164,6 +197,7 @@ impl DecreaseRewardsBuilder {
                 .decreased_weighted_stake_number
                 .clone()
                 .expect("decreased_weighted_stake_number is not set"),
+            reason_code: self.reason_code.clone().expect("reason_code is not set"),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -178,6 +212,8 @@ pub struct DecreaseRewardsCpiAccounts<'a, 'b> {
     pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    pub penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
 }
 
 /// `decrease_rewards` CPI instruction.
@@ -190,6 +226,8 @@ pub struct DecreaseRewardsCpi<'a, 'b> {
     pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    pub penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     /// The arguments for the instruction.
     pub __args: DecreaseRewardsInstructionArgs,
 }
@@ -205,6 +243,7 @@ impl<'a, 'b> DecreaseRewardsCpi<'a, 'b> {
             deposit_authority: accounts.deposit_authority,
             reward_pool: accounts.reward_pool,
             mining: accounts.mining,
+            penalty_history: accounts.penalty_history,
             __args: args,
         }
     }
@@ -241,7 +280,7 @@ impl<'a, 'b> DecreaseRewardsCpi<'a, 'b> {
             bool,
         )],
     ) -> solana_program::entrypoint::ProgramResult {
-        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             *self.deposit_authority.key,
             true,
@@ -254,6 +293,17 @@ impl<'a, 'b> DecreaseRewardsCpi<'a, 'b> {
             *self.mining.key,
             false,
         ));
+        if let Some(penalty_history) = self.penalty_history {
+            accounts.push(solana_program::instruction::AccountMeta::new(
+                *penalty_history.key,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         remaining_accounts.iter().for_each(|remaining_account| {
             accounts.push(solana_program::instruction::AccountMeta {
                 pubkey: *remaining_account.0.key,
@@ -270,11 +320,14 @@ impl<'a, 'b> DecreaseRewardsCpi<'a, 'b> {
             accounts,
             data,
         };
-        let mut account_infos = Vec::with_capacity(3 + 1 + remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(4 + 1 + remaining_accounts.len());
         account_infos.push(self.__program.clone());
         account_infos.push(self.deposit_authority.clone());
         account_infos.push(self.reward_pool.clone());
         account_infos.push(self.mining.clone());
+        if let Some(penalty_history) = self.penalty_history {
+            account_infos.push(penalty_history.clone());
+        }
         remaining_accounts
             .iter()
             .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
@@ -294,6 +347,7 @@ impl<'a, 'b> DecreaseRewardsCpi<'a, 'b> {
 ///   0. `[signer]` deposit_authority
 ///   1. `[writable]` reward_pool
 ///   2. `[writable]` mining
+///   3. `[writable, optional]` penalty_history
 pub struct DecreaseRewardsCpiBuilder<'a, 'b> {
     instruction: Box<DecreaseRewardsCpiBuilderInstruction<'a, 'b>>,
 }
@@ -305,8 +359,10 @@ impl<'a, 'b> DecreaseRewardsCpiBuilder<'a, 'b> {
             deposit_authority: None,
             reward_pool: None,
             mining: None,
+            penalty_history: None,
             mining_owner: None,
             decreased_weighted_stake_number: None,
+            reason_code: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -338,6 +394,16 @@ impl<'a, 'b> DecreaseRewardsCpiBuilder<'a, 'b> {
         self.instruction.mining = Some(mining);
         self
     }
+    /// `[optional account]`
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    #[inline(always)]
+    pub fn penalty_history(
+        &mut self,
+        penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.penalty_history = penalty_history;
+        self
+    }
     #[inline(always)]
     pub fn mining_owner(&mut self, mining_owner: Pubkey) -> &mut Self {
         self.instruction.mining_owner = Some(mining_owner);
@@ -351,6 +417,11 @@ impl<'a, 'b> DecreaseRewardsCpiBuilder<'a, 'b> {
         self.instruction.decreased_weighted_stake_number = Some(decreased_weighted_stake_number);
         self
     }
+    #[inline(always)]
+    pub fn reason_code(&mut self, reason_code: u32) -> &mut Self {
+        self.instruction.reason_code = Some(reason_code);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -403,6 +474,11 @@ impl<'a, 'b> DecreaseRewardsCpiBuilder<'a, 'b> {
                 .decreased_weighted_stake_number
                 .clone()
                 .expect("decreased_weighted_stake_number is not set"),
+            reason_code: self
+                .instruction
+                .reason_code
+                .clone()
+                .expect("reason_code is not set"),
         };
         let instruction = DecreaseRewardsCpi {
             __program: self.instruction.__program,
@@ -418,6 +494,8 @@ impl<'a, 'b> DecreaseRewardsCpiBuilder<'a, 'b> {
                 .expect("reward_pool is not set"),
 
             mining: self.instruction.mining.expect("mining is not set"),
+
+            penalty_history: self.instruction.penalty_history,
             __args: args,
         };
         instruction.invoke_signed_with_remaining_accounts(
@@ -432,8 +510,10 @@ struct DecreaseRewardsCpiBuilderInstruction<'a, 'b> {
     deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     reward_pool: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     mining_owner: Option<Pubkey>,
     decreased_weighted_stake_number: Option<u64>,
+    reason_code: Option<u32>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
@@ -20,6 +20,8 @@ pub struct DepositMining {
     pub deposit_authority: solana_program::pubkey::Pubkey,
     /// The address of Mining Account that might be used as a delegate in delegated staking model
     pub delegate_mining: solana_program::pubkey::Pubkey,
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
+    pub delegate_registry: Option<solana_program::pubkey::Pubkey>,
 }
 
 impl DepositMining {
@@ -35,7 +37,7 @@ impl DepositMining {
         args: DepositMiningInstructionArgs,
         remaining_accounts: &[solana_program::instruction::AccountMeta],
     ) -> solana_program::instruction::Instruction {
-        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(5 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             self.reward_pool,
             false,
@@ -48,10 +50,21 @@ impl DepositMining {
             self.deposit_authority,
             true,
         ));
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+        accounts.push(solana_program::instruction::AccountMeta::new(
             self.delegate_mining,
             false,
         ));
+        if let Some(delegate_registry) = self.delegate_registry {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                delegate_registry,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         accounts.extend_from_slice(remaining_accounts);
         let mut data = DepositMiningInstructionData::new().try_to_vec().unwrap();
         let mut args = args.try_to_vec().unwrap();
@@ -79,10 +92,12 @@ impl DepositMiningInstructionData {
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DepositMiningInstructionArgs {
+    pub deposit_id: u64,
     pub amount: u64,
     pub lockup_period: LockupPeriod,
     pub mining_owner: Pubkey,
     pub delegate: Pubkey,
+    pub delegate_weights: Vec<u16>,
 }
 
 /// Instruction builder for `DepositMining`.
@@ -92,17 +107,21 @@ pub struct DepositMiningInstructionArgs {
 ///   0. `[writable]` reward_pool
 ///   1. `[writable]` mining
 ///   2. `[signer]` deposit_authority
-///   3. `[]` delegate_mining
+///   3. `[writable]` delegate_mining
+///   4. `[optional]` delegate_registry
 #[derive(Default)]
 pub struct DepositMiningBuilder {
     reward_pool: Option<solana_program::pubkey::Pubkey>,
     mining: Option<solana_program::pubkey::Pubkey>,
     deposit_authority: Option<solana_program::pubkey::Pubkey>,
     delegate_mining: Option<solana_program::pubkey::Pubkey>,
+    delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    deposit_id: Option<u64>,
     amount: Option<u64>,
     lockup_period: Option<LockupPeriod>,
     mining_owner: Option<Pubkey>,
     delegate: Option<Pubkey>,
+    delegate_weights: Option<Vec<u16>>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -140,6 +159,21 @@ impl DepositMiningBuilder {
         self.delegate_mining = Some(delegate_mining);
         self
     }
+    /// `[optional account]`
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
+    #[inline(always)]
+    pub fn delegate_registry(
+        &mut self,
+        delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    ) -> &mut Self {
+        self.delegate_registry = delegate_registry;
+        self
+    }
+    #[inline(always)]
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.deposit_id = Some(deposit_id);
+        self
+    }
     #[inline(always)]
     pub fn amount(&mut self, amount: u64) -> &mut Self {
         self.amount = Some(amount);
@@ -160,6 +194,11 @@ impl DepositMiningBuilder {
         self.delegate = Some(delegate);
         self
     }
+    #[inline(always)]
+    pub fn delegate_weights(&mut self, delegate_weights: Vec<u16>) -> &mut Self {
+        self.delegate_weights = Some(delegate_weights);
+        self
+    }
     /// Add an aditional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -187,8 +226,10 @@ impl DepositMiningBuilder {
                 .deposit_authority
                 .expect("deposit_authority is not set"),
             delegate_mining: self.delegate_mining.expect("delegate_mining is not set"),
+            delegate_registry: self.delegate_registry,
         };
         let args = DepositMiningInstructionArgs {
+            deposit_id: self.deposit_id.clone().expect("deposit_id is not set"),
             amount: self.amount.clone().expect("amount is not set"),
             lockup_period: self
                 .lockup_period
@@ -196,6 +237,10 @@ impl DepositMiningBuilder {
                 .expect("lockup_period is not set"),
             mining_owner: self.mining_owner.clone().expect("mining_owner is not set"),
             delegate: self.delegate.clone().expect("delegate is not set"),
+            delegate_weights: self
+                .delegate_weights
+                .clone()
+                .expect("delegate_weights is not set"),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -212,6 +257,8 @@ pub struct DepositMiningCpiAccounts<'a, 'b> {
     pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of Mining Account that might be used as a delegate in delegated staking model
     pub delegate_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
+    pub delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
 }
 
 /// `deposit_mining` CPI instruction.
@@ -226,6 +273,8 @@ pub struct DepositMiningCpi<'a, 'b> {
     pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of Mining Account that might be used as a delegate in delegated staking model
     pub delegate_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
+    pub delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     /// The arguments for the instruction.
     pub __args: DepositMiningInstructionArgs,
 }
@@ -242,6 +291,7 @@ impl<'a, 'b> DepositMiningCpi<'a, 'b> {
             mining: accounts.mining,
             deposit_authority: accounts.deposit_authority,
             delegate_mining: accounts.delegate_mining,
+            delegate_registry: accounts.delegate_registry,
             __args: args,
         }
     }
@@ -278,7 +328,7 @@ impl<'a, 'b> DepositMiningCpi<'a, 'b> {
             bool,
         )],
     ) -> solana_program::entrypoint::ProgramResult {
-        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(5 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             *self.reward_pool.key,
             false,
@@ -291,10 +341,21 @@ impl<'a, 'b> DepositMiningCpi<'a, 'b> {
             *self.deposit_authority.key,
             true,
         ));
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+        accounts.push(solana_program::instruction::AccountMeta::new(
             *self.delegate_mining.key,
             false,
         ));
+        if let Some(delegate_registry) = self.delegate_registry {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *delegate_registry.key,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         remaining_accounts.iter().for_each(|remaining_account| {
             accounts.push(solana_program::instruction::AccountMeta {
                 pubkey: *remaining_account.0.key,
@@ -311,12 +372,15 @@ impl<'a, 'b> DepositMiningCpi<'a, 'b> {
             accounts,
             data,
         };
-        let mut account_infos = Vec::with_capacity(4 + 1 + remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(5 + 1 + remaining_accounts.len());
         account_infos.push(self.__program.clone());
         account_infos.push(self.reward_pool.clone());
         account_infos.push(self.mining.clone());
         account_infos.push(self.deposit_authority.clone());
         account_infos.push(self.delegate_mining.clone());
+        if let Some(delegate_registry) = self.delegate_registry {
+            account_infos.push(delegate_registry.clone());
+        }
         remaining_accounts
             .iter()
             .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
@@ -336,7 +400,8 @@ impl<'a, 'b> DepositMiningCpi<'a, 'b> {
 ///   0. `[writable]` reward_pool
 ///   1. `[writable]` mining
 ///   2. `[signer]` deposit_authority
-///   3. `[]` delegate_mining
+///   3. `[writable]` delegate_mining
+///   4. `[optional]` delegate_registry
 pub struct DepositMiningCpiBuilder<'a, 'b> {
     instruction: Box<DepositMiningCpiBuilderInstruction<'a, 'b>>,
 }
@@ -349,10 +414,13 @@ impl<'a, 'b> DepositMiningCpiBuilder<'a, 'b> {
             mining: None,
             deposit_authority: None,
             delegate_mining: None,
+            delegate_registry: None,
+            deposit_id: None,
             amount: None,
             lockup_period: None,
             mining_owner: None,
             delegate: None,
+            delegate_weights: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -393,6 +461,21 @@ impl<'a, 'b> DepositMiningCpiBuilder<'a, 'b> {
         self.instruction.delegate_mining = Some(delegate_mining);
         self
     }
+    /// `[optional account]`
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
+    #[inline(always)]
+    pub fn delegate_registry(
+        &mut self,
+        delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.delegate_registry = delegate_registry;
+        self
+    }
+    #[inline(always)]
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.instruction.deposit_id = Some(deposit_id);
+        self
+    }
     #[inline(always)]
     pub fn amount(&mut self, amount: u64) -> &mut Self {
         self.instruction.amount = Some(amount);
@@ -413,6 +496,11 @@ impl<'a, 'b> DepositMiningCpiBuilder<'a, 'b> {
         self.instruction.delegate = Some(delegate);
         self
     }
+    #[inline(always)]
+    pub fn delegate_weights(&mut self, delegate_weights: Vec<u16>) -> &mut Self {
+        self.instruction.delegate_weights = Some(delegate_weights);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -455,6 +543,11 @@ impl<'a, 'b> DepositMiningCpiBuilder<'a, 'b> {
         signers_seeds: &[&[&[u8]]],
     ) -> solana_program::entrypoint::ProgramResult {
         let args = DepositMiningInstructionArgs {
+            deposit_id: self
+                .instruction
+                .deposit_id
+                .clone()
+                .expect("deposit_id is not set"),
             amount: self.instruction.amount.clone().expect("amount is not set"),
             lockup_period: self
                 .instruction
@@ -471,6 +564,11 @@ impl<'a, 'b> DepositMiningCpiBuilder<'a, 'b> {
                 .delegate
                 .clone()
                 .expect("delegate is not set"),
+            delegate_weights: self
+                .instruction
+                .delegate_weights
+                .clone()
+                .expect("delegate_weights is not set"),
         };
         let instruction = DepositMiningCpi {
             __program: self.instruction.__program,
@@ -491,6 +589,8 @@ impl<'a, 'b> DepositMiningCpiBuilder<'a, 'b> {
                 .instruction
                 .delegate_mining
                 .expect("delegate_mining is not set"),
+
+            delegate_registry: self.instruction.delegate_registry,
             __args: args,
         };
         instruction.invoke_signed_with_remaining_accounts(
@@ -506,10 +606,13 @@ struct DepositMiningCpiBuilderInstruction<'a, 'b> {
     mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     delegate_mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    deposit_id: Option<u64>,
     amount: Option<u64>,
     lockup_period: Option<LockupPeriod>,
     mining_owner: Option<Pubkey>,
     delegate: Option<Pubkey>,
+    delegate_weights: Option<Vec<u16>>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
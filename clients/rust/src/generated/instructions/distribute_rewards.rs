@@ -10,10 +10,12 @@ use borsh::BorshSerialize;
 
 /// Accounts.
 pub struct DistributeRewards {
-    /// The address of the reward pool
+    /// The address of the reward pool, which stops distributing once its index history keeps 1095 distributions
     pub reward_pool: solana_program::pubkey::Pubkey,
     /// The address of Authority who is eligble for distributiong rewards for users
     pub distribute_authority: solana_program::pubkey::Pubkey,
+    /// The address of the reward vault
+    pub vault: solana_program::pubkey::Pubkey,
 }
 
 impl DistributeRewards {
@@ -25,7 +27,7 @@ impl DistributeRewards {
         &self,
         remaining_accounts: &[solana_program::instruction::AccountMeta],
     ) -> solana_program::instruction::Instruction {
-        let mut accounts = Vec::with_capacity(2 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             self.reward_pool,
             false,
@@ -34,6 +36,9 @@ impl DistributeRewards {
             self.distribute_authority,
             true,
         ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.vault, false,
+        ));
         accounts.extend_from_slice(remaining_accounts);
         let data = DistributeRewardsInstructionData::new()
             .try_to_vec()
@@ -64,10 +69,12 @@ impl DistributeRewardsInstructionData {
 ///
 ///   0. `[writable]` reward_pool
 ///   1. `[signer]` distribute_authority
+///   2. `[]` vault
 #[derive(Default)]
 pub struct DistributeRewardsBuilder {
     reward_pool: Option<solana_program::pubkey::Pubkey>,
     distribute_authority: Option<solana_program::pubkey::Pubkey>,
+    vault: Option<solana_program::pubkey::Pubkey>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -75,7 +82,7 @@ impl DistributeRewardsBuilder {
     pub fn new() -> Self {
         Self::default()
     }
-    /// The address of the reward pool
+    /// The address of the reward pool, which stops distributing once its index history keeps 1095 distributions
     #[inline(always)]
     pub fn reward_pool(&mut self, reward_pool: solana_program::pubkey::Pubkey) -> &mut Self {
         self.reward_pool = Some(reward_pool);
@@ -90,6 +97,12 @@ impl DistributeRewardsBuilder {
         self.distribute_authority = Some(distribute_authority);
         self
     }
+    /// The address of the reward vault
+    #[inline(always)]
+    pub fn vault(&mut self, vault: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.vault = Some(vault);
+        self
+    }
     /// Add an aditional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -115,6 +128,7 @@ impl DistributeRewardsBuilder {
             distribute_authority: self
                 .distribute_authority
                 .expect("distribute_authority is not set"),
+            vault: self.vault.expect("vault is not set"),
         };
 
         accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
@@ -123,20 +137,24 @@ impl DistributeRewardsBuilder {
 
 /// `distribute_rewards` CPI accounts.
 pub struct DistributeRewardsCpiAccounts<'a, 'b> {
-    /// The address of the reward pool
+    /// The address of the reward pool, which stops distributing once its index history keeps 1095 distributions
     pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of Authority who is eligble for distributiong rewards for users
     pub distribute_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward vault
+    pub vault: &'b solana_program::account_info::AccountInfo<'a>,
 }
 
 /// `distribute_rewards` CPI instruction.
 pub struct DistributeRewardsCpi<'a, 'b> {
     /// The program to invoke.
     pub __program: &'b solana_program::account_info::AccountInfo<'a>,
-    /// The address of the reward pool
+    /// The address of the reward pool, which stops distributing once its index history keeps 1095 distributions
     pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of Authority who is eligble for distributiong rewards for users
     pub distribute_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward vault
+    pub vault: &'b solana_program::account_info::AccountInfo<'a>,
 }
 
 impl<'a, 'b> DistributeRewardsCpi<'a, 'b> {
@@ -148,6 +166,7 @@ impl<'a, 'b> DistributeRewardsCpi<'a, 'b> {
             __program: program,
             reward_pool: accounts.reward_pool,
             distribute_authority: accounts.distribute_authority,
+            vault: accounts.vault,
         }
     }
     #[inline(always)]
@@ -183,7 +202,7 @@ impl<'a, 'b> DistributeRewardsCpi<'a, 'b> {
             bool,
         )],
     ) -> solana_program::entrypoint::ProgramResult {
-        let mut accounts = Vec::with_capacity(2 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             *self.reward_pool.key,
             false,
@@ -192,6 +211,10 @@ impl<'a, 'b> DistributeRewardsCpi<'a, 'b> {
             *self.distribute_authority.key,
             true,
         ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.vault.key,
+            false,
+        ));
         remaining_accounts.iter().for_each(|remaining_account| {
             accounts.push(solana_program::instruction::AccountMeta {
                 pubkey: *remaining_account.0.key,
@@ -208,10 +231,11 @@ impl<'a, 'b> DistributeRewardsCpi<'a, 'b> {
             accounts,
             data,
         };
-        let mut account_infos = Vec::with_capacity(2 + 1 + remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(3 + 1 + remaining_accounts.len());
         account_infos.push(self.__program.clone());
         account_infos.push(self.reward_pool.clone());
         account_infos.push(self.distribute_authority.clone());
+        account_infos.push(self.vault.clone());
         remaining_accounts
             .iter()
             .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
@@ -230,6 +254,7 @@ impl<'a, 'b> DistributeRewardsCpi<'a, 'b> {
 ///
 ///   0. `[writable]` reward_pool
 ///   1. `[signer]` distribute_authority
+///   2. `[]` vault
 pub struct DistributeRewardsCpiBuilder<'a, 'b> {
     instruction: Box<DistributeRewardsCpiBuilderInstruction<'a, 'b>>,
 }
@@ -240,11 +265,12 @@ impl<'a, 'b> DistributeRewardsCpiBuilder<'a, 'b> {
             __program: program,
             reward_pool: None,
             distribute_authority: None,
+            vault: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
     }
-    /// The address of the reward pool
+    /// The address of the reward pool, which stops distributing once its index history keeps 1095 distributions
     #[inline(always)]
     pub fn reward_pool(
         &mut self,
@@ -262,6 +288,12 @@ impl<'a, 'b> DistributeRewardsCpiBuilder<'a, 'b> {
         self.instruction.distribute_authority = Some(distribute_authority);
         self
     }
+    /// The address of the reward vault
+    #[inline(always)]
+    pub fn vault(&mut self, vault: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+        self.instruction.vault = Some(vault);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -315,6 +347,8 @@ impl<'a, 'b> DistributeRewardsCpiBuilder<'a, 'b> {
                 .instruction
                 .distribute_authority
                 .expect("distribute_authority is not set"),
+
+            vault: self.instruction.vault.expect("vault is not set"),
         };
         instruction.invoke_signed_with_remaining_accounts(
             signers_seeds,
@@ -327,6 +361,7 @@ struct DistributeRewardsCpiBuilderInstruction<'a, 'b> {
     __program: &'b solana_program::account_info::AccountInfo<'a>,
     reward_pool: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     distribute_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    vault: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
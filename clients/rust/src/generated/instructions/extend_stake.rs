@@ -16,12 +16,12 @@ pub struct ExtendStake {
     pub reward_pool: solana_program::pubkey::Pubkey,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: solana_program::pubkey::Pubkey,
-    /// The address of the reward mint
-    pub reward_mint: solana_program::pubkey::Pubkey,
     /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
     pub deposit_authority: solana_program::pubkey::Pubkey,
     /// The address of Mining Account that might be used as a delegate in delegated staking model
     pub delegate_mining: solana_program::pubkey::Pubkey,
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
+    pub delegate_registry: Option<solana_program::pubkey::Pubkey>,
 }
 
 impl ExtendStake {
@@ -46,18 +46,25 @@ impl ExtendStake {
             self.mining,
             false,
         ));
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
-            self.reward_mint,
-            false,
-        ));
         accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             self.deposit_authority,
             true,
         ));
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+        accounts.push(solana_program::instruction::AccountMeta::new(
             self.delegate_mining,
             false,
         ));
+        if let Some(delegate_registry) = self.delegate_registry {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                delegate_registry,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         accounts.extend_from_slice(remaining_accounts);
         let mut data = ExtendStakeInstructionData::new().try_to_vec().unwrap();
         let mut args = args.try_to_vec().unwrap();
@@ -85,13 +92,12 @@ impl ExtendStakeInstructionData {
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtendStakeInstructionArgs {
-    pub old_lockup_period: LockupPeriod,
+    pub deposit_id: u64,
     pub new_lockup_period: LockupPeriod,
-    pub deposit_start_ts: u64,
-    pub base_amount: u64,
     pub additional_amount: u64,
     pub mining_owner: Pubkey,
     pub delegate: Pubkey,
+    pub delegate_weights: Vec<u16>,
 }
 
 /// Instruction builder for `ExtendStake`.
@@ -100,23 +106,22 @@ pub struct ExtendStakeInstructionArgs {
 ///
 ///   0. `[writable]` reward_pool
 ///   1. `[writable]` mining
-///   2. `[]` reward_mint
-///   3. `[signer]` deposit_authority
-///   4. `[]` delegate_mining
+///   2. `[signer]` deposit_authority
+///   3. `[writable]` delegate_mining
+///   4. `[optional]` delegate_registry
 #[derive(Default)]
 pub struct ExtendStakeBuilder {
     reward_pool: Option<solana_program::pubkey::Pubkey>,
     mining: Option<solana_program::pubkey::Pubkey>,
-    reward_mint: Option<solana_program::pubkey::Pubkey>,
     deposit_authority: Option<solana_program::pubkey::Pubkey>,
     delegate_mining: Option<solana_program::pubkey::Pubkey>,
-    old_lockup_period: Option<LockupPeriod>,
+    delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    deposit_id: Option<u64>,
     new_lockup_period: Option<LockupPeriod>,
-    deposit_start_ts: Option<u64>,
-    base_amount: Option<u64>,
     additional_amount: Option<u64>,
     mining_owner: Option<Pubkey>,
     delegate: Option<Pubkey>,
+    delegate_weights: Option<Vec<u16>>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -136,12 +141,6 @@ impl ExtendStakeBuilder {
         self.mining = Some(mining);
         self
     }
-    /// The address of the reward mint
-    #[inline(always)]
-    pub fn reward_mint(&mut self, reward_mint: solana_program::pubkey::Pubkey) -> &mut Self {
-        self.reward_mint = Some(reward_mint);
-        self
-    }
     /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
     #[inline(always)]
     pub fn deposit_authority(
@@ -160,24 +159,24 @@ impl ExtendStakeBuilder {
         self.delegate_mining = Some(delegate_mining);
         self
     }
+    /// `[optional account]`
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
     #[inline(always)]
-    pub fn old_lockup_period(&mut self, old_lockup_period: LockupPeriod) -> &mut Self {
-        self.old_lockup_period = Some(old_lockup_period);
-        self
-    }
-    #[inline(always)]
-    pub fn new_lockup_period(&mut self, new_lockup_period: LockupPeriod) -> &mut Self {
-        self.new_lockup_period = Some(new_lockup_period);
+    pub fn delegate_registry(
+        &mut self,
+        delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    ) -> &mut Self {
+        self.delegate_registry = delegate_registry;
         self
     }
     #[inline(always)]
-    pub fn deposit_start_ts(&mut self, deposit_start_ts: u64) -> &mut Self {
-        self.deposit_start_ts = Some(deposit_start_ts);
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.deposit_id = Some(deposit_id);
         self
     }
     #[inline(always)]
-    pub fn base_amount(&mut self, base_amount: u64) -> &mut Self {
-        self.base_amount = Some(base_amount);
+    pub fn new_lockup_period(&mut self, new_lockup_period: LockupPeriod) -> &mut Self {
+        self.new_lockup_period = Some(new_lockup_period);
         self
     }
     #[inline(always)]
@@ -195,6 +194,11 @@ impl ExtendStakeBuilder {
         self.delegate = Some(delegate);
         self
     }
+    #[inline(always)]
+    pub fn delegate_weights(&mut self, delegate_weights: Vec<u16>) -> &mut Self {
+        self.delegate_weights = Some(delegate_weights);
+        self
+    }
     /// Add an aditional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -218,32 +222,28 @@ impl ExtendStakeBuilder {
         let accounts = ExtendStake {
             reward_pool: self.reward_pool.expect("reward_pool is not set"),
             mining: self.mining.expect("mining is not set"),
-            reward_mint: self.reward_mint.expect("reward_mint is not set"),
             deposit_authority: self
                 .deposit_authority
                 .expect("deposit_authority is not set"),
             delegate_mining: self.delegate_mining.expect("delegate_mining is not set"),
+            delegate_registry: self.delegate_registry,
         };
         let args = ExtendStakeInstructionArgs {
-            old_lockup_period: self
-                .old_lockup_period
-                .clone()
-                .expect("old_lockup_period is not set"),
+            deposit_id: self.deposit_id.clone().expect("deposit_id is not set"),
             new_lockup_period: self
                 .new_lockup_period
                 .clone()
                 .expect("new_lockup_period is not set"),
-            deposit_start_ts: self
-                .deposit_start_ts
-                .clone()
-                .expect("deposit_start_ts is not set"),
-            base_amount: self.base_amount.clone().expect("base_amount is not set"),
             additional_amount: self
                 .additional_amount
                 .clone()
                 .expect("additional_amount is not set"),
             mining_owner: self.mining_owner.clone().expect("mining_owner is not set"),
             delegate: self.delegate.clone().expect("delegate is not set"),
+            delegate_weights: self
+                .delegate_weights
+                .clone()
+                .expect("delegate_weights is not set"),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -256,12 +256,12 @@ pub struct ExtendStakeCpiAccounts<'a, 'b> {
     pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: &'b solana_program::account_info::AccountInfo<'a>,
-    /// The address of the reward mint
-    pub reward_mint: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
     pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of Mining Account that might be used as a delegate in delegated staking model
     pub delegate_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
+    pub delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
 }
 
 /// `extend_stake` CPI instruction.
@@ -272,12 +272,12 @@ pub struct ExtendStakeCpi<'a, 'b> {
     pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: &'b solana_program::account_info::AccountInfo<'a>,
-    /// The address of the reward mint
-    pub reward_mint: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
     pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of Mining Account that might be used as a delegate in delegated staking model
     pub delegate_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
+    pub delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     /// The arguments for the instruction.
     pub __args: ExtendStakeInstructionArgs,
 }
@@ -292,9 +292,9 @@ impl<'a, 'b> ExtendStakeCpi<'a, 'b> {
             __program: program,
             reward_pool: accounts.reward_pool,
             mining: accounts.mining,
-            reward_mint: accounts.reward_mint,
             deposit_authority: accounts.deposit_authority,
             delegate_mining: accounts.delegate_mining,
+            delegate_registry: accounts.delegate_registry,
             __args: args,
         }
     }
@@ -340,18 +340,25 @@ impl<'a, 'b> ExtendStakeCpi<'a, 'b> {
             *self.mining.key,
             false,
         ));
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
-            *self.reward_mint.key,
-            false,
-        ));
         accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             *self.deposit_authority.key,
             true,
         ));
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+        accounts.push(solana_program::instruction::AccountMeta::new(
             *self.delegate_mining.key,
             false,
         ));
+        if let Some(delegate_registry) = self.delegate_registry {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                *delegate_registry.key,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         remaining_accounts.iter().for_each(|remaining_account| {
             accounts.push(solana_program::instruction::AccountMeta {
                 pubkey: *remaining_account.0.key,
@@ -372,9 +379,11 @@ impl<'a, 'b> ExtendStakeCpi<'a, 'b> {
         account_infos.push(self.__program.clone());
         account_infos.push(self.reward_pool.clone());
         account_infos.push(self.mining.clone());
-        account_infos.push(self.reward_mint.clone());
         account_infos.push(self.deposit_authority.clone());
         account_infos.push(self.delegate_mining.clone());
+        if let Some(delegate_registry) = self.delegate_registry {
+            account_infos.push(delegate_registry.clone());
+        }
         remaining_accounts
             .iter()
             .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
@@ -393,9 +402,9 @@ impl<'a, 'b> ExtendStakeCpi<'a, 'b> {
 ///
 ///   0. `[writable]` reward_pool
 ///   1. `[writable]` mining
-///   2. `[]` reward_mint
-///   3. `[signer]` deposit_authority
-///   4. `[]` delegate_mining
+///   2. `[signer]` deposit_authority
+///   3. `[writable]` delegate_mining
+///   4. `[optional]` delegate_registry
 pub struct ExtendStakeCpiBuilder<'a, 'b> {
     instruction: Box<ExtendStakeCpiBuilderInstruction<'a, 'b>>,
 }
@@ -406,16 +415,15 @@ impl<'a, 'b> ExtendStakeCpiBuilder<'a, 'b> {
             __program: program,
             reward_pool: None,
             mining: None,
-            reward_mint: None,
             deposit_authority: None,
             delegate_mining: None,
-            old_lockup_period: None,
+            delegate_registry: None,
+            deposit_id: None,
             new_lockup_period: None,
-            deposit_start_ts: None,
-            base_amount: None,
             additional_amount: None,
             mining_owner: None,
             delegate: None,
+            delegate_weights: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -438,15 +446,6 @@ impl<'a, 'b> ExtendStakeCpiBuilder<'a, 'b> {
         self.instruction.mining = Some(mining);
         self
     }
-    /// The address of the reward mint
-    #[inline(always)]
-    pub fn reward_mint(
-        &mut self,
-        reward_mint: &'b solana_program::account_info::AccountInfo<'a>,
-    ) -> &mut Self {
-        self.instruction.reward_mint = Some(reward_mint);
-        self
-    }
     /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
     #[inline(always)]
     pub fn deposit_authority(
@@ -465,24 +464,24 @@ impl<'a, 'b> ExtendStakeCpiBuilder<'a, 'b> {
         self.instruction.delegate_mining = Some(delegate_mining);
         self
     }
+    /// `[optional account]`
+    /// Registry of the delegate mining, required when the delegate mining differs from the mining
     #[inline(always)]
-    pub fn old_lockup_period(&mut self, old_lockup_period: LockupPeriod) -> &mut Self {
-        self.instruction.old_lockup_period = Some(old_lockup_period);
-        self
-    }
-    #[inline(always)]
-    pub fn new_lockup_period(&mut self, new_lockup_period: LockupPeriod) -> &mut Self {
-        self.instruction.new_lockup_period = Some(new_lockup_period);
+    pub fn delegate_registry(
+        &mut self,
+        delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.delegate_registry = delegate_registry;
         self
     }
     #[inline(always)]
-    pub fn deposit_start_ts(&mut self, deposit_start_ts: u64) -> &mut Self {
-        self.instruction.deposit_start_ts = Some(deposit_start_ts);
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.instruction.deposit_id = Some(deposit_id);
         self
     }
     #[inline(always)]
-    pub fn base_amount(&mut self, base_amount: u64) -> &mut Self {
-        self.instruction.base_amount = Some(base_amount);
+    pub fn new_lockup_period(&mut self, new_lockup_period: LockupPeriod) -> &mut Self {
+        self.instruction.new_lockup_period = Some(new_lockup_period);
         self
     }
     #[inline(always)]
@@ -500,6 +499,11 @@ impl<'a, 'b> ExtendStakeCpiBuilder<'a, 'b> {
         self.instruction.delegate = Some(delegate);
         self
     }
+    #[inline(always)]
+    pub fn delegate_weights(&mut self, delegate_weights: Vec<u16>) -> &mut Self {
+        self.instruction.delegate_weights = Some(delegate_weights);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -542,26 +546,16 @@ impl<'a, 'b> ExtendStakeCpiBuilder<'a, 'b> {
         signers_seeds: &[&[&[u8]]],
     ) -> solana_program::entrypoint::ProgramResult {
         let args = ExtendStakeInstructionArgs {
-            old_lockup_period: self
+            deposit_id: self
                 .instruction
-                .old_lockup_period
+                .deposit_id
                 .clone()
-                .expect("old_lockup_period is not set"),
+                .expect("deposit_id is not set"),
             new_lockup_period: self
                 .instruction
                 .new_lockup_period
                 .clone()
                 .expect("new_lockup_period is not set"),
-            deposit_start_ts: self
-                .instruction
-                .deposit_start_ts
-                .clone()
-                .expect("deposit_start_ts is not set"),
-            base_amount: self
-                .instruction
-                .base_amount
-                .clone()
-                .expect("base_amount is not set"),
             additional_amount: self
                 .instruction
                 .additional_amount
@@ -577,6 +571,11 @@ impl<'a, 'b> ExtendStakeCpiBuilder<'a, 'b> {
                 .delegate
                 .clone()
                 .expect("delegate is not set"),
+            delegate_weights: self
+                .instruction
+                .delegate_weights
+                .clone()
+                .expect("delegate_weights is not set"),
         };
         let instruction = ExtendStakeCpi {
             __program: self.instruction.__program,
@@ -588,11 +587,6 @@ impl<'a, 'b> ExtendStakeCpiBuilder<'a, 'b> {
 
             mining: self.instruction.mining.expect("mining is not set"),
 
-            reward_mint: self
-                .instruction
-                .reward_mint
-                .expect("reward_mint is not set"),
-
             deposit_authority: self
                 .instruction
                 .deposit_authority
@@ -602,6 +596,8 @@ impl<'a, 'b> ExtendStakeCpiBuilder<'a, 'b> {
                 .instruction
                 .delegate_mining
                 .expect("delegate_mining is not set"),
+
+            delegate_registry: self.instruction.delegate_registry,
             __args: args,
         };
         instruction.invoke_signed_with_remaining_accounts(
@@ -615,16 +611,15 @@ struct ExtendStakeCpiBuilderInstruction<'a, 'b> {
     __program: &'b solana_program::account_info::AccountInfo<'a>,
     reward_pool: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
-    reward_mint: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     delegate_mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
-    old_lockup_period: Option<LockupPeriod>,
+    delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    deposit_id: Option<u64>,
     new_lockup_period: Option<LockupPeriod>,
-    deposit_start_ts: Option<u64>,
-    base_amount: Option<u64>,
     additional_amount: Option<u64>,
     mining_owner: Option<Pubkey>,
     delegate: Option<Pubkey>,
+    delegate_weights: Option<Vec<u16>>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
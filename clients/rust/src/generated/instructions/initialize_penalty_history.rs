@@ -0,0 +1,409 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+
+/// Accounts.
+pub struct InitializePenaltyHistory {
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: solana_program::pubkey::Pubkey,
+    /// The address of the penalty history of the mining account
+    pub penalty_history: solana_program::pubkey::Pubkey,
+
+    pub payer: solana_program::pubkey::Pubkey,
+    /// The system program
+    pub system_program: solana_program::pubkey::Pubkey,
+}
+
+impl InitializePenaltyHistory {
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        self.instruction_with_remaining_accounts(&[])
+    }
+    #[allow(clippy::vec_init_then_push)]
+    pub fn instruction_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[solana_program::instruction::AccountMeta],
+    ) -> solana_program::instruction::Instruction {
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.mining,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.penalty_history,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.payer, true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.system_program,
+            false,
+        ));
+        accounts.extend_from_slice(remaining_accounts);
+        let data = InitializePenaltyHistoryInstructionData::new()
+            .try_to_vec()
+            .unwrap();
+
+        solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct InitializePenaltyHistoryInstructionData {
+    discriminator: u8,
+}
+
+impl InitializePenaltyHistoryInstructionData {
+    pub fn new() -> Self {
+        Self { discriminator: 12 }
+    }
+}
+
+/// Instruction builder for `InitializePenaltyHistory`.
+///
+/// ### Accounts:
+///
+///   0. `[]` mining
+///   1. `[writable]` penalty_history
+///   2. `[writable, signer]` payer
+///   3. `[optional]` system_program (default to `11111111111111111111111111111111`)
+#[derive(Default)]
+pub struct InitializePenaltyHistoryBuilder {
+    mining: Option<solana_program::pubkey::Pubkey>,
+    penalty_history: Option<solana_program::pubkey::Pubkey>,
+    payer: Option<solana_program::pubkey::Pubkey>,
+    system_program: Option<solana_program::pubkey::Pubkey>,
+    __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
+}
+
+impl InitializePenaltyHistoryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    #[inline(always)]
+    pub fn mining(&mut self, mining: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.mining = Some(mining);
+        self
+    }
+    /// The address of the penalty history of the mining account
+    #[inline(always)]
+    pub fn penalty_history(
+        &mut self,
+        penalty_history: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.penalty_history = Some(penalty_history);
+        self
+    }
+    #[inline(always)]
+    pub fn payer(&mut self, payer: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.payer = Some(payer);
+        self
+    }
+    /// `[optional account, default to '11111111111111111111111111111111']`
+    /// The system program
+    #[inline(always)]
+    pub fn system_program(&mut self, system_program: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.system_program = Some(system_program);
+        self
+    }
+    /// Add an aditional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: solana_program::instruction::AccountMeta,
+    ) -> &mut Self {
+        self.__remaining_accounts.push(account);
+        self
+    }
+    /// Add additional accounts to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[solana_program::instruction::AccountMeta],
+    ) -> &mut Self {
+        self.__remaining_accounts.extend_from_slice(accounts);
+        self
+    }
+    #[allow(clippy::clone_on_copy)]
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = InitializePenaltyHistory {
+            mining: self.mining.expect("mining is not set"),
+            penalty_history: self.penalty_history.expect("penalty_history is not set"),
+            payer: self.payer.expect("payer is not set"),
+            system_program: self
+                .system_program
+                .unwrap_or(solana_program::pubkey!("11111111111111111111111111111111")),
+        };
+
+        accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
+    }
+}
+
+/// `initialize_penalty_history` CPI accounts.
+pub struct InitializePenaltyHistoryCpiAccounts<'a, 'b> {
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the mining account
+    pub penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub payer: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The system program
+    pub system_program: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+/// `initialize_penalty_history` CPI instruction.
+pub struct InitializePenaltyHistoryCpi<'a, 'b> {
+    /// The program to invoke.
+    pub __program: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the mining account
+    pub penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub payer: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The system program
+    pub system_program: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+impl<'a, 'b> InitializePenaltyHistoryCpi<'a, 'b> {
+    pub fn new(
+        program: &'b solana_program::account_info::AccountInfo<'a>,
+        accounts: InitializePenaltyHistoryCpiAccounts<'a, 'b>,
+    ) -> Self {
+        Self {
+            __program: program,
+            mining: accounts.mining,
+            penalty_history: accounts.penalty_history,
+            payer: accounts.payer,
+            system_program: accounts.system_program,
+        }
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], &[])
+    }
+    #[inline(always)]
+    pub fn invoke_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], remaining_accounts)
+    }
+    #[inline(always)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(signers_seeds, &[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed_with_remaining_accounts(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.mining.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.penalty_history.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.payer.key,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.system_program.key,
+            false,
+        ));
+        remaining_accounts.iter().for_each(|remaining_account| {
+            accounts.push(solana_program::instruction::AccountMeta {
+                pubkey: *remaining_account.0.key,
+                is_signer: remaining_account.1,
+                is_writable: remaining_account.2,
+            })
+        });
+        let data = InitializePenaltyHistoryInstructionData::new()
+            .try_to_vec()
+            .unwrap();
+
+        let instruction = solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        };
+        let mut account_infos = Vec::with_capacity(4 + 1 + remaining_accounts.len());
+        account_infos.push(self.__program.clone());
+        account_infos.push(self.mining.clone());
+        account_infos.push(self.penalty_history.clone());
+        account_infos.push(self.payer.clone());
+        account_infos.push(self.system_program.clone());
+        remaining_accounts
+            .iter()
+            .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
+
+        if signers_seeds.is_empty() {
+            solana_program::program::invoke(&instruction, &account_infos)
+        } else {
+            solana_program::program::invoke_signed(&instruction, &account_infos, signers_seeds)
+        }
+    }
+}
+
+/// Instruction builder for `InitializePenaltyHistory` via CPI.
+///
+/// ### Accounts:
+///
+///   0. `[]` mining
+///   1. `[writable]` penalty_history
+///   2. `[writable, signer]` payer
+///   3. `[]` system_program
+pub struct InitializePenaltyHistoryCpiBuilder<'a, 'b> {
+    instruction: Box<InitializePenaltyHistoryCpiBuilderInstruction<'a, 'b>>,
+}
+
+impl<'a, 'b> InitializePenaltyHistoryCpiBuilder<'a, 'b> {
+    pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
+        let instruction = Box::new(InitializePenaltyHistoryCpiBuilderInstruction {
+            __program: program,
+            mining: None,
+            penalty_history: None,
+            payer: None,
+            system_program: None,
+            __remaining_accounts: Vec::new(),
+        });
+        Self { instruction }
+    }
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    #[inline(always)]
+    pub fn mining(
+        &mut self,
+        mining: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.mining = Some(mining);
+        self
+    }
+    /// The address of the penalty history of the mining account
+    #[inline(always)]
+    pub fn penalty_history(
+        &mut self,
+        penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.penalty_history = Some(penalty_history);
+        self
+    }
+    #[inline(always)]
+    pub fn payer(&mut self, payer: &'b solana_program::account_info::AccountInfo<'a>) -> &mut Self {
+        self.instruction.payer = Some(payer);
+        self
+    }
+    /// The system program
+    #[inline(always)]
+    pub fn system_program(
+        &mut self,
+        system_program: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.system_program = Some(system_program);
+        self
+    }
+    /// Add an additional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: &'b solana_program::account_info::AccountInfo<'a>,
+        is_writable: bool,
+        is_signer: bool,
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .push((account, is_writable, is_signer));
+        self
+    }
+    /// Add additional accounts to the instruction.
+    ///
+    /// Each account is represented by a tuple of the `AccountInfo`, a `bool` indicating whether the account is writable or not,
+    /// and a `bool` indicating whether the account is a signer or not.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .extend_from_slice(accounts);
+        self
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed(&[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let instruction = InitializePenaltyHistoryCpi {
+            __program: self.instruction.__program,
+
+            mining: self.instruction.mining.expect("mining is not set"),
+
+            penalty_history: self
+                .instruction
+                .penalty_history
+                .expect("penalty_history is not set"),
+
+            payer: self.instruction.payer.expect("payer is not set"),
+
+            system_program: self
+                .instruction
+                .system_program
+                .expect("system_program is not set"),
+        };
+        instruction.invoke_signed_with_remaining_accounts(
+            signers_seeds,
+            &self.instruction.__remaining_accounts,
+        )
+    }
+}
+
+struct InitializePenaltyHistoryCpiBuilderInstruction<'a, 'b> {
+    __program: &'b solana_program::account_info::AccountInfo<'a>,
+    mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    payer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+    __remaining_accounts: Vec<(
+        &'b solana_program::account_info::AccountInfo<'a>,
+        bool,
+        bool,
+    )>,
+}
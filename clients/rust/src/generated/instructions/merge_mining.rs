@@ -0,0 +1,638 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+
+/// Accounts.
+pub struct MergeMining {
+    /// The address of the reward pool
+    pub reward_pool: solana_program::pubkey::Pubkey,
+    /// The address of the mining account which is merged and closed
+    pub source_mining: solana_program::pubkey::Pubkey,
+    /// The address of the mining account the position is merged into
+    pub mining: solana_program::pubkey::Pubkey,
+    /// The end user the source mining account belongs to
+    pub source_mining_owner: solana_program::pubkey::Pubkey,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: solana_program::pubkey::Pubkey,
+    /// The address where lamports from account closing will be transferred
+    pub target_account: solana_program::pubkey::Pubkey,
+    /// The address of the penalty history of the source mining, which must not exist
+    pub source_penalty_history: solana_program::pubkey::Pubkey,
+    /// The address of the delegate registry of the source mining, which must not exist
+    pub source_delegate_registry: solana_program::pubkey::Pubkey,
+}
+
+impl MergeMining {
+    pub fn instruction(
+        &self,
+        args: MergeMiningInstructionArgs,
+    ) -> solana_program::instruction::Instruction {
+        self.instruction_with_remaining_accounts(args, &[])
+    }
+    #[allow(clippy::vec_init_then_push)]
+    pub fn instruction_with_remaining_accounts(
+        &self,
+        args: MergeMiningInstructionArgs,
+        remaining_accounts: &[solana_program::instruction::AccountMeta],
+    ) -> solana_program::instruction::Instruction {
+        let mut accounts = Vec::with_capacity(8 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.reward_pool,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.source_mining,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.mining,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.source_mining_owner,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.deposit_authority,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.target_account,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.source_penalty_history,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.source_delegate_registry,
+            false,
+        ));
+        accounts.extend_from_slice(remaining_accounts);
+        let mut data = MergeMiningInstructionData::new().try_to_vec().unwrap();
+        let mut args = args.try_to_vec().unwrap();
+        data.append(&mut args);
+
+        solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MergeMiningInstructionData {
+    discriminator: u8,
+}
+
+impl MergeMiningInstructionData {
+    pub fn new() -> Self {
+        Self { discriminator: 23 }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MergeMiningInstructionArgs {
+    pub deposit_ids: Vec<u64>,
+}
+
+/// Instruction builder for `MergeMining`.
+///
+/// ### Accounts:
+///
+///   0. `[writable]` reward_pool
+///   1. `[writable]` source_mining
+///   2. `[writable]` mining
+///   3. `[signer]` source_mining_owner
+///   4. `[signer]` deposit_authority
+///   5. `[writable]` target_account
+///   6. `[]` source_penalty_history
+///   7. `[]` source_delegate_registry
+#[derive(Default)]
+pub struct MergeMiningBuilder {
+    reward_pool: Option<solana_program::pubkey::Pubkey>,
+    source_mining: Option<solana_program::pubkey::Pubkey>,
+    mining: Option<solana_program::pubkey::Pubkey>,
+    source_mining_owner: Option<solana_program::pubkey::Pubkey>,
+    deposit_authority: Option<solana_program::pubkey::Pubkey>,
+    target_account: Option<solana_program::pubkey::Pubkey>,
+    source_penalty_history: Option<solana_program::pubkey::Pubkey>,
+    source_delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    deposit_ids: Option<Vec<u64>>,
+    __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
+}
+
+impl MergeMiningBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The address of the reward pool
+    #[inline(always)]
+    pub fn reward_pool(&mut self, reward_pool: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.reward_pool = Some(reward_pool);
+        self
+    }
+    /// The address of the mining account which is merged and closed
+    #[inline(always)]
+    pub fn source_mining(&mut self, source_mining: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.source_mining = Some(source_mining);
+        self
+    }
+    /// The address of the mining account the position is merged into
+    #[inline(always)]
+    pub fn mining(&mut self, mining: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.mining = Some(mining);
+        self
+    }
+    /// The end user the source mining account belongs to
+    #[inline(always)]
+    pub fn source_mining_owner(
+        &mut self,
+        source_mining_owner: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.source_mining_owner = Some(source_mining_owner);
+        self
+    }
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    #[inline(always)]
+    pub fn deposit_authority(
+        &mut self,
+        deposit_authority: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.deposit_authority = Some(deposit_authority);
+        self
+    }
+    /// The address where lamports from account closing will be transferred
+    #[inline(always)]
+    pub fn target_account(&mut self, target_account: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.target_account = Some(target_account);
+        self
+    }
+    /// The address of the penalty history of the source mining, which must not exist
+    #[inline(always)]
+    pub fn source_penalty_history(
+        &mut self,
+        source_penalty_history: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.source_penalty_history = Some(source_penalty_history);
+        self
+    }
+    /// The address of the delegate registry of the source mining, which must not exist
+    #[inline(always)]
+    pub fn source_delegate_registry(
+        &mut self,
+        source_delegate_registry: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.source_delegate_registry = Some(source_delegate_registry);
+        self
+    }
+    #[inline(always)]
+    pub fn deposit_ids(&mut self, deposit_ids: Vec<u64>) -> &mut Self {
+        self.deposit_ids = Some(deposit_ids);
+        self
+    }
+    /// Add an aditional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: solana_program::instruction::AccountMeta,
+    ) -> &mut Self {
+        self.__remaining_accounts.push(account);
+        self
+    }
+    /// Add additional accounts to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[solana_program::instruction::AccountMeta],
+    ) -> &mut Self {
+        self.__remaining_accounts.extend_from_slice(accounts);
+        self
+    }
+    #[allow(clippy::clone_on_copy)]
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = MergeMining {
+            reward_pool: self.reward_pool.expect("reward_pool is not set"),
+            source_mining: self.source_mining.expect("source_mining is not set"),
+            mining: self.mining.expect("mining is not set"),
+            source_mining_owner: self
+                .source_mining_owner
+                .expect("source_mining_owner is not set"),
+            deposit_authority: self
+                .deposit_authority
+                .expect("deposit_authority is not set"),
+            target_account: self.target_account.expect("target_account is not set"),
+            source_penalty_history: self
+                .source_penalty_history
+                .expect("source_penalty_history is not set"),
+            source_delegate_registry: self
+                .source_delegate_registry
+                .expect("source_delegate_registry is not set"),
+        };
+        let args = MergeMiningInstructionArgs {
+            deposit_ids: self.deposit_ids.clone().expect("deposit_ids is not set"),
+        };
+
+        accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
+    }
+}
+
+/// `merge_mining` CPI accounts.
+pub struct MergeMiningCpiAccounts<'a, 'b> {
+    /// The address of the reward pool
+    pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account which is merged and closed
+    pub source_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account the position is merged into
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The end user the source mining account belongs to
+    pub source_mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address where lamports from account closing will be transferred
+    pub target_account: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the source mining, which must not exist
+    pub source_penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the delegate registry of the source mining, which must not exist
+    pub source_delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+/// `merge_mining` CPI instruction.
+pub struct MergeMiningCpi<'a, 'b> {
+    /// The program to invoke.
+    pub __program: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward pool
+    pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account which is merged and closed
+    pub source_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account the position is merged into
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The end user the source mining account belongs to
+    pub source_mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address where lamports from account closing will be transferred
+    pub target_account: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the source mining, which must not exist
+    pub source_penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the delegate registry of the source mining, which must not exist
+    pub source_delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The arguments for the instruction.
+    pub __args: MergeMiningInstructionArgs,
+}
+
+impl<'a, 'b> MergeMiningCpi<'a, 'b> {
+    pub fn new(
+        program: &'b solana_program::account_info::AccountInfo<'a>,
+        accounts: MergeMiningCpiAccounts<'a, 'b>,
+        args: MergeMiningInstructionArgs,
+    ) -> Self {
+        Self {
+            __program: program,
+            reward_pool: accounts.reward_pool,
+            source_mining: accounts.source_mining,
+            mining: accounts.mining,
+            source_mining_owner: accounts.source_mining_owner,
+            deposit_authority: accounts.deposit_authority,
+            target_account: accounts.target_account,
+            source_penalty_history: accounts.source_penalty_history,
+            source_delegate_registry: accounts.source_delegate_registry,
+            __args: args,
+        }
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], &[])
+    }
+    #[inline(always)]
+    pub fn invoke_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], remaining_accounts)
+    }
+    #[inline(always)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(signers_seeds, &[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed_with_remaining_accounts(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let mut accounts = Vec::with_capacity(8 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.reward_pool.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.source_mining.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.mining.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.source_mining_owner.key,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.deposit_authority.key,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.target_account.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.source_penalty_history.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.source_delegate_registry.key,
+            false,
+        ));
+        remaining_accounts.iter().for_each(|remaining_account| {
+            accounts.push(solana_program::instruction::AccountMeta {
+                pubkey: *remaining_account.0.key,
+                is_signer: remaining_account.1,
+                is_writable: remaining_account.2,
+            })
+        });
+        let mut data = MergeMiningInstructionData::new().try_to_vec().unwrap();
+        let mut args = self.__args.try_to_vec().unwrap();
+        data.append(&mut args);
+
+        let instruction = solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        };
+        let mut account_infos = Vec::with_capacity(8 + 1 + remaining_accounts.len());
+        account_infos.push(self.__program.clone());
+        account_infos.push(self.reward_pool.clone());
+        account_infos.push(self.source_mining.clone());
+        account_infos.push(self.mining.clone());
+        account_infos.push(self.source_mining_owner.clone());
+        account_infos.push(self.deposit_authority.clone());
+        account_infos.push(self.target_account.clone());
+        account_infos.push(self.source_penalty_history.clone());
+        account_infos.push(self.source_delegate_registry.clone());
+        remaining_accounts
+            .iter()
+            .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
+
+        if signers_seeds.is_empty() {
+            solana_program::program::invoke(&instruction, &account_infos)
+        } else {
+            solana_program::program::invoke_signed(&instruction, &account_infos, signers_seeds)
+        }
+    }
+}
+
+/// Instruction builder for `MergeMining` via CPI.
+///
+/// ### Accounts:
+///
+///   0. `[writable]` reward_pool
+///   1. `[writable]` source_mining
+///   2. `[writable]` mining
+///   3. `[signer]` source_mining_owner
+///   4. `[signer]` deposit_authority
+///   5. `[writable]` target_account
+///   6. `[]` source_penalty_history
+///   7. `[]` source_delegate_registry
+pub struct MergeMiningCpiBuilder<'a, 'b> {
+    instruction: Box<MergeMiningCpiBuilderInstruction<'a, 'b>>,
+}
+
+impl<'a, 'b> MergeMiningCpiBuilder<'a, 'b> {
+    pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
+        let instruction = Box::new(MergeMiningCpiBuilderInstruction {
+            __program: program,
+            reward_pool: None,
+            source_mining: None,
+            mining: None,
+            source_mining_owner: None,
+            deposit_authority: None,
+            target_account: None,
+            source_penalty_history: None,
+            source_delegate_registry: None,
+            deposit_ids: None,
+            __remaining_accounts: Vec::new(),
+        });
+        Self { instruction }
+    }
+    /// The address of the reward pool
+    #[inline(always)]
+    pub fn reward_pool(
+        &mut self,
+        reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.reward_pool = Some(reward_pool);
+        self
+    }
+    /// The address of the mining account which is merged and closed
+    #[inline(always)]
+    pub fn source_mining(
+        &mut self,
+        source_mining: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.source_mining = Some(source_mining);
+        self
+    }
+    /// The address of the mining account the position is merged into
+    #[inline(always)]
+    pub fn mining(
+        &mut self,
+        mining: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.mining = Some(mining);
+        self
+    }
+    /// The end user the source mining account belongs to
+    #[inline(always)]
+    pub fn source_mining_owner(
+        &mut self,
+        source_mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.source_mining_owner = Some(source_mining_owner);
+        self
+    }
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    #[inline(always)]
+    pub fn deposit_authority(
+        &mut self,
+        deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.deposit_authority = Some(deposit_authority);
+        self
+    }
+    /// The address where lamports from account closing will be transferred
+    #[inline(always)]
+    pub fn target_account(
+        &mut self,
+        target_account: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.target_account = Some(target_account);
+        self
+    }
+    /// The address of the penalty history of the source mining, which must not exist
+    #[inline(always)]
+    pub fn source_penalty_history(
+        &mut self,
+        source_penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.source_penalty_history = Some(source_penalty_history);
+        self
+    }
+    /// The address of the delegate registry of the source mining, which must not exist
+    #[inline(always)]
+    pub fn source_delegate_registry(
+        &mut self,
+        source_delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.source_delegate_registry = Some(source_delegate_registry);
+        self
+    }
+    #[inline(always)]
+    pub fn deposit_ids(&mut self, deposit_ids: Vec<u64>) -> &mut Self {
+        self.instruction.deposit_ids = Some(deposit_ids);
+        self
+    }
+    /// Add an additional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: &'b solana_program::account_info::AccountInfo<'a>,
+        is_writable: bool,
+        is_signer: bool,
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .push((account, is_writable, is_signer));
+        self
+    }
+    /// Add additional accounts to the instruction.
+    ///
+    /// Each account is represented by a tuple of the `AccountInfo`, a `bool` indicating whether the account is writable or not,
+    /// and a `bool` indicating whether the account is a signer or not.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .extend_from_slice(accounts);
+        self
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed(&[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let args = MergeMiningInstructionArgs {
+            deposit_ids: self
+                .instruction
+                .deposit_ids
+                .clone()
+                .expect("deposit_ids is not set"),
+        };
+        let instruction = MergeMiningCpi {
+            __program: self.instruction.__program,
+
+            reward_pool: self
+                .instruction
+                .reward_pool
+                .expect("reward_pool is not set"),
+
+            source_mining: self
+                .instruction
+                .source_mining
+                .expect("source_mining is not set"),
+
+            mining: self.instruction.mining.expect("mining is not set"),
+
+            source_mining_owner: self
+                .instruction
+                .source_mining_owner
+                .expect("source_mining_owner is not set"),
+
+            deposit_authority: self
+                .instruction
+                .deposit_authority
+                .expect("deposit_authority is not set"),
+
+            target_account: self
+                .instruction
+                .target_account
+                .expect("target_account is not set"),
+
+            source_penalty_history: self
+                .instruction
+                .source_penalty_history
+                .expect("source_penalty_history is not set"),
+
+            source_delegate_registry: self
+                .instruction
+                .source_delegate_registry
+                .expect("source_delegate_registry is not set"),
+            __args: args,
+        };
+        instruction.invoke_signed_with_remaining_accounts(
+            signers_seeds,
+            &self.instruction.__remaining_accounts,
+        )
+    }
+}
+
+struct MergeMiningCpiBuilderInstruction<'a, 'b> {
+    __program: &'b solana_program::account_info::AccountInfo<'a>,
+    reward_pool: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    source_mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    source_mining_owner: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    target_account: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    source_penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    source_delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    deposit_ids: Option<Vec<u64>>,
+    /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+    __remaining_accounts: Vec<(
+        &'b solana_program::account_info::AccountInfo<'a>,
+        bool,
+        bool,
+    )>,
+}
@@ -5,28 +5,68 @@
 //! [https://github.com/metaplex-foundation/kinobi]
 //!
 
+pub(crate) mod r#apply_boost;
 pub(crate) mod r#change_delegate;
 pub(crate) mod r#claim;
 pub(crate) mod r#close_mining;
+pub(crate) mod r#close_pool;
 pub(crate) mod r#decrease_rewards;
 pub(crate) mod r#deposit_mining;
 pub(crate) mod r#distribute_rewards;
 pub(crate) mod r#extend_stake;
 pub(crate) mod r#fill_vault;
 pub(crate) mod r#initialize_mining;
+pub(crate) mod r#initialize_penalty_history;
 pub(crate) mod r#initialize_pool;
+pub(crate) mod r#merge_mining;
+pub(crate) mod r#migrate_mining;
+pub(crate) mod r#migrate_pool;
+pub(crate) mod r#record_deposit;
+pub(crate) mod r#register_delegate;
+pub(crate) mod r#report_solvency;
+pub(crate) mod r#request_withdraw;
+pub(crate) mod r#restore_penalty;
+pub(crate) mod r#set_boost_config;
+pub(crate) mod r#set_epoch_length;
+pub(crate) mod r#set_pool_limits;
+pub(crate) mod r#set_unbonding_config;
 pub(crate) mod r#slash;
+pub(crate) mod r#split_mining;
+pub(crate) mod r#sunset_pool;
+pub(crate) mod r#transfer_mining;
+pub(crate) mod r#unregister_delegate;
+pub(crate) mod r#update_delegate_registry;
 pub(crate) mod r#withdraw_mining;
 
+pub use self::r#apply_boost::*;
 pub use self::r#change_delegate::*;
 pub use self::r#claim::*;
 pub use self::r#close_mining::*;
+pub use self::r#close_pool::*;
 pub use self::r#decrease_rewards::*;
 pub use self::r#deposit_mining::*;
 pub use self::r#distribute_rewards::*;
 pub use self::r#extend_stake::*;
 pub use self::r#fill_vault::*;
 pub use self::r#initialize_mining::*;
+pub use self::r#initialize_penalty_history::*;
 pub use self::r#initialize_pool::*;
+pub use self::r#merge_mining::*;
+pub use self::r#migrate_mining::*;
+pub use self::r#migrate_pool::*;
+pub use self::r#record_deposit::*;
+pub use self::r#register_delegate::*;
+pub use self::r#report_solvency::*;
+pub use self::r#request_withdraw::*;
+pub use self::r#restore_penalty::*;
+pub use self::r#set_boost_config::*;
+pub use self::r#set_epoch_length::*;
+pub use self::r#set_pool_limits::*;
+pub use self::r#set_unbonding_config::*;
 pub use self::r#slash::*;
+pub use self::r#split_mining::*;
+pub use self::r#sunset_pool::*;
+pub use self::r#transfer_mining::*;
+pub use self::r#unregister_delegate::*;
+pub use self::r#update_delegate_registry::*;
 pub use self::r#withdraw_mining::*;
@@ -0,0 +1,502 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+
+/// Accounts.
+pub struct RestorePenalty {
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: solana_program::pubkey::Pubkey,
+    /// The address of the reward pool
+    pub reward_pool: solana_program::pubkey::Pubkey,
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: solana_program::pubkey::Pubkey,
+    /// The address of the penalty history of the mining account
+    pub penalty_history: solana_program::pubkey::Pubkey,
+}
+
+impl RestorePenalty {
+    pub fn instruction(
+        &self,
+        args: RestorePenaltyInstructionArgs,
+    ) -> solana_program::instruction::Instruction {
+        self.instruction_with_remaining_accounts(args, &[])
+    }
+    #[allow(clippy::vec_init_then_push)]
+    pub fn instruction_with_remaining_accounts(
+        &self,
+        args: RestorePenaltyInstructionArgs,
+        remaining_accounts: &[solana_program::instruction::AccountMeta],
+    ) -> solana_program::instruction::Instruction {
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.deposit_authority,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.reward_pool,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.mining,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.penalty_history,
+            false,
+        ));
+        accounts.extend_from_slice(remaining_accounts);
+        let mut data = RestorePenaltyInstructionData::new().try_to_vec().unwrap();
+        let mut args = args.try_to_vec().unwrap();
+        data.append(&mut args);
+
+        solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct RestorePenaltyInstructionData {
+    discriminator: u8,
+}
+
+impl RestorePenaltyInstructionData {
+    pub fn new() -> Self {
+        Self { discriminator: 13 }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RestorePenaltyInstructionArgs {
+    pub mining_owner: Pubkey,
+    pub penalty_id: u64,
+    pub credit_lost_rewards: bool,
+}
+
+/// Instruction builder for `RestorePenalty`.
+///
+/// ### Accounts:
+///
+///   0. `[signer]` deposit_authority
+///   1. `[writable]` reward_pool
+///   2. `[writable]` mining
+///   3. `[writable]` penalty_history
+#[derive(Default)]
+pub struct RestorePenaltyBuilder {
+    deposit_authority: Option<solana_program::pubkey::Pubkey>,
+    reward_pool: Option<solana_program::pubkey::Pubkey>,
+    mining: Option<solana_program::pubkey::Pubkey>,
+    penalty_history: Option<solana_program::pubkey::Pubkey>,
+    mining_owner: Option<Pubkey>,
+    penalty_id: Option<u64>,
+    credit_lost_rewards: Option<bool>,
+    __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
+}
+
+impl RestorePenaltyBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    #[inline(always)]
+    pub fn deposit_authority(
+        &mut self,
+        deposit_authority: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.deposit_authority = Some(deposit_authority);
+        self
+    }
+    /// The address of the reward pool
+    #[inline(always)]
+    pub fn reward_pool(&mut self, reward_pool: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.reward_pool = Some(reward_pool);
+        self
+    }
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    #[inline(always)]
+    pub fn mining(&mut self, mining: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.mining = Some(mining);
+        self
+    }
+    /// The address of the penalty history of the mining account
+    #[inline(always)]
+    pub fn penalty_history(
+        &mut self,
+        penalty_history: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.penalty_history = Some(penalty_history);
+        self
+    }
+    #[inline(always)]
+    pub fn mining_owner(&mut self, mining_owner: Pubkey) -> &mut Self {
+        self.mining_owner = Some(mining_owner);
+        self
+    }
+    #[inline(always)]
+    pub fn penalty_id(&mut self, penalty_id: u64) -> &mut Self {
+        self.penalty_id = Some(penalty_id);
+        self
+    }
+    #[inline(always)]
+    pub fn credit_lost_rewards(&mut self, credit_lost_rewards: bool) -> &mut Self {
+        self.credit_lost_rewards = Some(credit_lost_rewards);
+        self
+    }
+    /// Add an aditional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: solana_program::instruction::AccountMeta,
+    ) -> &mut Self {
+        self.__remaining_accounts.push(account);
+        self
+    }
+    /// Add additional accounts to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[solana_program::instruction::AccountMeta],
+    ) -> &mut Self {
+        self.__remaining_accounts.extend_from_slice(accounts);
+        self
+    }
+    #[allow(clippy::clone_on_copy)]
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = RestorePenalty {
+            deposit_authority: self
+                .deposit_authority
+                .expect("deposit_authority is not set"),
+            reward_pool: self.reward_pool.expect("reward_pool is not set"),
+            mining: self.mining.expect("mining is not set"),
+            penalty_history: self.penalty_history.expect("penalty_history is not set"),
+        };
+        let args = RestorePenaltyInstructionArgs {
+            mining_owner: self.mining_owner.clone().expect("mining_owner is not set"),
+            penalty_id: self.penalty_id.clone().expect("penalty_id is not set"),
+            credit_lost_rewards: self
+                .credit_lost_rewards
+                .clone()
+                .expect("credit_lost_rewards is not set"),
+        };
+
+        accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
+    }
+}
+
+/// `restore_penalty` CPI accounts.
+pub struct RestorePenaltyCpiAccounts<'a, 'b> {
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward pool
+    pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the mining account
+    pub penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+/// `restore_penalty` CPI instruction.
+pub struct RestorePenaltyCpi<'a, 'b> {
+    /// The program to invoke.
+    pub __program: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    pub deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the reward pool
+    pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the mining account
+    pub penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The arguments for the instruction.
+    pub __args: RestorePenaltyInstructionArgs,
+}
+
+impl<'a, 'b> RestorePenaltyCpi<'a, 'b> {
+    pub fn new(
+        program: &'b solana_program::account_info::AccountInfo<'a>,
+        accounts: RestorePenaltyCpiAccounts<'a, 'b>,
+        args: RestorePenaltyInstructionArgs,
+    ) -> Self {
+        Self {
+            __program: program,
+            deposit_authority: accounts.deposit_authority,
+            reward_pool: accounts.reward_pool,
+            mining: accounts.mining,
+            penalty_history: accounts.penalty_history,
+            __args: args,
+        }
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], &[])
+    }
+    #[inline(always)]
+    pub fn invoke_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], remaining_accounts)
+    }
+    #[inline(always)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(signers_seeds, &[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed_with_remaining_accounts(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.deposit_authority.key,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.reward_pool.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.mining.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.penalty_history.key,
+            false,
+        ));
+        remaining_accounts.iter().for_each(|remaining_account| {
+            accounts.push(solana_program::instruction::AccountMeta {
+                pubkey: *remaining_account.0.key,
+                is_signer: remaining_account.1,
+                is_writable: remaining_account.2,
+            })
+        });
+        let mut data = RestorePenaltyInstructionData::new().try_to_vec().unwrap();
+        let mut args = self.__args.try_to_vec().unwrap();
+        data.append(&mut args);
+
+        let instruction = solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        };
+        let mut account_infos = Vec::with_capacity(4 + 1 + remaining_accounts.len());
+        account_infos.push(self.__program.clone());
+        account_infos.push(self.deposit_authority.clone());
+        account_infos.push(self.reward_pool.clone());
+        account_infos.push(self.mining.clone());
+        account_infos.push(self.penalty_history.clone());
+        remaining_accounts
+            .iter()
+            .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
+
+        if signers_seeds.is_empty() {
+            solana_program::program::invoke(&instruction, &account_infos)
+        } else {
+            solana_program::program::invoke_signed(&instruction, &account_infos, signers_seeds)
+        }
+    }
+}
+
+/// Instruction builder for `RestorePenalty` via CPI.
+///
+/// ### Accounts:
+///
+///   0. `[signer]` deposit_authority
+///   1. `[writable]` reward_pool
+///   2. `[writable]` mining
+///   3. `[writable]` penalty_history
+pub struct RestorePenaltyCpiBuilder<'a, 'b> {
+    instruction: Box<RestorePenaltyCpiBuilderInstruction<'a, 'b>>,
+}
+
+impl<'a, 'b> RestorePenaltyCpiBuilder<'a, 'b> {
+    pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
+        let instruction = Box::new(RestorePenaltyCpiBuilderInstruction {
+            __program: program,
+            deposit_authority: None,
+            reward_pool: None,
+            mining: None,
+            penalty_history: None,
+            mining_owner: None,
+            penalty_id: None,
+            credit_lost_rewards: None,
+            __remaining_accounts: Vec::new(),
+        });
+        Self { instruction }
+    }
+    /// The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs
+    #[inline(always)]
+    pub fn deposit_authority(
+        &mut self,
+        deposit_authority: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.deposit_authority = Some(deposit_authority);
+        self
+    }
+    /// The address of the reward pool
+    #[inline(always)]
+    pub fn reward_pool(
+        &mut self,
+        reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.reward_pool = Some(reward_pool);
+        self
+    }
+    /// The address of the mining account which belongs to the user and stores info about user's rewards
+    #[inline(always)]
+    pub fn mining(
+        &mut self,
+        mining: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.mining = Some(mining);
+        self
+    }
+    /// The address of the penalty history of the mining account
+    #[inline(always)]
+    pub fn penalty_history(
+        &mut self,
+        penalty_history: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.penalty_history = Some(penalty_history);
+        self
+    }
+    #[inline(always)]
+    pub fn mining_owner(&mut self, mining_owner: Pubkey) -> &mut Self {
+        self.instruction.mining_owner = Some(mining_owner);
+        self
+    }
+    #[inline(always)]
+    pub fn penalty_id(&mut self, penalty_id: u64) -> &mut Self {
+        self.instruction.penalty_id = Some(penalty_id);
+        self
+    }
+    #[inline(always)]
+    pub fn credit_lost_rewards(&mut self, credit_lost_rewards: bool) -> &mut Self {
+        self.instruction.credit_lost_rewards = Some(credit_lost_rewards);
+        self
+    }
+    /// Add an additional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: &'b solana_program::account_info::AccountInfo<'a>,
+        is_writable: bool,
+        is_signer: bool,
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .push((account, is_writable, is_signer));
+        self
+    }
+    /// Add additional accounts to the instruction.
+    ///
+    /// Each account is represented by a tuple of the `AccountInfo`, a `bool` indicating whether the account is writable or not,
+    /// and a `bool` indicating whether the account is a signer or not.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .extend_from_slice(accounts);
+        self
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed(&[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let args = RestorePenaltyInstructionArgs {
+            mining_owner: self
+                .instruction
+                .mining_owner
+                .clone()
+                .expect("mining_owner is not set"),
+            penalty_id: self
+                .instruction
+                .penalty_id
+                .clone()
+                .expect("penalty_id is not set"),
+            credit_lost_rewards: self
+                .instruction
+                .credit_lost_rewards
+                .clone()
+                .expect("credit_lost_rewards is not set"),
+        };
+        let instruction = RestorePenaltyCpi {
+            __program: self.instruction.__program,
+
+            deposit_authority: self
+                .instruction
+                .deposit_authority
+                .expect("deposit_authority is not set"),
+
+            reward_pool: self
+                .instruction
+                .reward_pool
+                .expect("reward_pool is not set"),
+
+            mining: self.instruction.mining.expect("mining is not set"),
+
+            penalty_history: self
+                .instruction
+                .penalty_history
+                .expect("penalty_history is not set"),
+            __args: args,
+        };
+        instruction.invoke_signed_with_remaining_accounts(
+            signers_seeds,
+            &self.instruction.__remaining_accounts,
+        )
+    }
+}
+
+struct RestorePenaltyCpiBuilderInstruction<'a, 'b> {
+    __program: &'b solana_program::account_info::AccountInfo<'a>,
+    deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    reward_pool: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    mining_owner: Option<Pubkey>,
+    penalty_id: Option<u64>,
+    credit_lost_rewards: Option<bool>,
+    /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+    __remaining_accounts: Vec<(
+        &'b solana_program::account_info::AccountInfo<'a>,
+        bool,
+        bool,
+    )>,
+}
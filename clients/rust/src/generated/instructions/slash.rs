@@ -17,6 +17,8 @@ pub struct Slash {
     pub reward_pool: solana_program::pubkey::Pubkey,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: solana_program::pubkey::Pubkey,
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    pub penalty_history: Option<solana_program::pubkey::Pubkey>,
 }
 
 impl Slash {
@@ -32,7 +34,7 @@ impl Slash {
         args: SlashInstructionArgs,
         remaining_accounts: &[solana_program::instruction::AccountMeta],
     ) -> solana_program::instruction::Instruction {
-        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             self.deposit_authority,
             true,
@@ -45,6 +47,17 @@ impl Slash {
             self.mining,
             false,
         ));
+        if let Some(penalty_history) = self.penalty_history {
+            accounts.push(solana_program::instruction::AccountMeta::new(
+                penalty_history,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         accounts.extend_from_slice(remaining_accounts);
         let mut data = SlashInstructionData::new().try_to_vec().unwrap();
         let mut args = args.try_to_vec().unwrap();
@@ -73,9 +86,9 @@ impl SlashInstructionData {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SlashInstructionArgs {
     pub mining_owner: Pubkey,
+    pub deposit_id: u64,
     pub slash_amount_in_native: u64,
-    pub slash_amount_multiplied_by_period: u64,
-    pub stake_expiration_date: Option<u64>,
+    pub reason_code: u32,
 }
 
 /// Instruction builder for `Slash`.
@@ -85,15 +98,17 @@ pub struct SlashInstructionArgs {
 ///   0. `[signer]` deposit_authority
 ///   1. `[writable]` reward_pool
 ///   2. `[writable]` mining
+///   3. `[writable, optional]` penalty_history
 #[derive(Default)]
 pub struct SlashBuilder {
     deposit_authority: Option<solana_program::pubkey::Pubkey>,
     reward_pool: Option<solana_program::pubkey::Pubkey>,
     mining: Option<solana_program::pubkey::Pubkey>,
+    penalty_history: Option<solana_program::pubkey::Pubkey>,
     mining_owner: Option<Pubkey>,
+    deposit_id: Option<u64>,
     slash_amount_in_native: Option<u64>,
-    slash_amount_multiplied_by_period: Option<u64>,
-    stake_expiration_date: Option<u64>,
+    reason_code: Option<u32>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -122,28 +137,34 @@ impl SlashBuilder {
         self.mining = Some(mining);
         self
     }
+    /// `[optional account]`
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    #[inline(always)]
+    pub fn penalty_history(
+        &mut self,
+        penalty_history: Option<solana_program::pubkey::Pubkey>,
+    ) -> &mut Self {
+        self.penalty_history = penalty_history;
+        self
+    }
     #[inline(always)]
     pub fn mining_owner(&mut self, mining_owner: Pubkey) -> &mut Self {
         self.mining_owner = Some(mining_owner);
         self
     }
     #[inline(always)]
-    pub fn slash_amount_in_native(&mut self, slash_amount_in_native: u64) -> &mut Self {
-        self.slash_amount_in_native = Some(slash_amount_in_native);
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.deposit_id = Some(deposit_id);
         self
     }
     #[inline(always)]
-    pub fn slash_amount_multiplied_by_period(
-        &mut self,
-        slash_amount_multiplied_by_period: u64,
-    ) -> &mut Self {
-        self.slash_amount_multiplied_by_period = Some(slash_amount_multiplied_by_period);
+    pub fn slash_amount_in_native(&mut self, slash_amount_in_native: u64) -> &mut Self {
+        self.slash_amount_in_native = Some(slash_amount_in_native);
         self
     }
-    /// `[optional argument]`
     #[inline(always)]
-    pub fn stake_expiration_date(&mut self, stake_expiration_date: u64) -> &mut Self {
-        self.stake_expiration_date = Some(stake_expiration_date);
+    pub fn reason_code(&mut self, reason_code: u32) -> &mut Self {
+        self.reason_code = Some(reason_code);
         self
     }
     /// Add an aditional account to the instruction.
@@ -172,18 +193,16 @@ impl SlashBuilder {
                 .expect("deposit_authority is not set"),
             reward_pool: self.reward_pool.expect("reward_pool is not set"),
             mining: self.mining.expect("mining is not set"),
+            penalty_history: self.penalty_history,
         };
         let args = SlashInstructionArgs {
             mining_owner: self.mining_owner.clone().expect("mining_owner is not set"),
+            deposit_id: self.deposit_id.clone().expect("deposit_id is not set"),
             slash_amount_in_native: self
                 .slash_amount_in_native
                 .clone()
                 .expect("slash_amount_in_native is not set"),
-            slash_amount_multiplied_by_period: self
-                .slash_amount_multiplied_by_period
-                .clone()
-                .expect("slash_amount_multiplied_by_period is not set"),
-            stake_expiration_date: self.stake_expiration_date.clone(),
+            reason_code: self.reason_code.clone().expect("reason_code is not set"),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -198,6 +217,8 @@ pub struct SlashCpiAccounts<'a, 'b> {
     pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    pub penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
 }
 
 /// `slash` CPI instruction.
@@ -210,6 +231,8 @@ pub struct SlashCpi<'a, 'b> {
     pub reward_pool: &'b solana_program::account_info::AccountInfo<'a>,
     /// The address of the mining account which belongs to the user and stores info about user's rewards
     pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    pub penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     /// The arguments for the instruction.
     pub __args: SlashInstructionArgs,
 }
@@ -225,6 +248,7 @@ impl<'a, 'b> SlashCpi<'a, 'b> {
             deposit_authority: accounts.deposit_authority,
             reward_pool: accounts.reward_pool,
             mining: accounts.mining,
+            penalty_history: accounts.penalty_history,
             __args: args,
         }
     }
@@ -261,7 +285,7 @@ impl<'a, 'b> SlashCpi<'a, 'b> {
             bool,
         )],
     ) -> solana_program::entrypoint::ProgramResult {
-        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new_readonly(
             *self.deposit_authority.key,
             true,
@@ -274,6 +298,17 @@ impl<'a, 'b> SlashCpi<'a, 'b> {
             *self.mining.key,
             false,
         ));
+        if let Some(penalty_history) = self.penalty_history {
+            accounts.push(solana_program::instruction::AccountMeta::new(
+                *penalty_history.key,
+                false,
+            ));
+        } else {
+            accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+                crate::MPLX_REWARDS_ID,
+                false,
+            ));
+        }
         remaining_accounts.iter().for_each(|remaining_account| {
             accounts.push(solana_program::instruction::AccountMeta {
                 pubkey: *remaining_account.0.key,
@@ -290,11 +325,14 @@ impl<'a, 'b> SlashCpi<'a, 'b> {
             accounts,
             data,
         };
-        let mut account_infos = Vec::with_capacity(3 + 1 + remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(4 + 1 + remaining_accounts.len());
         account_infos.push(self.__program.clone());
         account_infos.push(self.deposit_authority.clone());
         account_infos.push(self.reward_pool.clone());
         account_infos.push(self.mining.clone());
+        if let Some(penalty_history) = self.penalty_history {
+            account_infos.push(penalty_history.clone());
+        }
         remaining_accounts
             .iter()
             .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
@@ -314,6 +352,7 @@ impl<'a, 'b> SlashCpi<'a, 'b> {
 ///   0. `[signer]` deposit_authority
 ///   1. `[writable]` reward_pool
 ///   2. `[writable]` mining
+///   3. `[writable, optional]` penalty_history
 pub struct SlashCpiBuilder<'a, 'b> {
     instruction: Box<SlashCpiBuilderInstruction<'a, 'b>>,
 }
@@ -325,10 +364,11 @@ impl<'a, 'b> SlashCpiBuilder<'a, 'b> {
             deposit_authority: None,
             reward_pool: None,
             mining: None,
+            penalty_history: None,
             mining_owner: None,
+            deposit_id: None,
             slash_amount_in_native: None,
-            slash_amount_multiplied_by_period: None,
-            stake_expiration_date: None,
+            reason_code: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -360,29 +400,34 @@ impl<'a, 'b> SlashCpiBuilder<'a, 'b> {
         self.instruction.mining = Some(mining);
         self
     }
+    /// `[optional account]`
+    /// The address of the penalty history of the mining account, the penalty isn't recorded without it
+    #[inline(always)]
+    pub fn penalty_history(
+        &mut self,
+        penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    ) -> &mut Self {
+        self.instruction.penalty_history = penalty_history;
+        self
+    }
     #[inline(always)]
     pub fn mining_owner(&mut self, mining_owner: Pubkey) -> &mut Self {
         self.instruction.mining_owner = Some(mining_owner);
         self
     }
     #[inline(always)]
-    pub fn slash_amount_in_native(&mut self, slash_amount_in_native: u64) -> &mut Self {
-        self.instruction.slash_amount_in_native = Some(slash_amount_in_native);
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.instruction.deposit_id = Some(deposit_id);
         self
     }
     #[inline(always)]
-    pub fn slash_amount_multiplied_by_period(
-        &mut self,
-        slash_amount_multiplied_by_period: u64,
-    ) -> &mut Self {
-        self.instruction.slash_amount_multiplied_by_period =
-            Some(slash_amount_multiplied_by_period);
+    pub fn slash_amount_in_native(&mut self, slash_amount_in_native: u64) -> &mut Self {
+        self.instruction.slash_amount_in_native = Some(slash_amount_in_native);
         self
     }
-    /// `[optional argument]`
     #[inline(always)]
-    pub fn stake_expiration_date(&mut self, stake_expiration_date: u64) -> &mut Self {
-        self.instruction.stake_expiration_date = Some(stake_expiration_date);
+    pub fn reason_code(&mut self, reason_code: u32) -> &mut Self {
+        self.instruction.reason_code = Some(reason_code);
         self
     }
     /// Add an additional account to the instruction.
@@ -432,17 +477,21 @@ impl<'a, 'b> SlashCpiBuilder<'a, 'b> {
                 .mining_owner
                 .clone()
                 .expect("mining_owner is not set"),
+            deposit_id: self
+                .instruction
+                .deposit_id
+                .clone()
+                .expect("deposit_id is not set"),
             slash_amount_in_native: self
                 .instruction
                 .slash_amount_in_native
                 .clone()
                 .expect("slash_amount_in_native is not set"),
-            slash_amount_multiplied_by_period: self
+            reason_code: self
                 .instruction
-                .slash_amount_multiplied_by_period
+                .reason_code
                 .clone()
-                .expect("slash_amount_multiplied_by_period is not set"),
-            stake_expiration_date: self.instruction.stake_expiration_date.clone(),
+                .expect("reason_code is not set"),
         };
         let instruction = SlashCpi {
             __program: self.instruction.__program,
@@ -458,6 +507,8 @@ impl<'a, 'b> SlashCpiBuilder<'a, 'b> {
                 .expect("reward_pool is not set"),
 
             mining: self.instruction.mining.expect("mining is not set"),
+
+            penalty_history: self.instruction.penalty_history,
             __args: args,
         };
         instruction.invoke_signed_with_remaining_accounts(
@@ -472,10 +523,11 @@ struct SlashCpiBuilderInstruction<'a, 'b> {
     deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     reward_pool: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    penalty_history: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     mining_owner: Option<Pubkey>,
+    deposit_id: Option<u64>,
     slash_amount_in_native: Option<u64>,
-    slash_amount_multiplied_by_period: Option<u64>,
-    stake_expiration_date: Option<u64>,
+    reason_code: Option<u32>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
@@ -0,0 +1,417 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+
+/// Accounts.
+pub struct UnregisterDelegate {
+    /// The address of the delegate's mining account
+    pub mining: solana_program::pubkey::Pubkey,
+    /// The address of the delegate registry of the mining account
+    pub delegate_registry: solana_program::pubkey::Pubkey,
+    /// The end user the mining accounts belongs to
+    pub mining_owner: solana_program::pubkey::Pubkey,
+    /// The address where lamports from account closing will be transferred
+    pub target_account: solana_program::pubkey::Pubkey,
+}
+
+impl UnregisterDelegate {
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        self.instruction_with_remaining_accounts(&[])
+    }
+    #[allow(clippy::vec_init_then_push)]
+    pub fn instruction_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[solana_program::instruction::AccountMeta],
+    ) -> solana_program::instruction::Instruction {
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.mining,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.delegate_registry,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.mining_owner,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.target_account,
+            false,
+        ));
+        accounts.extend_from_slice(remaining_accounts);
+        let data = UnregisterDelegateInstructionData::new()
+            .try_to_vec()
+            .unwrap();
+
+        solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct UnregisterDelegateInstructionData {
+    discriminator: u8,
+}
+
+impl UnregisterDelegateInstructionData {
+    pub fn new() -> Self {
+        Self { discriminator: 27 }
+    }
+}
+
+/// Instruction builder for `UnregisterDelegate`.
+///
+/// ### Accounts:
+///
+///   0. `[]` mining
+///   1. `[writable]` delegate_registry
+///   2. `[signer]` mining_owner
+///   3. `[writable]` target_account
+#[derive(Default)]
+pub struct UnregisterDelegateBuilder {
+    mining: Option<solana_program::pubkey::Pubkey>,
+    delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    mining_owner: Option<solana_program::pubkey::Pubkey>,
+    target_account: Option<solana_program::pubkey::Pubkey>,
+    __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
+}
+
+impl UnregisterDelegateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The address of the delegate's mining account
+    #[inline(always)]
+    pub fn mining(&mut self, mining: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.mining = Some(mining);
+        self
+    }
+    /// The address of the delegate registry of the mining account
+    #[inline(always)]
+    pub fn delegate_registry(
+        &mut self,
+        delegate_registry: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.delegate_registry = Some(delegate_registry);
+        self
+    }
+    /// The end user the mining accounts belongs to
+    #[inline(always)]
+    pub fn mining_owner(&mut self, mining_owner: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.mining_owner = Some(mining_owner);
+        self
+    }
+    /// The address where lamports from account closing will be transferred
+    #[inline(always)]
+    pub fn target_account(&mut self, target_account: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.target_account = Some(target_account);
+        self
+    }
+    /// Add an aditional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: solana_program::instruction::AccountMeta,
+    ) -> &mut Self {
+        self.__remaining_accounts.push(account);
+        self
+    }
+    /// Add additional accounts to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[solana_program::instruction::AccountMeta],
+    ) -> &mut Self {
+        self.__remaining_accounts.extend_from_slice(accounts);
+        self
+    }
+    #[allow(clippy::clone_on_copy)]
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = UnregisterDelegate {
+            mining: self.mining.expect("mining is not set"),
+            delegate_registry: self
+                .delegate_registry
+                .expect("delegate_registry is not set"),
+            mining_owner: self.mining_owner.expect("mining_owner is not set"),
+            target_account: self.target_account.expect("target_account is not set"),
+        };
+
+        accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
+    }
+}
+
+/// `unregister_delegate` CPI accounts.
+pub struct UnregisterDelegateCpiAccounts<'a, 'b> {
+    /// The address of the delegate's mining account
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the delegate registry of the mining account
+    pub delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The end user the mining accounts belongs to
+    pub mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address where lamports from account closing will be transferred
+    pub target_account: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+/// `unregister_delegate` CPI instruction.
+pub struct UnregisterDelegateCpi<'a, 'b> {
+    /// The program to invoke.
+    pub __program: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the delegate's mining account
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the delegate registry of the mining account
+    pub delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The end user the mining accounts belongs to
+    pub mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address where lamports from account closing will be transferred
+    pub target_account: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+impl<'a, 'b> UnregisterDelegateCpi<'a, 'b> {
+    pub fn new(
+        program: &'b solana_program::account_info::AccountInfo<'a>,
+        accounts: UnregisterDelegateCpiAccounts<'a, 'b>,
+    ) -> Self {
+        Self {
+            __program: program,
+            mining: accounts.mining,
+            delegate_registry: accounts.delegate_registry,
+            mining_owner: accounts.mining_owner,
+            target_account: accounts.target_account,
+        }
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], &[])
+    }
+    #[inline(always)]
+    pub fn invoke_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], remaining_accounts)
+    }
+    #[inline(always)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(signers_seeds, &[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed_with_remaining_accounts(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.mining.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.delegate_registry.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.mining_owner.key,
+            true,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.target_account.key,
+            false,
+        ));
+        remaining_accounts.iter().for_each(|remaining_account| {
+            accounts.push(solana_program::instruction::AccountMeta {
+                pubkey: *remaining_account.0.key,
+                is_signer: remaining_account.1,
+                is_writable: remaining_account.2,
+            })
+        });
+        let data = UnregisterDelegateInstructionData::new()
+            .try_to_vec()
+            .unwrap();
+
+        let instruction = solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        };
+        let mut account_infos = Vec::with_capacity(4 + 1 + remaining_accounts.len());
+        account_infos.push(self.__program.clone());
+        account_infos.push(self.mining.clone());
+        account_infos.push(self.delegate_registry.clone());
+        account_infos.push(self.mining_owner.clone());
+        account_infos.push(self.target_account.clone());
+        remaining_accounts
+            .iter()
+            .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
+
+        if signers_seeds.is_empty() {
+            solana_program::program::invoke(&instruction, &account_infos)
+        } else {
+            solana_program::program::invoke_signed(&instruction, &account_infos, signers_seeds)
+        }
+    }
+}
+
+/// Instruction builder for `UnregisterDelegate` via CPI.
+///
+/// ### Accounts:
+///
+///   0. `[]` mining
+///   1. `[writable]` delegate_registry
+///   2. `[signer]` mining_owner
+///   3. `[writable]` target_account
+pub struct UnregisterDelegateCpiBuilder<'a, 'b> {
+    instruction: Box<UnregisterDelegateCpiBuilderInstruction<'a, 'b>>,
+}
+
+impl<'a, 'b> UnregisterDelegateCpiBuilder<'a, 'b> {
+    pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
+        let instruction = Box::new(UnregisterDelegateCpiBuilderInstruction {
+            __program: program,
+            mining: None,
+            delegate_registry: None,
+            mining_owner: None,
+            target_account: None,
+            __remaining_accounts: Vec::new(),
+        });
+        Self { instruction }
+    }
+    /// The address of the delegate's mining account
+    #[inline(always)]
+    pub fn mining(
+        &mut self,
+        mining: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.mining = Some(mining);
+        self
+    }
+    /// The address of the delegate registry of the mining account
+    #[inline(always)]
+    pub fn delegate_registry(
+        &mut self,
+        delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.delegate_registry = Some(delegate_registry);
+        self
+    }
+    /// The end user the mining accounts belongs to
+    #[inline(always)]
+    pub fn mining_owner(
+        &mut self,
+        mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.mining_owner = Some(mining_owner);
+        self
+    }
+    /// The address where lamports from account closing will be transferred
+    #[inline(always)]
+    pub fn target_account(
+        &mut self,
+        target_account: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.target_account = Some(target_account);
+        self
+    }
+    /// Add an additional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: &'b solana_program::account_info::AccountInfo<'a>,
+        is_writable: bool,
+        is_signer: bool,
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .push((account, is_writable, is_signer));
+        self
+    }
+    /// Add additional accounts to the instruction.
+    ///
+    /// Each account is represented by a tuple of the `AccountInfo`, a `bool` indicating whether the account is writable or not,
+    /// and a `bool` indicating whether the account is a signer or not.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .extend_from_slice(accounts);
+        self
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed(&[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let instruction = UnregisterDelegateCpi {
+            __program: self.instruction.__program,
+
+            mining: self.instruction.mining.expect("mining is not set"),
+
+            delegate_registry: self
+                .instruction
+                .delegate_registry
+                .expect("delegate_registry is not set"),
+
+            mining_owner: self
+                .instruction
+                .mining_owner
+                .expect("mining_owner is not set"),
+
+            target_account: self
+                .instruction
+                .target_account
+                .expect("target_account is not set"),
+        };
+        instruction.invoke_signed_with_remaining_accounts(
+            signers_seeds,
+            &self.instruction.__remaining_accounts,
+        )
+    }
+}
+
+struct UnregisterDelegateCpiBuilderInstruction<'a, 'b> {
+    __program: &'b solana_program::account_info::AccountInfo<'a>,
+    mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    mining_owner: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    target_account: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+    __remaining_accounts: Vec<(
+        &'b solana_program::account_info::AccountInfo<'a>,
+        bool,
+        bool,
+    )>,
+}
@@ -0,0 +1,461 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+
+/// Accounts.
+pub struct UpdateDelegateRegistry {
+    /// The address of the delegate's mining account
+    pub mining: solana_program::pubkey::Pubkey,
+    /// The address of the delegate registry of the mining account
+    pub delegate_registry: solana_program::pubkey::Pubkey,
+    /// The end user the mining accounts belongs to
+    pub mining_owner: solana_program::pubkey::Pubkey,
+}
+
+impl UpdateDelegateRegistry {
+    pub fn instruction(
+        &self,
+        args: UpdateDelegateRegistryInstructionArgs,
+    ) -> solana_program::instruction::Instruction {
+        self.instruction_with_remaining_accounts(args, &[])
+    }
+    #[allow(clippy::vec_init_then_push)]
+    pub fn instruction_with_remaining_accounts(
+        &self,
+        args: UpdateDelegateRegistryInstructionArgs,
+        remaining_accounts: &[solana_program::instruction::AccountMeta],
+    ) -> solana_program::instruction::Instruction {
+        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.mining,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            self.delegate_registry,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.mining_owner,
+            true,
+        ));
+        accounts.extend_from_slice(remaining_accounts);
+        let mut data = UpdateDelegateRegistryInstructionData::new()
+            .try_to_vec()
+            .unwrap();
+        let mut args = args.try_to_vec().unwrap();
+        data.append(&mut args);
+
+        solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct UpdateDelegateRegistryInstructionData {
+    discriminator: u8,
+}
+
+impl UpdateDelegateRegistryInstructionData {
+    pub fn new() -> Self {
+        Self { discriminator: 26 }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateDelegateRegistryInstructionArgs {
+    pub max_stake_from_others: u64,
+    pub allow_list: Vec<Pubkey>,
+    pub deny_list: Vec<Pubkey>,
+}
+
+/// Instruction builder for `UpdateDelegateRegistry`.
+///
+/// ### Accounts:
+///
+///   0. `[]` mining
+///   1. `[writable]` delegate_registry
+///   2. `[signer]` mining_owner
+#[derive(Default)]
+pub struct UpdateDelegateRegistryBuilder {
+    mining: Option<solana_program::pubkey::Pubkey>,
+    delegate_registry: Option<solana_program::pubkey::Pubkey>,
+    mining_owner: Option<solana_program::pubkey::Pubkey>,
+    max_stake_from_others: Option<u64>,
+    allow_list: Option<Vec<Pubkey>>,
+    deny_list: Option<Vec<Pubkey>>,
+    __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
+}
+
+impl UpdateDelegateRegistryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The address of the delegate's mining account
+    #[inline(always)]
+    pub fn mining(&mut self, mining: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.mining = Some(mining);
+        self
+    }
+    /// The address of the delegate registry of the mining account
+    #[inline(always)]
+    pub fn delegate_registry(
+        &mut self,
+        delegate_registry: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.delegate_registry = Some(delegate_registry);
+        self
+    }
+    /// The end user the mining accounts belongs to
+    #[inline(always)]
+    pub fn mining_owner(&mut self, mining_owner: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.mining_owner = Some(mining_owner);
+        self
+    }
+    #[inline(always)]
+    pub fn max_stake_from_others(&mut self, max_stake_from_others: u64) -> &mut Self {
+        self.max_stake_from_others = Some(max_stake_from_others);
+        self
+    }
+    #[inline(always)]
+    pub fn allow_list(&mut self, allow_list: Vec<Pubkey>) -> &mut Self {
+        self.allow_list = Some(allow_list);
+        self
+    }
+    #[inline(always)]
+    pub fn deny_list(&mut self, deny_list: Vec<Pubkey>) -> &mut Self {
+        self.deny_list = Some(deny_list);
+        self
+    }
+    /// Add an aditional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: solana_program::instruction::AccountMeta,
+    ) -> &mut Self {
+        self.__remaining_accounts.push(account);
+        self
+    }
+    /// Add additional accounts to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[solana_program::instruction::AccountMeta],
+    ) -> &mut Self {
+        self.__remaining_accounts.extend_from_slice(accounts);
+        self
+    }
+    #[allow(clippy::clone_on_copy)]
+    pub fn instruction(&self) -> solana_program::instruction::Instruction {
+        let accounts = UpdateDelegateRegistry {
+            mining: self.mining.expect("mining is not set"),
+            delegate_registry: self
+                .delegate_registry
+                .expect("delegate_registry is not set"),
+            mining_owner: self.mining_owner.expect("mining_owner is not set"),
+        };
+        let args = UpdateDelegateRegistryInstructionArgs {
+            max_stake_from_others: self
+                .max_stake_from_others
+                .clone()
+                .expect("max_stake_from_others is not set"),
+            allow_list: self.allow_list.clone().expect("allow_list is not set"),
+            deny_list: self.deny_list.clone().expect("deny_list is not set"),
+        };
+
+        accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
+    }
+}
+
+/// `update_delegate_registry` CPI accounts.
+pub struct UpdateDelegateRegistryCpiAccounts<'a, 'b> {
+    /// The address of the delegate's mining account
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the delegate registry of the mining account
+    pub delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The end user the mining accounts belongs to
+    pub mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+}
+
+/// `update_delegate_registry` CPI instruction.
+pub struct UpdateDelegateRegistryCpi<'a, 'b> {
+    /// The program to invoke.
+    pub __program: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the delegate's mining account
+    pub mining: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The address of the delegate registry of the mining account
+    pub delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The end user the mining accounts belongs to
+    pub mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+    /// The arguments for the instruction.
+    pub __args: UpdateDelegateRegistryInstructionArgs,
+}
+
+impl<'a, 'b> UpdateDelegateRegistryCpi<'a, 'b> {
+    pub fn new(
+        program: &'b solana_program::account_info::AccountInfo<'a>,
+        accounts: UpdateDelegateRegistryCpiAccounts<'a, 'b>,
+        args: UpdateDelegateRegistryInstructionArgs,
+    ) -> Self {
+        Self {
+            __program: program,
+            mining: accounts.mining,
+            delegate_registry: accounts.delegate_registry,
+            mining_owner: accounts.mining_owner,
+            __args: args,
+        }
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], &[])
+    }
+    #[inline(always)]
+    pub fn invoke_with_remaining_accounts(
+        &self,
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(&[], remaining_accounts)
+    }
+    #[inline(always)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed_with_remaining_accounts(signers_seeds, &[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed_with_remaining_accounts(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+        remaining_accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.mining.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new(
+            *self.delegate_registry.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.mining_owner.key,
+            true,
+        ));
+        remaining_accounts.iter().for_each(|remaining_account| {
+            accounts.push(solana_program::instruction::AccountMeta {
+                pubkey: *remaining_account.0.key,
+                is_signer: remaining_account.1,
+                is_writable: remaining_account.2,
+            })
+        });
+        let mut data = UpdateDelegateRegistryInstructionData::new()
+            .try_to_vec()
+            .unwrap();
+        let mut args = self.__args.try_to_vec().unwrap();
+        data.append(&mut args);
+
+        let instruction = solana_program::instruction::Instruction {
+            program_id: crate::MPLX_REWARDS_ID,
+            accounts,
+            data,
+        };
+        let mut account_infos = Vec::with_capacity(3 + 1 + remaining_accounts.len());
+        account_infos.push(self.__program.clone());
+        account_infos.push(self.mining.clone());
+        account_infos.push(self.delegate_registry.clone());
+        account_infos.push(self.mining_owner.clone());
+        remaining_accounts
+            .iter()
+            .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
+
+        if signers_seeds.is_empty() {
+            solana_program::program::invoke(&instruction, &account_infos)
+        } else {
+            solana_program::program::invoke_signed(&instruction, &account_infos, signers_seeds)
+        }
+    }
+}
+
+/// Instruction builder for `UpdateDelegateRegistry` via CPI.
+///
+/// ### Accounts:
+///
+///   0. `[]` mining
+///   1. `[writable]` delegate_registry
+///   2. `[signer]` mining_owner
+pub struct UpdateDelegateRegistryCpiBuilder<'a, 'b> {
+    instruction: Box<UpdateDelegateRegistryCpiBuilderInstruction<'a, 'b>>,
+}
+
+impl<'a, 'b> UpdateDelegateRegistryCpiBuilder<'a, 'b> {
+    pub fn new(program: &'b solana_program::account_info::AccountInfo<'a>) -> Self {
+        let instruction = Box::new(UpdateDelegateRegistryCpiBuilderInstruction {
+            __program: program,
+            mining: None,
+            delegate_registry: None,
+            mining_owner: None,
+            max_stake_from_others: None,
+            allow_list: None,
+            deny_list: None,
+            __remaining_accounts: Vec::new(),
+        });
+        Self { instruction }
+    }
+    /// The address of the delegate's mining account
+    #[inline(always)]
+    pub fn mining(
+        &mut self,
+        mining: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.mining = Some(mining);
+        self
+    }
+    /// The address of the delegate registry of the mining account
+    #[inline(always)]
+    pub fn delegate_registry(
+        &mut self,
+        delegate_registry: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.delegate_registry = Some(delegate_registry);
+        self
+    }
+    /// The end user the mining accounts belongs to
+    #[inline(always)]
+    pub fn mining_owner(
+        &mut self,
+        mining_owner: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.mining_owner = Some(mining_owner);
+        self
+    }
+    #[inline(always)]
+    pub fn max_stake_from_others(&mut self, max_stake_from_others: u64) -> &mut Self {
+        self.instruction.max_stake_from_others = Some(max_stake_from_others);
+        self
+    }
+    #[inline(always)]
+    pub fn allow_list(&mut self, allow_list: Vec<Pubkey>) -> &mut Self {
+        self.instruction.allow_list = Some(allow_list);
+        self
+    }
+    #[inline(always)]
+    pub fn deny_list(&mut self, deny_list: Vec<Pubkey>) -> &mut Self {
+        self.instruction.deny_list = Some(deny_list);
+        self
+    }
+    /// Add an additional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(
+        &mut self,
+        account: &'b solana_program::account_info::AccountInfo<'a>,
+        is_writable: bool,
+        is_signer: bool,
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .push((account, is_writable, is_signer));
+        self
+    }
+    /// Add additional accounts to the instruction.
+    ///
+    /// Each account is represented by a tuple of the `AccountInfo`, a `bool` indicating whether the account is writable or not,
+    /// and a `bool` indicating whether the account is a signer or not.
+    #[inline(always)]
+    pub fn add_remaining_accounts(
+        &mut self,
+        accounts: &[(
+            &'b solana_program::account_info::AccountInfo<'a>,
+            bool,
+            bool,
+        )],
+    ) -> &mut Self {
+        self.instruction
+            .__remaining_accounts
+            .extend_from_slice(accounts);
+        self
+    }
+    #[inline(always)]
+    pub fn invoke(&self) -> solana_program::entrypoint::ProgramResult {
+        self.invoke_signed(&[])
+    }
+    #[allow(clippy::clone_on_copy)]
+    #[allow(clippy::vec_init_then_push)]
+    pub fn invoke_signed(
+        &self,
+        signers_seeds: &[&[&[u8]]],
+    ) -> solana_program::entrypoint::ProgramResult {
+        let args = UpdateDelegateRegistryInstructionArgs {
+            max_stake_from_others: self
+                .instruction
+                .max_stake_from_others
+                .clone()
+                .expect("max_stake_from_others is not set"),
+            allow_list: self
+                .instruction
+                .allow_list
+                .clone()
+                .expect("allow_list is not set"),
+            deny_list: self
+                .instruction
+                .deny_list
+                .clone()
+                .expect("deny_list is not set"),
+        };
+        let instruction = UpdateDelegateRegistryCpi {
+            __program: self.instruction.__program,
+
+            mining: self.instruction.mining.expect("mining is not set"),
+
+            delegate_registry: self
+                .instruction
+                .delegate_registry
+                .expect("delegate_registry is not set"),
+
+            mining_owner: self
+                .instruction
+                .mining_owner
+                .expect("mining_owner is not set"),
+            __args: args,
+        };
+        instruction.invoke_signed_with_remaining_accounts(
+            signers_seeds,
+            &self.instruction.__remaining_accounts,
+        )
+    }
+}
+
+struct UpdateDelegateRegistryCpiBuilderInstruction<'a, 'b> {
+    __program: &'b solana_program::account_info::AccountInfo<'a>,
+    mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    delegate_registry: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    mining_owner: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    max_stake_from_others: Option<u64>,
+    allow_list: Option<Vec<Pubkey>>,
+    deny_list: Option<Vec<Pubkey>>,
+    /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+    __remaining_accounts: Vec<(
+        &'b solana_program::account_info::AccountInfo<'a>,
+        bool,
+        bool,
+    )>,
+}
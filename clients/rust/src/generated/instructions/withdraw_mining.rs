@@ -47,7 +47,7 @@ impl WithdrawMining {
             self.deposit_authority,
             true,
         ));
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+        accounts.push(solana_program::instruction::AccountMeta::new(
             self.delegate_mining,
             false,
         ));
@@ -78,9 +78,11 @@ impl WithdrawMiningInstructionData {
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WithdrawMiningInstructionArgs {
+    pub deposit_id: u64,
     pub amount: u64,
     pub mining_owner: Pubkey,
     pub delegate: Pubkey,
+    pub delegate_weights: Vec<u16>,
 }
 
 /// Instruction builder for `WithdrawMining`.
@@ -90,16 +92,18 @@ pub struct WithdrawMiningInstructionArgs {
 ///   0. `[writable]` reward_pool
 ///   1. `[writable]` mining
 ///   2. `[signer]` deposit_authority
-///   3. `[]` delegate_mining
+///   3. `[writable]` delegate_mining
 #[derive(Default)]
 pub struct WithdrawMiningBuilder {
     reward_pool: Option<solana_program::pubkey::Pubkey>,
     mining: Option<solana_program::pubkey::Pubkey>,
     deposit_authority: Option<solana_program::pubkey::Pubkey>,
     delegate_mining: Option<solana_program::pubkey::Pubkey>,
+    deposit_id: Option<u64>,
     amount: Option<u64>,
     mining_owner: Option<Pubkey>,
     delegate: Option<Pubkey>,
+    delegate_weights: Option<Vec<u16>>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -138,6 +142,11 @@ impl WithdrawMiningBuilder {
         self
     }
     #[inline(always)]
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.deposit_id = Some(deposit_id);
+        self
+    }
+    #[inline(always)]
     pub fn amount(&mut self, amount: u64) -> &mut Self {
         self.amount = Some(amount);
         self
@@ -152,6 +161,11 @@ impl WithdrawMiningBuilder {
         self.delegate = Some(delegate);
         self
     }
+    #[inline(always)]
+    pub fn delegate_weights(&mut self, delegate_weights: Vec<u16>) -> &mut Self {
+        self.delegate_weights = Some(delegate_weights);
+        self
+    }
     /// Add an aditional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -181,9 +195,14 @@ impl WithdrawMiningBuilder {
             delegate_mining: self.delegate_mining.expect("delegate_mining is not set"),
         };
         let args = WithdrawMiningInstructionArgs {
+            deposit_id: self.deposit_id.clone().expect("deposit_id is not set"),
             amount: self.amount.clone().expect("amount is not set"),
             mining_owner: self.mining_owner.clone().expect("mining_owner is not set"),
             delegate: self.delegate.clone().expect("delegate is not set"),
+            delegate_weights: self
+                .delegate_weights
+                .clone()
+                .expect("delegate_weights is not set"),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -279,7 +298,7 @@ impl<'a, 'b> WithdrawMiningCpi<'a, 'b> {
             *self.deposit_authority.key,
             true,
         ));
-        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+        accounts.push(solana_program::instruction::AccountMeta::new(
             *self.delegate_mining.key,
             false,
         ));
@@ -324,7 +343,7 @@ impl<'a, 'b> WithdrawMiningCpi<'a, 'b> {
 ///   0. `[writable]` reward_pool
 ///   1. `[writable]` mining
 ///   2. `[signer]` deposit_authority
-///   3. `[]` delegate_mining
+///   3. `[writable]` delegate_mining
 pub struct WithdrawMiningCpiBuilder<'a, 'b> {
     instruction: Box<WithdrawMiningCpiBuilderInstruction<'a, 'b>>,
 }
@@ -337,9 +356,11 @@ impl<'a, 'b> WithdrawMiningCpiBuilder<'a, 'b> {
             mining: None,
             deposit_authority: None,
             delegate_mining: None,
+            deposit_id: None,
             amount: None,
             mining_owner: None,
             delegate: None,
+            delegate_weights: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -381,6 +402,11 @@ impl<'a, 'b> WithdrawMiningCpiBuilder<'a, 'b> {
         self
     }
     #[inline(always)]
+    pub fn deposit_id(&mut self, deposit_id: u64) -> &mut Self {
+        self.instruction.deposit_id = Some(deposit_id);
+        self
+    }
+    #[inline(always)]
     pub fn amount(&mut self, amount: u64) -> &mut Self {
         self.instruction.amount = Some(amount);
         self
@@ -395,6 +421,11 @@ impl<'a, 'b> WithdrawMiningCpiBuilder<'a, 'b> {
         self.instruction.delegate = Some(delegate);
         self
     }
+    #[inline(always)]
+    pub fn delegate_weights(&mut self, delegate_weights: Vec<u16>) -> &mut Self {
+        self.instruction.delegate_weights = Some(delegate_weights);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -437,6 +468,11 @@ impl<'a, 'b> WithdrawMiningCpiBuilder<'a, 'b> {
         signers_seeds: &[&[&[u8]]],
     ) -> solana_program::entrypoint::ProgramResult {
         let args = WithdrawMiningInstructionArgs {
+            deposit_id: self
+                .instruction
+                .deposit_id
+                .clone()
+                .expect("deposit_id is not set"),
             amount: self.instruction.amount.clone().expect("amount is not set"),
             mining_owner: self
                 .instruction
@@ -448,6 +484,11 @@ impl<'a, 'b> WithdrawMiningCpiBuilder<'a, 'b> {
                 .delegate
                 .clone()
                 .expect("delegate is not set"),
+            delegate_weights: self
+                .instruction
+                .delegate_weights
+                .clone()
+                .expect("delegate_weights is not set"),
         };
         let instruction = WithdrawMiningCpi {
             __program: self.instruction.__program,
@@ -483,9 +524,11 @@ struct WithdrawMiningCpiBuilderInstruction<'a, 'b> {
     mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     deposit_authority: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     delegate_mining: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    deposit_id: Option<u64>,
     amount: Option<u64>,
     mining_owner: Option<Pubkey>,
     delegate: Option<Pubkey>,
+    delegate_weights: Option<Vec<u16>>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
@@ -17,4 +17,7 @@ pub enum AccountType {
     Uninitialized,
     RewardPool,
     Mining,
+    PenaltyHistory,
+    DelegateRegistry,
+    BoostRecord,
 }
@@ -6,7 +6,15 @@
 //!
 
 pub(crate) mod r#account_type;
+pub(crate) mod r#boost_proof;
 pub(crate) mod r#lockup_period;
+pub(crate) mod r#penalty_record;
+pub(crate) mod r#penalty_stake_diff;
+pub(crate) mod r#solvency_report;
 
 pub use self::r#account_type::*;
+pub use self::r#boost_proof::*;
 pub use self::r#lockup_period::*;
+pub use self::r#penalty_record::*;
+pub use self::r#penalty_stake_diff::*;
+pub use self::r#solvency_report::*;
@@ -0,0 +1,27 @@
+//! This code was AUTOGENERATED using the kinobi library.
+//! Please DO NOT EDIT THIS FILE, instead use visitors
+//! to add features, then rerun kinobi to update it.
+//!
+//! [https://github.com/metaplex-foundation/kinobi]
+//!
+
+use crate::generated::types::PenaltyStakeDiff;
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PenaltyRecord {
+    pub index_with_precision: u128,
+    pub id: u64,
+    pub timestamp: u64,
+    pub native_amount: u64,
+    pub weighted_amount: u64,
+    pub reason_code: u32,
+    pub kind: u8,
+    pub flags: u8,
+    pub stake_diffs_len: u8,
+    pub padding: [u8; 1],
+    pub deposit_id: u64,
+    pub stake_diffs: [PenaltyStakeDiff; 8],
+}
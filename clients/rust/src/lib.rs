@@ -1,4 +1,8 @@
+mod decoders;
+// kinobi output, which isn't supposed to be edited by hand
+#[allow(clippy::new_without_default, non_local_definitions)]
 mod generated;
 
+pub use decoders::*;
 pub use generated::programs::MPLX_REWARDS_ID as ID;
 pub use generated::*;
@@ -1,51 +1,149 @@
 #![cfg(feature = "test-sbf")]
 
-// use rewards::{accounts::MyAccount, instructions::CreateBuilder};
-use solana_program_test::tokio;
+use mplx_rewards::state::WrappedRewardPool;
+use rewards::{
+    accounts::{Mining, RewardPool},
+    decode_claim_accounts,
+    instructions::{InitializeMiningBuilder, InitializePoolBuilder},
+};
+use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program_test::{tokio, ProgramTest};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
 
 #[tokio::test]
-async fn create() {
-    // let mut context = ProgramTest::new("mpl_project_name_program", mpl_project_name::ID, None)
-    //     .start_with_context()
-    //     .await;
-
-    // Given a new keypair.
-
-    // let address = Keypair::new();
-
-    // let ix = CreateBuilder::new()
-    //     .address(address.pubkey())
-    //     .authority(context.payer.pubkey())
-    //     .payer(context.payer.pubkey())
-    //     .arg1(1)
-    //     .arg2(2)
-    //     .instruction();
-
-    // When we create a new account.
-
-    // let tx = Transaction::new_signed_with_payer(
-    //     &[ix],
-    //     Some(&context.payer.pubkey()),
-    //     &[&context.payer, &address],
-    //     context.last_blockhash,
-    // );
-    // context.banks_client.process_transaction(tx).await.unwrap();
-
-    // Then an account was created with the correct data.
-
-    // let account = context
-    //     .banks_client
-    //     .get_account(address.pubkey())
-    //     .await
-    //     .unwrap();
-
-    // assert!(account.is_some());
-
-    // let account = account.unwrap();
-    // assert_eq!(account.data.len(), MyAccount::LEN);
-
-    // let mut account_data = account.data.as_ref();
-    // let my_account = MyAccount::deserialize(&mut account_data).unwrap();
-    // assert_eq!(my_account.data.field1, 1);
-    // assert_eq!(my_account.data.field2, 2);
+async fn create_pool_and_mining() {
+    let mut context = ProgramTest::new("mplx_rewards", rewards::ID, None)
+        .start_with_context()
+        .await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    // Given a reward mint and an account for the reward pool.
+
+    let mint = Keypair::new();
+    let reward_pool = Keypair::new();
+    let deposit_authority = Keypair::new();
+    let (vault, _) = Pubkey::find_program_address(
+        &[
+            b"vault".as_ref(),
+            reward_pool.pubkey().as_ref(),
+            mint.pubkey().as_ref(),
+        ],
+        &rewards::ID,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &context.payer.pubkey(),
+                None,
+                6,
+            )
+            .unwrap(),
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &reward_pool.pubkey(),
+                rent.minimum_balance(WrappedRewardPool::LEN),
+                WrappedRewardPool::LEN as u64,
+                &rewards::ID,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint, &reward_pool],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // When we initialize the pool and a mining of the pool.
+
+    let fill_authority = Pubkey::new_unique();
+    let distribute_authority = Pubkey::new_unique();
+    let mining_owner = Pubkey::new_unique();
+    let (mining, _) = Pubkey::find_program_address(
+        &[
+            b"mining".as_ref(),
+            mining_owner.as_ref(),
+            reward_pool.pubkey().as_ref(),
+        ],
+        &rewards::ID,
+    );
+
+    let initialize_pool_ix = InitializePoolBuilder::new()
+        .reward_pool(reward_pool.pubkey())
+        .reward_mint(mint.pubkey())
+        .vault(vault)
+        .payer(context.payer.pubkey())
+        .deposit_authority(deposit_authority.pubkey())
+        .fill_authority(fill_authority)
+        .distribute_authority(distribute_authority)
+        .instruction();
+    let initialize_mining_ix = InitializeMiningBuilder::new()
+        .reward_pool(reward_pool.pubkey())
+        .mining(mining)
+        .payer(context.payer.pubkey())
+        .mining_owner(mining_owner)
+        .instruction();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[initialize_pool_ix, initialize_mining_ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &deposit_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Then both accounts are created and decoded by the client.
+
+    let pool_account = context
+        .banks_client
+        .get_account(reward_pool.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let pool = RewardPool::from_bytes(&pool_account.data).unwrap();
+    assert_eq!(pool.deposit_authority, deposit_authority.pubkey());
+    assert_eq!(pool.fill_authority, fill_authority);
+    assert_eq!(pool.distribute_authority, distribute_authority);
+    assert_eq!(pool.reward_mint, mint.pubkey());
+    assert_eq!(pool.total_share, 0);
+
+    let mining_account = context
+        .banks_client
+        .get_account(mining)
+        .await
+        .unwrap()
+        .unwrap();
+    let mining = Mining::from_bytes(&mining_account.data).unwrap();
+    assert_eq!(mining.reward_pool, reward_pool.pubkey());
+    assert_eq!(mining.owner, mining_owner);
+    assert_eq!(mining.reward_mint, mint.pubkey());
+    assert_eq!(mining.share, 0);
+
+    // And the accounts pass the reward mint checks of the claim.
+
+    let vault_account = context
+        .banks_client
+        .get_account(vault)
+        .await
+        .unwrap()
+        .unwrap();
+    let (pool, mining) = decode_claim_accounts(
+        &pool_account.data,
+        &mining_account.data,
+        &vault_account.data,
+        &vault_account.data,
+    )
+    .unwrap();
+    assert_eq!(pool.reward_mint, mining.reward_mint);
 }
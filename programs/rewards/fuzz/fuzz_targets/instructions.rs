@@ -0,0 +1,74 @@
+//! Coverage guided counterpart of the randomized property test: the fuzzer input is decoded
+//! into a sequence of instructions run one after another over the same accounts,
+//! and the program must never panic on any of them.
+//!
+//! Run it from programs/rewards with `cargo +nightly fuzz run instructions`.
+
+#![no_main]
+
+#[path = "../../tests/random_instructions/harness.rs"]
+mod harness;
+
+use harness::*;
+use libfuzzer_sys::fuzz_target;
+use rand::RngCore;
+use std::sync::{Once, OnceLock};
+
+/// Most instructions a single input is decoded into
+const MAX_INSTRUCTIONS: usize = 16;
+
+static STUBS: Once = Once::new();
+static FIXTURE: OnceLock<Fixture> = OnceLock::new();
+
+/// Serves the fuzzer input as the random source of the generators,
+/// so that the fuzzer mutates the choices they make. Zeroes follow the end of the input.
+struct InputRng<'a>(&'a [u8]);
+
+impl RngCore for InputRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let len = dest.len().min(self.0.len());
+        let (taken, rest) = self.0.split_at(len);
+        dest[..len].copy_from_slice(taken);
+        dest[len..].fill(0);
+        self.0 = rest;
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    STUBS.call_once(|| {
+        solana_program::program_stubs::set_syscall_stubs(Box::new(Stubs));
+    });
+    let mut f = FIXTURE.get_or_init(Fixture::new).clone();
+    let mut rng = InputRng(data);
+
+    for i in 0..MAX_INSTRUCTIONS {
+        if rng.0.is_empty() {
+            break;
+        }
+        let ix = random_instruction(&mut rng, &mut f);
+
+        assert!(
+            !panics(&mut f, &ix),
+            "program panicked, instruction {i}\ninstruction data: {:?}\naccounts: {:#?}",
+            ix.data,
+            ix.accounts
+        );
+    }
+});
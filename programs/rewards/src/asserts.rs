@@ -1,12 +1,15 @@
 //! Asserts for account verifications
+use mpl_token_metadata::accounts::Metadata;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
-    pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+    program_pack::Pack, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
 };
+use spl_token::state::Account as SplTokenAccount;
 
 use crate::{
     error::MplxRewardsError,
-    state::{WrappedMining, WrappedRewardPool},
+    state::{PenaltyHistory, WrappedMining, WrappedRewardPool},
+    utils::create_penalty_history_address,
 };
 
 /// Assert signer.
@@ -72,6 +75,20 @@ pub fn assert_pubkey_eq(given: &Pubkey, expected: &Pubkey) -> ProgramResult {
     }
 }
 
+/// Assert the mint is the reward mint of the pool
+pub fn assert_reward_mint(mint: &Pubkey, reward_mint: &Pubkey) -> ProgramResult {
+    if mint == reward_mint {
+        Ok(())
+    } else {
+        msg!(
+            "Assert reward mint error. Got {} Expected {}",
+            *mint,
+            *reward_mint
+        );
+        Err(MplxRewardsError::InvalidRewardMint.into())
+    }
+}
+
 pub fn assert_account_len(account: &AccountInfo, len: usize) -> ProgramResult {
     if account.data_len() == len {
         Ok(())
@@ -138,3 +155,50 @@ pub fn assert_and_get_pool_and_mining<'a>(
 
     Ok((wrapped_reward_pool, wrapped_mining))
 }
+
+pub fn assert_and_get_penalty_history<'a>(
+    program_id: &Pubkey,
+    mining: &AccountInfo,
+    penalty_history: &AccountInfo,
+    penalty_history_data: &'a mut [u8],
+) -> Result<&'a mut PenaltyHistory, ProgramError> {
+    let history = PenaltyHistory::from_bytes_mut(penalty_history_data)?;
+
+    let penalty_history_pubkey =
+        create_penalty_history_address(program_id, mining.key, history.bump)?;
+    assert_account_key(penalty_history, &penalty_history_pubkey)?;
+    assert_account_key(mining, &history.mining)?;
+
+    Ok(history)
+}
+
+/// Asserts the token account holds an NFT of the given collection and belongs to the owner.
+/// Returns the mint of the NFT.
+pub fn assert_collection_membership(
+    collection: &Pubkey,
+    owner: &Pubkey,
+    token_account: &AccountInfo,
+    metadata: &AccountInfo,
+) -> Result<Pubkey, ProgramError> {
+    if *collection == Pubkey::default() {
+        return Err(MplxRewardsError::InvalidBoostProof.into());
+    }
+
+    assert_account_owner(token_account, &spl_token::id())?;
+    let token = SplTokenAccount::unpack(&token_account.try_borrow_data()?)?;
+    if token.owner != *owner || token.amount == 0 {
+        return Err(MplxRewardsError::InvalidBoostProof.into());
+    }
+
+    assert_account_owner(metadata, &mpl_token_metadata::ID)?;
+    assert_account_key(metadata, &Metadata::find_pda(&token.mint).0)?;
+    let metadata = Metadata::try_from(metadata).map_err(|_| MplxRewardsError::InvalidBoostProof)?;
+    match metadata.collection {
+        Some(metadata_collection)
+            if metadata_collection.verified && metadata_collection.key == *collection =>
+        {
+            Ok(token.mint)
+        }
+        _ => Err(MplxRewardsError::InvalidBoostProof.into()),
+    }
+}
@@ -91,6 +91,238 @@ pub enum MplxRewardsError {
         "Rewards: Penalty is not apliable becase it's bigger than the mining's weighted stake"
     )]
     DecreaseRewardsTooBig,
+
+    /// 16
+    /// Unknown kind of the penalty record
+    #[error("Rewards: Invalid penalty kind")]
+    InvalidPenaltyKind,
+
+    /// 17
+    /// Penalty record isn't found in the penalty history
+    #[error("Rewards: Penalty record not found")]
+    PenaltyNotFound,
+
+    /// 18
+    /// Penalty record has been restored already
+    #[error("Rewards: Penalty has already been restored")]
+    PenaltyAlreadyRestored,
+
+    /// 19
+    /// Penalty has changed too many weighted stake modifiers to be reversed,
+    /// or the slashed deposit has left the mining
+    #[error("Rewards: Penalty cannot be restored")]
+    PenaltyNotRestorable,
+
+    /// 20
+    /// There's no space for the new weighted stake modifier
+    #[error("Rewards: No space left for weighted stake modifiers")]
+    WeightedStakeDiffsAreFull,
+
+    /// 21
+    /// Deposit amount is lower than the pool's minimal deposit
+    #[error("Rewards: Deposit amount is lower than the minimal deposit")]
+    DepositIsTooSmall,
+
+    /// 22
+    /// Mining's weighted stake exceeds the pool's limit per mining
+    #[error("Rewards: Weighted stake limit per mining is exceeded")]
+    MiningShareLimitExceeded,
+
+    /// 23
+    /// Pool's total share exceeds the pool's cap
+    #[error("Rewards: Pool's total share limit is exceeded")]
+    PoolShareLimitExceeded,
+
+    /// 24
+    /// Boost has zero basis points, expires in the past or outlasts the longest lockup
+    #[error("Rewards: Invalid boost")]
+    InvalidBoost,
+
+    /// 25
+    /// Boost proof doesn't match the pool's boost configuration
+    #[error("Rewards: Invalid boost proof")]
+    InvalidBoostProof,
+
+    /// 26
+    /// Mining already has an active boost
+    #[error("Rewards: Boost is already active")]
+    BoostIsAlreadyActive,
+
+    /// 27
+    /// Epoch length can only be changed before the pool has any stakes or distributions
+    #[error("Rewards: Epoch length cannot be changed")]
+    EpochLengthCannotBeChanged,
+
+    /// 28
+    /// Epoch length must be a divisor of a day, which is long enough
+    /// for the index history to cover the longest lockup
+    #[error("Rewards: Invalid epoch length")]
+    InvalidEpochLength,
+
+    /// 29
+    /// Pool cannot be closed while it has stakes
+    #[error("Rewards: Pool still has stakes")]
+    PoolHasStakes,
+
+    /// 30
+    /// Pool has been sunset and doesn't accept deposits and distributions
+    #[error("Rewards: Pool is sunset")]
+    PoolIsSunset,
+
+    /// 31
+    /// Grace period of the sunset pool has elapsed
+    #[error("Rewards: Sunset grace period has elapsed")]
+    SunsetGracePeriodElapsed,
+
+    /// 32
+    /// Account is stored in the outdated layout and must be migrated first
+    #[error("Rewards: Account must be migrated to the current layout")]
+    AccountNeedsMigration,
+
+    /// 33
+    /// Account layout is unknown to the program
+    #[error("Rewards: Unsupported account version")]
+    UnsupportedAccountVersion,
+
+    /// 34
+    /// The same mining account is passed as the source and the destination
+    #[error("Rewards: Source and destination minings are the same")]
+    MiningsAreTheSame,
+
+    /// 35
+    /// Mining account hasn't been registered as a delegate
+    #[error("Rewards: Delegate is not registered")]
+    DelegateIsNotRegistered,
+
+    /// 36
+    /// Delegate doesn't accept stake from the mining owner
+    #[error("Rewards: Delegation is not allowed by the delegate")]
+    DelegationIsNotAllowed,
+
+    /// 37
+    /// Delegate's stake from others limit is exceeded
+    #[error("Rewards: Delegate's stake from others limit is exceeded")]
+    DelegateStakeLimitExceeded,
+
+    /// 38
+    /// Allow or deny list of the delegate is too long
+    #[error("Rewards: Delegate list is too long")]
+    DelegateListIsTooLong,
+
+    /// 39
+    /// Delegate weights contain zero or there are too many delegates
+    #[error("Rewards: Invalid delegate weights")]
+    InvalidDelegateWeights,
+
+    /// 40
+    /// The same delegate mining is passed more than once or it's the mining itself
+    #[error("Rewards: Delegate is duplicated")]
+    DelegateIsDuplicated,
+
+    /// 41
+    /// Reward mint is owned neither by SPL Token nor by Token-2022
+    #[error("Rewards: Unsupported token program")]
+    UnsupportedTokenProgram,
+
+    /// 42
+    /// Vault balance is lower than the tokens available for distribution and unclaimed rewards
+    #[error("Rewards: Vault doesn't cover pool's liabilities")]
+    VaultIsInsolvent,
+
+    /// 43
+    /// Account data doesn't hold any of the program's account types
+    #[error("Rewards: Invalid account type")]
+    InvalidAccountType,
+
+    /// 44
+    /// Account data length doesn't match the layout of the account type
+    #[error("Rewards: Invalid account length")]
+    InvalidAccountLength,
+
+    /// 45
+    /// Account hasn't been initialized yet
+    #[error("Rewards: Account is not initialized")]
+    AccountIsNotInitialized,
+
+    /// 46
+    /// Account is bound to another reward mint than the reward pool
+    #[error("Rewards: Reward mint doesn't match the pool")]
+    InvalidRewardMint,
+
+    /// 47
+    /// Deposit with the given id has already been applied to the mining
+    #[error("Rewards: Deposit is already applied")]
+    DepositIsAlreadyApplied,
+
+    /// 48
+    /// Mining has no position for the deposit with the given id
+    #[error("Rewards: Deposit is not found")]
+    DepositIsNotFound,
+
+    /// 49
+    /// Mining has no room for more positions
+    #[error("Rewards: No space left for positions")]
+    PositionsAreFull,
+
+    /// 50
+    /// Amount exceeds the number of tokens the deposit has or doesn't match it
+    #[error("Rewards: Amount doesn't match the deposit")]
+    InvalidDepositAmount,
+
+    /// 51
+    /// Deposit ids don't match the deposits of the mining
+    #[error("Rewards: Invalid deposit ids")]
+    InvalidDepositIds,
+
+    /// 52
+    /// Pool requires withdrawals to be requested, while the deposit has no unbonding tokens
+    #[error("Rewards: Withdrawal hasn't been requested")]
+    WithdrawalIsNotRequested,
+
+    /// 53
+    /// Unbonding tokens cannot be withdrawn until the unbonding period of the pool is over
+    #[error("Rewards: Unbonding period isn't over yet")]
+    UnbondingIsNotOver,
+
+    /// 54
+    /// Unbonding multiplier exceeds the Flex one
+    #[error("Rewards: Invalid unbonding multiplier")]
+    InvalidUnbondingMultiplier,
+
+    /// 55
+    /// There's no space left for the index of the new distribution
+    #[error("Rewards: No space left in the index history")]
+    IndexHistoryIsFull,
+
+    /// 56
+    /// Pool cannot be closed while miners have rewards to claim
+    #[error("Rewards: Pool still has unclaimed rewards")]
+    PoolHasUnclaimedRewards,
+
+    /// 57
+    /// Penalty history or delegate registry would be orphaned by closing the mining
+    #[error("Rewards: Mining has penalty history or delegate registry")]
+    MiningHasLinkedAccounts,
+
+    /// 58
+    /// Reward mint has the Token-2022 extension the pool cannot work with
+    #[error("Rewards: Unsupported mint extension")]
+    UnsupportedMintExtension,
+
+    /// 59
+    /// NFT proves the boost which hasn't expired yet
+    #[error("Rewards: NFT is already used for an active boost")]
+    NftIsAlreadyUsedForBoost,
+
+    /// 60
+    /// Delegates don't match the ones the deposit's stake is delegated to
+    #[error("Rewards: Delegates don't match the deposit")]
+    DelegatesDontMatchDeposit,
+
+    /// 61
+    /// Mining has no room for more stakes delegated to several delegates
+    #[error("Rewards: No space left for delegated stakes")]
+    DelegatedStakesAreFull,
 }
 
 impl PrintProgramError for MplxRewardsError {
@@ -8,13 +8,18 @@ use solana_program::{
     system_program, sysvar,
 };
 
-use crate::utils::LockupPeriod;
+use crate::utils::{
+    find_delegate_registry_program_address, find_penalty_history_program_address, BoostProof,
+    LockupPeriod,
+};
 
 /// Instructions supported by the program
 #[derive(Debug, BorshDeserialize, BorshSerialize, PartialEq, Eq, ShankInstruction, ShankContext)]
 #[rustfmt::skip]
 pub enum RewardsInstruction {
-    /// Creates and initializes a reward pool account
+    /// Creates and initializes a reward pool account.
+    /// The reward mint might belong either to the SPL Token or to the Token-2022 program.
+    /// Token-2022 mint might only have the transfer fee and metadata extensions.
     #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
     #[account(1, name = "reward_mint", desc = "The address of the reward mint")]
     #[account(2, writable, name = "vault", desc = "The address of the reward vault")]
@@ -37,7 +42,9 @@ pub enum RewardsInstruction {
     #[account(4, writable, name = "source_token_account", desc = "The address of the TA from which tokens will be spent")]
     #[account(5, name = "token_program", desc = "The address of the Token program where rewards are minted")]
     FillVault {
-        /// Amount to fill
+        /// Amount to fill. If the mint charges a transfer fee,
+        /// only the amount the vault has received is distributed.
+        /// Fails if the vault balance doesn't cover the pool's liabilities afterwards
         rewards: u64,
         /// Rewards distribution ends at given date
         distribution_ends_at: u64,
@@ -53,12 +60,19 @@ pub enum RewardsInstruction {
         mining_owner: Pubkey,
     },
 
-    /// Deposits amount of supply to the mining account
+    /// Deposits amount of supply to the mining account.
+    /// If the stake is split between several delegates, `delegate_mining` is the mining itself,
+    /// and the remaining accounts are pairs of delegate mining (writable) and its registry.
     #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
     #[account(1, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
     #[account(2, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
-    #[account(3, name = "delegate_mining", desc = "The address of Mining Account that might be used as a delegate in delegated staking model")]
+    #[account(3, writable, name = "delegate_mining", desc = "The address of Mining Account that might be used as a delegate in delegated staking model")]
+    #[account(4, optional, name = "delegate_registry", desc = "Registry of the delegate mining, required when the delegate mining differs from the mining")]
     DepositMining {
+        /// Id of the deposit assigned by the deposit authority, e.g. the index
+        /// of the deposit entry on the staking contract. A deposit with the id
+        /// the mining already has a position for is rejected
+        deposit_id: u64,
         /// Amount to deposit
         amount: u64,
         /// Lockup Period
@@ -66,23 +80,35 @@ pub enum RewardsInstruction {
         /// Specifies the owner of the Mining Account
         mining_owner: Pubkey,
         delegate: Pubkey,
+        /// Weights the delegated stake is split by between the delegate minings
+        /// passed in the remaining accounts. Empty if the stake isn't split
+        delegate_weights: Vec<u16>,
     },
 
-    /// Withdraws amount of supply to the mining account
+    /// Withdraws amount of supply to the mining account.
+    /// If the stake is split between several delegates, `delegate_mining` is the mining itself,
+    /// and the remaining accounts are the delegate minings (writable).
     #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
     #[account(1, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
     #[account(2, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
-    #[account(3, name = "delegate_mining", desc = "The address of Mining Account that might be used as a delegate in delegated staking model")]
+    #[account(3, writable, name = "delegate_mining", desc = "The address of Mining Account that might be used as a delegate in delegated staking model")]
     WithdrawMining {
+        /// Id of the deposit the tokens are withdrawn from. The position of the deposit
+        /// is removed once it's withdrawn completely, so that the withdrawal cannot be repeated
+        deposit_id: u64,
         /// Amount to withdraw
         amount: u64,
         /// Specifies the owner of the Mining Account
         mining_owner: Pubkey,
         delegate: Pubkey,
+        /// Weights the delegated stake is split by between the delegate minings
+        /// passed in the remaining accounts. Empty if the stake isn't split
+        delegate_weights: Vec<u16>,
     },
 
-    /// Claims amount of rewards
-    #[account(0, name = "reward_pool", desc = "The address of the reward pool")]
+    /// Claims amount of rewards.
+    /// Fails if the vault balance doesn't cover the pool's liabilities.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
     #[account(1, name = "reward_mint", desc = "The address of the reward mint")]
     #[account(2, writable, name = "vault", desc = "The address of the reward vault")]
     #[account(3, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
@@ -92,24 +118,20 @@ pub enum RewardsInstruction {
     #[account(7, name = "token_program", desc = "The address of the Token program where rewards are minted")]
     Claim,
 
-    /// Extends stake
+    /// Extends stake.
+    /// If the stake is split between several delegates, `delegate_mining` is the mining itself,
+    /// and the remaining accounts are pairs of delegate mining (writable) and its registry.
     #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
     #[account(1, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
-    #[account(2, name = "reward_mint", desc = "The address of the reward mint")]
-    #[account(3, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
-    #[account(4, name = "delegate_mining", desc = "The address of Mining Account that might be used as a delegate in delegated staking model")]
+    #[account(2, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    #[account(3, writable, name = "delegate_mining", desc = "The address of Mining Account that might be used as a delegate in delegated staking model")]
+    #[account(4, optional, name = "delegate_registry", desc = "Registry of the delegate mining, required when the delegate mining differs from the mining")]
     ExtendStake {
-        /// Lockup period before restaking. Actually it's only needed
-        /// for Flex to AnyPeriod edge case
-        old_lockup_period: LockupPeriod,
+        /// Id of the deposit which is restaked. The tokens of the deposit
+        /// and its old lockup are taken from the position of the deposit
+        deposit_id: u64,
         /// Requested lockup period for restaking
         new_lockup_period: LockupPeriod,
-        /// Deposit start_ts
-        deposit_start_ts: u64,
-        /// Amount of tokens to be restaked, this
-        /// number cannot be decreased. It reflects the number of staked tokens
-        /// before the extend_stake function call
-        base_amount: u64,
         /// In case user wants to increase it's staked number of tokens,
         /// the addition amount might be provided
         additional_amount: u64,
@@ -117,55 +139,362 @@ pub enum RewardsInstruction {
         mining_owner: Pubkey,
         /// Wallet addres of delegate
         delegate: Pubkey,
+        /// Weights the delegated stake is split by between the delegate minings
+        /// passed in the remaining accounts. Empty if the stake isn't split
+        delegate_weights: Vec<u16>,
     },
 
-    /// Distributes tokens among mining owners
-    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    /// Distributes tokens among mining owners.
+    /// Fails if the vault balance doesn't cover the pool's liabilities afterwards,
+    /// or once the index history keeps INDEX_HISTORY_MAX_SIZE (1095) distributions.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool, which stops distributing once its index history keeps 1095 distributions")]
     #[account(1, signer, name = "distribute_authority", desc = "The address of Authority who is eligble for distributiong rewards for users")]
+    #[account(2, name = "vault", desc = "The address of the reward vault")]
     DistributeRewards,
 
     /// Closes mining account and transfers all lamports to the target account
-    #[account(0, name = "mining", desc = "The address of the user's mining account")]
+    #[account(0, writable, name = "mining", desc = "The address of the user's mining account")]
     #[account(1, signer, name = "mining_owner", desc = "The end user the mining accounts belongs to")]
     #[account(2, writable, name = "target_account", desc = "The address where lamports from account closing will be transferred")]
     #[account(3, signer, name = "deposit_authority")]
-    #[account(4, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(4, name = "reward_pool", desc = "The address of the reward pool")]
     CloseMining,
 
-    /// Changes delegate mining account
+    /// Changes delegate mining account of the deposit. The whole stake delegated by the deposit
+    /// is taken back from the old delegates, and the staked amount is delegated to the new ones.
+    /// If the old stake is split, `old_delegate_mining` is the mining itself and the old delegate minings
+    /// (writable) are passed in the remaining accounts. If the new stake is split, `new_delegate_mining`
+    /// is the mining itself and pairs of new delegate mining (writable) and its registry follow them.
     #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
     #[account(1, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
     #[account(2, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
     #[account(3, signer, name = "mining_owner", desc = "The end user the mining accounts belongs to")]
     #[account(4, writable, name = "old_delegate_mining", desc = "The address of the old delegate mining account")]
     #[account(5, writable, name = "new_delegate_mining", desc = "The address of the new delegate mining account")]
+    #[account(6, optional, name = "new_delegate_registry", desc = "Registry of the new delegate mining, required when the new delegate mining differs from the mining")]
     ChangeDelegate {
+        /// Id of the deposit whose stake is delegated
+        deposit_id: u64,
         /// Amount of staked tokens
         staked_amount: u64,
         new_delegate: Pubkey,
+        /// Weights the stake is split by between the old delegate minings
+        /// passed in the remaining accounts. Empty if the stake isn't split
+        old_delegate_weights: Vec<u16>,
+        /// Weights the stake is split by between the new delegate minings passed
+        /// in the remaining accounts after the old ones. Empty if the stake isn't split
+        new_delegate_weights: Vec<u16>,
     },
 
+    /// Slashes the number of tokens of the deposit. The penalty is recorded in the penalty history
+    /// of the mining once it's created, the penalty itself doesn't depend on the history.
     #[account(0, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
     #[account(1, writable, name = "reward_pool", desc = "The address of the reward pool")]
     #[account(2, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
+    #[account(3, writable, optional, name = "penalty_history", desc = "The address of the penalty history of the mining account, the penalty isn't recorded without it")]
     Slash {
         mining_owner: Pubkey,
+        // id of the slashed deposit, its position provides the weighted stake of the tokens
+        deposit_id: u64,
         // number of tokens that had been slashed
         slash_amount_in_native: u64,
-        // weighted stake part for the slashed number of tokens multiplied by the period
-        slash_amount_multiplied_by_period: u64,
-        // None if it's Flex period, because it's already expired
-        stake_expiration_date: Option<u64>,
+        // The reason of the penalty, stored in the penalty history
+        reason_code: u32,
     },
 
+    /// Decreases the weighted stake of the mining. The penalty is recorded in the penalty history
+    /// of the mining once it's created, the penalty itself doesn't depend on the history.
     #[account(0, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
     #[account(1, writable, name = "reward_pool", desc = "The address of the reward pool")]
     #[account(2, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
+    #[account(3, writable, optional, name = "penalty_history", desc = "The address of the penalty history of the mining account, the penalty isn't recorded without it")]
     DecreaseRewards {
         mining_owner: Pubkey,
         // The number by which weighted stake should be decreased
         decreased_weighted_stake_number: u64,
+        // The reason of the penalty, stored in the penalty history
+        reason_code: u32,
+    },
+
+    /// Creates the account which stores the history of penalties applied to the mining
+    #[account(0, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
+    #[account(1, writable, name = "penalty_history", desc = "The address of the penalty history of the mining account")]
+    #[account(2, writable, signer, name = "payer")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    InitializePenaltyHistory,
+
+    /// Reverses the penalty recorded in the penalty history.
+    /// Lost weighted stake is returned to the mining account and,
+    /// optionally, rewards lost because of the penalty are credited back.
+    #[account(0, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    #[account(1, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(2, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
+    #[account(3, writable, name = "penalty_history", desc = "The address of the penalty history of the mining account")]
+    RestorePenalty {
+        mining_owner: Pubkey,
+        // The id of the penalty record to be reversed
+        penalty_id: u64,
+        // Whether rewards lost because of the penalty should be credited back
+        credit_lost_rewards: bool,
+    },
+
+    /// Sets stake limits of the reward pool. Zero means no limit.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    SetPoolLimits {
+        /// The minimal number of tokens a single deposit can be made with
+        min_deposit: u64,
+        /// The maximal weighted stake a single mining account can have
+        max_weighted_share_per_mining: u64,
+        /// The maximal total share of the pool
+        max_total_share: u64,
+    },
+
+    /// Sets sources of proofs the weighted stake boosts are granted by.
+    /// Default pubkey disables the corresponding kind of proof.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    SetBoostConfig {
+        /// The collection whose NFT holders are eligible for the boost
+        boost_collection: Pubkey,
+        /// The address which may attest mining owners for the boost
+        boost_attester: Pubkey,
+    },
+
+    /// Applies temporary weighted stake boost to the mining account.
+    /// The NFT of the collection proof cannot boost another mining until its boost expires.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
+    #[account(2, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    #[account(3, name = "proof", desc = "NFT token account owned by the mining owner or the attester who signs the boost")]
+    #[account(4, optional, name = "proof_metadata", desc = "Metadata account of the NFT, required for the collection proof")]
+    #[account(5, writable, optional, name = "boost_record", desc = "The address of the boost record of the NFT mint, required for the collection proof")]
+    #[account(6, writable, signer, optional, name = "payer", desc = "The address of the payer for the boost record creation, required for the collection proof")]
+    #[account(7, optional, name = "system_program", desc = "The system program, required for the collection proof")]
+    ApplyBoost {
+        /// The wallet who owns the mining account
+        mining_owner: Pubkey,
+        /// Boost of the mining's weighted stake in basis points
+        boost_bps: u16,
+        /// The date the boost expires at, not later than the end of
+        /// the longest (one year) lockup started now
+        expires_at: u64,
+        /// Kind of the proof passed in
+        proof_kind: BoostProof,
     },
+
+    /// Sets the length of the distribution epoch.
+    /// Might be done only before the pool has any stakes or distributions.
+    /// The index history isn't pruned and keeps at most INDEX_HISTORY_MAX_SIZE (1095)
+    /// distributions, after which the pool cannot distribute anymore. That's one year
+    /// of the shortest epochs or three years of daily ones, so hourly epochs aren't supported.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool, whose index history keeps at most 1095 distributions: one year of the shortest (8 hours) epochs")]
+    #[account(1, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    SetEpochLength {
+        /// The length of the epoch in seconds, must be a divisor of a day
+        /// not shorter than MIN_EPOCH_LENGTH_SECS (8 hours)
+        epoch_length_secs: u64,
+    },
+
+    /// Closes the reward pool. Might be done if the pool has neither stakes nor
+    /// unclaimed rewards, or the grace period of the sunset pool has elapsed.
+    /// Remaining rewards are swept to the token account
+    /// of the fill authority, the vault and the pool accounts are closed
+    /// and their lamports are transferred to the target account.
+    /// Transfer fees withheld in the vault of Token-2022 mint are harvested to the mint.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, name = "reward_mint", desc = "The address of the reward mint")]
+    #[account(2, writable, name = "vault", desc = "The address of the reward vault")]
+    #[account(3, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    #[account(4, writable, name = "recipient_token_account", desc = "The token account of the fill authority remaining rewards are swept to")]
+    #[account(5, writable, name = "target_account", desc = "The address where lamports from accounts closing will be transferred")]
+    #[account(6, name = "token_program", desc = "The address of the Token program where rewards are minted")]
+    ClosePool,
+
+    /// Sunsets the reward pool. Sunset pool doesn't accept new deposits and distributions,
+    /// while withdrawals keep working and rewards can be claimed during the grace period.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    SunsetPool {
+        /// The period after the sunset during which rewards still can be claimed
+        grace_period_secs: u64,
+    },
+
+    /// Rewrites the reward pool stored in the outdated layout to the current one.
+    /// Permissionless, the payer only covers the rent for the grown account.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, signer, name = "payer")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, name = "vault", desc = "The address of the reward vault, its balance backfills the rewards left unclaimed by the migration")]
+    MigratePool,
+
+    /// Rewrites the mining account stored in the outdated layout to the current one
+    /// and records the reward mint of the pool in it.
+    /// Permissionless, the payer only covers the rent for the grown account.
+    #[account(0, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, name = "mining", desc = "The address of the mining account")]
+    #[account(2, writable, signer, name = "payer")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    MigrateMining,
+
+    /// Moves the mining position into a fresh mining account of the new owner
+    /// and closes the old one. Penalty history stays bound to the old mining account.
+    /// The mining must not have stake from others, since delegators refer it by its address.
+    #[account(0, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, name = "mining", desc = "The address of the mining account which is transferred")]
+    #[account(2, writable, name = "new_mining", desc = "The address of the mining account of the new owner")]
+    #[account(3, signer, name = "mining_owner", desc = "The end user the mining accounts belongs to")]
+    #[account(4, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    #[account(5, writable, signer, name = "payer")]
+    #[account(6, writable, name = "target_account", desc = "The address where lamports from account closing will be transferred")]
+    #[account(7, name = "system_program", desc = "The system program")]
+    TransferMining {
+        /// The end user who becomes the owner of the mining
+        new_owner: Pubkey,
+    },
+
+    /// Moves the whole position of the source mining into the mining account
+    /// and closes the source one. Both minings must belong to the same pool.
+    /// The source mining must have neither stake from others, nor penalty history,
+    /// nor delegate registry, since those refer it by its address.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, name = "source_mining", desc = "The address of the mining account which is merged and closed")]
+    #[account(2, writable, name = "mining", desc = "The address of the mining account the position is merged into")]
+    #[account(3, signer, name = "source_mining_owner", desc = "The end user the source mining account belongs to")]
+    #[account(4, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    #[account(5, writable, name = "target_account", desc = "The address where lamports from account closing will be transferred")]
+    #[account(6, name = "source_penalty_history", desc = "The address of the penalty history of the source mining, which must not exist")]
+    #[account(7, name = "source_delegate_registry", desc = "The address of the delegate registry of the source mining, which must not exist")]
+    MergeMining {
+        /// Ids the source deposits get in the mining, ordered by the source deposit ids
+        deposit_ids: Vec<u64>,
+    },
+
+    /// Moves the part of the mining's position to another mining account of the same pool
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, name = "mining", desc = "The address of the mining account the part of position is taken from")]
+    #[account(2, writable, name = "destination_mining", desc = "The address of the mining account the part of position is moved to")]
+    #[account(3, signer, name = "mining_owner", desc = "The end user the mining accounts belongs to")]
+    #[account(4, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    SplitMining {
+        // id of the deposit the tokens are taken from
+        deposit_id: u64,
+        // id the moved part of the deposit gets in the destination mining
+        destination_deposit_id: u64,
+        // number of tokens that are moved along with their weighted stake
+        amount: u64,
+    },
+
+    /// Registers the mining as a delegate, so it may receive stake from others
+    #[account(0, name = "mining", desc = "The address of the mining account which is registered as a delegate")]
+    #[account(1, writable, name = "delegate_registry", desc = "The address of the delegate registry of the mining account")]
+    #[account(2, signer, name = "mining_owner", desc = "The end user the mining accounts belongs to")]
+    #[account(3, writable, signer, name = "payer")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    RegisterDelegate {
+        /// The maximal stake from others the delegate accepts. Zero means no limit
+        max_stake_from_others: u64,
+        /// Mining owners who are allowed to delegate. Empty list means everyone is allowed
+        allow_list: Vec<Pubkey>,
+        /// Mining owners who aren't allowed to delegate
+        deny_list: Vec<Pubkey>,
+    },
+
+    /// Replaces the conditions the delegate accepts stake on
+    #[account(0, name = "mining", desc = "The address of the delegate's mining account")]
+    #[account(1, writable, name = "delegate_registry", desc = "The address of the delegate registry of the mining account")]
+    #[account(2, signer, name = "mining_owner", desc = "The end user the mining accounts belongs to")]
+    UpdateDelegateRegistry {
+        /// The maximal stake from others the delegate accepts. Zero means no limit
+        max_stake_from_others: u64,
+        /// Mining owners who are allowed to delegate. Empty list means everyone is allowed
+        allow_list: Vec<Pubkey>,
+        /// Mining owners who aren't allowed to delegate
+        deny_list: Vec<Pubkey>,
+    },
+
+    /// Closes the delegate registry, so the mining doesn't accept new stake from others.
+    /// Stake which is already delegated stays untouched.
+    #[account(0, name = "mining", desc = "The address of the delegate's mining account")]
+    #[account(1, writable, name = "delegate_registry", desc = "The address of the delegate registry of the mining account")]
+    #[account(2, signer, name = "mining_owner", desc = "The end user the mining accounts belongs to")]
+    #[account(3, writable, name = "target_account", desc = "The address where lamports from account closing will be transferred")]
+    UnregisterDelegate,
+
+    /// Compares the vault balance with the pool's liabilities, which are tokens available
+    /// for distribution and rewards distributed but not claimed yet.
+    /// Doesn't change any state, the SolvencyReport is returned as the return data.
+    #[account(0, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, name = "vault", desc = "The address of the reward vault")]
+    ReportSolvency,
+
+    /// Records the position of the deposit which has been applied to the mining
+    /// before positions were introduced. The stake itself isn't changed.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
+    #[account(2, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    RecordDeposit {
+        /// Specifies the owner of the Mining Account
+        mining_owner: Pubkey,
+        /// Id of the deposit assigned by the deposit authority
+        deposit_id: u64,
+        /// Number of tokens staked by the deposit
+        amount: u64,
+        /// Lockup period of the deposit
+        lockup_period: LockupPeriod,
+        /// The time the lockup of the deposit has started at
+        deposit_start_ts: u64,
+    },
+
+    /// Sets the unbonding period of the reward pool along with the multiplier
+    /// the tokens requested for withdrawal earn with while unbonding.
+    /// Zero period means withdrawals don't have to be requested.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, signer, name = "deposit_authority", desc = "The address of the Staking program's Registrar, which is PDA and is responsible for signing CPIs")]
+    SetUnbondingConfig {
+        /// The period in seconds the requested tokens stay unbonding for
+        unbonding_period_secs: u64,
+        /// Weighted stake of the unbonding tokens in basis points of their number,
+        /// cannot exceed the Flex multiplier
+        unbonding_multiplier_bps: u16,
+    },
+
+    /// Requests the withdrawal of the number of tokens of the deposit. The tokens stop earning
+    /// with their lockup straight away and earn with the pool's unbonding multiplier instead.
+    /// They can be withdrawn with the WithdrawMining instruction once the unbonding period is over.
+    /// Only the mining owner signs it, since it moves no tokens.
+    #[account(0, writable, name = "reward_pool", desc = "The address of the reward pool")]
+    #[account(1, writable, name = "mining", desc = "The address of the mining account which belongs to the user and stores info about user's rewards")]
+    #[account(2, signer, name = "mining_owner", desc = "The end user the mining accounts belongs to")]
+    RequestWithdraw {
+        /// Id of the deposit the tokens are requested from
+        deposit_id: u64,
+        /// Number of tokens requested for withdrawal
+        amount: u64,
+    },
+}
+
+/// Appends the delegate minings the stake is split between,
+/// each one followed by its registry if `with_registries` is set
+fn push_split_delegates(
+    accounts: &mut Vec<AccountMeta>,
+    program_id: &Pubkey,
+    split_delegates: &[(Pubkey, u16)],
+    with_registries: bool,
+) {
+    for (delegate_mining, _) in split_delegates {
+        accounts.push(AccountMeta::new(*delegate_mining, false));
+        if with_registries {
+            accounts.push(AccountMeta::new_readonly(
+                find_delegate_registry_program_address(program_id, delegate_mining).0,
+                false,
+            ));
+        }
+    }
+}
+
+fn delegate_weights(split_delegates: &[(Pubkey, u16)]) -> Vec<u16> {
+    split_delegates.iter().map(|(_, weight)| *weight).collect()
 }
 
 /// Creates 'InitializePool' instruction.
@@ -179,6 +508,7 @@ pub fn initialize_pool(
     deposit_authority: &Pubkey,
     fill_authority: &Pubkey,
     distribute_authority: &Pubkey,
+    token_program: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*reward_pool, false),
@@ -187,7 +517,7 @@ pub fn initialize_pool(
         AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(*deposit_authority, true),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
-        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
         AccountMeta::new_readonly(system_program::id(), false),
     ];
 
@@ -212,6 +542,7 @@ pub fn fill_vault(
     from: &Pubkey,
     rewards: u64,
     distribution_ends_at: u64,
+    token_program: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*reward_pool, false),
@@ -219,7 +550,7 @@ pub fn fill_vault(
         AccountMeta::new(*vault, false),
         AccountMeta::new_readonly(*authority, true),
         AccountMeta::new(*from, false),
-        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
     ];
 
     Instruction::new_with_borsh(
@@ -264,25 +595,36 @@ pub fn deposit_mining(
     mining: &Pubkey,
     deposit_authority: &Pubkey,
     delegate_mining: &Pubkey,
+    deposit_id: u64,
     amount: u64,
     lockup_period: LockupPeriod,
     mining_owner: &Pubkey,
     delegate: &Pubkey,
+    split_delegates: &[(Pubkey, u16)],
 ) -> Instruction {
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*reward_pool, false),
         AccountMeta::new(*mining, false),
         AccountMeta::new_readonly(*deposit_authority, true),
         AccountMeta::new(*delegate_mining, false),
     ];
+    if delegate_mining != mining {
+        accounts.push(AccountMeta::new_readonly(
+            find_delegate_registry_program_address(program_id, delegate_mining).0,
+            false,
+        ));
+    }
+    push_split_delegates(&mut accounts, program_id, split_delegates, true);
 
     Instruction::new_with_borsh(
         *program_id,
         &RewardsInstruction::DepositMining {
+            deposit_id,
             amount,
             lockup_period,
             mining_owner: *mining_owner,
             delegate: *delegate,
+            delegate_weights: delegate_weights(split_delegates),
         },
         accounts,
     )
@@ -296,23 +638,28 @@ pub fn withdraw_mining(
     mining: &Pubkey,
     deposit_authority: &Pubkey,
     delegate_mining: &Pubkey,
+    deposit_id: u64,
     amount: u64,
     mining_owner: &Pubkey,
     delegate: &Pubkey,
+    split_delegates: &[(Pubkey, u16)],
 ) -> Instruction {
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*reward_pool, false),
         AccountMeta::new(*mining, false),
         AccountMeta::new_readonly(*deposit_authority, true),
         AccountMeta::new(*delegate_mining, false),
     ];
+    push_split_delegates(&mut accounts, program_id, split_delegates, false);
 
     Instruction::new_with_borsh(
         *program_id,
         &RewardsInstruction::WithdrawMining {
+            deposit_id,
             amount,
             mining_owner: *mining_owner,
             delegate: *delegate,
+            delegate_weights: delegate_weights(split_delegates),
         },
         accounts,
     )
@@ -329,16 +676,17 @@ pub fn claim(
     mining_owner: &Pubkey,
     deposit_authority: &Pubkey,
     mining_owner_reward_token: &Pubkey,
+    token_program: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
-        AccountMeta::new_readonly(*reward_pool, false),
+        AccountMeta::new(*reward_pool, false),
         AccountMeta::new_readonly(*reward_mint, false),
         AccountMeta::new(*vault, false),
         AccountMeta::new(*mining, false),
         AccountMeta::new_readonly(*mining_owner, true),
         AccountMeta::new_readonly(*deposit_authority, true),
         AccountMeta::new(*mining_owner_reward_token, false),
-        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*token_program, false),
     ];
 
     Instruction::new_with_borsh(*program_id, &RewardsInstruction::Claim, accounts)
@@ -352,31 +700,36 @@ pub fn extend_stake(
     mining: &Pubkey,
     deposit_authority: &Pubkey,
     delegate_mining: &Pubkey,
-    old_lockup_period: LockupPeriod,
+    deposit_id: u64,
     new_lockup_period: LockupPeriod,
-    deposit_start_ts: u64,
-    base_amount: u64,
     additional_amount: u64,
     mining_owner: &Pubkey,
     delegate: &Pubkey,
+    split_delegates: &[(Pubkey, u16)],
 ) -> Instruction {
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*reward_pool, false),
         AccountMeta::new(*mining, false),
         AccountMeta::new_readonly(*deposit_authority, true),
         AccountMeta::new(*delegate_mining, false),
     ];
+    if delegate_mining != mining {
+        accounts.push(AccountMeta::new_readonly(
+            find_delegate_registry_program_address(program_id, delegate_mining).0,
+            false,
+        ));
+    }
+    push_split_delegates(&mut accounts, program_id, split_delegates, true);
 
     Instruction::new_with_borsh(
         *program_id,
         &RewardsInstruction::ExtendStake {
-            old_lockup_period,
+            deposit_id,
             new_lockup_period,
-            deposit_start_ts,
-            base_amount,
             additional_amount,
             mining_owner: *mining_owner,
             delegate: *delegate,
+            delegate_weights: delegate_weights(split_delegates),
         },
         accounts,
     )
@@ -388,10 +741,12 @@ pub fn distribute_rewards(
     program_id: &Pubkey,
     reward_pool: &Pubkey,
     distribute_authority: &Pubkey,
+    vault: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*reward_pool, false),
         AccountMeta::new_readonly(*distribute_authority, true),
+        AccountMeta::new_readonly(*vault, false),
     ];
 
     Instruction::new_with_borsh(
@@ -433,9 +788,12 @@ pub fn change_delegate(
     old_delegate_mining: &Pubkey,
     new_delegate_mining: &Pubkey,
     new_delegate: &Pubkey,
+    deposit_id: u64,
     staked_amount: u64,
+    old_split_delegates: &[(Pubkey, u16)],
+    new_split_delegates: &[(Pubkey, u16)],
 ) -> Instruction {
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*reward_pool, false),
         AccountMeta::new(*mining, false),
         AccountMeta::new_readonly(*deposit_authority, true),
@@ -443,12 +801,23 @@ pub fn change_delegate(
         AccountMeta::new(*old_delegate_mining, false),
         AccountMeta::new(*new_delegate_mining, false),
     ];
+    push_split_delegates(&mut accounts, program_id, old_split_delegates, false);
+    if new_delegate_mining != mining {
+        accounts.push(AccountMeta::new_readonly(
+            find_delegate_registry_program_address(program_id, new_delegate_mining).0,
+            false,
+        ));
+    }
+    push_split_delegates(&mut accounts, program_id, new_split_delegates, true);
 
     Instruction::new_with_borsh(
         *program_id,
         &RewardsInstruction::ChangeDelegate {
+            deposit_id,
             staked_amount,
             new_delegate: *new_delegate,
+            old_delegate_weights: delegate_weights(old_split_delegates),
+            new_delegate_weights: delegate_weights(new_split_delegates),
         },
         accounts,
     )
@@ -460,41 +829,47 @@ pub fn slash(
     deposit_authority: &Pubkey,
     reward_pool: &Pubkey,
     mining: &Pubkey,
+    penalty_history: &Pubkey,
     mining_owner: &Pubkey,
+    deposit_id: u64,
     slash_amount_in_native: u64,
-    slash_amount_multiplied_by_period: u64,
-    stake_expiration_date: Option<u64>,
+    reason_code: u32,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new_readonly(*deposit_authority, true),
         AccountMeta::new(*reward_pool, false),
         AccountMeta::new(*mining, false),
+        AccountMeta::new(*penalty_history, false),
     ];
 
     Instruction::new_with_borsh(
         *program_id,
         &RewardsInstruction::Slash {
             mining_owner: *mining_owner,
+            deposit_id,
             slash_amount_in_native,
-            slash_amount_multiplied_by_period,
-            stake_expiration_date,
+            reason_code,
         },
         accounts,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn decrease_rewards(
     program_id: &Pubkey,
     deposit_authority: &Pubkey,
     reward_pool: &Pubkey,
     mining: &Pubkey,
+    penalty_history: &Pubkey,
     mining_owner: &Pubkey,
     decreased_weighted_stake_number: u64,
+    reason_code: u32,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new_readonly(*deposit_authority, true),
         AccountMeta::new(*reward_pool, false),
         AccountMeta::new(*mining, false),
+        AccountMeta::new(*penalty_history, false),
     ];
 
     Instruction::new_with_borsh(
@@ -502,7 +877,528 @@ pub fn decrease_rewards(
         &RewardsInstruction::DecreaseRewards {
             mining_owner: *mining_owner,
             decreased_weighted_stake_number,
+            reason_code,
         },
         accounts,
     )
 }
+
+/// Creates 'InitializePenaltyHistory' instruction.
+pub fn initialize_penalty_history(
+    program_id: &Pubkey,
+    mining: &Pubkey,
+    penalty_history: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*mining, false),
+        AccountMeta::new(*penalty_history, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::InitializePenaltyHistory,
+        accounts,
+    )
+}
+
+/// Creates 'RestorePenalty' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn restore_penalty(
+    program_id: &Pubkey,
+    deposit_authority: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    penalty_history: &Pubkey,
+    mining_owner: &Pubkey,
+    penalty_id: u64,
+    credit_lost_rewards: bool,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*deposit_authority, true),
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new(*penalty_history, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::RestorePenalty {
+            mining_owner: *mining_owner,
+            penalty_id,
+            credit_lost_rewards,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'SetPoolLimits' instruction.
+pub fn set_pool_limits(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    deposit_authority: &Pubkey,
+    min_deposit: u64,
+    max_weighted_share_per_mining: u64,
+    max_total_share: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new_readonly(*deposit_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::SetPoolLimits {
+            min_deposit,
+            max_weighted_share_per_mining,
+            max_total_share,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'SetBoostConfig' instruction.
+pub fn set_boost_config(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    deposit_authority: &Pubkey,
+    boost_collection: &Pubkey,
+    boost_attester: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new_readonly(*deposit_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::SetBoostConfig {
+            boost_collection: *boost_collection,
+            boost_attester: *boost_attester,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'ApplyBoost' instruction.
+/// `proof_accounts` are the NFT token account, its metadata, the boost record of the NFT mint
+/// and the payer for the boost record creation for the collection proof
+/// or the attester for the attester proof.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_boost(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    deposit_authority: &Pubkey,
+    proof_accounts: &[Pubkey],
+    mining_owner: &Pubkey,
+    boost_bps: u16,
+    expires_at: u64,
+    proof: BoostProof,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new_readonly(*deposit_authority, true),
+    ];
+    match proof {
+        BoostProof::Collection => {
+            accounts.extend(
+                proof_accounts
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, key)| match idx {
+                        2 => AccountMeta::new(*key, false),
+                        3 => AccountMeta::new(*key, true),
+                        _ => AccountMeta::new_readonly(*key, false),
+                    }),
+            );
+            accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+        }
+        BoostProof::Attester => accounts.extend(
+            proof_accounts
+                .iter()
+                .map(|key| AccountMeta::new_readonly(*key, true)),
+        ),
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::ApplyBoost {
+            mining_owner: *mining_owner,
+            boost_bps,
+            expires_at,
+            proof_kind: proof,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'SetEpochLength' instruction.
+pub fn set_epoch_length(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    deposit_authority: &Pubkey,
+    epoch_length_secs: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new_readonly(*deposit_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::SetEpochLength { epoch_length_secs },
+        accounts,
+    )
+}
+
+/// Creates 'ClosePool' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn close_pool(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    reward_mint: &Pubkey,
+    vault: &Pubkey,
+    deposit_authority: &Pubkey,
+    recipient_token_account: &Pubkey,
+    target_account: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*reward_mint, false),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new_readonly(*deposit_authority, true),
+        AccountMeta::new(*recipient_token_account, false),
+        AccountMeta::new(*target_account, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RewardsInstruction::ClosePool, accounts)
+}
+
+/// Creates 'SunsetPool' instruction.
+pub fn sunset_pool(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    deposit_authority: &Pubkey,
+    grace_period_secs: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new_readonly(*deposit_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::SunsetPool { grace_period_secs },
+        accounts,
+    )
+}
+
+/// Creates 'MigratePool' instruction.
+pub fn migrate_pool(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    payer: &Pubkey,
+    vault: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*vault, false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RewardsInstruction::MigratePool, accounts)
+}
+
+/// Creates 'MigrateMining' instruction.
+pub fn migrate_mining(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RewardsInstruction::MigrateMining, accounts)
+}
+
+/// Creates 'TransferMining' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_mining(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    new_mining: &Pubkey,
+    mining_owner: &Pubkey,
+    new_owner: &Pubkey,
+    deposit_authority: &Pubkey,
+    payer: &Pubkey,
+    target_account: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new(*new_mining, false),
+        AccountMeta::new_readonly(*mining_owner, true),
+        AccountMeta::new_readonly(*deposit_authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*target_account, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::TransferMining {
+            new_owner: *new_owner,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'MergeMining' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn merge_mining(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    source_mining: &Pubkey,
+    mining: &Pubkey,
+    source_mining_owner: &Pubkey,
+    deposit_authority: &Pubkey,
+    target_account: &Pubkey,
+    deposit_ids: Vec<u64>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*source_mining, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new_readonly(*source_mining_owner, true),
+        AccountMeta::new_readonly(*deposit_authority, true),
+        AccountMeta::new(*target_account, false),
+        AccountMeta::new_readonly(
+            find_penalty_history_program_address(program_id, source_mining).0,
+            false,
+        ),
+        AccountMeta::new_readonly(
+            find_delegate_registry_program_address(program_id, source_mining).0,
+            false,
+        ),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::MergeMining { deposit_ids },
+        accounts,
+    )
+}
+
+/// Creates 'SplitMining' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn split_mining(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    destination_mining: &Pubkey,
+    mining_owner: &Pubkey,
+    deposit_authority: &Pubkey,
+    deposit_id: u64,
+    destination_deposit_id: u64,
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new(*destination_mining, false),
+        AccountMeta::new_readonly(*mining_owner, true),
+        AccountMeta::new_readonly(*deposit_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::SplitMining {
+            deposit_id,
+            destination_deposit_id,
+            amount,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'RegisterDelegate' instruction.
+pub fn register_delegate(
+    program_id: &Pubkey,
+    mining: &Pubkey,
+    mining_owner: &Pubkey,
+    payer: &Pubkey,
+    max_stake_from_others: u64,
+    allow_list: Vec<Pubkey>,
+    deny_list: Vec<Pubkey>,
+) -> Instruction {
+    let (delegate_registry, _) = find_delegate_registry_program_address(program_id, mining);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*mining, false),
+        AccountMeta::new(delegate_registry, false),
+        AccountMeta::new_readonly(*mining_owner, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::RegisterDelegate {
+            max_stake_from_others,
+            allow_list,
+            deny_list,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'UpdateDelegateRegistry' instruction.
+pub fn update_delegate_registry(
+    program_id: &Pubkey,
+    mining: &Pubkey,
+    mining_owner: &Pubkey,
+    max_stake_from_others: u64,
+    allow_list: Vec<Pubkey>,
+    deny_list: Vec<Pubkey>,
+) -> Instruction {
+    let (delegate_registry, _) = find_delegate_registry_program_address(program_id, mining);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*mining, false),
+        AccountMeta::new(delegate_registry, false),
+        AccountMeta::new_readonly(*mining_owner, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::UpdateDelegateRegistry {
+            max_stake_from_others,
+            allow_list,
+            deny_list,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'UnregisterDelegate' instruction.
+pub fn unregister_delegate(
+    program_id: &Pubkey,
+    mining: &Pubkey,
+    mining_owner: &Pubkey,
+    target_account: &Pubkey,
+) -> Instruction {
+    let (delegate_registry, _) = find_delegate_registry_program_address(program_id, mining);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*mining, false),
+        AccountMeta::new(delegate_registry, false),
+        AccountMeta::new_readonly(*mining_owner, true),
+        AccountMeta::new(*target_account, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::UnregisterDelegate,
+        accounts,
+    )
+}
+
+/// Creates 'ReportSolvency' instruction.
+pub fn report_solvency(program_id: &Pubkey, reward_pool: &Pubkey, vault: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*reward_pool, false),
+        AccountMeta::new_readonly(*vault, false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RewardsInstruction::ReportSolvency, accounts)
+}
+
+/// Creates 'RecordDeposit' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn record_deposit(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    deposit_authority: &Pubkey,
+    mining_owner: &Pubkey,
+    deposit_id: u64,
+    amount: u64,
+    lockup_period: LockupPeriod,
+    deposit_start_ts: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new_readonly(*deposit_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::RecordDeposit {
+            mining_owner: *mining_owner,
+            deposit_id,
+            amount,
+            lockup_period,
+            deposit_start_ts,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'SetUnbondingConfig' instruction.
+pub fn set_unbonding_config(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    deposit_authority: &Pubkey,
+    unbonding_period_secs: u64,
+    unbonding_multiplier_bps: u16,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new_readonly(*deposit_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::SetUnbondingConfig {
+            unbonding_period_secs,
+            unbonding_multiplier_bps,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'RequestWithdraw' instruction.
+pub fn request_withdraw(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    mining_owner: &Pubkey,
+    deposit_id: u64,
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new_readonly(*mining_owner, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::RequestWithdraw { deposit_id, amount },
+        accounts,
+    )
+}
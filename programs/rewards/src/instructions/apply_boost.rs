@@ -0,0 +1,130 @@
+use crate::{
+    asserts::{
+        assert_account_key, assert_account_owner, assert_and_get_pool_and_mining,
+        assert_collection_membership,
+    },
+    error::MplxRewardsError,
+    state::BoostRecord,
+    utils::{
+        create_account, find_boost_record_program_address, get_curr_unix_ts, AccountLoader,
+        BoostProof,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+pub fn process_apply_boost<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    mining_owner: &Pubkey,
+    boost_bps: u16,
+    expires_at: u64,
+    proof: BoostProof,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+
+    let (mut wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
+        program_id,
+        mining_owner,
+        mining,
+        reward_pool,
+        deposit_authority,
+        reward_pool_data,
+        mining_data,
+    )?;
+
+    let nft_proof = match proof {
+        BoostProof::Collection => {
+            let token_account = AccountLoader::next_unchecked(account_info_iter)?;
+            let metadata = AccountLoader::next_unchecked(account_info_iter)?;
+            let boost_record = AccountLoader::next_unchecked(account_info_iter)?;
+            let payer = AccountLoader::next_signer(account_info_iter)?;
+            let _system_program =
+                AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+            let nft_mint = assert_collection_membership(
+                &wrapped_reward_pool.pool.boost_collection,
+                mining_owner,
+                token_account,
+                metadata,
+            )?;
+            Some((nft_mint, boost_record, payer))
+        }
+        BoostProof::Attester => {
+            let attester = AccountLoader::next_signer(account_info_iter)?;
+            if wrapped_reward_pool.pool.boost_attester == Pubkey::default() {
+                return Err(MplxRewardsError::InvalidBoostProof.into());
+            }
+            assert_account_key(attester, &wrapped_reward_pool.pool.boost_attester)
+                .map_err(|_| ProgramError::from(MplxRewardsError::InvalidBoostProof))?;
+            None
+        }
+    };
+
+    let curr_ts = get_curr_unix_ts();
+    wrapped_reward_pool.apply_boost(&mut wrapped_mining, boost_bps, expires_at, curr_ts)?;
+
+    if let Some((nft_mint, boost_record, payer)) = nft_proof {
+        record_nft_boost(
+            program_id,
+            reward_pool.key,
+            mining.key,
+            &nft_mint,
+            boost_record,
+            payer,
+            wrapped_mining.mining.boost_expires_at,
+            curr_ts,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Records the boost in the boost record of the NFT, which is created on the first boost
+#[allow(clippy::too_many_arguments)]
+fn record_nft_boost<'a>(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    nft_mint: &Pubkey,
+    boost_record: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    expires_at: u64,
+    curr_ts: u64,
+) -> ProgramResult {
+    let (pubkey, bump) = find_boost_record_program_address(program_id, reward_pool, nft_mint);
+    assert_account_key(boost_record, &pubkey)?;
+
+    if boost_record.data_is_empty() {
+        let signers_seeds = &[
+            "boost_record".as_bytes(),
+            &reward_pool.to_bytes(),
+            &nft_mint.to_bytes(),
+            &[bump],
+        ];
+        create_account(
+            program_id,
+            payer.clone(),
+            boost_record.clone(),
+            BoostRecord::LEN,
+            &[signers_seeds],
+        )?;
+
+        let boost_record_data = &mut boost_record.try_borrow_mut_data()?;
+        let record = BoostRecord::from_uninitialized_bytes_mut(boost_record_data)?;
+        *record = BoostRecord::initialize(*nft_mint, bump);
+    }
+
+    assert_account_owner(boost_record, program_id)?;
+    let boost_record_data = &mut boost_record.try_borrow_mut_data()?;
+    let record = BoostRecord::from_bytes_mut(boost_record_data)?;
+    record.record(mining, expires_at, curr_ts)
+}
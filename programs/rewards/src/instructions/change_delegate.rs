@@ -1,15 +1,21 @@
 use crate::{
     asserts::assert_and_get_pool_and_mining,
     error::MplxRewardsError,
-    utils::{get_delegate_mining, verify_delegate_mining_address, AccountLoader},
+    utils::{
+        get_curr_unix_ts, get_delegate_mining, next_delegates, next_split_delegates, AccountLoader,
+        DelegateAccounts,
+    },
 };
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
 pub fn process_change_delegate<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
+    deposit_id: u64,
     staked_amount: u64,
     new_delegate: &Pubkey,
+    old_delegate_weights: &[u16],
+    new_delegate_weights: &[u16],
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter().enumerate();
 
@@ -20,12 +26,15 @@ pub fn process_change_delegate<'a>(
     let old_delegate_mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
     let new_delegate_mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
 
-    if new_delegate_mining.key == old_delegate_mining.key {
+    if old_delegate_weights.is_empty()
+        && new_delegate_weights.is_empty()
+        && new_delegate_mining.key == old_delegate_mining.key
+    {
         return Err(MplxRewardsError::DelegatesAreTheSame.into());
     }
 
-    let mining_data = &mut mining.data.borrow_mut();
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
 
     let (mut wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
         program_id,
@@ -37,23 +46,45 @@ pub fn process_change_delegate<'a>(
         mining_data,
     )?;
 
-    let new_delegate_mining = get_delegate_mining(new_delegate_mining, mining)?;
-    if let Some(new_delegate_mining) = new_delegate_mining {
-        verify_delegate_mining_address(
+    let old_delegates = if old_delegate_weights.is_empty() {
+        get_delegate_mining(old_delegate_mining, mining)?
+            .map(|old_delegate_mining| DelegateAccounts {
+                mining: old_delegate_mining,
+                registry: None,
+                weight: 1,
+            })
+            .into_iter()
+            .collect()
+    } else {
+        next_split_delegates(
+            account_info_iter,
             program_id,
-            new_delegate_mining,
-            new_delegate,
             reward_pool.key,
+            mining,
+            old_delegate_mining,
+            old_delegate_weights,
+            false,
         )?
-    }
+    };
 
-    let old_delegate_mining = get_delegate_mining(old_delegate_mining, mining)?;
+    let new_delegates = next_delegates(
+        account_info_iter,
+        program_id,
+        reward_pool.key,
+        mining,
+        new_delegate_mining,
+        new_delegate,
+        new_delegate_weights,
+        true,
+    )?;
 
     wrapped_reward_pool.change_delegate(
         &mut wrapped_mining,
-        new_delegate_mining,
-        old_delegate_mining,
+        deposit_id,
+        &new_delegates,
+        &old_delegates,
         staked_amount,
+        get_curr_unix_ts(),
     )?;
 
     Ok(())
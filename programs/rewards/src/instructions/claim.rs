@@ -1,74 +1,80 @@
 use crate::{
-    asserts::{assert_account_key, assert_account_owner},
+    asserts::{assert_account_key, assert_account_owner, assert_reward_mint},
     state::{WrappedMining, WrappedRewardPool},
-    utils::{spl_transfer, AccountLoader},
+    utils::{
+        assert_token_program, get_curr_unix_ts, spl_transfer, unpack_token_account, AccountLoader,
+    },
 };
 use borsh::BorshSerialize;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program::set_return_data,
-    program_pack::Pack, pubkey::Pubkey,
+    account_info::AccountInfo, entrypoint::ProgramResult, program::set_return_data, pubkey::Pubkey,
 };
-use spl_token::state::Account;
 
 pub fn process_claim<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter().enumerate();
 
     let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
-    let reward_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
-    let vault = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+    let reward_mint = AccountLoader::next_unchecked(account_info_iter)?;
+    let vault = AccountLoader::next_with_owner(account_info_iter, reward_mint.owner)?;
     let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
     let mining_owner = AccountLoader::next_signer(account_info_iter)?;
     let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
     let mining_owner_reward_token_account =
-        AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
-    let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+        AccountLoader::next_with_owner(account_info_iter, reward_mint.owner)?;
+    let _token_program = AccountLoader::next_with_key(account_info_iter, reward_mint.owner)?;
 
-    {
-        let mining_user_rewards =
-            Account::unpack(&mining_owner_reward_token_account.data.borrow())?;
-        assert_account_key(mining_owner, &mining_user_rewards.owner)?;
-    }
+    assert_token_program(reward_mint.owner)?;
+
+    let mining_user_rewards = unpack_token_account(mining_owner_reward_token_account)?;
+    assert_account_key(mining_owner, &mining_user_rewards.owner)?;
 
     let amount = {
-        let reward_pool_data = &mut reward_pool.data.borrow_mut();
-        let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+        let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+        let mut wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
 
         assert_account_key(
             deposit_authority,
             &wrapped_reward_pool.pool.deposit_authority,
         )?;
 
-        let amount = {
-            let mining_data = &mut mining.data.borrow_mut();
-            let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
+        let mining_data = &mut mining.try_borrow_mut_data()?;
+        let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
+
+        assert_account_owner(reward_pool, program_id)?;
+        assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
+        assert_account_key(reward_pool, &wrapped_mining.mining.reward_pool)?;
 
-            assert_account_owner(reward_pool, program_id)?;
-            assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
-            assert_account_key(reward_pool, &wrapped_mining.mining.reward_pool)?;
+        // every account taking part in the claim must be bound to the reward mint of the pool
+        let pool_reward_mint = wrapped_reward_pool.pool.reward_mint;
+        let vault_account = unpack_token_account(vault)?;
+        assert_reward_mint(reward_mint.key, &pool_reward_mint)?;
+        assert_reward_mint(&wrapped_mining.mining.reward_mint, &pool_reward_mint)?;
+        assert_reward_mint(&vault_account.mint, &pool_reward_mint)?;
+        assert_reward_mint(&mining_user_rewards.mint, &pool_reward_mint)?;
 
-            let vault_seeds = &[
-                b"vault".as_ref(),
-                &reward_pool.key.to_bytes(),
-                &reward_mint.key.to_bytes(),
-                &[wrapped_reward_pool.pool.token_account_bump],
-            ];
-            assert_account_key(
-                vault,
-                &Pubkey::create_program_address(vault_seeds, program_id)?,
-            )?;
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            &reward_pool.key.to_bytes(),
+            &reward_mint.key.to_bytes(),
+            &[wrapped_reward_pool.pool.token_account_bump],
+        ];
+        assert_account_key(
+            vault,
+            &Pubkey::create_program_address(vault_seeds, program_id)?,
+        )?;
 
-            wrapped_mining.refresh_rewards(&*wrapped_reward_pool.cumulative_index)?;
-            let amount = wrapped_mining.mining.unclaimed_rewards;
-            wrapped_mining.mining.claim();
-            amount
-        };
+        // the claimed amount is still a part of the liabilities until it's transferred
+        wrapped_reward_pool
+            .pool
+            .assert_solvency(vault_account.amount)?;
 
-        amount
+        wrapped_reward_pool.claim(&mut wrapped_mining, get_curr_unix_ts())?
     };
 
     if amount > 0 {
         spl_transfer(
             vault.to_owned(),
+            reward_mint.to_owned(),
             mining_owner_reward_token_account.to_owned(),
             deposit_authority.to_owned(),
             amount,
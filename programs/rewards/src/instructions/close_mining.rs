@@ -2,7 +2,7 @@ use crate::{
     asserts::assert_account_key,
     error::MplxRewardsError,
     state::{WrappedMining, WrappedRewardPool},
-    utils::{AccountLoader, SafeArithmeticOperations},
+    utils::{get_curr_unix_ts, AccountLoader, SafeArithmeticOperations},
 };
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
@@ -21,18 +21,22 @@ pub fn process_close_mining<'a>(
     let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
 
     {
-        let reward_pool_data = &mut reward_pool.data.borrow_mut();
+        let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
         let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
         assert_account_key(
             deposit_authority,
             &wrapped_reward_pool.pool.deposit_authority,
         )?;
 
-        let mining_data = &mut (*mining.data).borrow_mut();
+        let mining_data = &mut mining.try_borrow_mut_data()?;
         let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
         assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
 
-        wrapped_mining.refresh_rewards(wrapped_reward_pool.cumulative_index)?;
+        wrapped_mining.refresh_rewards(
+            wrapped_reward_pool.cumulative_index,
+            wrapped_reward_pool.pool.epoch_length(),
+            get_curr_unix_ts(),
+        )?;
 
         if wrapped_mining.mining.stake_from_others > 0 {
             return Err(MplxRewardsError::StakeFromOthersMustBeZero.into());
@@ -46,9 +50,10 @@ pub fn process_close_mining<'a>(
     // https://solanacookbook.com/references/accounts.html#how-to-close-accounts
     let dest_starting_lamports = target_account.lamports();
 
-    **target_account.lamports.borrow_mut() = dest_starting_lamports.safe_add(mining.lamports())?;
-    **mining.lamports.borrow_mut() = 0;
-    let mut source_data = mining.data.borrow_mut();
+    **target_account.try_borrow_mut_lamports()? =
+        dest_starting_lamports.safe_add(mining.lamports())?;
+    **mining.try_borrow_mut_lamports()? = 0;
+    let mut source_data = mining.try_borrow_mut_data()?;
     source_data.fill(0);
 
     Ok(())
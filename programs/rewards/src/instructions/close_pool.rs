@@ -0,0 +1,99 @@
+use crate::{
+    asserts::{assert_account_key, assert_pubkey_eq},
+    error::MplxRewardsError,
+    state::WrappedRewardPool,
+    utils::{
+        assert_token_program, get_curr_unix_ts, spl_close_account, spl_harvest_withheld_tokens,
+        spl_transfer, unpack_token_account, AccountLoader, SafeArithmeticOperations,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+pub fn process_close_pool<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let reward_mint = AccountLoader::next_unchecked(account_info_iter)?;
+    let vault = AccountLoader::next_with_owner(account_info_iter, reward_mint.owner)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+    let recipient_token_account =
+        AccountLoader::next_with_owner(account_info_iter, reward_mint.owner)?;
+    let target_account = AccountLoader::next_with_owner(account_info_iter, &system_program::id())?;
+    let _token_program = AccountLoader::next_with_key(account_info_iter, reward_mint.owner)?;
+
+    assert_token_program(reward_mint.owner)?;
+
+    {
+        let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+        let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+
+        assert_account_key(
+            deposit_authority,
+            &wrapped_reward_pool.pool.deposit_authority,
+        )?;
+        assert_account_key(reward_mint, &wrapped_reward_pool.pool.reward_mint)?;
+
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            reward_pool.key.as_ref(),
+            reward_mint.key.as_ref(),
+            &[wrapped_reward_pool.pool.token_account_bump],
+        ];
+        assert_account_key(
+            vault,
+            &Pubkey::create_program_address(vault_seeds, program_id)?,
+        )?;
+
+        let recipient = unpack_token_account(recipient_token_account)?;
+        assert_pubkey_eq(&recipient.owner, &wrapped_reward_pool.pool.fill_authority)?;
+
+        if !wrapped_reward_pool
+            .pool
+            .is_sunset_grace_period_elapsed(get_curr_unix_ts())?
+        {
+            if wrapped_reward_pool.pool.total_share != 0 {
+                return Err(MplxRewardsError::PoolHasStakes.into());
+            }
+            // rewards owed to the miners stay in the vault until they're claimed
+            if wrapped_reward_pool.pool.unclaimed_rewards() != 0 {
+                return Err(MplxRewardsError::PoolHasUnclaimedRewards.into());
+            }
+        }
+    }
+
+    let vault_balance = unpack_token_account(vault)?.amount;
+    if vault_balance > 0 {
+        spl_transfer(
+            vault.clone(),
+            reward_mint.clone(),
+            recipient_token_account.clone(),
+            deposit_authority.clone(),
+            vault_balance,
+            &[],
+        )?;
+    }
+    spl_harvest_withheld_tokens(vault.clone(), reward_mint.clone())?;
+    spl_close_account(
+        vault.clone(),
+        target_account.clone(),
+        deposit_authority.clone(),
+        &[],
+    )?;
+
+    // Snippet from solana cookbook
+    // https://solanacookbook.com/references/accounts.html#how-to-close-accounts
+    let dest_starting_lamports = target_account.lamports();
+
+    **target_account.try_borrow_mut_lamports()? =
+        dest_starting_lamports.safe_add(reward_pool.lamports())?;
+    **reward_pool.try_borrow_mut_lamports()? = 0;
+    let mut source_data = reward_pool.try_borrow_mut_data()?;
+    source_data.fill(0);
+
+    Ok(())
+}
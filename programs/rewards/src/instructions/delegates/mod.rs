@@ -0,0 +1,7 @@
+mod register_delegate;
+mod unregister_delegate;
+mod update_delegate_registry;
+
+pub(crate) use register_delegate::*;
+pub(crate) use unregister_delegate::*;
+pub(crate) use update_delegate_registry::*;
@@ -0,0 +1,61 @@
+use crate::{
+    asserts::assert_account_key,
+    state::{DelegateRegistry, WrappedImmutableMining},
+    utils::{find_delegate_registry_program_address, AccountLoader},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed, pubkey::Pubkey,
+    rent::Rent, system_instruction, system_program, sysvar::Sysvar,
+};
+
+pub fn process_register_delegate<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    max_stake_from_others: u64,
+    allow_list: &[Pubkey],
+    deny_list: &[Pubkey],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let delegate_registry = AccountLoader::next_uninitialized(account_info_iter)?;
+    let mining_owner = AccountLoader::next_signer(account_info_iter)?;
+    let payer = AccountLoader::next_signer(account_info_iter)?;
+    let _system_program = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+    {
+        let mining_data = &mining.try_borrow_data()?;
+        let wrapped_mining = WrappedImmutableMining::from_bytes(mining_data)?;
+        assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
+    }
+
+    let (pubkey, bump) = find_delegate_registry_program_address(program_id, mining.key);
+    assert_account_key(delegate_registry, &pubkey)?;
+
+    let signers_seeds = &[
+        "delegate_registry".as_bytes(),
+        &mining.key.to_bytes(),
+        &[bump],
+    ];
+
+    let rent = Rent::get()?;
+    let ix = system_instruction::create_account(
+        payer.key,
+        delegate_registry.key,
+        rent.minimum_balance(DelegateRegistry::LEN),
+        DelegateRegistry::LEN as u64,
+        program_id,
+    );
+    invoke_signed(
+        &ix,
+        &[payer.clone(), delegate_registry.clone()],
+        &[signers_seeds],
+    )?;
+
+    let delegate_registry_data = &mut delegate_registry.try_borrow_mut_data()?;
+    let registry = DelegateRegistry::from_uninitialized_bytes_mut(delegate_registry_data)?;
+    *registry = DelegateRegistry::initialize(*mining.key, bump);
+    registry.configure(max_stake_from_others, allow_list, deny_list)?;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::{
+    asserts::assert_account_key,
+    error::MplxRewardsError,
+    state::{DelegateRegistry, WrappedImmutableMining},
+    utils::{AccountLoader, SafeArithmeticOperations},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+pub fn process_unregister_delegate<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let delegate_registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining_owner = AccountLoader::next_signer(account_info_iter)?;
+    let target_account = AccountLoader::next_with_owner(account_info_iter, &system_program::id())?;
+
+    {
+        let mining_data = &mining.try_borrow_data()?;
+        let wrapped_mining = WrappedImmutableMining::from_bytes(mining_data)?;
+        assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
+
+        let delegate_registry_data = &delegate_registry.try_borrow_data()?;
+        let registry = DelegateRegistry::from_bytes(delegate_registry_data)?;
+        if registry.mining != *mining.key {
+            return Err(MplxRewardsError::DelegateIsNotRegistered.into());
+        }
+    }
+
+    // Snippet from solana cookbook
+    // https://solanacookbook.com/references/accounts.html#how-to-close-accounts
+    let dest_starting_lamports = target_account.lamports();
+
+    **target_account.try_borrow_mut_lamports()? =
+        dest_starting_lamports.safe_add(delegate_registry.lamports())?;
+    **delegate_registry.try_borrow_mut_lamports()? = 0;
+    let mut source_data = delegate_registry.try_borrow_mut_data()?;
+    source_data.fill(0);
+
+    Ok(())
+}
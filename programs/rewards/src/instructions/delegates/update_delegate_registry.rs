@@ -0,0 +1,33 @@
+use crate::{
+    asserts::assert_account_key,
+    error::MplxRewardsError,
+    state::{DelegateRegistry, WrappedImmutableMining},
+    utils::AccountLoader,
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn process_update_delegate_registry<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    max_stake_from_others: u64,
+    allow_list: &[Pubkey],
+    deny_list: &[Pubkey],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let delegate_registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining_owner = AccountLoader::next_signer(account_info_iter)?;
+
+    let mining_data = &mining.try_borrow_data()?;
+    let wrapped_mining = WrappedImmutableMining::from_bytes(mining_data)?;
+    assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
+
+    let delegate_registry_data = &mut delegate_registry.try_borrow_mut_data()?;
+    let registry = DelegateRegistry::from_bytes_mut(delegate_registry_data)?;
+    if registry.mining != *mining.key {
+        return Err(MplxRewardsError::DelegateIsNotRegistered.into());
+    }
+
+    registry.configure(max_stake_from_others, allow_list, deny_list)
+}
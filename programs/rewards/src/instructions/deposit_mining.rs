@@ -1,16 +1,19 @@
 use crate::{
     asserts::assert_and_get_pool_and_mining,
-    utils::{get_delegate_mining, verify_delegate_mining_address, AccountLoader, LockupPeriod},
+    utils::{get_curr_unix_ts, next_delegates, AccountLoader, LockupPeriod},
 };
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
+#[allow(clippy::too_many_arguments)]
 pub fn process_deposit_mining<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
+    deposit_id: u64,
     amount: u64,
     lockup_period: LockupPeriod,
     mining_owner: &Pubkey,
     delegate: &Pubkey,
+    delegate_weights: &[u16],
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter().enumerate();
 
@@ -19,8 +22,8 @@ pub fn process_deposit_mining<'a>(
     let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
     let delegate_mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
 
-    let mining_data = &mut mining.data.borrow_mut();
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
 
     let (mut wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
         program_id,
@@ -32,12 +35,25 @@ pub fn process_deposit_mining<'a>(
         mining_data,
     )?;
 
-    let delegate_mining = get_delegate_mining(delegate_mining, mining)?;
-    if let Some(delegate_mining) = delegate_mining {
-        verify_delegate_mining_address(program_id, delegate_mining, delegate, reward_pool.key)?
-    }
+    let delegates = next_delegates(
+        account_info_iter,
+        program_id,
+        reward_pool.key,
+        mining,
+        delegate_mining,
+        delegate,
+        delegate_weights,
+        true,
+    )?;
 
-    wrapped_reward_pool.deposit(&mut wrapped_mining, amount, lockup_period, delegate_mining)?;
+    wrapped_reward_pool.deposit(
+        &mut wrapped_mining,
+        deposit_id,
+        amount,
+        lockup_period,
+        &delegates,
+        get_curr_unix_ts(),
+    )?;
 
     Ok(())
 }
@@ -1,4 +1,8 @@
-use crate::{asserts::assert_account_key, state::WrappedRewardPool, utils::AccountLoader};
+use crate::{
+    asserts::assert_account_key,
+    state::WrappedRewardPool,
+    utils::{get_curr_unix_ts, unpack_token_account, AccountLoader},
+};
 
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
@@ -10,16 +14,24 @@ pub fn process_distribute_rewards<'a>(
 
     let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
     let distribute_authority = AccountLoader::next_signer(account_info_iter)?;
+    let vault = AccountLoader::next_unchecked(account_info_iter)?;
 
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
     let mut wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
-    let rewards_to_distribute = wrapped_reward_pool.pool.rewards_to_distribute()?;
+    let curr_ts = get_curr_unix_ts();
+    let rewards_to_distribute = wrapped_reward_pool.pool.rewards_to_distribute(curr_ts)?;
     assert_account_key(
         distribute_authority,
         &wrapped_reward_pool.pool.distribute_authority,
     )?;
+    wrapped_reward_pool
+        .pool
+        .assert_vault(program_id, reward_pool.key, vault)?;
 
-    wrapped_reward_pool.distribute(rewards_to_distribute)?;
+    wrapped_reward_pool.distribute(rewards_to_distribute, curr_ts)?;
+    wrapped_reward_pool
+        .pool
+        .assert_solvency(unpack_token_account(vault)?.amount)?;
 
     Ok(())
 }
@@ -1,6 +1,6 @@
 use crate::{
     asserts::assert_and_get_pool_and_mining,
-    utils::{get_delegate_mining, verify_delegate_mining_address, AccountLoader, LockupPeriod},
+    utils::{get_curr_unix_ts, next_delegates, AccountLoader, LockupPeriod},
 };
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
@@ -8,13 +8,12 @@ use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubke
 pub fn process_extend_stake<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
-    old_lockup_period: LockupPeriod,
+    deposit_id: u64,
     new_lockup_period: LockupPeriod,
-    deposit_start_ts: u64,
-    base_amount: u64,
     additional_amount: u64,
     mining_owner: &Pubkey,
     delegate: &Pubkey,
+    delegate_weights: &[u16],
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter().enumerate();
 
@@ -23,8 +22,8 @@ pub fn process_extend_stake<'a>(
     let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
     let delegate_mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
 
-    let mining_data = &mut mining.data.borrow_mut();
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
 
     let (mut wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
         program_id,
@@ -36,20 +35,24 @@ pub fn process_extend_stake<'a>(
         mining_data,
     )?;
 
-    let delegate_mining = get_delegate_mining(delegate_mining, mining)?;
-
-    if let Some(delegate_mining) = delegate_mining {
-        verify_delegate_mining_address(program_id, delegate_mining, delegate, reward_pool.key)?
-    }
+    let delegates = next_delegates(
+        account_info_iter,
+        program_id,
+        reward_pool.key,
+        mining,
+        delegate_mining,
+        delegate,
+        delegate_weights,
+        true,
+    )?;
 
     wrapped_reward_pool.extend(
         &mut wrapped_mining,
-        old_lockup_period,
+        deposit_id,
         new_lockup_period,
-        deposit_start_ts,
-        base_amount,
         additional_amount,
-        delegate_mining,
+        &delegates,
+        get_curr_unix_ts(),
     )?;
 
     Ok(())
@@ -2,11 +2,12 @@ use crate::{
     asserts::assert_account_key,
     error::MplxRewardsError,
     state::WrappedRewardPool,
-    utils::{get_curr_unix_ts, spl_transfer, AccountLoader, SafeArithmeticOperations},
-};
-use solana_program::{
-    account_info::AccountInfo, clock::SECONDS_PER_DAY, entrypoint::ProgramResult, pubkey::Pubkey,
+    utils::{
+        assert_token_program, get_curr_unix_ts, spl_transfer, unpack_token_account, AccountLoader,
+        SafeArithmeticOperations,
+    },
 };
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
 pub fn process_fill_vault<'a>(
     program_id: &Pubkey,
@@ -17,20 +18,24 @@ pub fn process_fill_vault<'a>(
     let account_info_iter = &mut accounts.iter().enumerate();
 
     let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
-    let reward_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
-    let vault = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+    let reward_mint = AccountLoader::next_unchecked(account_info_iter)?;
+    let vault = AccountLoader::next_with_owner(account_info_iter, reward_mint.owner)?;
     let fill_authority = AccountLoader::next_signer(account_info_iter)?;
-    let source_token_account = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
-    let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+    let source_token_account =
+        AccountLoader::next_with_owner(account_info_iter, reward_mint.owner)?;
+    let _token_program = AccountLoader::next_with_key(account_info_iter, reward_mint.owner)?;
+
+    assert_token_program(reward_mint.owner)?;
 
     if rewards == 0 {
         return Err(MplxRewardsError::RewardsMustBeGreaterThanZero.into());
     }
 
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
     let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
 
     assert_account_key(fill_authority, &wrapped_reward_pool.pool.fill_authority)?;
+    wrapped_reward_pool.pool.assert_not_sunset()?;
 
     {
         let vault_seeds = &[
@@ -45,37 +50,24 @@ pub fn process_fill_vault<'a>(
         )?;
     }
 
-    {
-        // beginning of the day where distribution_ends_at
-        let distribution_ends_at_day_start =
-            distribution_ends_at - (distribution_ends_at % SECONDS_PER_DAY);
-        let curr_ts = get_curr_unix_ts();
-        let beginning_of_the_curr_day = curr_ts - (curr_ts % SECONDS_PER_DAY);
-        if distribution_ends_at_day_start < beginning_of_the_curr_day {
-            return Err(MplxRewardsError::DistributionInThePast.into());
-        }
-
-        let days_diff = distribution_ends_at_day_start
-            .safe_sub(wrapped_reward_pool.pool.distribution_ends_at)?;
-
-        wrapped_reward_pool.pool.distribution_ends_at = wrapped_reward_pool
-            .pool
-            .distribution_ends_at
-            .safe_add(days_diff)?;
-
-        wrapped_reward_pool.pool.tokens_available_for_distribution = wrapped_reward_pool
-            .pool
-            .tokens_available_for_distribution
-            .safe_add(rewards)?;
-    }
-
+    // transfer fee of Token-2022 mints is withheld from the amount,
+    // so only what the vault has actually received is distributed
+    let vault_balance_before = unpack_token_account(vault)?.amount;
     spl_transfer(
         source_token_account.clone(),
+        reward_mint.clone(),
         vault.clone(),
         fill_authority.clone(),
         rewards,
         &[],
     )?;
+    let vault_balance = unpack_token_account(vault)?.amount;
+    let received_rewards = vault_balance.safe_sub(vault_balance_before)?;
+
+    wrapped_reward_pool
+        .pool
+        .fill(received_rewards, distribution_ends_at, get_curr_unix_ts())?;
+    wrapped_reward_pool.pool.assert_solvency(vault_balance)?;
 
     Ok(())
 }
@@ -1,6 +1,6 @@
 use crate::{
     asserts::assert_account_key,
-    state::{Mining, WrappedMining},
+    state::{Mining, WrappedImmutableRewardPool, WrappedMining},
     utils::{find_mining_program_address, AccountLoader},
 };
 use solana_program::{
@@ -20,6 +20,10 @@ pub fn process_initialize_mining<'a>(
     let payer = AccountLoader::next_signer(account_info_iter)?;
     let _system_program = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
 
+    let reward_mint = WrappedImmutableRewardPool::from_bytes(&reward_pool.try_borrow_data()?)?
+        .pool
+        .reward_mint;
+
     let (pubkey, bump) = find_mining_program_address(program_id, mining_owner, reward_pool.key);
     assert_account_key(mining, &pubkey)?;
 
@@ -40,11 +44,13 @@ pub fn process_initialize_mining<'a>(
     );
     invoke_signed(&ix, &[payer.clone(), mining.clone()], &[signers_seeds])?;
 
-    let mining_data = &mut mining.data.borrow_mut();
-    let wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
-    let mining = Mining::initialize(*reward_pool.key, *mining_owner, bump);
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let wrapped_mining = WrappedMining::from_uninitialized_bytes_mut(mining_data)?;
+    let mining = Mining::initialize(*reward_pool.key, *mining_owner, reward_mint, bump);
     *wrapped_mining.mining = mining;
     wrapped_mining.weighted_stake_diffs.initialize();
+    wrapped_mining.positions.initialize();
+    wrapped_mining.delegated_stakes.initialize();
 
     Ok(())
 }
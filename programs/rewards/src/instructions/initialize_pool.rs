@@ -1,14 +1,15 @@
 use crate::{
-    asserts::{assert_account_key, assert_account_len, assert_account_owner},
-    error::MplxRewardsError,
+    asserts::assert_account_key,
     state::{RewardPool, WrappedRewardPool},
-    utils::{create_account, find_vault_program_address, initialize_account, AccountLoader},
+    utils::{
+        assert_mint_extensions, assert_token_program, create_account, find_vault_program_address,
+        get_token_account_len, initialize_account, AccountLoader,
+    },
 };
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_pack::IsInitialized,
-    pubkey::Pubkey, rent::Rent, system_program, sysvar::SysvarId,
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, rent::Rent,
+    system_program, sysvar::SysvarId,
 };
-use spl_token::state::Account as SplTokenAccount;
 
 pub fn process_initialize_pool<'a>(
     program_id: &Pubkey,
@@ -19,22 +20,19 @@ pub fn process_initialize_pool<'a>(
     let account_info_iter = &mut accounts.iter().enumerate();
 
     let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
-    let reward_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+    let reward_mint = AccountLoader::next_unchecked(account_info_iter)?;
     let reward_vault = AccountLoader::next_uninitialized(account_info_iter)?;
     let payer = AccountLoader::next_signer(account_info_iter)?;
     let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
     let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
-    let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+    let _token_program = AccountLoader::next_with_key(account_info_iter, reward_mint.owner)?;
     let _system_program = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
 
-    assert_account_owner(reward_pool, program_id)?;
-    assert_account_len(reward_pool, WrappedRewardPool::LEN)?;
+    assert_token_program(reward_mint.owner)?;
+    assert_mint_extensions(reward_mint)?;
 
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
-    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
-    if wrapped_reward_pool.pool.is_initialized() {
-        return Err(MplxRewardsError::AlreadyInitialized.into());
-    }
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let wrapped_reward_pool = WrappedRewardPool::from_uninitialized_bytes_mut(reward_pool_data)?;
 
     let (vault_pubkey, token_account_bump) =
         find_vault_program_address(program_id, reward_pool.key, reward_mint.key);
@@ -46,10 +44,11 @@ pub fn process_initialize_pool<'a>(
         &[token_account_bump],
     ];
 
-    create_account::<SplTokenAccount>(
-        &spl_token::id(),
+    create_account(
+        reward_mint.owner,
         payer.clone(),
         reward_vault.clone(),
+        get_token_account_len(reward_mint)?,
         &[vault_seeds],
     )?;
     initialize_account(
@@ -0,0 +1,83 @@
+use crate::{
+    asserts::assert_account_key,
+    error::MplxRewardsError,
+    state::{WrappedMining, WrappedRewardPool},
+    utils::{
+        find_delegate_registry_program_address, find_penalty_history_program_address,
+        get_curr_unix_ts, AccountLoader, SafeArithmeticOperations,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey, system_program,
+};
+
+pub fn process_merge_mining<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    deposit_ids: &[u64],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let source_mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let source_mining_owner = AccountLoader::next_signer(account_info_iter)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+    let target_account = AccountLoader::next_with_owner(account_info_iter, &system_program::id())?;
+    let source_penalty_history = AccountLoader::next_with_key(
+        account_info_iter,
+        &find_penalty_history_program_address(program_id, source_mining.key).0,
+    )?;
+    let source_delegate_registry = AccountLoader::next_with_key(
+        account_info_iter,
+        &find_delegate_registry_program_address(program_id, source_mining.key).0,
+    )?;
+
+    if source_mining.key == mining.key {
+        return Err(MplxRewardsError::MiningsAreTheSame.into());
+    }
+
+    {
+        let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+        let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+        assert_account_key(
+            deposit_authority,
+            &wrapped_reward_pool.pool.deposit_authority,
+        )?;
+
+        let source_mining_data = &mut source_mining.try_borrow_mut_data()?;
+        let mut wrapped_source_mining = WrappedMining::from_bytes_mut(source_mining_data)?;
+        assert_account_key(source_mining_owner, &wrapped_source_mining.mining.owner)?;
+        assert_account_key(reward_pool, &wrapped_source_mining.mining.reward_pool)?;
+
+        let mining_data = &mut mining.try_borrow_mut_data()?;
+        let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
+        assert_account_key(reward_pool, &wrapped_mining.mining.reward_pool)?;
+
+        wrapped_reward_pool.merge_mining(
+            &mut wrapped_source_mining,
+            &mut wrapped_mining,
+            deposit_ids,
+            get_curr_unix_ts(),
+        )?;
+    }
+
+    // both accounts are bound to the source mining address and would outlive it
+    for linked_account in [source_penalty_history, source_delegate_registry] {
+        if linked_account.owner == program_id && linked_account.lamports() > 0 {
+            return Err(MplxRewardsError::MiningHasLinkedAccounts.into());
+        }
+    }
+
+    // Snippet from solana cookbook
+    // https://solanacookbook.com/references/accounts.html#how-to-close-accounts
+    let dest_starting_lamports = target_account.lamports();
+
+    **target_account.try_borrow_mut_lamports()? =
+        dest_starting_lamports.safe_add(source_mining.lamports())?;
+    **source_mining.try_borrow_mut_lamports()? = 0;
+    let mut source_data = source_mining.try_borrow_mut_data()?;
+    source_data.fill(0);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::{
+    asserts::assert_account_key,
+    state::{
+        rewrite_layout, Mining, WrappedImmutableRewardPool, WrappedMining, ACCOUNT_VERSION_BYTE,
+    },
+    utils::{realloc_account, AccountLoader},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey, system_program,
+};
+
+pub fn process_migrate_mining<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let payer = AccountLoader::next_signer(account_info_iter)?;
+    let _system_program = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+    let outdated_header = WrappedMining::outdated_header(&mining.try_borrow_data()?)?;
+    let Some((mut header, outdated_header_len)) = outdated_header else {
+        msg!("Mining is already up to date");
+        return Ok(());
+    };
+    header.data[ACCOUNT_VERSION_BYTE] = Mining::VERSION;
+
+    // minings of the outdated layouts have never had the reward mint recorded
+    assert_account_key(reward_pool, &header.reward_pool)?;
+    header.reward_mint = WrappedImmutableRewardPool::from_bytes(&reward_pool.try_borrow_data()?)?
+        .pool
+        .reward_mint;
+
+    let outdated_len = mining.data_len();
+    realloc_account(mining, payer, WrappedMining::LEN)?;
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    rewrite_layout(mining_data, outdated_header_len, &header);
+    let wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
+    // minings of the layouts before the version 3 have no positions,
+    // deposits applied before are recorded with the RecordDeposit instruction
+    if outdated_len != WrappedMining::LEN_V3 {
+        wrapped_mining.positions.initialize();
+    }
+    // the stakes of the deposits split before are taken back by the weights
+    wrapped_mining.delegated_stakes.initialize();
+
+    Ok(())
+}
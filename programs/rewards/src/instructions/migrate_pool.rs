@@ -0,0 +1,45 @@
+use crate::{
+    state::{rewrite_layout, RewardPool, WrappedRewardPool, ACCOUNT_VERSION_BYTE},
+    utils::{realloc_account, unpack_token_account, AccountLoader},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey, system_program,
+};
+
+pub fn process_migrate_pool<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let payer = AccountLoader::next_signer(account_info_iter)?;
+    let _system_program = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+    let vault = AccountLoader::next_unchecked(account_info_iter)?;
+
+    let outdated_header = WrappedRewardPool::outdated_header(&reward_pool.try_borrow_data()?)?;
+    let Some((mut header, outdated_header_len)) = outdated_header else {
+        msg!("Reward pool is already up to date");
+        return Ok(());
+    };
+    header.data[ACCOUNT_VERSION_BYTE] = RewardPool::VERSION;
+
+    if outdated_header_len < RewardPool::REWARDS_TRACKING_HEADER_LEN {
+        // the vault holds the tokens to be distributed and the rewards indexed but not
+        // claimed yet, so the latter are backfilled as if nothing had been claimed before
+        header.assert_vault(program_id, reward_pool.key, vault)?;
+        let vault_balance = unpack_token_account(vault)?.amount;
+        header.total_rewards_indexed =
+            vault_balance.saturating_sub(header.tokens_available_for_distribution);
+        header.total_rewards_claimed = 0;
+    }
+
+    realloc_account(reward_pool, payer, WrappedRewardPool::LEN)?;
+    rewrite_layout(
+        &mut reward_pool.try_borrow_mut_data()?,
+        outdated_header_len,
+        &header,
+    );
+
+    Ok(())
+}
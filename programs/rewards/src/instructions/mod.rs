@@ -3,28 +3,60 @@ use crate::instruction::RewardsInstruction;
 use borsh::BorshDeserialize;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
 
+mod apply_boost;
 mod change_delegate;
 mod claim;
 mod close_mining;
+mod close_pool;
+mod delegates;
 mod deposit_mining;
 mod distribute_rewards;
 mod extend_stake;
 mod fill_vault;
 mod initialize_mining;
 mod initialize_pool;
+mod merge_mining;
+mod migrate_mining;
+mod migrate_pool;
 mod penalties;
+mod record_deposit;
+mod report_solvency;
+mod request_withdraw;
+mod set_boost_config;
+mod set_epoch_length;
+mod set_pool_limits;
+mod set_unbonding_config;
+mod split_mining;
+mod sunset_pool;
+mod transfer_mining;
 mod withdraw_mining;
 
+pub(crate) use apply_boost::*;
 pub(crate) use change_delegate::*;
 pub(crate) use claim::*;
 pub(crate) use close_mining::*;
+pub(crate) use close_pool::*;
+pub(crate) use delegates::*;
 pub(crate) use deposit_mining::*;
 pub(crate) use distribute_rewards::*;
 pub(crate) use extend_stake::*;
 pub(crate) use fill_vault::*;
 pub(crate) use initialize_mining::*;
 pub(crate) use initialize_pool::*;
+pub(crate) use merge_mining::*;
+pub(crate) use migrate_mining::*;
+pub(crate) use migrate_pool::*;
 pub(crate) use penalties::*;
+pub(crate) use record_deposit::*;
+pub(crate) use report_solvency::*;
+pub(crate) use request_withdraw::*;
+pub(crate) use set_boost_config::*;
+pub(crate) use set_epoch_length::*;
+pub(crate) use set_pool_limits::*;
+pub(crate) use set_unbonding_config::*;
+pub(crate) use split_mining::*;
+pub(crate) use sunset_pool::*;
+pub(crate) use transfer_mining::*;
 pub(crate) use withdraw_mining::*;
 
 pub fn process_instruction<'a>(
@@ -54,53 +86,65 @@ pub fn process_instruction<'a>(
             process_initialize_mining(program_id, accounts, &mining_owner)
         }
         RewardsInstruction::DepositMining {
+            deposit_id,
             amount,
             lockup_period,
             mining_owner,
             delegate,
+            delegate_weights,
         } => {
             msg!("RewardsInstruction: DepositMining");
             process_deposit_mining(
                 program_id,
                 accounts,
+                deposit_id,
                 amount,
                 lockup_period,
                 &mining_owner,
                 &delegate,
+                &delegate_weights,
             )
         }
         RewardsInstruction::WithdrawMining {
+            deposit_id,
             amount,
             mining_owner,
             delegate,
+            delegate_weights,
         } => {
             msg!("RewardsInstruction: WithdrawMining");
-            process_withdraw_mining(program_id, accounts, amount, &mining_owner, &delegate)
+            process_withdraw_mining(
+                program_id,
+                accounts,
+                deposit_id,
+                amount,
+                &mining_owner,
+                &delegate,
+                &delegate_weights,
+            )
         }
         RewardsInstruction::Claim => {
             msg!("RewardsInstruction: Claim");
             process_claim(program_id, accounts)
         }
         RewardsInstruction::ExtendStake {
-            old_lockup_period,
+            deposit_id,
             new_lockup_period,
-            deposit_start_ts,
-            base_amount,
             additional_amount,
             mining_owner,
             delegate,
+            delegate_weights,
         } => {
             msg!("RewardsInstruction: ExtendStake");
             process_extend_stake(
                 program_id,
                 accounts,
-                old_lockup_period,
+                deposit_id,
                 new_lockup_period,
-                deposit_start_ts,
-                base_amount,
                 additional_amount,
                 &mining_owner,
                 &delegate,
+                &delegate_weights,
             )
         }
         RewardsInstruction::DistributeRewards => {
@@ -112,31 +156,43 @@ pub fn process_instruction<'a>(
             process_close_mining(program_id, accounts)
         }
         RewardsInstruction::ChangeDelegate {
+            deposit_id,
             staked_amount,
             new_delegate,
+            old_delegate_weights,
+            new_delegate_weights,
         } => {
             msg!("RewardsInstruction: ChangeDelegate");
-            process_change_delegate(program_id, accounts, staked_amount, &new_delegate)
+            process_change_delegate(
+                program_id,
+                accounts,
+                deposit_id,
+                staked_amount,
+                &new_delegate,
+                &old_delegate_weights,
+                &new_delegate_weights,
+            )
         }
         RewardsInstruction::Slash {
             mining_owner,
+            deposit_id,
             slash_amount_in_native,
-            slash_amount_multiplied_by_period,
-            stake_expiration_date,
+            reason_code,
         } => {
             msg!("RewardsInstruction: Slash");
             process_slash(
                 program_id,
                 accounts,
                 &mining_owner,
+                deposit_id,
                 slash_amount_in_native,
-                slash_amount_multiplied_by_period,
-                stake_expiration_date,
+                reason_code,
             )
         }
         RewardsInstruction::DecreaseRewards {
             mining_owner,
             decreased_weighted_stake_number,
+            reason_code,
         } => {
             msg!("RewardsInstruction: DecreaseRewards");
             process_decrease_rewards(
@@ -144,7 +200,175 @@ pub fn process_instruction<'a>(
                 accounts,
                 &mining_owner,
                 decreased_weighted_stake_number,
+                reason_code,
+            )
+        }
+        RewardsInstruction::InitializePenaltyHistory => {
+            msg!("RewardsInstruction: InitializePenaltyHistory");
+            process_initialize_penalty_history(program_id, accounts)
+        }
+        RewardsInstruction::RestorePenalty {
+            mining_owner,
+            penalty_id,
+            credit_lost_rewards,
+        } => {
+            msg!("RewardsInstruction: RestorePenalty");
+            process_restore_penalty(
+                program_id,
+                accounts,
+                &mining_owner,
+                penalty_id,
+                credit_lost_rewards,
+            )
+        }
+        RewardsInstruction::SetPoolLimits {
+            min_deposit,
+            max_weighted_share_per_mining,
+            max_total_share,
+        } => {
+            msg!("RewardsInstruction: SetPoolLimits");
+            process_set_pool_limits(
+                program_id,
+                accounts,
+                min_deposit,
+                max_weighted_share_per_mining,
+                max_total_share,
+            )
+        }
+        RewardsInstruction::SetBoostConfig {
+            boost_collection,
+            boost_attester,
+        } => {
+            msg!("RewardsInstruction: SetBoostConfig");
+            process_set_boost_config(program_id, accounts, boost_collection, boost_attester)
+        }
+        RewardsInstruction::ApplyBoost {
+            mining_owner,
+            boost_bps,
+            expires_at,
+            proof_kind,
+        } => {
+            msg!("RewardsInstruction: ApplyBoost");
+            process_apply_boost(
+                program_id,
+                accounts,
+                &mining_owner,
+                boost_bps,
+                expires_at,
+                proof_kind,
+            )
+        }
+        RewardsInstruction::SetEpochLength { epoch_length_secs } => {
+            msg!("RewardsInstruction: SetEpochLength");
+            process_set_epoch_length(program_id, accounts, epoch_length_secs)
+        }
+        RewardsInstruction::ClosePool => {
+            msg!("RewardsInstruction: ClosePool");
+            process_close_pool(program_id, accounts)
+        }
+        RewardsInstruction::SunsetPool { grace_period_secs } => {
+            msg!("RewardsInstruction: SunsetPool");
+            process_sunset_pool(program_id, accounts, grace_period_secs)
+        }
+        RewardsInstruction::MigratePool => {
+            msg!("RewardsInstruction: MigratePool");
+            process_migrate_pool(program_id, accounts)
+        }
+        RewardsInstruction::MigrateMining => {
+            msg!("RewardsInstruction: MigrateMining");
+            process_migrate_mining(program_id, accounts)
+        }
+        RewardsInstruction::TransferMining { new_owner } => {
+            msg!("RewardsInstruction: TransferMining");
+            process_transfer_mining(program_id, accounts, &new_owner)
+        }
+        RewardsInstruction::MergeMining { deposit_ids } => {
+            msg!("RewardsInstruction: MergeMining");
+            process_merge_mining(program_id, accounts, &deposit_ids)
+        }
+        RewardsInstruction::SplitMining {
+            deposit_id,
+            destination_deposit_id,
+            amount,
+        } => {
+            msg!("RewardsInstruction: SplitMining");
+            process_split_mining(
+                program_id,
+                accounts,
+                deposit_id,
+                destination_deposit_id,
+                amount,
+            )
+        }
+        RewardsInstruction::RegisterDelegate {
+            max_stake_from_others,
+            allow_list,
+            deny_list,
+        } => {
+            msg!("RewardsInstruction: RegisterDelegate");
+            process_register_delegate(
+                program_id,
+                accounts,
+                max_stake_from_others,
+                &allow_list,
+                &deny_list,
+            )
+        }
+        RewardsInstruction::UpdateDelegateRegistry {
+            max_stake_from_others,
+            allow_list,
+            deny_list,
+        } => {
+            msg!("RewardsInstruction: UpdateDelegateRegistry");
+            process_update_delegate_registry(
+                program_id,
+                accounts,
+                max_stake_from_others,
+                &allow_list,
+                &deny_list,
             )
         }
+        RewardsInstruction::UnregisterDelegate => {
+            msg!("RewardsInstruction: UnregisterDelegate");
+            process_unregister_delegate(program_id, accounts)
+        }
+        RewardsInstruction::ReportSolvency => {
+            msg!("RewardsInstruction: ReportSolvency");
+            process_report_solvency(program_id, accounts)
+        }
+        RewardsInstruction::RecordDeposit {
+            mining_owner,
+            deposit_id,
+            amount,
+            lockup_period,
+            deposit_start_ts,
+        } => {
+            msg!("RewardsInstruction: RecordDeposit");
+            process_record_deposit(
+                program_id,
+                accounts,
+                &mining_owner,
+                deposit_id,
+                amount,
+                lockup_period,
+                deposit_start_ts,
+            )
+        }
+        RewardsInstruction::SetUnbondingConfig {
+            unbonding_period_secs,
+            unbonding_multiplier_bps,
+        } => {
+            msg!("RewardsInstruction: SetUnbondingConfig");
+            process_set_unbonding_config(
+                program_id,
+                accounts,
+                unbonding_period_secs,
+                unbonding_multiplier_bps,
+            )
+        }
+        RewardsInstruction::RequestWithdraw { deposit_id, amount } => {
+            msg!("RewardsInstruction: RequestWithdraw");
+            process_request_withdraw(program_id, accounts, deposit_id, amount)
+        }
     }
 }
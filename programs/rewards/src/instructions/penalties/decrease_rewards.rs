@@ -1,4 +1,8 @@
-use crate::{asserts::assert_and_get_pool_and_mining, utils::AccountLoader};
+use crate::{
+    asserts::{assert_and_get_penalty_history, assert_and_get_pool_and_mining},
+    state::{PenaltyKind, PenaltyRecord},
+    utils::{get_curr_unix_ts, AccountLoader},
+};
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
 pub fn process_decrease_rewards<'a>(
@@ -6,17 +10,19 @@ pub fn process_decrease_rewards<'a>(
     accounts: &'a [AccountInfo<'a>],
     mining_owner: &Pubkey,
     decreased_weighted_stake_number: u64,
+    reason_code: u32,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter().enumerate();
 
     let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
     let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
     let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let penalty_history = AccountLoader::next_optional_with_owner(account_info_iter, program_id)?;
 
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
-    let mining_data = &mut mining.data.borrow_mut();
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let mining_data = &mut mining.try_borrow_mut_data()?;
 
-    let (_, mut wrapped_mining) = assert_and_get_pool_and_mining(
+    let (wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
         program_id,
         mining_owner,
         mining,
@@ -26,7 +32,26 @@ pub fn process_decrease_rewards<'a>(
         mining_data,
     )?;
 
-    wrapped_mining.decrease_rewards(decreased_weighted_stake_number)?;
+    let decreased_stake_diffs = wrapped_mining.decrease_rewards(decreased_weighted_stake_number)?;
+
+    // the decrease doesn't depend on the history, it's only recorded once the history is created
+    let Some(penalty_history) = penalty_history else {
+        return Ok(());
+    };
+    let penalty_history_data = &mut penalty_history.try_borrow_mut_data()?;
+    let history =
+        assert_and_get_penalty_history(program_id, mining, penalty_history, penalty_history_data)?;
+
+    let mut record = PenaltyRecord {
+        index_with_precision: wrapped_reward_pool.pool.index_with_precision,
+        timestamp: get_curr_unix_ts(),
+        weighted_amount: decreased_weighted_stake_number,
+        reason_code,
+        kind: PenaltyKind::DecreaseRewards.into(),
+        ..Default::default()
+    };
+    record.set_stake_diffs(&decreased_stake_diffs);
+    history.append(record);
 
     Ok(())
 }
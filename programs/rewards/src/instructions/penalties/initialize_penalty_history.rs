@@ -0,0 +1,53 @@
+use crate::{
+    asserts::assert_account_key,
+    state::{PenaltyHistory, WrappedImmutableMining},
+    utils::{find_penalty_history_program_address, AccountLoader},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed, pubkey::Pubkey,
+    rent::Rent, system_instruction, system_program, sysvar::Sysvar,
+};
+
+pub fn process_initialize_penalty_history<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let penalty_history = AccountLoader::next_uninitialized(account_info_iter)?;
+    let payer = AccountLoader::next_signer(account_info_iter)?;
+    let _system_program = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+    // the history is kept only for the initialized minings
+    WrappedImmutableMining::from_bytes(&mining.try_borrow_data()?)?;
+
+    let (pubkey, bump) = find_penalty_history_program_address(program_id, mining.key);
+    assert_account_key(penalty_history, &pubkey)?;
+
+    let signers_seeds = &[
+        "penalty_history".as_bytes(),
+        &mining.key.to_bytes(),
+        &[bump],
+    ];
+
+    let rent = Rent::get()?;
+    let ix = system_instruction::create_account(
+        payer.key,
+        penalty_history.key,
+        rent.minimum_balance(PenaltyHistory::LEN),
+        PenaltyHistory::LEN as u64,
+        program_id,
+    );
+    invoke_signed(
+        &ix,
+        &[payer.clone(), penalty_history.clone()],
+        &[signers_seeds],
+    )?;
+
+    let penalty_history_data = &mut penalty_history.try_borrow_mut_data()?;
+    let history = PenaltyHistory::from_uninitialized_bytes_mut(penalty_history_data)?;
+    *history = PenaltyHistory::initialize(*mining.key, bump);
+
+    Ok(())
+}
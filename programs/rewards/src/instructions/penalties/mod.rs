@@ -1,5 +1,9 @@
 mod decrease_rewards;
+mod initialize_penalty_history;
+mod restore_penalty;
 mod slash;
 
 pub(crate) use decrease_rewards::*;
+pub(crate) use initialize_penalty_history::*;
+pub(crate) use restore_penalty::*;
 pub(crate) use slash::*;
@@ -0,0 +1,58 @@
+use crate::{
+    asserts::{assert_and_get_penalty_history, assert_and_get_pool_and_mining},
+    error::MplxRewardsError,
+    state::PENALTY_FLAG_RESTORED,
+    utils::{get_curr_unix_ts, AccountLoader},
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn process_restore_penalty<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    mining_owner: &Pubkey,
+    penalty_id: u64,
+    credit_lost_rewards: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let penalty_history = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let penalty_history_data = &mut penalty_history.try_borrow_mut_data()?;
+
+    let (mut wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
+        program_id,
+        mining_owner,
+        mining,
+        reward_pool,
+        deposit_authority,
+        reward_pool_data,
+        mining_data,
+    )?;
+    let history =
+        assert_and_get_penalty_history(program_id, mining, penalty_history, penalty_history_data)?;
+
+    let record = history
+        .get_mut(penalty_id)
+        .ok_or(MplxRewardsError::PenaltyNotFound)?;
+    if record.is_restored() {
+        return Err(MplxRewardsError::PenaltyAlreadyRestored.into());
+    }
+    if !record.is_restorable() {
+        return Err(MplxRewardsError::PenaltyNotRestorable.into());
+    }
+
+    wrapped_reward_pool.restore_penalty(
+        &mut wrapped_mining,
+        record,
+        credit_lost_rewards,
+        get_curr_unix_ts(),
+    )?;
+    record.flags |= PENALTY_FLAG_RESTORED;
+
+    Ok(())
+}
@@ -1,22 +1,27 @@
-use crate::{asserts::assert_and_get_pool_and_mining, utils::AccountLoader};
+use crate::{
+    asserts::{assert_and_get_penalty_history, assert_and_get_pool_and_mining},
+    state::{PenaltyKind, PenaltyRecord, PENALTY_FLAG_DEPOSIT},
+    utils::{get_curr_unix_ts, AccountLoader},
+};
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
 pub fn process_slash<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
     mining_owner: &Pubkey,
+    deposit_id: u64,
     slash_amount_in_native: u64,
-    slash_amount_multiplied_by_period: u64,
-    stake_expiration_date: Option<u64>,
+    reason_code: u32,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter().enumerate();
 
     let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
     let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
     let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let penalty_history = AccountLoader::next_optional_with_owner(account_info_iter, program_id)?;
 
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
-    let mining_data = &mut mining.data.borrow_mut();
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let mining_data = &mut mining.try_borrow_mut_data()?;
 
     let (mut wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
         program_id,
@@ -28,12 +33,36 @@ pub fn process_slash<'a>(
         mining_data,
     )?;
 
-    wrapped_reward_pool.slash(
+    let curr_ts = get_curr_unix_ts();
+    let index_with_precision = wrapped_reward_pool.pool.index_with_precision;
+    let (slashed_weighted_stake, decreased_stake_diffs) = wrapped_reward_pool.slash(
         &mut wrapped_mining,
+        deposit_id,
         slash_amount_in_native,
-        slash_amount_multiplied_by_period,
-        stake_expiration_date,
+        curr_ts,
     )?;
 
+    // the slash doesn't depend on the history, it's only recorded once the history is created
+    let Some(penalty_history) = penalty_history else {
+        return Ok(());
+    };
+    let penalty_history_data = &mut penalty_history.try_borrow_mut_data()?;
+    let history =
+        assert_and_get_penalty_history(program_id, mining, penalty_history, penalty_history_data)?;
+
+    let mut record = PenaltyRecord {
+        index_with_precision,
+        timestamp: curr_ts,
+        native_amount: slash_amount_in_native,
+        weighted_amount: slashed_weighted_stake,
+        reason_code,
+        kind: PenaltyKind::Slash.into(),
+        flags: PENALTY_FLAG_DEPOSIT,
+        deposit_id,
+        ..Default::default()
+    };
+    record.set_stake_diffs(&decreased_stake_diffs);
+    history.append(record);
+
     Ok(())
 }
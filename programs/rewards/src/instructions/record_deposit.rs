@@ -0,0 +1,46 @@
+use crate::{
+    asserts::assert_and_get_pool_and_mining,
+    utils::{get_curr_unix_ts, AccountLoader, LockupPeriod},
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+#[allow(clippy::too_many_arguments)]
+pub fn process_record_deposit<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    mining_owner: &Pubkey,
+    deposit_id: u64,
+    amount: u64,
+    lockup_period: LockupPeriod,
+    deposit_start_ts: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+
+    let (wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
+        program_id,
+        mining_owner,
+        mining,
+        reward_pool,
+        deposit_authority,
+        reward_pool_data,
+        mining_data,
+    )?;
+
+    wrapped_reward_pool.record_deposit(
+        &mut wrapped_mining,
+        deposit_id,
+        amount,
+        lockup_period,
+        deposit_start_ts,
+        get_curr_unix_ts(),
+    )?;
+
+    Ok(())
+}
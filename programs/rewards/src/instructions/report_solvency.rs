@@ -0,0 +1,42 @@
+use crate::{
+    state::WrappedImmutableRewardPool,
+    utils::{unpack_token_account, AccountLoader},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::set_return_data,
+    pubkey::Pubkey,
+};
+
+pub fn process_report_solvency<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let vault = AccountLoader::next_unchecked(account_info_iter)?;
+
+    let reward_pool_data = &reward_pool.try_borrow_data()?;
+    let wrapped_reward_pool = WrappedImmutableRewardPool::from_bytes(reward_pool_data)?;
+    wrapped_reward_pool
+        .pool
+        .assert_vault(program_id, reward_pool.key, vault)?;
+
+    let report = wrapped_reward_pool
+        .pool
+        .solvency_report(unpack_token_account(vault)?.amount)?;
+    msg!(
+        "Vault balance {}, liabilities {}, surplus {}, deficit {}",
+        report.vault_balance,
+        report.liabilities,
+        report.surplus,
+        report.deficit
+    );
+
+    let mut report_writer = vec![];
+    report.serialize(&mut report_writer)?;
+    set_return_data(&report_writer);
+
+    Ok(())
+}
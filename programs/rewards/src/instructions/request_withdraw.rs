@@ -0,0 +1,36 @@
+use crate::{
+    asserts::assert_account_key,
+    state::{WrappedMining, WrappedRewardPool},
+    utils::{get_curr_unix_ts, AccountLoader},
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn process_request_withdraw<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    deposit_id: u64,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining_owner = AccountLoader::next_signer(account_info_iter)?;
+
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let mut wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
+    assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
+    assert_account_key(reward_pool, &wrapped_mining.mining.reward_pool)?;
+
+    wrapped_reward_pool.request_withdraw(
+        &mut wrapped_mining,
+        deposit_id,
+        amount,
+        get_curr_unix_ts(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::{
+    asserts::assert_account_key,
+    error::MplxRewardsError,
+    state::{WrappedRewardPool, MIN_EPOCH_LENGTH_SECS},
+    utils::AccountLoader,
+};
+
+use sokoban::NodeAllocatorMap;
+use solana_program::{
+    account_info::AccountInfo, clock::SECONDS_PER_DAY, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+
+pub fn process_set_epoch_length<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    epoch_length_secs: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+    assert_account_key(
+        deposit_authority,
+        &wrapped_reward_pool.pool.deposit_authority,
+    )?;
+
+    // lockup periods are counted in days, so their ends must match epochs' beginnings,
+    // while the index history must keep the distributions of the longest lockup
+    if SECONDS_PER_DAY.checked_rem(epoch_length_secs) != Some(0)
+        || epoch_length_secs < MIN_EPOCH_LENGTH_SECS
+    {
+        return Err(MplxRewardsError::InvalidEpochLength.into());
+    }
+
+    // all the stored dates are aligned to the epoch beginnings
+    if wrapped_reward_pool.pool.total_share != 0
+        || wrapped_reward_pool.weighted_stake_diffs.len() != 0
+        || wrapped_reward_pool.cumulative_index.len() != 0
+    {
+        return Err(MplxRewardsError::EpochLengthCannotBeChanged.into());
+    }
+
+    wrapped_reward_pool.pool.epoch_length_secs = epoch_length_secs;
+
+    Ok(())
+}
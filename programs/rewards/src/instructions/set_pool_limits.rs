@@ -0,0 +1,29 @@
+use crate::{asserts::assert_account_key, state::WrappedRewardPool, utils::AccountLoader};
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn process_set_pool_limits<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    min_deposit: u64,
+    max_weighted_share_per_mining: u64,
+    max_total_share: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+    assert_account_key(
+        deposit_authority,
+        &wrapped_reward_pool.pool.deposit_authority,
+    )?;
+
+    wrapped_reward_pool.pool.min_deposit = min_deposit;
+    wrapped_reward_pool.pool.max_weighted_share_per_mining = max_weighted_share_per_mining;
+    wrapped_reward_pool.pool.max_total_share = max_total_share;
+
+    Ok(())
+}
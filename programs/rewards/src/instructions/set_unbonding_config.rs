@@ -0,0 +1,26 @@
+use crate::{asserts::assert_account_key, state::WrappedRewardPool, utils::AccountLoader};
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn process_set_unbonding_config<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    unbonding_period_secs: u64,
+    unbonding_multiplier_bps: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+    assert_account_key(
+        deposit_authority,
+        &wrapped_reward_pool.pool.deposit_authority,
+    )?;
+
+    wrapped_reward_pool
+        .pool
+        .set_unbonding_config(unbonding_period_secs, unbonding_multiplier_bps)
+}
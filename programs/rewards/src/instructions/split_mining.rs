@@ -0,0 +1,52 @@
+use crate::{
+    asserts::assert_account_key,
+    error::MplxRewardsError,
+    state::{WrappedMining, WrappedRewardPool},
+    utils::{get_curr_unix_ts, AccountLoader},
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn process_split_mining<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    deposit_id: u64,
+    destination_deposit_id: u64,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let destination_mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining_owner = AccountLoader::next_signer(account_info_iter)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+
+    if mining.key == destination_mining.key {
+        return Err(MplxRewardsError::MiningsAreTheSame.into());
+    }
+
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+    assert_account_key(
+        deposit_authority,
+        &wrapped_reward_pool.pool.deposit_authority,
+    )?;
+
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
+    assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
+    assert_account_key(reward_pool, &wrapped_mining.mining.reward_pool)?;
+
+    let destination_mining_data = &mut destination_mining.try_borrow_mut_data()?;
+    let mut wrapped_destination_mining = WrappedMining::from_bytes_mut(destination_mining_data)?;
+    assert_account_key(reward_pool, &wrapped_destination_mining.mining.reward_pool)?;
+
+    wrapped_reward_pool.split_mining(
+        &mut wrapped_mining,
+        &mut wrapped_destination_mining,
+        deposit_id,
+        destination_deposit_id,
+        amount,
+        get_curr_unix_ts(),
+    )
+}
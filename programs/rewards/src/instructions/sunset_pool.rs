@@ -0,0 +1,31 @@
+use crate::{
+    asserts::assert_account_key,
+    state::WrappedRewardPool,
+    utils::{get_curr_unix_ts, AccountLoader},
+};
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn process_sunset_pool<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    grace_period_secs: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data)?;
+    assert_account_key(
+        deposit_authority,
+        &wrapped_reward_pool.pool.deposit_authority,
+    )?;
+    wrapped_reward_pool.pool.assert_not_sunset()?;
+
+    wrapped_reward_pool.pool.sunset_at = get_curr_unix_ts();
+    wrapped_reward_pool.pool.sunset_grace_period_secs = grace_period_secs;
+
+    Ok(())
+}
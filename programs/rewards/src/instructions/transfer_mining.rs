@@ -0,0 +1,96 @@
+use crate::{
+    asserts::assert_account_key,
+    error::MplxRewardsError,
+    state::{WrappedImmutableRewardPool, WrappedMining},
+    utils::{
+        find_mining_program_address, get_curr_unix_ts, AccountLoader, SafeArithmeticOperations,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed, pubkey::Pubkey,
+    rent::Rent, system_instruction, system_program, sysvar::Sysvar,
+};
+
+pub fn process_transfer_mining<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    new_owner: &Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter().enumerate();
+
+    let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+    let new_mining = AccountLoader::next_uninitialized(account_info_iter)?;
+    let mining_owner = AccountLoader::next_signer(account_info_iter)?;
+    let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
+    let payer = AccountLoader::next_signer(account_info_iter)?;
+    let target_account = AccountLoader::next_with_owner(account_info_iter, &system_program::id())?;
+    let _system_program = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+    let (new_mining_pubkey, new_mining_bump) =
+        find_mining_program_address(program_id, new_owner, reward_pool.key);
+    assert_account_key(new_mining, &new_mining_pubkey)?;
+
+    let signers_seeds = &[
+        "mining".as_bytes(),
+        &new_owner.to_bytes(),
+        &reward_pool.key.to_bytes(),
+        &[new_mining_bump],
+    ];
+
+    let rent = Rent::get()?;
+    let ix = system_instruction::create_account(
+        payer.key,
+        new_mining.key,
+        rent.minimum_balance(WrappedMining::LEN),
+        WrappedMining::LEN as u64,
+        program_id,
+    );
+    invoke_signed(&ix, &[payer.clone(), new_mining.clone()], &[signers_seeds])?;
+
+    {
+        let reward_pool_data = &reward_pool.try_borrow_data()?;
+        let wrapped_reward_pool = WrappedImmutableRewardPool::from_bytes(reward_pool_data)?;
+        assert_account_key(
+            deposit_authority,
+            &wrapped_reward_pool.pool.deposit_authority,
+        )?;
+
+        let mining_data = &mut mining.try_borrow_mut_data()?;
+        let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data)?;
+        assert_account_key(mining_owner, &wrapped_mining.mining.owner)?;
+        assert_account_key(reward_pool, &wrapped_mining.mining.reward_pool)?;
+
+        // rewards are calculated up to the moment of the transfer,
+        // so the new mining continues from the same index position
+        wrapped_mining.refresh_rewards(
+            wrapped_reward_pool.cumulative_index,
+            wrapped_reward_pool.pool.epoch_length(),
+            get_curr_unix_ts(),
+        )?;
+
+        // delegators refer the mining by its address, so their stake cannot be moved
+        if wrapped_mining.mining.stake_from_others > 0 {
+            return Err(MplxRewardsError::StakeFromOthersMustBeZero.into());
+        }
+
+        let new_mining_data = &mut new_mining.try_borrow_mut_data()?;
+        WrappedMining::from_uninitialized_bytes_mut(new_mining_data)?;
+        new_mining_data.copy_from_slice(&mining_data[..WrappedMining::LEN]);
+        let wrapped_new_mining = WrappedMining::from_bytes_mut(new_mining_data)?;
+        wrapped_new_mining.mining.owner = *new_owner;
+        wrapped_new_mining.mining.bump = new_mining_bump;
+    }
+
+    // Snippet from solana cookbook
+    // https://solanacookbook.com/references/accounts.html#how-to-close-accounts
+    let dest_starting_lamports = target_account.lamports();
+
+    **target_account.try_borrow_mut_lamports()? =
+        dest_starting_lamports.safe_add(mining.lamports())?;
+    **mining.try_borrow_mut_lamports()? = 0;
+    let mut source_data = mining.try_borrow_mut_data()?;
+    source_data.fill(0);
+
+    Ok(())
+}
@@ -1,17 +1,17 @@
 use crate::{
     asserts::assert_and_get_pool_and_mining,
-    utils::{get_delegate_mining, AccountLoader},
+    utils::{get_curr_unix_ts, next_delegates, AccountLoader},
 };
-
-use crate::utils::verify_delegate_mining_address;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
 pub fn process_withdraw_mining<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
+    deposit_id: u64,
     amount: u64,
     mining_owner: &Pubkey,
     delegate: &Pubkey,
+    delegate_weights: &[u16],
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter().enumerate();
 
@@ -20,8 +20,8 @@ pub fn process_withdraw_mining<'a>(
     let deposit_authority = AccountLoader::next_signer(account_info_iter)?;
     let delegate_mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
 
-    let mining_data = &mut mining.data.borrow_mut();
-    let reward_pool_data = &mut reward_pool.data.borrow_mut();
+    let mining_data = &mut mining.try_borrow_mut_data()?;
+    let reward_pool_data = &mut reward_pool.try_borrow_mut_data()?;
 
     let (mut wrapped_reward_pool, mut wrapped_mining) = assert_and_get_pool_and_mining(
         program_id,
@@ -33,12 +33,24 @@ pub fn process_withdraw_mining<'a>(
         mining_data,
     )?;
 
-    let delegate_mining = get_delegate_mining(delegate_mining, mining)?;
-    if let Some(delegate_mining) = delegate_mining {
-        verify_delegate_mining_address(program_id, delegate_mining, delegate, reward_pool.key)?
-    }
+    let delegates = next_delegates(
+        account_info_iter,
+        program_id,
+        reward_pool.key,
+        mining,
+        delegate_mining,
+        delegate,
+        delegate_weights,
+        false,
+    )?;
 
-    wrapped_reward_pool.withdraw(&mut wrapped_mining, amount, delegate_mining)?;
+    wrapped_reward_pool.withdraw(
+        &mut wrapped_mining,
+        deposit_id,
+        amount,
+        &delegates,
+        get_curr_unix_ts(),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,328 @@
+//! Pool-wide accounting invariants.
+//!
+//! Checks that the reward pool agrees with the full set of its mining accounts.
+//! It isn't used by the program itself, since it needs every mining of the pool at once,
+//! but it's meant for tests and off-chain monitoring.
+use std::collections::BTreeMap;
+
+use sokoban::NodeAllocatorMap;
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+use crate::state::{
+    Mining, MiningWeightedStakeDiffs, PenaltyHistory, PenaltyKind, WrappedImmutableMining,
+    WrappedImmutableRewardPool, WrappedMining,
+};
+
+/// Mining account of the pool along with its penalty history, if it has been initialized
+pub struct MiningAccounts<'a> {
+    pub mining: WrappedImmutableMining<'a>,
+    pub penalty_history: Option<&'a PenaltyHistory>,
+}
+
+/// Broken invariant of the pool's accounting
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum InvariantViolation {
+    /// Pool's total share differs from the sum of minings' weighted stakes
+    #[error("Total share {pool} doesn't match the sum of minings' shares {minings}")]
+    TotalShare { pool: u128, minings: u128 },
+
+    /// Pool's weighted stake modifier differs from the sum of minings' modifiers at the date
+    #[error("Weighted stake modifier {pool} at {date} doesn't match minings' modifiers {minings}")]
+    WeightedStakeDiff {
+        date: u64,
+        pool: u128,
+        minings: u128,
+    },
+
+    /// Minings are owed more rewards than the pool has indexed and not paid out yet
+    #[error("Minings are owed {owed} rewards, while only {indexed} are indexed and unclaimed")]
+    RewardsOverissued { owed: u128, indexed: u128 },
+
+    /// Accounts cannot be projected to the given date
+    #[error("Accounts cannot be projected to the date: {0}")]
+    Projection(ProgramError),
+}
+
+impl From<ProgramError> for InvariantViolation {
+    fn from(e: ProgramError) -> Self {
+        InvariantViolation::Projection(e)
+    }
+}
+
+/// Checks the pool against the full set of its minings at the given timestamp.
+///
+/// Accounts are refreshed lazily, so both the pool and the minings are projected to the
+/// beginning of the epoch of `now` first, as if every one of them had been refreshed then.
+/// After that:
+/// - pool's total share equals the sum of minings' `share + stake_from_others`
+/// - pool's upcoming weighted stake modifiers equal the sum of minings' modifiers date by date
+/// - rewards owed to minings don't exceed the rewards indexed and not claimed yet
+///
+/// Rewards decrease keeps the decreased weighted stake in the pool, so it's taken from
+/// the penalty histories until the penalty is restored. Decreases that have been
+/// overwritten in the history, or have touched more modifiers than a record keeps,
+/// cannot be accounted for. The same goes for pools migrated from the layout
+/// that hadn't tracked indexed and claimed rewards.
+pub fn check_invariants(
+    pool: &WrappedImmutableRewardPool,
+    minings: &[MiningAccounts],
+    now: u64,
+) -> Result<(), InvariantViolation> {
+    let beginning_of_the_epoch = pool.pool.beginning_of_the_epoch(now);
+
+    let mut pool_share = u128::from(pool.pool.total_share);
+    let mut pool_diffs = BTreeMap::new();
+    for (date, diff) in pool.weighted_stake_diffs.iter() {
+        if *date <= beginning_of_the_epoch {
+            pool_share = pool_share.saturating_sub(u128::from(*diff));
+        } else {
+            add_diff(&mut pool_diffs, *date, *diff);
+        }
+    }
+
+    let mut minings_share = 0;
+    let mut minings_diffs = BTreeMap::new();
+    let mut owed_rewards = 0;
+    for accounts in minings {
+        let (mining, weighted_stake_diffs) = project_mining(&accounts.mining, pool, now)?;
+
+        minings_share += u128::from(mining.share) + u128::from(mining.stake_from_others);
+        owed_rewards += u128::from(mining.unclaimed_rewards);
+        for (date, diff) in weighted_stake_diffs.iter() {
+            add_diff(&mut minings_diffs, *date, *diff);
+        }
+
+        let decreases = accounts
+            .penalty_history
+            .into_iter()
+            .flat_map(|history| history.records())
+            .filter(|record| {
+                !record.is_restored() && record.kind() == Ok(PenaltyKind::DecreaseRewards)
+            });
+        for record in decreases {
+            let mut decreased_share = u128::from(record.weighted_amount);
+            for stake_diff in record.stake_diffs() {
+                if stake_diff.date <= beginning_of_the_epoch {
+                    decreased_share = decreased_share.saturating_sub(u128::from(stake_diff.diff));
+                } else {
+                    add_diff(&mut minings_diffs, stake_diff.date, stake_diff.diff);
+                }
+            }
+            minings_share += decreased_share;
+        }
+    }
+
+    if pool_share != minings_share {
+        return Err(InvariantViolation::TotalShare {
+            pool: pool_share,
+            minings: minings_share,
+        });
+    }
+
+    pool_diffs.retain(|_, diff| *diff > 0);
+    minings_diffs.retain(|_, diff| *diff > 0);
+    if pool_diffs != minings_diffs {
+        let date = pool_diffs
+            .keys()
+            .chain(minings_diffs.keys())
+            .find(|date| pool_diffs.get(date) != minings_diffs.get(date))
+            .copied()
+            .unwrap_or_default();
+        return Err(InvariantViolation::WeightedStakeDiff {
+            date,
+            pool: pool_diffs.get(&date).copied().unwrap_or_default(),
+            minings: minings_diffs.get(&date).copied().unwrap_or_default(),
+        });
+    }
+
+    let indexed_rewards = u128::from(pool.pool.unclaimed_rewards());
+    if owed_rewards > indexed_rewards {
+        return Err(InvariantViolation::RewardsOverissued {
+            owed: owed_rewards,
+            indexed: indexed_rewards,
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the copy of the mining refreshed at the given date
+fn project_mining(
+    mining: &WrappedImmutableMining,
+    pool: &WrappedImmutableRewardPool,
+    now: u64,
+) -> Result<(Mining, MiningWeightedStakeDiffs), ProgramError> {
+    let mut projected = *mining.mining;
+    let mut weighted_stake_diffs = *mining.weighted_stake_diffs;
+    let mut positions = *mining.positions;
+    let mut delegated_stakes = *mining.delegated_stakes;
+
+    WrappedMining {
+        mining: &mut projected,
+        weighted_stake_diffs: &mut weighted_stake_diffs,
+        positions: &mut positions,
+        delegated_stakes: &mut delegated_stakes,
+    }
+    .refresh_rewards(pool.cumulative_index, pool.pool.epoch_length(), now)?;
+
+    Ok((projected, weighted_stake_diffs))
+}
+
+fn add_diff(diffs: &mut BTreeMap<u64, u128>, date: u64, diff: u64) {
+    *diffs.entry(date).or_default() += u128::from(diff);
+}
+
+#[cfg(test)]
+mod test {
+    use sokoban::NodeAllocatorMap;
+    use solana_program::clock::SECONDS_PER_DAY;
+
+    use super::{check_invariants, InvariantViolation, MiningAccounts};
+    use crate::state::{
+        Mining, PenaltyHistory, PenaltyKind, PenaltyRecord, PenaltyStakeDiff, RewardPool,
+        WrappedImmutableMining, WrappedImmutableRewardPool, WrappedMining, WrappedRewardPool,
+    };
+
+    fn mining_bytes(share: u64, stake_diffs: &[(u64, u64)]) -> Vec<u8> {
+        let mut bytes = vec![0; WrappedMining::LEN];
+        let wrapped_mining = WrappedMining::from_uninitialized_bytes_mut(&mut bytes).unwrap();
+        *wrapped_mining.mining = Mining::initialize(
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            0,
+        );
+        wrapped_mining.weighted_stake_diffs.initialize();
+        wrapped_mining.positions.initialize();
+        wrapped_mining.delegated_stakes.initialize();
+        wrapped_mining.mining.share = share;
+        for (date, diff) in stake_diffs {
+            wrapped_mining.weighted_stake_diffs.insert(*date, *diff);
+        }
+        bytes
+    }
+
+    fn pool_bytes(total_share: u64, stake_diffs: &[(u64, u64)]) -> Vec<u8> {
+        let mut bytes = vec![0; WrappedRewardPool::LEN];
+        let wrapped_pool = WrappedRewardPool::from_uninitialized_bytes_mut(&mut bytes).unwrap();
+        *wrapped_pool.pool = RewardPool::initialize(
+            0,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        );
+        wrapped_pool.weighted_stake_diffs.initialize();
+        wrapped_pool.cumulative_index.initialize();
+        wrapped_pool.pool.total_share = total_share;
+        for (date, diff) in stake_diffs {
+            wrapped_pool.weighted_stake_diffs.insert(*date, *diff);
+        }
+        bytes
+    }
+
+    fn check(
+        pool: &[u8],
+        minings: &[&[u8]],
+        penalty_history: Option<&PenaltyHistory>,
+        now: u64,
+    ) -> Result<(), InvariantViolation> {
+        let pool = WrappedImmutableRewardPool::from_bytes(pool).unwrap();
+        let minings: Vec<_> = minings
+            .iter()
+            .map(|mining| MiningAccounts {
+                mining: WrappedImmutableMining::from_bytes(mining).unwrap(),
+                penalty_history,
+            })
+            .collect();
+
+        check_invariants(&pool, &minings, now)
+    }
+
+    #[test]
+    fn test_minings_match_the_pool_before_and_after_modifiers() {
+        let expiration = 10 * SECONDS_PER_DAY;
+        let pool = pool_bytes(3_000, &[(expiration, 1_000)]);
+        let flex_mining = mining_bytes(1_000, &[]);
+        let locked_mining = mining_bytes(2_000, &[(expiration, 1_000)]);
+
+        assert_eq!(
+            check(
+                &pool,
+                &[&flex_mining, &locked_mining],
+                None,
+                SECONDS_PER_DAY
+            ),
+            Ok(())
+        );
+        // the modifier has been reached, but neither the pool nor the mining has consumed it yet
+        assert_eq!(
+            check(&pool, &[&flex_mining, &locked_mining], None, expiration + 1),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_mismatching_share_and_modifiers() {
+        let expiration = 10 * SECONDS_PER_DAY;
+        let flex_mining = mining_bytes(1_000, &[]);
+        let locked_mining = mining_bytes(2_000, &[(expiration, 1_000)]);
+
+        let pool = pool_bytes(3_500, &[(expiration, 1_000)]);
+        assert_eq!(
+            check(
+                &pool,
+                &[&flex_mining, &locked_mining],
+                None,
+                SECONDS_PER_DAY
+            ),
+            Err(InvariantViolation::TotalShare {
+                pool: 3_500,
+                minings: 3_000,
+            })
+        );
+
+        let pool = pool_bytes(3_000, &[(expiration, 1_500)]);
+        assert_eq!(
+            check(
+                &pool,
+                &[&flex_mining, &locked_mining],
+                None,
+                SECONDS_PER_DAY
+            ),
+            Err(InvariantViolation::WeightedStakeDiff {
+                date: expiration,
+                pool: 1_500,
+                minings: 1_000,
+            })
+        );
+    }
+
+    #[test]
+    fn test_decreased_stake_is_taken_from_the_penalty_history() {
+        let expiration = 10 * SECONDS_PER_DAY;
+        let pool = pool_bytes(2_000, &[(expiration, 1_000)]);
+        // 1_500 out of 2_000 has been decreased, including 1_000 scheduled at the expiration
+        let mining = mining_bytes(500, &[(expiration, 0)]);
+
+        let mut history = PenaltyHistory::initialize(Default::default(), 255);
+        let mut record = PenaltyRecord {
+            kind: PenaltyKind::DecreaseRewards.into(),
+            weighted_amount: 1_500,
+            ..Default::default()
+        };
+        record.set_stake_diffs(&[PenaltyStakeDiff {
+            date: expiration,
+            diff: 1_000,
+        }]);
+        history.append(record);
+
+        assert_eq!(
+            check(&pool, &[&mining], Some(&history), SECONDS_PER_DAY),
+            Ok(())
+        );
+        assert_eq!(check(&pool, &[&mining], Some(&history), expiration), Ok(()));
+        assert!(check(&pool, &[&mining], None, SECONDS_PER_DAY).is_err());
+    }
+}
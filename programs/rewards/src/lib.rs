@@ -8,6 +8,9 @@ pub mod entrypoint;
 pub mod error;
 pub mod instruction;
 pub mod instructions;
+// checks every mining of the pool at once, so it's never a part of the on-chain program
+#[cfg(not(target_os = "solana"))]
+pub mod invariants;
 pub mod state;
 pub mod utils;
 
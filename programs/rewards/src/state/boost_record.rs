@@ -0,0 +1,91 @@
+use crate::{
+    error::MplxRewardsError,
+    state::{assert_account_type, assert_data_len, assert_uninitialized, AccountType},
+};
+use bytemuck::{Pod, Zeroable};
+use shank::ShankAccount;
+use sokoban::ZeroCopy;
+use solana_program::{
+    entrypoint::ProgramResult, program_error::ProgramError, program_pack::IsInitialized,
+    pubkey::Pubkey,
+};
+
+/// Record of the boost proven with the NFT of the boost collection. It's a PDA of
+/// the reward pool and the NFT mint, so the same NFT cannot boost several minings at once.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable, ShankAccount)]
+pub struct BoostRecord {
+    /// The address of the NFT mint the boost has been proven with.
+    pub nft_mint: Pubkey,
+    /// The address of the mining account boosted with the NFT the last time.
+    pub mining: Pubkey,
+    /// The date the last boost proven with the NFT expires at.
+    pub expires_at: u64,
+    /// Bump of the boost record account
+    pub bump: u8,
+    /// Account type - BoostRecord. This discriminator should exist in order to prevent
+    /// shenanigans with customly modified accounts and their fields.
+    /// 0: account type
+    /// 1-6: unused
+    pub data: [u8; 7],
+}
+
+impl ZeroCopy for BoostRecord {}
+
+impl BoostRecord {
+    pub const LEN: usize = std::mem::size_of::<BoostRecord>();
+
+    /// Initialize a Boost Record
+    pub fn initialize(nft_mint: Pubkey, bump: u8) -> BoostRecord {
+        let mut data = [0; 7];
+        data[0] = AccountType::BoostRecord.into();
+
+        BoostRecord {
+            nft_mint,
+            mining: Pubkey::default(),
+            expires_at: 0,
+            bump,
+            data,
+        }
+    }
+
+    /// Loads the initialized account
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let account = BoostRecord::load_mut_bytes(bytes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        assert_account_type(account.data[0], AccountType::BoostRecord)?;
+
+        Ok(account)
+    }
+
+    /// Loads the account which is about to be initialized
+    pub fn from_uninitialized_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let account = BoostRecord::load_mut_bytes(bytes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        assert_uninitialized(account.data[0])?;
+
+        Ok(account)
+    }
+
+    /// Records the boost of the mining, unless the NFT still boosts another one
+    pub fn record(&mut self, mining: &Pubkey, expires_at: u64, curr_ts: u64) -> ProgramResult {
+        if self.expires_at > curr_ts {
+            return Err(MplxRewardsError::NftIsAlreadyUsedForBoost.into());
+        }
+
+        self.mining = *mining;
+        self.expires_at = expires_at;
+
+        Ok(())
+    }
+}
+
+impl IsInitialized for BoostRecord {
+    fn is_initialized(&self) -> bool {
+        self.data[0] == <u8>::from(AccountType::BoostRecord)
+    }
+}
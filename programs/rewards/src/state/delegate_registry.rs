@@ -0,0 +1,206 @@
+use crate::{
+    error::MplxRewardsError,
+    state::{assert_account_type, assert_data_len, assert_uninitialized, AccountType},
+};
+use bytemuck::{Pod, Zeroable};
+use shank::ShankAccount;
+use sokoban::ZeroCopy;
+use solana_program::{
+    entrypoint::ProgramResult, program_error::ProgramError, program_pack::IsInitialized,
+    pubkey::Pubkey,
+};
+
+/// Maximal number of mining owners kept in each of the allow and deny lists
+pub const DELEGATE_LIST_CAPACITY: usize = 16;
+
+/// Registry of the delegate. It's a companion PDA of the mining account, and only
+/// registered minings may receive stake from others. It also keeps the conditions
+/// the delegate accepts stake on.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable, ShankAccount)]
+pub struct DelegateRegistry {
+    /// The address of the delegate's mining account.
+    pub mining: Pubkey,
+    /// The maximal stake from others the delegate accepts. Zero means no limit.
+    pub max_stake_from_others: u64,
+    /// Bump of the delegate registry account
+    pub bump: u8,
+    /// Account type - DelegateRegistry. This discriminator should exist in order to prevent
+    /// shenanigans with customly modified accounts and their fields.
+    /// 0: account type
+    /// 1-6: unused
+    pub data: [u8; 7],
+    /// Number of used entries in `allow_list`
+    pub allow_list_len: u8,
+    /// Number of used entries in `deny_list`
+    pub deny_list_len: u8,
+    pub padding: [u8; 6],
+    /// Mining owners who are allowed to delegate. Empty list means everyone is allowed.
+    pub allow_list: [Pubkey; 16],
+    /// Mining owners who aren't allowed to delegate.
+    pub deny_list: [Pubkey; 16],
+}
+
+impl ZeroCopy for DelegateRegistry {}
+
+// Shank only extracts literal array lengths, so the account spells the capacity out.
+// The ascriptions stop the build once the lists and the capacity diverge.
+const _: fn(&DelegateRegistry) -> &[Pubkey; DELEGATE_LIST_CAPACITY] =
+    |registry| &registry.allow_list;
+const _: fn(&DelegateRegistry) -> &[Pubkey; DELEGATE_LIST_CAPACITY] =
+    |registry| &registry.deny_list;
+
+impl DelegateRegistry {
+    pub const LEN: usize = std::mem::size_of::<DelegateRegistry>();
+
+    /// Initialize a Delegate Registry
+    pub fn initialize(mining: Pubkey, bump: u8) -> DelegateRegistry {
+        let mut data = [0; 7];
+        data[0] = AccountType::DelegateRegistry.into();
+
+        DelegateRegistry {
+            mining,
+            max_stake_from_others: 0,
+            bump,
+            data,
+            allow_list_len: 0,
+            deny_list_len: 0,
+            padding: [0; 6],
+            allow_list: [Pubkey::default(); DELEGATE_LIST_CAPACITY],
+            deny_list: [Pubkey::default(); DELEGATE_LIST_CAPACITY],
+        }
+    }
+
+    /// Loads the initialized account
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let account = DelegateRegistry::load_mut_bytes(bytes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        assert_account_type(account.data[0], AccountType::DelegateRegistry)?;
+
+        Ok(account)
+    }
+
+    /// Loads the initialized account
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let account = DelegateRegistry::load_bytes(bytes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        assert_account_type(account.data[0], AccountType::DelegateRegistry)?;
+
+        Ok(account)
+    }
+
+    /// Loads the account which is about to be initialized
+    pub fn from_uninitialized_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let account = DelegateRegistry::load_mut_bytes(bytes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        assert_uninitialized(account.data[0])?;
+
+        Ok(account)
+    }
+
+    pub fn allow_list(&self) -> &[Pubkey] {
+        &self.allow_list[..self.allow_list_len as usize]
+    }
+
+    pub fn deny_list(&self) -> &[Pubkey] {
+        &self.deny_list[..self.deny_list_len as usize]
+    }
+
+    /// Replaces the delegation conditions
+    pub fn configure(
+        &mut self,
+        max_stake_from_others: u64,
+        allow_list: &[Pubkey],
+        deny_list: &[Pubkey],
+    ) -> ProgramResult {
+        if allow_list.len() > DELEGATE_LIST_CAPACITY || deny_list.len() > DELEGATE_LIST_CAPACITY {
+            return Err(MplxRewardsError::DelegateListIsTooLong.into());
+        }
+
+        self.max_stake_from_others = max_stake_from_others;
+
+        self.allow_list = [Pubkey::default(); DELEGATE_LIST_CAPACITY];
+        self.allow_list[..allow_list.len()].copy_from_slice(allow_list);
+        self.allow_list_len = allow_list.len() as u8;
+
+        self.deny_list = [Pubkey::default(); DELEGATE_LIST_CAPACITY];
+        self.deny_list[..deny_list.len()].copy_from_slice(deny_list);
+        self.deny_list_len = deny_list.len() as u8;
+
+        Ok(())
+    }
+
+    /// Checks the delegate accepts the stake of the given mining owner,
+    /// which makes the delegate's stake from others equal to `stake_from_others`
+    pub fn assert_accepts(
+        &self,
+        delegate_mining: &Pubkey,
+        mining_owner: &Pubkey,
+        stake_from_others: u64,
+    ) -> ProgramResult {
+        if self.mining != *delegate_mining {
+            return Err(MplxRewardsError::DelegateIsNotRegistered.into());
+        }
+
+        if self.deny_list().contains(mining_owner)
+            || (!self.allow_list().is_empty() && !self.allow_list().contains(mining_owner))
+        {
+            return Err(MplxRewardsError::DelegationIsNotAllowed.into());
+        }
+
+        if self.max_stake_from_others > 0 && stake_from_others > self.max_stake_from_others {
+            return Err(MplxRewardsError::DelegateStakeLimitExceeded.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl IsInitialized for DelegateRegistry {
+    fn is_initialized(&self) -> bool {
+        self.data[0] == <u8>::from(AccountType::DelegateRegistry)
+    }
+}
+
+mod test {
+    #[allow(unused_imports)]
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_delegate_registry_accepts_stake_by_its_lists_and_limit() {
+        let delegate_mining = Pubkey::new_unique();
+        let allowed = Pubkey::new_unique();
+        let denied = Pubkey::new_unique();
+
+        let mut registry = super::DelegateRegistry::initialize(delegate_mining, 255);
+        assert!(registry
+            .assert_accepts(&delegate_mining, &denied, u64::MAX)
+            .is_ok());
+        assert!(registry
+            .assert_accepts(&Pubkey::new_unique(), &allowed, 0)
+            .is_err());
+
+        registry.configure(100, &[allowed], &[denied]).unwrap();
+        assert!(registry
+            .assert_accepts(&delegate_mining, &allowed, 100)
+            .is_ok());
+        assert!(registry
+            .assert_accepts(&delegate_mining, &allowed, 101)
+            .is_err());
+        assert!(registry
+            .assert_accepts(&delegate_mining, &denied, 0)
+            .is_err());
+        assert!(registry
+            .assert_accepts(&delegate_mining, &Pubkey::new_unique(), 0)
+            .is_err());
+
+        let too_long = vec![Pubkey::new_unique(); super::DELEGATE_LIST_CAPACITY + 1];
+        assert!(registry.configure(0, &too_long, &[]).is_err());
+    }
+}
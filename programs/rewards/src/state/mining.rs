@@ -1,20 +1,19 @@
 use crate::{error::MplxRewardsError, state::PRECISION};
 
-use crate::utils::SafeArithmeticOperations;
+use crate::utils::{split_by_weights, LockupPeriod, SafeArithmeticOperations, MAX_DELEGATES};
 use bytemuck::{Pod, Zeroable};
 use shank::ShankAccount;
 use sokoban::{NodeAllocatorMap, ZeroCopy};
 use solana_program::{
-    clock::{Clock, SECONDS_PER_DAY},
-    entrypoint::ProgramResult,
-    program_error::ProgramError,
-    program_pack::IsInitialized,
+    entrypoint::ProgramResult, program_error::ProgramError, program_pack::IsInitialized,
     pubkey::Pubkey,
-    sysvar::Sysvar,
 };
 
 use super::{
-    find_max_value_limited_by_key, AccountType, CumulativeIndex, MiningWeightedStakeDiffs,
+    assert_account_type, assert_account_version, assert_data_len, assert_uninitialized,
+    find_max_value_limited_by_key, increase_weighted_stake_diff, read_outdated_header, AccountType,
+    CumulativeIndex, MiningDelegatedStakes, MiningPositions, MiningWeightedStakeDiffs,
+    PenaltyStakeDiff, ACCOUNT_VERSION_BYTE,
 };
 
 pub struct WrappedMining<'a> {
@@ -23,6 +22,11 @@ pub struct WrappedMining<'a> {
     /// where staking ends. This modifier will be applied on the specified date to the global stake,
     /// so that rewards distribution will change. BTreeMap<unix_timestamp, modifier diff>
     pub weighted_stake_diffs: &'a mut MiningWeightedStakeDiffs,
+    /// Positions of the deposits applied to the mining. BTreeMap<deposit_id, position>
+    pub positions: &'a mut MiningPositions,
+    /// Stakes the deposits split between several delegates have delegated to each of them.
+    /// BTreeMap<deposit_id, delegated stakes>
+    pub delegated_stakes: &'a mut MiningDelegatedStakes,
 }
 pub struct WrappedImmutableMining<'a> {
     pub mining: &'a Mining,
@@ -30,36 +34,129 @@ pub struct WrappedImmutableMining<'a> {
     /// where staking ends. This modifier will be applied on the specified date to the global stake,
     /// so that rewards distribution will change. BTreeMap<unix_timestamp, modifier diff>
     pub weighted_stake_diffs: &'a MiningWeightedStakeDiffs,
+    /// Positions of the deposits applied to the mining. BTreeMap<deposit_id, position>
+    pub positions: &'a MiningPositions,
+    /// Stakes the deposits split between several delegates have delegated to each of them.
+    /// BTreeMap<deposit_id, delegated stakes>
+    pub delegated_stakes: &'a MiningDelegatedStakes,
 }
 
 pub const ACCOUNT_TYPE_BYTE: usize = 0;
 
 impl<'a> WrappedMining<'a> {
-    pub const LEN: usize =
-        std::mem::size_of::<Mining>() + std::mem::size_of::<MiningWeightedStakeDiffs>();
-
+    pub const LEN: usize = std::mem::size_of::<Mining>()
+        + std::mem::size_of::<MiningWeightedStakeDiffs>()
+        + std::mem::size_of::<MiningPositions>()
+        + std::mem::size_of::<MiningDelegatedStakes>();
+    /// Length of the layout version 3, which didn't record the delegated stakes
+    pub const LEN_V3: usize = std::mem::size_of::<Mining>()
+        + std::mem::size_of::<MiningWeightedStakeDiffs>()
+        + std::mem::size_of::<MiningPositions>();
+
+    /// Loads the initialized mining stored in the current layout
     pub fn from_bytes_mut(bytes: &'a mut [u8]) -> Result<Self, ProgramError> {
-        let (mining, weighted_stake_diffs) = bytes.split_at_mut(Mining::LEN);
+        Self::assert_len(bytes)?;
+
+        let wrapped_mining = Self::load_mut(bytes)?;
+        wrapped_mining.mining.assert_account_type()?;
+        wrapped_mining.mining.assert_version()?;
+
+        Ok(wrapped_mining)
+    }
+
+    /// Loads the account the mining is about to be initialized in
+    pub fn from_uninitialized_bytes_mut(bytes: &'a mut [u8]) -> Result<Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let wrapped_mining = Self::load_mut(bytes)?;
+        assert_uninitialized(wrapped_mining.mining.data[ACCOUNT_TYPE_BYTE])?;
+
+        Ok(wrapped_mining)
+    }
+
+    fn load_mut(bytes: &'a mut [u8]) -> Result<Self, ProgramError> {
+        let (mining, trees) = bytes.split_at_mut(Mining::LEN);
+        let (weighted_stake_diffs, trees) =
+            trees.split_at_mut(std::mem::size_of::<MiningWeightedStakeDiffs>());
+        let (positions, delegated_stakes) =
+            trees.split_at_mut(std::mem::size_of::<MiningPositions>());
         let mining = Mining::load_mut_bytes(mining)
             .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
 
         let weighted_stake_diffs = MiningWeightedStakeDiffs::load_mut_bytes(weighted_stake_diffs)
             .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        let positions = MiningPositions::load_mut_bytes(positions)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        let delegated_stakes = MiningDelegatedStakes::load_mut_bytes(delegated_stakes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
 
         Ok(Self {
             mining,
             weighted_stake_diffs,
+            positions,
+            delegated_stakes,
         })
     }
 
+    /// Checks the account has the length of the current layout.
+    /// Accounts stored in the outdated layouts are shorter and need the migration.
+    fn assert_len(bytes: &[u8]) -> Result<(), MplxRewardsError> {
+        if bytes.len() == Self::LEN {
+            return Ok(());
+        }
+
+        match Self::outdated_header(bytes) {
+            Ok(Some(_)) => Err(MplxRewardsError::AccountNeedsMigration),
+            _ => Err(MplxRewardsError::InvalidAccountLength),
+        }
+    }
+
+    /// Returns the header of the mining stored in the outdated layout along with
+    /// the header length of that layout. None means the mining has the current layout.
+    pub fn outdated_header(bytes: &[u8]) -> Result<Option<(Mining, usize)>, ProgramError> {
+        // positions have been introduced along with the version 3 and the delegated stakes
+        // along with the version 4, so the layouts before have the weighted stake diffs
+        // only after the header
+        let header_len = if bytes.len() == Self::LEN || bytes.len() == Self::LEN_V3 {
+            Mining::LEN
+        } else {
+            bytes
+                .len()
+                .checked_sub(std::mem::size_of::<MiningWeightedStakeDiffs>())
+                .filter(|header_len| *header_len <= Mining::LEN)
+                .ok_or(MplxRewardsError::UnsupportedAccountVersion)?
+        };
+
+        let mining: Mining = read_outdated_header(bytes, header_len);
+        if mining.data[ACCOUNT_TYPE_BYTE] != u8::from(AccountType::Mining) {
+            return Err(MplxRewardsError::UnsupportedAccountVersion.into());
+        }
+
+        match mining.version() {
+            Mining::VERSION if bytes.len() == Self::LEN => Ok(None),
+            3 if bytes.len() == Self::LEN_V3 => Ok(Some((mining, header_len))),
+            0 if Mining::UNVERSIONED_HEADER_LENS.contains(&header_len) => {
+                Ok(Some((mining, header_len)))
+            }
+            version if Mining::VERSIONED_HEADER_LENS.contains(&(version, header_len)) => {
+                Ok(Some((mining, header_len)))
+            }
+            _ => Err(MplxRewardsError::UnsupportedAccountVersion.into()),
+        }
+    }
+
     /// Refresh rewards
-    pub fn refresh_rewards(&mut self, cumulative_index: &CumulativeIndex) -> ProgramResult {
-        let curr_ts = Clock::get().unwrap().unix_timestamp as u64;
-        let beginning_of_the_day = curr_ts - (curr_ts % SECONDS_PER_DAY);
+    pub fn refresh_rewards(
+        &mut self,
+        cumulative_index: &CumulativeIndex,
+        epoch_length: u64,
+        curr_ts: u64,
+    ) -> ProgramResult {
+        let beginning_of_the_epoch = curr_ts - (curr_ts % epoch_length);
         let mut share = self.mining.share.safe_add(self.mining.stake_from_others)?;
 
         share = self.mining.consume_old_modifiers(
-            beginning_of_the_day,
+            beginning_of_the_epoch,
             share,
             cumulative_index,
             self.weighted_stake_diffs,
@@ -76,12 +173,252 @@ impl<'a> WrappedMining<'a> {
         Ok(())
     }
 
-    /// Decrease rewards
-    pub fn decrease_rewards(&mut self, mut decreased_weighted_stake_number: u64) -> ProgramResult {
-        if decreased_weighted_stake_number == 0 {
+    /// Records the position of the deposit applied to the mining, so that the deposit
+    /// cannot be applied twice. The empty position left by slashing the whole deposit
+    /// is replaced, since the staking contract reuses ids of the closed deposits.
+    pub fn record_deposit(
+        &mut self,
+        deposit_id: u64,
+        position: Position,
+    ) -> Result<(), MplxRewardsError> {
+        match self.positions.get_mut(&deposit_id) {
+            Some(recorded) if !recorded.is_empty() => {
+                return Err(MplxRewardsError::DepositIsAlreadyApplied)
+            }
+            Some(recorded) => *recorded = position,
+            None => {
+                self.positions
+                    .insert(deposit_id, position)
+                    .ok_or(MplxRewardsError::PositionsAreFull)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the position of the deposit applied to the mining
+    pub fn position(&mut self, deposit_id: u64) -> Result<&mut Position, MplxRewardsError> {
+        self.positions
+            .get_mut(&deposit_id)
+            .ok_or(MplxRewardsError::DepositIsNotFound)
+    }
+
+    /// Takes the number of tokens out of the deposit. Returns the position
+    /// the deposit had before. The position is removed once the deposit is empty,
+    /// so a retried withdrawal of the whole deposit is rejected.
+    pub fn take_from_deposit(
+        &mut self,
+        deposit_id: u64,
+        amount: u64,
+    ) -> Result<Position, MplxRewardsError> {
+        let position = self.position(deposit_id)?;
+        let taken_from = *position;
+        if amount > position.amount {
+            return Err(MplxRewardsError::InvalidDepositAmount);
+        }
+
+        position.amount = position.amount.safe_sub(amount)?;
+        if position.is_empty() {
+            self.remove_position(deposit_id);
+        }
+
+        Ok(taken_from)
+    }
+
+    /// Removes the position of the deposit along with its delegated stakes
+    fn remove_position(&mut self, deposit_id: u64) {
+        self.positions.remove(&deposit_id);
+        self.delegated_stakes.remove(&deposit_id);
+    }
+
+    /// Records the stakes the deposit has delegated to each of its delegates.
+    /// Only the stake split between several delegates is recorded,
+    /// since the single delegate always has the whole one.
+    pub fn record_delegated_stakes(
+        &mut self,
+        deposit_id: u64,
+        stakes: &[u64],
+    ) -> Result<(), MplxRewardsError> {
+        if stakes.len() < 2 {
+            self.delegated_stakes.remove(&deposit_id);
             return Ok(());
         }
 
+        let delegated_stakes = DelegatedStakes::new(stakes)?;
+        match self.delegated_stakes.get_mut(&deposit_id) {
+            Some(recorded) => *recorded = delegated_stakes,
+            None => {
+                self.delegated_stakes
+                    .insert(deposit_id, delegated_stakes)
+                    .ok_or(MplxRewardsError::DelegatedStakesAreFull)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes the stake the number of tokens of the deposit has delegated back from
+    /// the delegates given by their weights. Returns the stake taken from each of them.
+    /// Deposits applied before the delegated stakes were recorded have the number
+    /// of tokens split by the weights.
+    pub fn undelegate(
+        &mut self,
+        deposit_id: u64,
+        amount: u64,
+        weights: &[u16],
+    ) -> Result<Vec<u64>, ProgramError> {
+        match self.delegated_stakes.get_mut(&deposit_id) {
+            Some(recorded) if recorded.stakes().len() == weights.len() => {
+                Ok(recorded.take(amount)?)
+            }
+            Some(_) => Err(MplxRewardsError::DelegatesDontMatchDeposit.into()),
+            None => split_by_weights(amount, weights),
+        }
+    }
+
+    /// Moves the number of tokens of the deposit to the unbonding ones, which have
+    /// the given weighted stake. Requesting more tokens restarts the unbonding
+    /// of the ones requested before. Returns the position the deposit had before.
+    pub fn unbond(
+        &mut self,
+        deposit_id: u64,
+        amount: u64,
+        unbonding_stake: u64,
+        unbonding_ends_at: u64,
+    ) -> Result<Position, MplxRewardsError> {
+        let position = self.position(deposit_id)?;
+        let unbonded_from = *position;
+        if amount == 0 || amount > position.amount {
+            return Err(MplxRewardsError::InvalidDepositAmount);
+        }
+
+        position.amount = position.amount.safe_sub(amount)?;
+        position.unbonding_amount = position.unbonding_amount.safe_add(amount)?;
+        position.unbonding_stake = position.unbonding_stake.safe_add(unbonding_stake)?;
+        position.unbonding_ends_at = unbonding_ends_at;
+
+        Ok(unbonded_from)
+    }
+
+    /// Takes the number of unbonding tokens out of the deposit once the unbonding is over.
+    /// Returns the weighted stake the tokens have had. The position is removed
+    /// once the deposit is empty, so a retried withdrawal of the whole deposit is rejected.
+    pub fn take_unbonded(
+        &mut self,
+        deposit_id: u64,
+        amount: u64,
+        curr_ts: u64,
+    ) -> Result<u64, MplxRewardsError> {
+        let position = self.position(deposit_id)?;
+        if position.unbonding_amount == 0 {
+            return Err(MplxRewardsError::WithdrawalIsNotRequested);
+        }
+        if amount > position.unbonding_amount {
+            return Err(MplxRewardsError::InvalidDepositAmount);
+        }
+        if curr_ts < position.unbonding_ends_at {
+            return Err(MplxRewardsError::UnbondingIsNotOver);
+        }
+
+        // the stake of the remaining tokens is rounded down,
+        // so that nothing is left once all of them are taken
+        let remaining_amount = position.unbonding_amount.safe_sub(amount)?;
+        let remaining_stake = u64::try_from(
+            u128::from(position.unbonding_stake)
+                .safe_mul(u128::from(remaining_amount))?
+                .safe_div(u128::from(position.unbonding_amount))?,
+        )
+        .map_err(|_| MplxRewardsError::InvalidPrimitiveTypesConversion)?;
+        let taken_stake = position.unbonding_stake.safe_sub(remaining_stake)?;
+
+        position.unbonding_amount = remaining_amount;
+        position.unbonding_stake = remaining_stake;
+        if position.is_empty() {
+            self.remove_position(deposit_id);
+        }
+
+        Ok(taken_stake)
+    }
+
+    /// Removes the weighted stake of the number of tokens of the position from the mining,
+    /// which has to be refreshed beforehand. Returns the removed weighted stake along with
+    /// the modifier it has been decreased by, unless the lockup has expired already.
+    pub fn remove_position_stake(
+        &mut self,
+        position: &Position,
+        amount: u64,
+        beginning_of_the_epoch: u64,
+    ) -> Result<(u64, Option<PenaltyStakeDiff>), MplxRewardsError> {
+        let weighted_amount = position.weighted_amount(amount, beginning_of_the_epoch)?;
+        self.mining.share = self.mining.share.safe_sub(weighted_amount)?;
+
+        // the part of the weighted stake which is removed on the expiration
+        let diff = weighted_amount.safe_sub(amount.safe_mul(LockupPeriod::Flex.multiplier())?)?;
+        if diff == 0 {
+            return Ok((weighted_amount, None));
+        }
+
+        let modifier = self
+            .weighted_stake_diffs
+            .get_mut(&position.stake_expiration_date)
+            .ok_or(MplxRewardsError::NoWeightedStakeModifiersAtADate)?;
+        *modifier = modifier.safe_sub(diff)?;
+
+        Ok((
+            weighted_amount,
+            Some(PenaltyStakeDiff {
+                date: position.stake_expiration_date,
+                diff,
+            }),
+        ))
+    }
+
+    /// Returns the number of tokens to the deposit along with the weighted stake of its
+    /// current lockup. The mining has to be refreshed beforehand. Returns the added weighted
+    /// stake along with the modifier it has been increased by, unless the lockup has expired.
+    pub fn add_to_deposit(
+        &mut self,
+        deposit_id: u64,
+        amount: u64,
+        beginning_of_the_epoch: u64,
+    ) -> Result<(u64, Option<PenaltyStakeDiff>), MplxRewardsError> {
+        let position = self.position(deposit_id)?;
+        position.amount = position.amount.safe_add(amount)?;
+        let position = *position;
+
+        let weighted_amount = position.weighted_amount(amount, beginning_of_the_epoch)?;
+        self.mining.share = self.mining.share.safe_add(weighted_amount)?;
+
+        // the part of the weighted stake which is removed on the expiration
+        let diff = weighted_amount.safe_sub(amount.safe_mul(LockupPeriod::Flex.multiplier())?)?;
+        if diff == 0 {
+            return Ok((weighted_amount, None));
+        }
+        increase_weighted_stake_diff(
+            self.weighted_stake_diffs,
+            position.stake_expiration_date,
+            diff,
+        )?;
+
+        Ok((
+            weighted_amount,
+            Some(PenaltyStakeDiff {
+                date: position.stake_expiration_date,
+                diff,
+            }),
+        ))
+    }
+
+    /// Decrease rewards. Returns weighted stake modifiers that have been decreased.
+    pub fn decrease_rewards(
+        &mut self,
+        mut decreased_weighted_stake_number: u64,
+    ) -> Result<Vec<PenaltyStakeDiff>, ProgramError> {
+        let mut decreased_stake_diffs = vec![];
+        if decreased_weighted_stake_number == 0 {
+            return Ok(decreased_stake_diffs);
+        }
+
         if decreased_weighted_stake_number > self.mining.share {
             return Err(MplxRewardsError::DecreaseRewardsTooBig.into());
         }
@@ -95,18 +432,28 @@ impl<'a> WrappedMining<'a> {
         // going through the weighted stake diffs backwards
         // and decreasing the modifiers accordingly to the decreased share number.
         // otherwise moddifier might decrease the share more then needed, even to negative value.
-        for (_, stake_diff) in self.weighted_stake_diffs.iter_mut().rev() {
+        for (date, stake_diff) in self.weighted_stake_diffs.iter_mut().rev() {
             if stake_diff >= &mut decreased_weighted_stake_number {
                 *stake_diff = stake_diff.safe_sub(decreased_weighted_stake_number)?;
+                decreased_stake_diffs.push(PenaltyStakeDiff {
+                    date: *date,
+                    diff: decreased_weighted_stake_number,
+                });
                 break;
             } else {
                 decreased_weighted_stake_number =
                     decreased_weighted_stake_number.safe_sub(*stake_diff)?;
+                if *stake_diff > 0 {
+                    decreased_stake_diffs.push(PenaltyStakeDiff {
+                        date: *date,
+                        diff: *stake_diff,
+                    });
+                }
                 *stake_diff = 0;
             }
         }
 
-        Ok(())
+        Ok(decreased_stake_diffs)
     }
 }
 
@@ -118,7 +465,8 @@ pub struct Mining {
     /// Mining owner. This user corresponds to the voter_authority
     /// on the staking contract, which means those idendities are the same.
     pub owner: Pubkey,
-    /// That is the mint of the Rewards Token
+    /// That is the mint of the Rewards Token. It's the reward mint of the pool,
+    /// which is recorded on the initialization or on the migration from the outdated layouts.
     pub reward_mint: Pubkey,
     /// That is the index that increases on each distribution.
     /// It points at the moment of time where the last reward was claimed.
@@ -130,40 +478,199 @@ pub struct Mining {
     /// After claim the value is set to zero.
     pub unclaimed_rewards: u64,
     /// This field sums up each time somebody stakes to that account as a delegate.
+    /// Delegated stake is counted with the Flex multiplier whatever the delegator's lockup is,
+    /// so it doesn't change once the lockup expires and has no weighted stake modifiers.
     pub stake_from_others: u64,
     /// Bump of the mining account
     pub bump: u8,
     /// Account type - Mining. This discriminator should exist in order to prevent
     /// shenanigans with customly modified accounts and their fields.
     /// 0: account type
-    /// 1-7: unused
+    /// 1: layout version
+    /// 2-6: unused
     pub data: [u8; 7],
+    /// Weighted stake granted by the latest boost.
+    /// It's removed from the share by the weighted stake modifier at `boost_expires_at`.
+    pub boost: u64,
+    /// The date the latest boost expires at.
+    pub boost_expires_at: u64,
 }
 
 impl ZeroCopy for Mining {}
 
+/// Position of the deposit applied to the mining, keyed by the deposit id.
+/// The deposit id is assigned by the deposit authority, e.g. it's the index
+/// of the deposit entry on the staking contract.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, Pod, Zeroable, PartialEq, Eq)]
+pub struct Position {
+    /// Number of tokens staked by the deposit, the unbonding ones aren't counted
+    pub amount: u64,
+    /// The time the lockup of the deposit has started at
+    pub start_ts: u64,
+    /// The date the weighted stake modifier of the deposit is scheduled for
+    pub stake_expiration_date: u64,
+    /// Number of tokens requested for withdrawal
+    pub unbonding_amount: u64,
+    /// The weighted stake the unbonding tokens have
+    pub unbonding_stake: u64,
+    /// The time the unbonding tokens can be withdrawn at
+    pub unbonding_ends_at: u64,
+    /// Lockup period of the deposit, see [`LockupPeriod`]
+    pub lockup_period: u8,
+    pub padding: [u8; 7],
+}
+
+impl Position {
+    pub fn new(
+        amount: u64,
+        lockup_period: LockupPeriod,
+        start_ts: u64,
+        epoch_length: u64,
+    ) -> Result<Position, MplxRewardsError> {
+        Ok(Position {
+            amount,
+            start_ts,
+            stake_expiration_date: lockup_period.end_timestamp(start_ts, epoch_length)?,
+            lockup_period: lockup_period.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Checks the deposit has neither staked nor unbonding tokens
+    pub fn is_empty(&self) -> bool {
+        self.amount == 0 && self.unbonding_amount == 0
+    }
+
+    pub fn lockup_period(&self) -> Result<LockupPeriod, MplxRewardsError> {
+        LockupPeriod::try_from(self.lockup_period)
+    }
+
+    /// Returns the weighted stake the number of tokens of the position has
+    /// in the epoch. Tokens of the expired lockup have the Flex multiplier.
+    pub fn weighted_amount(
+        &self,
+        amount: u64,
+        beginning_of_the_epoch: u64,
+    ) -> Result<u64, MplxRewardsError> {
+        let lockup_period = if self.stake_expiration_date > beginning_of_the_epoch {
+            self.lockup_period()?
+        } else {
+            LockupPeriod::Flex
+        };
+
+        amount.safe_mul(lockup_period.multiplier())
+    }
+}
+
+/// Stakes the deposit split between several delegates has delegated to each of them,
+/// in the order of the delegates
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, Pod, Zeroable, PartialEq, Eq)]
+pub struct DelegatedStakes {
+    /// Number of the delegates the stake is split between
+    pub delegates_len: u8,
+    pub padding: [u8; 7],
+    pub stakes: [u64; MAX_DELEGATES],
+}
+
+impl DelegatedStakes {
+    pub fn new(stakes: &[u64]) -> Result<DelegatedStakes, MplxRewardsError> {
+        if stakes.len() > MAX_DELEGATES {
+            return Err(MplxRewardsError::InvalidDelegateWeights);
+        }
+
+        let mut delegated_stakes = DelegatedStakes {
+            delegates_len: stakes.len() as u8,
+            ..Default::default()
+        };
+        delegated_stakes.stakes[..stakes.len()].copy_from_slice(stakes);
+
+        Ok(delegated_stakes)
+    }
+
+    pub fn stakes(&self) -> &[u64] {
+        &self.stakes[..usize::from(self.delegates_len)]
+    }
+
+    /// Takes the stake delegated by the number of tokens. Each stake is decreased
+    /// proportionally and rounded down, while the remainder is spread between
+    /// the rounded ones. So no stake is decreased by more than it has, and all of them
+    /// are taken once the last tokens are. Returns the stake taken from each delegate.
+    pub fn take(&mut self, amount: u64) -> Result<Vec<u64>, MplxRewardsError> {
+        let stakes = &mut self.stakes[..usize::from(self.delegates_len)];
+        let total_stake = stakes.iter().map(|stake| u128::from(*stake)).sum::<u128>();
+        let remaining_stake = total_stake.saturating_sub(u128::from(amount));
+
+        let mut remainder = remaining_stake;
+        let mut remaining_stakes = Vec::with_capacity(stakes.len());
+        for stake in stakes.iter() {
+            let scaled = u128::from(*stake).safe_mul(remaining_stake)?;
+            let remaining = scaled.checked_div(total_stake).unwrap_or(0);
+            remainder = remainder.safe_sub(remaining)?;
+            remaining_stakes.push((remaining as u64, scaled % total_stake.max(1) > 0));
+        }
+
+        let mut taken_stakes = Vec::with_capacity(stakes.len());
+        for (stake, (mut remaining, rounded)) in stakes.iter_mut().zip(remaining_stakes) {
+            if rounded && remainder > 0 {
+                remaining = remaining.safe_add(1)?;
+                remainder -= 1;
+            }
+            taken_stakes.push(stake.safe_sub(remaining)?);
+            *stake = remaining;
+        }
+
+        Ok(taken_stakes)
+    }
+}
+
 impl Mining {
     /// Bytes required to store the `Mining`.
     pub const LEN: usize = std::mem::size_of::<Mining>();
-
-    /// Initialize a Reward Pool
-    pub fn initialize(reward_pool: Pubkey, owner: Pubkey, bump: u8) -> Mining {
+    /// Version of the current layout
+    pub const VERSION: u8 = 4;
+    /// Header lengths of the layouts mining accounts had been created with before
+    /// the version byte was introduced
+    pub const UNVERSIONED_HEADER_LENS: [usize; 2] = [144, 160];
+    /// Header lengths of the outdated versioned layouts, which had no positions yet,
+    /// along with their versions
+    pub const VERSIONED_HEADER_LENS: [(u8, usize); 2] = [(1, 160), (2, 160)];
+
+    /// Initialize a Mining
+    pub fn initialize(reward_pool: Pubkey, owner: Pubkey, reward_mint: Pubkey, bump: u8) -> Mining {
         let account_type = AccountType::Mining.into();
 
         let mut data = [0; 7];
         data[ACCOUNT_TYPE_BYTE] = account_type;
+        data[ACCOUNT_VERSION_BYTE] = Self::VERSION;
 
         Mining {
             bump,
             data,
             reward_pool,
             owner,
+            reward_mint,
             ..Default::default()
         }
     }
 
-    pub fn account_type(&self) -> AccountType {
-        AccountType::from(self.data[ACCOUNT_TYPE_BYTE])
+    pub fn account_type(&self) -> Result<AccountType, MplxRewardsError> {
+        AccountType::try_from(self.data[ACCOUNT_TYPE_BYTE])
+    }
+
+    pub fn version(&self) -> u8 {
+        self.data[ACCOUNT_VERSION_BYTE]
+    }
+
+    /// Checks the mining is stored in the current layout
+    pub fn assert_version(&self) -> Result<(), MplxRewardsError> {
+        assert_account_version(self.data[ACCOUNT_TYPE_BYTE], self.version(), Self::VERSION)
+    }
+
+    /// Checks the account holds an initialized mining
+    pub fn assert_account_type(&self) -> Result<(), MplxRewardsError> {
+        assert_account_type(self.data[ACCOUNT_TYPE_BYTE], AccountType::Mining)
     }
 
     /// Claim reward
@@ -174,14 +681,14 @@ impl Mining {
     /// Consume old modifiers
     pub fn consume_old_modifiers(
         &mut self,
-        beginning_of_the_day: u64,
+        beginning_of_the_epoch: u64,
         mut total_share: u64,
         cumulative_index: &CumulativeIndex,
         weighted_stake_diffs: &mut MiningWeightedStakeDiffs,
     ) -> Result<u64, ProgramError> {
         let mut processed_dates = vec![];
         for (date, modifier_diff) in weighted_stake_diffs.iter() {
-            if date > &beginning_of_the_day {
+            if date > &beginning_of_the_epoch {
                 break;
             }
 
@@ -215,13 +722,8 @@ impl Mining {
         let vault_index_for_date =
             find_max_value_limited_by_key(cumulative_index, date).unwrap_or(0);
 
-        let rewards = u64::try_from(
-            vault_index_for_date
-                .safe_sub(*index_with_precision)?
-                .safe_mul(u128::from(total_share))?
-                .safe_div(PRECISION)?,
-        )
-        .map_err(|_| MplxRewardsError::InvalidPrimitiveTypesConversion)?;
+        let rewards =
+            Self::calculate_rewards(*index_with_precision, vault_index_for_date, total_share)?;
 
         if rewards > 0 {
             *unclaimed_rewards = (*unclaimed_rewards).safe_add(rewards)?;
@@ -231,6 +733,21 @@ impl Mining {
 
         Ok(())
     }
+
+    /// Calculates rewards for the given weighted stake between two index values
+    pub fn calculate_rewards(
+        index_from: u128,
+        index_to: u128,
+        weighted_stake: u64,
+    ) -> Result<u64, ProgramError> {
+        Ok(u64::try_from(
+            index_to
+                .safe_sub(index_from)?
+                .safe_mul(u128::from(weighted_stake))?
+                .safe_div(PRECISION)?,
+        )
+        .map_err(|_| MplxRewardsError::InvalidPrimitiveTypesConversion)?)
+    }
 }
 
 impl IsInitialized for Mining {
@@ -241,16 +758,29 @@ impl IsInitialized for Mining {
 
 impl<'a> WrappedImmutableMining<'a> {
     pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
-        let (mining, weighted_stake_diffs) = bytes.split_at(Mining::LEN);
+        WrappedMining::assert_len(bytes)?;
+
+        let (mining, trees) = bytes.split_at(Mining::LEN);
+        let (weighted_stake_diffs, trees) =
+            trees.split_at(std::mem::size_of::<MiningWeightedStakeDiffs>());
+        let (positions, delegated_stakes) = trees.split_at(std::mem::size_of::<MiningPositions>());
         let mining =
             Mining::load_bytes(mining).ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        mining.assert_account_type()?;
+        mining.assert_version()?;
 
         let weighted_stake_diffs = MiningWeightedStakeDiffs::load_bytes(weighted_stake_diffs)
             .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        let positions = MiningPositions::load_bytes(positions)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        let delegated_stakes = MiningDelegatedStakes::load_bytes(delegated_stakes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
 
         Ok(Self {
             mining,
             weighted_stake_diffs,
+            positions,
+            delegated_stakes,
         })
     }
 }
@@ -270,7 +800,10 @@ mod test {
     #[test]
     fn test_can_deserialize_wrapped_immutable_mining_from_bytes_initialized_with_wrapped_mining() {
         let mut bytes = vec![0; super::WrappedMining::LEN];
-        let wrapped_mining = super::WrappedMining::from_bytes_mut(&mut bytes).unwrap();
+        let wrapped_mining =
+            super::WrappedMining::from_uninitialized_bytes_mut(&mut bytes).unwrap();
+        *wrapped_mining.mining =
+            super::Mining::initialize(Pubkey::default(), Pubkey::default(), Pubkey::default(), 0);
         let reward_pool = solana_program::pubkey::Pubkey::new_unique();
         let mining_owner = solana_program::pubkey::Pubkey::new_unique();
         let reward_mint = solana_program::pubkey::Pubkey::new_unique();
@@ -307,6 +840,30 @@ mod test {
         assert_eq!(wrapped_immutable_mining.mining.bump, bump);
     }
 
+    #[test]
+    fn position_is_weighted_by_flex_after_expiration() {
+        let position = Position::new(100, LockupPeriod::SixMonths, 0, 86400).unwrap();
+
+        assert_eq!(position.weighted_amount(50, 0).unwrap(), 50 * 4);
+        assert_eq!(
+            position
+                .weighted_amount(50, position.stake_expiration_date)
+                .unwrap(),
+            50
+        );
+    }
+
+    #[test]
+    fn delegated_stakes_are_taken_proportionally() {
+        let mut delegated_stakes = DelegatedStakes::new(&[33, 33, 34]).unwrap();
+
+        assert_eq!(delegated_stakes.take(50).unwrap(), vec![16, 17, 17]);
+        assert_eq!(delegated_stakes.stakes(), &[17, 16, 17]);
+
+        assert_eq!(delegated_stakes.take(50).unwrap(), vec![17, 16, 17]);
+        assert_eq!(delegated_stakes.stakes(), &[0, 0, 0]);
+    }
+
     #[test]
     fn slighly_decrease_rewards() {
         let mut wrapped_mining = super::WrappedMining {
@@ -315,6 +872,8 @@ mod test {
                 ..Default::default()
             },
             weighted_stake_diffs: &mut Default::default(),
+            positions: &mut Default::default(),
+            delegated_stakes: &mut Default::default(),
         };
         // three stakes:
         // - 500 x4 (six months)
@@ -338,6 +897,8 @@ mod test {
                 ..Default::default()
             },
             weighted_stake_diffs: &mut Default::default(),
+            positions: &mut Default::default(),
+            delegated_stakes: &mut Default::default(),
         };
         // three stakes:
         // - 500 x4 (six months)
@@ -361,6 +922,8 @@ mod test {
                 ..Default::default()
             },
             weighted_stake_diffs: &mut Default::default(),
+            positions: &mut Default::default(),
+            delegated_stakes: &mut Default::default(),
         };
         // three stakes:
         // - 500 x4 (six months)
@@ -1,24 +1,49 @@
 //! State types
 
+mod boost_record;
+mod delegate_registry;
 mod mining;
+mod penalty_history;
 mod reward_pool;
 
+use crate::{error::MplxRewardsError, utils::SafeArithmeticOperations};
+pub use boost_record::*;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use bytemuck::Pod;
+pub use delegate_registry::*;
 pub use mining::*;
+pub use penalty_history::*;
 pub use reward_pool::*;
-use sokoban::{RedBlackTree, SENTINEL};
+use sokoban::{NodeAllocatorMap, RedBlackTree, SENTINEL};
+use solana_program::clock::SECONDS_PER_DAY;
 use std::fmt::Debug;
 
 pub const MINING_MODIFIERS_TREE_CAPACITY: usize = 50;
 pub const POOL_MODIFIERS_TREE_CAPACITY: usize = 365;
+/// Flex deposits don't schedule modifiers, so a mining keeps more positions than modifiers
+pub const MINING_POSITIONS_CAPACITY: usize = 64;
+/// Only the deposits split between several delegates record their delegated stakes,
+/// the capacity keeps the mining within the size an account can be created with
+pub const MINING_DELEGATED_STAKES_CAPACITY: usize = 32;
+/// Number of distributions the pool keeps the index of. Minings may refer any of them,
+/// so the history isn't pruned and the pool stops distributing once it's full
 pub const INDEX_HISTORY_MAX_SIZE: usize = 1095;
+/// The shortest epoch the index history still covers the longest lockup (one year) with
+pub const MIN_EPOCH_LENGTH_SECS: u64 = 365 * SECONDS_PER_DAY / INDEX_HISTORY_MAX_SIZE as u64;
 /// Precision for index calculation
 pub const PRECISION: u128 = 10_000_000_000_000_000;
+/// Denominator of the values expressed in basis points
+pub const BASIS_POINTS: u64 = 10_000;
 
 pub type CumulativeIndex = RedBlackTree<u64, u128, INDEX_HISTORY_MAX_SIZE>;
 pub type PoolWeightedStakeDiffs = RedBlackTree<u64, u64, POOL_MODIFIERS_TREE_CAPACITY>;
 pub type MiningWeightedStakeDiffs = RedBlackTree<u64, u64, MINING_MODIFIERS_TREE_CAPACITY>;
+pub type MiningPositions = RedBlackTree<u64, Position, MINING_POSITIONS_CAPACITY>;
+pub type MiningDelegatedStakes =
+    RedBlackTree<u64, DelegatedStakes, MINING_DELEGATED_STAKES_CAPACITY>;
+
+/// Index of the account version within the `data` field of the account header
+pub const ACCOUNT_VERSION_BYTE: usize = 1;
 
 /// Enum representing the account type managed by the program
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
@@ -30,15 +55,26 @@ pub enum AccountType {
     RewardPool,
     /// Mining Account
     Mining,
+    /// Penalty history of the mining account
+    PenaltyHistory,
+    /// Registry of the delegate mining account
+    DelegateRegistry,
+    /// Record of the boost proven with the NFT
+    BoostRecord,
 }
 
-impl From<u8> for AccountType {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for AccountType {
+    type Error = MplxRewardsError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => AccountType::Uninitialized,
-            1 => AccountType::RewardPool,
-            2 => AccountType::Mining,
-            _ => panic!("invalid AccountType value: {value}"),
+            0 => Ok(AccountType::Uninitialized),
+            1 => Ok(AccountType::RewardPool),
+            2 => Ok(AccountType::Mining),
+            3 => Ok(AccountType::PenaltyHistory),
+            4 => Ok(AccountType::DelegateRegistry),
+            5 => Ok(AccountType::BoostRecord),
+            _ => Err(MplxRewardsError::InvalidAccountType),
         }
     }
 }
@@ -49,10 +85,100 @@ impl From<AccountType> for u8 {
             AccountType::Uninitialized => 0,
             AccountType::RewardPool => 1,
             AccountType::Mining => 2,
+            AccountType::PenaltyHistory => 3,
+            AccountType::DelegateRegistry => 4,
+            AccountType::BoostRecord => 5,
         }
     }
 }
 
+/// Checks the account is stored in the current layout.
+/// Uninitialized accounts are always created with the current layout.
+fn assert_account_version(
+    account_type: u8,
+    version: u8,
+    current_version: u8,
+) -> Result<(), MplxRewardsError> {
+    if account_type == u8::from(AccountType::Uninitialized) || version == current_version {
+        return Ok(());
+    }
+
+    if version < current_version {
+        Err(MplxRewardsError::AccountNeedsMigration)
+    } else {
+        Err(MplxRewardsError::UnsupportedAccountVersion)
+    }
+}
+
+/// Checks the account holds an initialized account of the expected type
+fn assert_account_type(account_type: u8, expected: AccountType) -> Result<(), MplxRewardsError> {
+    match AccountType::try_from(account_type)? {
+        AccountType::Uninitialized => Err(MplxRewardsError::AccountIsNotInitialized),
+        account_type if account_type == expected => Ok(()),
+        _ => Err(MplxRewardsError::InvalidAccountType),
+    }
+}
+
+/// Checks the account hasn't been initialized with any of the account types yet
+fn assert_uninitialized(account_type: u8) -> Result<(), MplxRewardsError> {
+    if account_type != u8::from(AccountType::Uninitialized) {
+        return Err(MplxRewardsError::AlreadyInitialized);
+    }
+
+    Ok(())
+}
+
+/// Checks the account data has exactly the length of the account layout
+fn assert_data_len(len: usize, expected: usize) -> Result<(), MplxRewardsError> {
+    if len != expected {
+        return Err(MplxRewardsError::InvalidAccountLength);
+    }
+
+    Ok(())
+}
+
+/// Reads the header stored in the outdated layout of the given length.
+/// Layouts have only been extended by appending fields to the header,
+/// so the fields missing in the outdated layout are zeroed.
+fn read_outdated_header<H: Pod>(bytes: &[u8], header_len: usize) -> H {
+    let mut header = vec![0; std::mem::size_of::<H>()];
+    header[..header_len].copy_from_slice(&bytes[..header_len]);
+
+    bytemuck::pod_read_unaligned(&header)
+}
+
+/// Writes the header of the current layout and moves the data that followed
+/// the outdated header right after it. The account must be already reallocated.
+pub fn rewrite_layout<H: Pod>(bytes: &mut [u8], outdated_header_len: usize, header: &H) {
+    let header_len = std::mem::size_of::<H>();
+    let tail_len = bytes.len() - header_len;
+
+    bytes.copy_within(
+        outdated_header_len..outdated_header_len + tail_len,
+        header_len,
+    );
+    bytes[..header_len].copy_from_slice(bytemuck::bytes_of(header));
+}
+
+/// Adds the diff to the weighted stake modifier at the given date,
+/// creating the modifier if there's none yet.
+fn increase_weighted_stake_diff<const CAP: usize>(
+    weighted_stake_diffs: &mut RedBlackTree<u64, u64, CAP>,
+    date: u64,
+    diff: u64,
+) -> Result<(), MplxRewardsError> {
+    match weighted_stake_diffs.get_mut(&date) {
+        Some(modifier) => *modifier = modifier.safe_add(diff)?,
+        None => {
+            weighted_stake_diffs
+                .insert(date, diff)
+                .ok_or(MplxRewardsError::WeightedStakeDiffsAreFull)?;
+        }
+    }
+
+    Ok(())
+}
+
 fn find_max_value_limited_by_key<
     K: Ord + Default + Pod + Debug,
     V: Default + Pod,
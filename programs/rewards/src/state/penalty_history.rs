@@ -0,0 +1,328 @@
+use crate::{
+    error::MplxRewardsError,
+    state::{assert_account_type, assert_data_len, assert_uninitialized, AccountType},
+};
+use bytemuck::{Pod, Zeroable};
+use shank::ShankAccount;
+use sokoban::ZeroCopy;
+use solana_program::{program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey};
+
+/// Number of the latest penalties kept in the history.
+/// Older records are overwritten by the new ones.
+pub const PENALTY_HISTORY_CAPACITY: usize = 32;
+
+/// Kind of the penalty applied to the mining account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PenaltyKind {
+    /// Tokens have been slashed on the staking contract
+    Slash,
+    /// Weighted stake has been decreased without touching staked tokens
+    DecreaseRewards,
+}
+
+impl From<PenaltyKind> for u8 {
+    fn from(value: PenaltyKind) -> Self {
+        match value {
+            PenaltyKind::Slash => 1,
+            PenaltyKind::DecreaseRewards => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for PenaltyKind {
+    type Error = MplxRewardsError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(PenaltyKind::Slash),
+            2 => Ok(PenaltyKind::DecreaseRewards),
+            _ => Err(MplxRewardsError::InvalidPenaltyKind),
+        }
+    }
+}
+
+/// Maximum number of weighted stake modifiers a single penalty record can keep
+pub const PENALTY_STAKE_DIFFS_CAPACITY: usize = 8;
+
+/// The record has been restored with the RestorePenalty instruction
+pub const PENALTY_FLAG_RESTORED: u8 = 1;
+/// The penalty has touched more weighted stake modifiers than the record can keep,
+/// so it cannot be reversed precisely
+pub const PENALTY_FLAG_NOT_RESTORABLE: u8 = 1 << 1;
+/// The slash has been applied to the deposit referred by `deposit_id`
+pub const PENALTY_FLAG_DEPOSIT: u8 = 1 << 2;
+
+/// Weighted stake modifier removed by the penalty
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, Pod, Zeroable, PartialEq, Eq)]
+pub struct PenaltyStakeDiff {
+    /// The date the modifier had been scheduled for
+    pub date: u64,
+    /// The value by which the modifier has been decreased
+    pub diff: u64,
+}
+
+/// Single entry of the penalty history
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, Pod, Zeroable, PartialEq, Eq)]
+pub struct PenaltyRecord {
+    /// Pool's index at the moment of the penalty.
+    /// Used to calculate rewards lost because of the penalty.
+    pub index_with_precision: u128,
+    /// Sequence number of the penalty within the mining account, starts from zero.
+    pub id: u64,
+    /// Unix timestamp when the penalty has been applied.
+    pub timestamp: u64,
+    /// Number of tokens that have been slashed. Zero for the rewards decrease.
+    pub native_amount: u64,
+    /// The number by which weighted stake has been decreased.
+    pub weighted_amount: u64,
+    /// Reason code supplied by the deposit authority. Isn't interpreted by the program.
+    pub reason_code: u32,
+    /// Kind of the penalty, see [`PenaltyKind`]
+    pub kind: u8,
+    /// Bit flags, see PENALTY_FLAG_* constants
+    pub flags: u8,
+    /// Number of used entries in `stake_diffs`
+    pub stake_diffs_len: u8,
+    pub padding: [u8; 1],
+    /// Id of the slashed deposit, set along with PENALTY_FLAG_DEPOSIT.
+    /// Slashes recorded before positions have been introduced don't refer any deposit.
+    pub deposit_id: u64,
+    /// Weighted stake modifiers decreased by the penalty, ordered by date.
+    /// Together with `weighted_amount` they describe how the lost
+    /// weighted stake would have changed in time.
+    pub stake_diffs: [PenaltyStakeDiff; PENALTY_STAKE_DIFFS_CAPACITY],
+}
+
+impl PenaltyRecord {
+    pub fn kind(&self) -> Result<PenaltyKind, MplxRewardsError> {
+        PenaltyKind::try_from(self.kind)
+    }
+
+    pub fn is_restored(&self) -> bool {
+        self.flags & PENALTY_FLAG_RESTORED != 0
+    }
+
+    pub fn is_restorable(&self) -> bool {
+        self.flags & PENALTY_FLAG_NOT_RESTORABLE == 0
+    }
+
+    /// Returns the id of the deposit the slash has been applied to
+    pub fn deposit_id(&self) -> Option<u64> {
+        (self.flags & PENALTY_FLAG_DEPOSIT != 0).then_some(self.deposit_id)
+    }
+
+    pub fn stake_diffs(&self) -> &[PenaltyStakeDiff] {
+        &self.stake_diffs[..self.stake_diffs_len as usize]
+    }
+
+    /// Stores modifiers decreased by the penalty. If there are too many of them,
+    /// the record is marked as not restorable.
+    pub fn set_stake_diffs(&mut self, stake_diffs: &[PenaltyStakeDiff]) {
+        let mut stake_diffs = stake_diffs.to_vec();
+        stake_diffs.sort_by_key(|d| d.date);
+
+        if stake_diffs.len() > PENALTY_STAKE_DIFFS_CAPACITY {
+            self.flags |= PENALTY_FLAG_NOT_RESTORABLE;
+            stake_diffs.truncate(PENALTY_STAKE_DIFFS_CAPACITY);
+        }
+
+        self.stake_diffs[..stake_diffs.len()].copy_from_slice(&stake_diffs);
+        self.stake_diffs_len = stake_diffs.len() as u8;
+    }
+}
+
+/// Bounded ring buffer of the penalties applied to the mining account.
+/// It's a companion PDA of the mining account which exists only
+/// to make penalties transparent for the end users.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable, ShankAccount)]
+pub struct PenaltyHistory {
+    /// The address of the mining account penalties belong to.
+    pub mining: Pubkey,
+    /// Total number of penalties ever recorded. Also it's the id of the next record.
+    pub records_count: u64,
+    /// Bump of the penalty history account
+    pub bump: u8,
+    /// Account type - PenaltyHistory. This discriminator should exist in order to prevent
+    /// shenanigans with customly modified accounts and their fields.
+    /// 0: account type
+    /// 1-6: unused
+    pub data: [u8; 7],
+    /// Records are stored in the order of appending, the oldest one is
+    /// overwritten once the buffer is full.
+    pub records: [PenaltyRecord; 32],
+}
+
+impl ZeroCopy for PenaltyHistory {}
+
+// Shank only extracts literal array lengths, so the account spells the capacity out.
+// The ascription stops the build once the two diverge.
+const _: fn(&PenaltyHistory) -> &[PenaltyRecord; PENALTY_HISTORY_CAPACITY] =
+    |history| &history.records;
+
+impl PenaltyHistory {
+    pub const LEN: usize = std::mem::size_of::<PenaltyHistory>();
+
+    /// Initialize a Penalty History
+    pub fn initialize(mining: Pubkey, bump: u8) -> PenaltyHistory {
+        let mut data = [0; 7];
+        data[0] = AccountType::PenaltyHistory.into();
+
+        PenaltyHistory {
+            mining,
+            records_count: 0,
+            bump,
+            data,
+            records: [PenaltyRecord::default(); PENALTY_HISTORY_CAPACITY],
+        }
+    }
+
+    /// Loads the initialized account
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let account = PenaltyHistory::load_mut_bytes(bytes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        assert_account_type(account.data[0], AccountType::PenaltyHistory)?;
+
+        Ok(account)
+    }
+
+    /// Loads the initialized account
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let account = PenaltyHistory::load_bytes(bytes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        assert_account_type(account.data[0], AccountType::PenaltyHistory)?;
+
+        Ok(account)
+    }
+
+    /// Loads the account which is about to be initialized
+    pub fn from_uninitialized_bytes_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let account = PenaltyHistory::load_mut_bytes(bytes)
+            .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        assert_uninitialized(account.data[0])?;
+
+        Ok(account)
+    }
+
+    /// Appends the record to the history, overwriting the oldest one if the history is full.
+    /// Returns the id of the appended record.
+    pub fn append(&mut self, mut record: PenaltyRecord) -> u64 {
+        let id = self.records_count;
+        record.id = id;
+        self.records[(id % PENALTY_HISTORY_CAPACITY as u64) as usize] = record;
+        self.records_count += 1;
+
+        id
+    }
+
+    /// Returns the record with the given id if it's still kept in the history
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut PenaltyRecord> {
+        if id >= self.records_count || self.records_count - id > PENALTY_HISTORY_CAPACITY as u64 {
+            return None;
+        }
+
+        Some(&mut self.records[(id % PENALTY_HISTORY_CAPACITY as u64) as usize])
+    }
+
+    /// Returns stored records starting from the oldest one
+    pub fn records(&self) -> impl Iterator<Item = &PenaltyRecord> {
+        let stored = self.records_count.min(PENALTY_HISTORY_CAPACITY as u64);
+        let first_id = self.records_count - stored;
+
+        (first_id..self.records_count)
+            .map(|id| &self.records[(id % PENALTY_HISTORY_CAPACITY as u64) as usize])
+    }
+}
+
+impl IsInitialized for PenaltyHistory {
+    fn is_initialized(&self) -> bool {
+        self.data[0] == <u8>::from(AccountType::PenaltyHistory)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        PenaltyHistory, PenaltyKind, PenaltyRecord, PenaltyStakeDiff, PENALTY_HISTORY_CAPACITY,
+        PENALTY_STAKE_DIFFS_CAPACITY,
+    };
+
+    fn record(kind: PenaltyKind, timestamp: u64, weighted_amount: u64) -> PenaltyRecord {
+        PenaltyRecord {
+            kind: kind.into(),
+            timestamp,
+            weighted_amount,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_penalty_history_keeps_the_latest_records() {
+        let mut history = PenaltyHistory::initialize(Default::default(), 255);
+        assert_eq!(history.records().count(), 0);
+
+        history.append(PenaltyRecord {
+            native_amount: 100,
+            reason_code: 7,
+            ..record(PenaltyKind::Slash, 10, 400)
+        });
+        history.append(record(PenaltyKind::DecreaseRewards, 20, 50));
+
+        let records: Vec<_> = history.records().collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, 0);
+        assert_eq!(records[0].kind(), Ok(PenaltyKind::Slash));
+        assert_eq!(records[0].native_amount, 100);
+        assert_eq!(records[0].weighted_amount, 400);
+        assert_eq!(records[0].reason_code, 7);
+        assert_eq!(records[1].id, 1);
+        assert_eq!(records[1].kind(), Ok(PenaltyKind::DecreaseRewards));
+        assert_eq!(records[1].timestamp, 20);
+    }
+
+    #[test]
+    fn test_penalty_history_overwrites_the_oldest_records() {
+        let mut history = PenaltyHistory::initialize(Default::default(), 255);
+        let total = PENALTY_HISTORY_CAPACITY as u64 + 5;
+        for i in 0..total {
+            history.append(record(PenaltyKind::Slash, i, i));
+        }
+
+        let ids: Vec<_> = history.records().map(|r| r.id).collect();
+        assert_eq!(ids.len(), PENALTY_HISTORY_CAPACITY);
+        assert_eq!(ids.first(), Some(&5));
+        assert_eq!(ids.last(), Some(&(total - 1)));
+        assert_eq!(history.records_count, total);
+
+        assert!(history.get_mut(4).is_none());
+        assert_eq!(history.get_mut(5).unwrap().timestamp, 5);
+        assert!(history.get_mut(total).is_none());
+    }
+
+    #[test]
+    fn test_too_many_stake_diffs_make_record_not_restorable() {
+        let mut penalty = record(PenaltyKind::DecreaseRewards, 0, 100);
+        let diffs: Vec<_> = (0..PENALTY_STAKE_DIFFS_CAPACITY as u64)
+            .rev()
+            .map(|date| PenaltyStakeDiff { date, diff: 1 })
+            .collect();
+        penalty.set_stake_diffs(&diffs);
+        assert!(penalty.is_restorable());
+        assert_eq!(penalty.stake_diffs().first().unwrap().date, 0);
+
+        let diffs: Vec<_> = (0..=PENALTY_STAKE_DIFFS_CAPACITY as u64)
+            .map(|date| PenaltyStakeDiff { date, diff: 1 })
+            .collect();
+        penalty.set_stake_diffs(&diffs);
+        assert!(!penalty.is_restorable());
+        assert_eq!(penalty.stake_diffs().len(), PENALTY_STAKE_DIFFS_CAPACITY);
+    }
+}
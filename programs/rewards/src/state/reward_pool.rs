@@ -1,23 +1,23 @@
 use crate::{
+    asserts::assert_account_key,
     error::MplxRewardsError,
     state::AccountType,
-    utils::{get_curr_unix_ts, LockupPeriod, SafeArithmeticOperations},
+    utils::{split_by_weights, DelegateAccounts, LockupPeriod, SafeArithmeticOperations},
 };
+use borsh::{BorshDeserialize, BorshSerialize};
 use bytemuck::{Pod, Zeroable};
 use shank::ShankAccount;
-use sokoban::{NodeAllocatorMap, ZeroCopy};
+use sokoban::{NodeAllocatorMap, RedBlackTree, ZeroCopy};
 use solana_program::{
-    account_info::AccountInfo,
-    clock::{Clock, SECONDS_PER_DAY},
-    entrypoint::ProgramResult,
-    program_error::ProgramError,
-    program_pack::IsInitialized,
-    pubkey::Pubkey,
-    sysvar::Sysvar,
+    account_info::AccountInfo, clock::SECONDS_PER_DAY, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey,
 };
 
 use super::{
-    CumulativeIndex, MiningWeightedStakeDiffs, PoolWeightedStakeDiffs, WrappedMining, PRECISION,
+    assert_account_type, assert_account_version, assert_data_len, assert_uninitialized,
+    find_max_value_limited_by_key, increase_weighted_stake_diff, read_outdated_header,
+    CumulativeIndex, DelegateRegistry, Mining, PenaltyKind, PenaltyRecord, PenaltyStakeDiff,
+    PoolWeightedStakeDiffs, Position, WrappedMining, ACCOUNT_VERSION_BYTE, BASIS_POINTS, PRECISION,
 };
 
 pub struct WrappedRewardPool<'a> {
@@ -44,12 +44,16 @@ pub struct WrappedImmutableRewardPool<'a> {
 
 impl<'a> WrappedImmutableRewardPool<'a> {
     pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        WrappedRewardPool::assert_len(bytes)?;
+
         let (pool, trees) = bytes.split_at(RewardPool::LEN);
         let (weighted_stake_diffs, cumulative_index) =
             trees.split_at(std::mem::size_of::<PoolWeightedStakeDiffs>());
 
         let pool = RewardPool::load_bytes(pool)
             .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
+        pool.assert_account_type()?;
+        pool.assert_version()?;
 
         let weighted_stake_diffs = PoolWeightedStakeDiffs::load_bytes(weighted_stake_diffs)
             .ok_or(MplxRewardsError::RetreivingZeroCopyAccountFailire)?;
@@ -66,9 +70,32 @@ impl<'a> WrappedImmutableRewardPool<'a> {
 }
 
 impl<'a> WrappedRewardPool<'a> {
-    pub const LEN: usize = 64480;
+    pub const LEN: usize = RewardPool::LEN
+        + std::mem::size_of::<PoolWeightedStakeDiffs>()
+        + std::mem::size_of::<CumulativeIndex>();
 
+    /// Loads the initialized pool stored in the current layout
     pub fn from_bytes_mut(bytes: &'a mut [u8]) -> Result<Self, ProgramError> {
+        Self::assert_len(bytes)?;
+
+        let wrapped_pool = Self::load_mut(bytes)?;
+        wrapped_pool.pool.assert_account_type()?;
+        wrapped_pool.pool.assert_version()?;
+
+        Ok(wrapped_pool)
+    }
+
+    /// Loads the account the pool is about to be initialized in
+    pub fn from_uninitialized_bytes_mut(bytes: &'a mut [u8]) -> Result<Self, ProgramError> {
+        assert_data_len(bytes.len(), Self::LEN)?;
+
+        let wrapped_pool = Self::load_mut(bytes)?;
+        assert_uninitialized(wrapped_pool.pool.data[0])?;
+
+        Ok(wrapped_pool)
+    }
+
+    fn load_mut(bytes: &'a mut [u8]) -> Result<Self, ProgramError> {
         let (pool, trees) = bytes.split_at_mut(RewardPool::LEN);
         let (weighted_stake_diffs, cumulative_index) =
             trees.split_at_mut(std::mem::size_of::<PoolWeightedStakeDiffs>());
@@ -89,15 +116,54 @@ impl<'a> WrappedRewardPool<'a> {
         })
     }
 
+    /// Checks the account has the length of the current layout.
+    /// Accounts stored in the outdated layouts are shorter and need the migration.
+    fn assert_len(bytes: &[u8]) -> Result<(), MplxRewardsError> {
+        if bytes.len() == Self::LEN {
+            return Ok(());
+        }
+
+        match Self::outdated_header(bytes) {
+            Ok(Some(_)) => Err(MplxRewardsError::AccountNeedsMigration),
+            _ => Err(MplxRewardsError::InvalidAccountLength),
+        }
+    }
+
+    /// Returns the header of the pool stored in the outdated layout along with
+    /// the header length of that layout. None means the pool has the current layout.
+    pub fn outdated_header(bytes: &[u8]) -> Result<Option<(RewardPool, usize)>, ProgramError> {
+        let header_len = bytes
+            .len()
+            .checked_sub(Self::LEN - RewardPool::LEN)
+            .filter(|header_len| *header_len <= RewardPool::LEN)
+            .ok_or(MplxRewardsError::UnsupportedAccountVersion)?;
+
+        let pool: RewardPool = read_outdated_header(bytes, header_len);
+        if !pool.is_initialized() {
+            return Err(MplxRewardsError::UnsupportedAccountVersion.into());
+        }
+
+        match pool.version() {
+            RewardPool::VERSION if header_len == RewardPool::LEN => Ok(None),
+            0 if RewardPool::UNVERSIONED_HEADER_LENS.contains(&header_len) => {
+                Ok(Some((pool, header_len)))
+            }
+            version if RewardPool::VERSIONED_HEADER_LENS.contains(&(version, header_len)) => {
+                Ok(Some((pool, header_len)))
+            }
+            _ => Err(MplxRewardsError::UnsupportedAccountVersion.into()),
+        }
+    }
+
     /// Consuming old total share modifiers in order to change the total share for the current date
     pub fn consume_old_modifiers(
         &mut self,
-        beginning_of_the_day: u64,
+        beginning_of_the_epoch: u64,
         mut total_share: u64,
     ) -> Result<u64, ProgramError> {
         let mut processed_dates = vec![];
         for (date_to_process, modifier) in self.weighted_stake_diffs.iter() {
-            if date_to_process > &beginning_of_the_day {
+            if date_to_process > &beginning_of_the_epoch {
                 break;
             }
 
@@ -124,24 +190,26 @@ impl<'a> WrappedRewardPool<'a> {
 
         let latest_index = index_with_precision.safe_add(index)?;
 
-        cumulative_index.insert(date_to_process, latest_index);
+        cumulative_index
+            .insert(date_to_process, latest_index)
+            .ok_or(MplxRewardsError::IndexHistoryIsFull)?;
         *index_with_precision = latest_index;
 
         Ok(())
     }
 
     /// Distributes rewards via calculating indexes and weighted stakes
-    pub fn distribute(&mut self, rewards: u64) -> ProgramResult {
+    pub fn distribute(&mut self, rewards: u64, curr_ts: u64) -> ProgramResult {
+        self.pool.assert_not_sunset()?;
         if self.pool.total_share == 0 {
             return Err(MplxRewardsError::RewardsNoDeposits.into());
         }
 
-        let curr_ts = Clock::get().unwrap().unix_timestamp as u64;
-        let beginning_of_the_day = curr_ts - (curr_ts % SECONDS_PER_DAY);
+        let beginning_of_the_epoch = self.pool.beginning_of_the_epoch(curr_ts);
 
         self.pool.total_share =
-            self.consume_old_modifiers(beginning_of_the_day, self.pool.total_share)?;
-        if self.cumulative_index.contains(&beginning_of_the_day) {
+            self.consume_old_modifiers(beginning_of_the_epoch, self.pool.total_share)?;
+        if self.cumulative_index.contains(&beginning_of_the_epoch) {
             return Ok(());
         }
 
@@ -150,62 +218,185 @@ impl<'a> WrappedRewardPool<'a> {
             &mut self.pool.index_with_precision,
             rewards,
             self.pool.total_share,
-            beginning_of_the_day,
+            beginning_of_the_epoch,
         )?;
 
         self.pool.tokens_available_for_distribution = self
             .pool
             .tokens_available_for_distribution
             .safe_sub(rewards)?;
+        self.pool.total_rewards_indexed = self.pool.total_rewards_indexed.safe_add(rewards)?;
 
         Ok(())
     }
 
+    /// Takes all the rewards the mining has earned so far. Returns the claimed amount.
+    pub fn claim(&mut self, mining: &mut WrappedMining, curr_ts: u64) -> Result<u64, ProgramError> {
+        if self.pool.is_sunset_grace_period_elapsed(curr_ts)? {
+            return Err(MplxRewardsError::SunsetGracePeriodElapsed.into());
+        }
+
+        mining.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
+        let amount = mining.mining.unclaimed_rewards;
+        mining.mining.claim();
+        self.pool.total_rewards_claimed = self.pool.total_rewards_claimed.safe_add(amount)?;
+
+        Ok(amount)
+    }
+
+    /// Moves the whole stake delegated by the deposit from the old delegates to the new ones,
+    /// which get the staked amount split by their weights
     pub fn change_delegate(
         &mut self,
         mining: &mut WrappedMining,
-        new_delegate_mining: Option<&AccountInfo>,
-        old_delegate_mining: Option<&AccountInfo>,
+        deposit_id: u64,
+        new_delegates: &[DelegateAccounts],
+        old_delegates: &[DelegateAccounts],
         staked_amount: u64,
+        curr_ts: u64,
+    ) -> ProgramResult {
+        mining.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
+
+        // the deposit must be applied to the mining
+        mining.position(deposit_id)?;
+        let old_stakes =
+            mining.undelegate(deposit_id, staked_amount, &delegate_weights(old_delegates))?;
+        let new_stakes = split_by_weights(staked_amount, &delegate_weights(new_delegates))?;
+        mining.record_delegated_stakes(deposit_id, &new_stakes)?;
+
+        self.remove_delegated_stake(old_delegates, &old_stakes, curr_ts)?;
+        self.add_delegated_stake(&mining.mining.owner, new_delegates, &new_stakes, curr_ts)
+    }
+
+    /// Delegates the stake of the mining owner, giving each delegate its part of the stake.
+    /// Only the Flex part of the stake is delegated, which is the part that stays
+    /// after the lockup expiration, so neither the delegates nor the pool need modifiers for it.
+    fn add_delegated_stake(
+        &mut self,
+        mining_owner: &Pubkey,
+        delegates: &[DelegateAccounts],
+        stakes: &[u64],
+        curr_ts: u64,
     ) -> ProgramResult {
-        mining.refresh_rewards(self.cumulative_index)?;
+        for (delegate, part) in delegates.iter().zip(stakes.iter().copied()) {
+            let registry = delegate
+                .registry
+                .ok_or(MplxRewardsError::DelegateIsNotRegistered)?;
+            let delegate_mining_data = &mut delegate.mining.try_borrow_mut_data()?;
+            let mut delegate_mining = WrappedMining::from_bytes_mut(delegate_mining_data)?;
+            // rewards for the stake the delegate has had so far
+            delegate_mining.refresh_rewards(
+                self.cumulative_index,
+                self.pool.epoch_length(),
+                curr_ts,
+            )?;
 
-        if let Some(old_delegate_info) = old_delegate_mining {
-            let old_delegate_mining_data = &mut old_delegate_info.data.borrow_mut();
-            let mut old_delegate_mining = WrappedMining::from_bytes_mut(old_delegate_mining_data)?;
+            delegate_mining.mining.stake_from_others =
+                delegate_mining.mining.stake_from_others.safe_add(part)?;
+            DelegateRegistry::from_bytes(&registry.try_borrow_data()?)?.assert_accepts(
+                delegate.mining.key,
+                mining_owner,
+                delegate_mining.mining.stake_from_others,
+            )?;
 
-            old_delegate_mining.mining.stake_from_others = old_delegate_mining
-                .mining
-                .stake_from_others
-                .safe_sub(staked_amount)?;
-            self.pool.total_share = self.pool.total_share.safe_sub(staked_amount)?;
-            old_delegate_mining.refresh_rewards(self.cumulative_index)?;
+            self.pool.total_share = self.pool.total_share.safe_add(part)?;
         }
 
-        if let Some(new_delegate_info) = new_delegate_mining {
-            let new_delegate_mining_data = &mut new_delegate_info.data.borrow_mut();
-            let mut new_delegate_mining = WrappedMining::from_bytes_mut(new_delegate_mining_data)?;
+        Ok(())
+    }
 
-            new_delegate_mining.mining.stake_from_others = new_delegate_mining
-                .mining
-                .stake_from_others
-                .safe_add(staked_amount)?;
-            self.pool.total_share = self.pool.total_share.safe_add(staked_amount)?;
-            new_delegate_mining.refresh_rewards(self.cumulative_index)?;
+    /// Takes the delegated stake back from delegates, each one giving back its part of the stake
+    fn remove_delegated_stake(
+        &mut self,
+        delegates: &[DelegateAccounts],
+        stakes: &[u64],
+        curr_ts: u64,
+    ) -> ProgramResult {
+        for (delegate, part) in delegates.iter().zip(stakes.iter().copied()) {
+            let delegate_mining_data = &mut delegate.mining.try_borrow_mut_data()?;
+            let mut delegate_mining = WrappedMining::from_bytes_mut(delegate_mining_data)?;
+            delegate_mining.refresh_rewards(
+                self.cumulative_index,
+                self.pool.epoch_length(),
+                curr_ts,
+            )?;
+
+            delegate_mining.mining.stake_from_others =
+                delegate_mining.mining.stake_from_others.safe_sub(part)?;
+
+            self.pool.total_share = self.pool.total_share.safe_sub(part)?;
         }
 
         Ok(())
     }
 
-    /// Process deposit
+    /// Process deposit. The deposit is recorded under the given id,
+    /// so the same deposit cannot be applied twice.
+    #[allow(clippy::too_many_arguments)]
     pub fn deposit(
         &mut self,
         mining: &mut WrappedMining,
+        deposit_id: u64,
         amount: u64,
         lockup_period: LockupPeriod,
-        delegate_mining: Option<&AccountInfo>,
+        delegates: &[DelegateAccounts],
+        curr_ts: u64,
     ) -> ProgramResult {
-        mining.refresh_rewards(self.cumulative_index)?;
+        self.pool.assert_not_sunset()?;
+        self.pool.assert_deposit_amount(amount)?;
+        let (position, delegated_stakes) =
+            self.add_stake(mining, amount, lockup_period, delegates, curr_ts)?;
+        mining.record_deposit(deposit_id, position)?;
+        mining.record_delegated_stakes(deposit_id, &delegated_stakes)?;
+        self.pool.assert_share_limits(mining.mining)
+    }
+
+    /// Records the deposit which has been applied to the mining
+    /// before positions were introduced. The stake itself isn't changed.
+    pub fn record_deposit(
+        &self,
+        mining: &mut WrappedMining,
+        deposit_id: u64,
+        amount: u64,
+        lockup_period: LockupPeriod,
+        deposit_start_ts: u64,
+        curr_ts: u64,
+    ) -> ProgramResult {
+        mining.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
+
+        let position = Position::new(
+            amount,
+            lockup_period,
+            deposit_start_ts,
+            self.pool.epoch_length(),
+        )?;
+        // the modifier of the deposit which hasn't expired yet must be scheduled
+        if position.stake_expiration_date > self.pool.beginning_of_the_epoch(curr_ts)
+            && mining
+                .weighted_stake_diffs
+                .get(&position.stake_expiration_date)
+                .is_none()
+        {
+            return Err(MplxRewardsError::NoWeightedStakeModifiersAtADate.into());
+        }
+
+        mining.record_deposit(deposit_id, position)?;
+
+        Ok(())
+    }
+
+    /// Adds the stake to the mining without checking the pool limits. The stake is delegated
+    /// split by the weights of the delegates. Returns the position of the added stake
+    /// along with the stake delegated to each of the delegates.
+    fn add_stake(
+        &mut self,
+        mining: &mut WrappedMining,
+        amount: u64,
+        lockup_period: LockupPeriod,
+        delegates: &[DelegateAccounts],
+        curr_ts: u64,
+    ) -> Result<(Position, Vec<u64>), ProgramError> {
+        mining.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
 
         // regular weighted stake which will be used in rewards distribution
         let weighted_stake = amount.safe_mul(lockup_period.multiplier())?;
@@ -218,208 +409,480 @@ impl<'a> WrappedRewardPool<'a> {
         self.pool.total_share = self.pool.total_share.safe_add(weighted_stake)?;
         mining.mining.share = mining.mining.share.safe_add(weighted_stake)?;
 
-        let stake_expiration_date = lockup_period.end_timestamp(get_curr_unix_ts())?;
+        let position = Position::new(amount, lockup_period, curr_ts, self.pool.epoch_length())?;
 
-        let modifier = if let Some(modifier) = self.weighted_stake_diffs.get(&stake_expiration_date)
-        {
-            *modifier
-        } else {
-            0
-        };
+        increase_weighted_stake_diff(
+            self.weighted_stake_diffs,
+            position.stake_expiration_date,
+            weighted_stake_diff,
+        )?;
+        increase_weighted_stake_diff(
+            mining.weighted_stake_diffs,
+            position.stake_expiration_date,
+            weighted_stake_diff,
+        )?;
 
-        self.weighted_stake_diffs.insert(
-            stake_expiration_date,
-            modifier.safe_add(weighted_stake_diff)?,
-        );
+        let delegated_stakes = split_by_weights(amount, &delegate_weights(delegates))?;
+        self.add_delegated_stake(&mining.mining.owner, delegates, &delegated_stakes, curr_ts)?;
 
-        if mining
-            .weighted_stake_diffs
-            .get(&stake_expiration_date)
-            .is_some()
+        Ok((position, delegated_stakes))
+    }
+
+    /// Process withdraw of the given number of tokens from the deposit.
+    /// Once the pool has the unbonding period, or the deposit has unbonding tokens,
+    /// only the unbonding tokens can be withdrawn.
+    pub fn withdraw(
+        &mut self,
+        mining: &mut WrappedMining,
+        deposit_id: u64,
+        amount: u64,
+        delegates: &[DelegateAccounts],
+        curr_ts: u64,
+    ) -> ProgramResult {
+        if self.pool.unbonding_period_secs > 0 || mining.position(deposit_id)?.unbonding_amount > 0
         {
-            let modifier = mining
-                .weighted_stake_diffs
-                .get_mut(&stake_expiration_date)
-                .unwrap();
-            *modifier = modifier.safe_add(weighted_stake_diff)?;
-        } else {
-            mining
-                .weighted_stake_diffs
-                .insert(stake_expiration_date, weighted_stake_diff);
+            return self.withdraw_unbonded(mining, deposit_id, amount, delegates, curr_ts);
         }
 
-        if let Some(delegate_mining_acc) = delegate_mining {
-            let delegate_mining_data = &mut delegate_mining_acc.data.borrow_mut();
-            let mut delegate_mining = WrappedMining::from_bytes_mut(delegate_mining_data)?;
+        let delegated_stakes =
+            mining.undelegate(deposit_id, amount, &delegate_weights(delegates))?;
+        let position = mining.take_from_deposit(deposit_id, amount)?;
+        self.remove_stake(mining, &position, amount, curr_ts)?;
 
-            delegate_mining.mining.stake_from_others =
-                delegate_mining.mining.stake_from_others.safe_add(amount)?;
+        self.remove_delegated_stake(delegates, &delegated_stakes, curr_ts)
+    }
 
-            self.pool.total_share = self.pool.total_share.safe_add(amount)?;
-            delegate_mining.refresh_rewards(self.cumulative_index)?;
-        }
+    /// Starts the unbonding of the number of tokens of the deposit. The tokens lose
+    /// the weighted stake of their lockup straight away and get the unbonding multiplier
+    /// of the pool instead. They can be withdrawn once the unbonding period is over.
+    pub fn request_withdraw(
+        &mut self,
+        mining: &mut WrappedMining,
+        deposit_id: u64,
+        amount: u64,
+        curr_ts: u64,
+    ) -> ProgramResult {
+        let unbonding_stake = self.pool.unbonding_stake(amount)?;
+        let unbonding_ends_at = curr_ts.safe_add(self.pool.unbonding_period_secs)?;
+        let position = mining.unbond(deposit_id, amount, unbonding_stake, unbonding_ends_at)?;
+
+        // the delegated stake is kept until the tokens are withdrawn
+        self.remove_stake(mining, &position, amount, curr_ts)?;
+
+        mining.mining.share = mining.mining.share.safe_add(unbonding_stake)?;
+        self.pool.total_share = self.pool.total_share.safe_add(unbonding_stake)?;
 
         Ok(())
     }
 
-    /// Process withdraw
-    pub fn withdraw(
+    /// Withdraws the number of unbonding tokens of the deposit along with their weighted stake
+    fn withdraw_unbonded(
         &mut self,
         mining: &mut WrappedMining,
+        deposit_id: u64,
         amount: u64,
-        delegate_mining: Option<&AccountInfo>,
+        delegates: &[DelegateAccounts],
+        curr_ts: u64,
     ) -> ProgramResult {
-        mining.refresh_rewards(self.cumulative_index)?;
+        mining.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
 
-        self.pool.total_share = self.pool.total_share.safe_sub(amount)?;
-        mining.mining.share = mining.mining.share.safe_sub(amount)?;
+        let delegated_stakes =
+            mining.undelegate(deposit_id, amount, &delegate_weights(delegates))?;
+        let unbonding_stake = mining.take_unbonded(deposit_id, amount, curr_ts)?;
+        mining.mining.share = mining.mining.share.safe_sub(unbonding_stake)?;
+        self.pool.total_share = self.pool.total_share.safe_sub(unbonding_stake)?;
 
-        let curr_ts = Clock::get().unwrap().unix_timestamp as u64;
-        let beginning_of_the_day = curr_ts - (curr_ts % SECONDS_PER_DAY);
-        let pool_share = self.consume_old_modifiers(beginning_of_the_day, self.pool.total_share)?;
+        let beginning_of_the_epoch = self.pool.beginning_of_the_epoch(curr_ts);
+        let pool_share =
+            self.consume_old_modifiers(beginning_of_the_epoch, self.pool.total_share)?;
         self.pool.total_share = pool_share;
 
-        if let Some(delegate_mining_acc) = delegate_mining {
-            let delegate_mining_data = &mut delegate_mining_acc.data.borrow_mut();
-            let mut delegate_mining = WrappedMining::from_bytes_mut(delegate_mining_data)?;
+        self.remove_delegated_stake(delegates, &delegated_stakes, curr_ts)
+    }
 
-            delegate_mining.mining.stake_from_others =
-                delegate_mining.mining.stake_from_others.safe_sub(amount)?;
+    /// Removes the number of tokens of the position from the mining along with
+    /// their weighted stake. Returns the removed weighted stake and the modifier
+    /// it has been decreased by, unless the lockup has expired already.
+    fn remove_stake(
+        &mut self,
+        mining: &mut WrappedMining,
+        position: &Position,
+        amount: u64,
+        curr_ts: u64,
+    ) -> Result<(u64, Option<PenaltyStakeDiff>), ProgramError> {
+        mining.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
 
-            self.pool.total_share = self.pool.total_share.safe_sub(amount)?;
-            delegate_mining.refresh_rewards(self.cumulative_index)?;
+        let beginning_of_the_epoch = self.pool.beginning_of_the_epoch(curr_ts);
+        let (weighted_amount, stake_diff) =
+            mining.remove_position_stake(position, amount, beginning_of_the_epoch)?;
+
+        self.pool.total_share = self.pool.total_share.safe_sub(weighted_amount)?;
+        if let Some(stake_diff) = stake_diff {
+            RewardPool::modify_weighted_stake_diffs(
+                self.weighted_stake_diffs,
+                stake_diff.date,
+                stake_diff.diff,
+            )?;
         }
 
-        Ok(())
+        let pool_share =
+            self.consume_old_modifiers(beginning_of_the_epoch, self.pool.total_share)?;
+        self.pool.total_share = pool_share;
+
+        Ok((weighted_amount, stake_diff))
     }
 
-    /// Process slash for specified number of tokens
+    /// Process slash for specified number of tokens of the deposit. The weighted stake
+    /// of the tokens is derived from the position. Returns the slashed weighted stake
+    /// along with weighted stake modifiers that have been decreased.
     pub fn slash(
         &mut self,
         mining: &mut WrappedMining,
+        deposit_id: u64,
         slash_amount_in_native: u64,
-        slash_amount_multiplied_by_period: u64,
-        stake_expiration_date: Option<u64>,
-    ) -> ProgramResult {
-        self.withdraw(mining, slash_amount_multiplied_by_period, None)?;
+        curr_ts: u64,
+    ) -> Result<(u64, Vec<PenaltyStakeDiff>), ProgramError> {
+        let position = mining.position(deposit_id)?;
+        if slash_amount_in_native > position.amount {
+            return Err(MplxRewardsError::InvalidDepositAmount.into());
+        }
+        // unlike the withdrawal, the empty position is kept for the penalty to be restored
+        position.amount = position.amount.safe_sub(slash_amount_in_native)?;
+        let position = *position;
 
-        if let Some(stake_expiration_date) = stake_expiration_date {
-            let beginning_of_the_stake_expiration_date =
-                stake_expiration_date - (stake_expiration_date % SECONDS_PER_DAY);
+        let (weighted_amount, stake_diff) =
+            self.remove_stake(mining, &position, slash_amount_in_native, curr_ts)?;
 
-            let diff_by_expiration_date =
-                slash_amount_multiplied_by_period.safe_sub(slash_amount_in_native)?;
+        Ok((weighted_amount, stake_diff.into_iter().collect()))
+    }
 
-            let diff_record = mining
-                .weighted_stake_diffs
-                .get_mut(&beginning_of_the_stake_expiration_date)
-                .ok_or(MplxRewardsError::NoWeightedStakeModifiersAtADate)?;
-            *diff_record = diff_record.safe_sub(diff_by_expiration_date)?;
+    /// Reverses the penalty described by the record.
+    /// Weighted stake lost because of the penalty is returned to the mining
+    /// along with the modifiers that haven't been reached yet. Slashed tokens are
+    /// returned to their deposit with the weighted stake of its current lockup,
+    /// since the deposit might have been restaked or split after the slash.
+    /// If `credit_lost_rewards` is set, rewards the mining hasn't got because of the penalty
+    /// are credited back to the mining. Rewards lost because of a slash are taken from the
+    /// tokens available for distribution, while ones lost because of a rewards decrease
+    /// have been indexed already and are just handed back.
+    /// Returns the number of credited rewards.
+    pub fn restore_penalty(
+        &mut self,
+        mining: &mut WrappedMining,
+        record: &PenaltyRecord,
+        credit_lost_rewards: bool,
+        curr_ts: u64,
+    ) -> Result<u64, ProgramError> {
+        mining.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
+
+        let beginning_of_the_epoch = self.pool.beginning_of_the_epoch(curr_ts);
+
+        let mut lost_weighted_stake = record.weighted_amount;
+        let mut lost_rewards = 0;
+        let mut index_from = record.index_with_precision;
+        for stake_diff in record.stake_diffs() {
+            if stake_diff.date > beginning_of_the_epoch {
+                // modifier hasn't been reached yet, so it has to be scheduled again,
+                // unless it's derived from the lockup of the slashed deposit
+                if record.deposit_id().is_some() {
+                    continue;
+                }
+                increase_weighted_stake_diff(
+                    mining.weighted_stake_diffs,
+                    stake_diff.date,
+                    stake_diff.diff,
+                )?;
+                if record.kind()? == PenaltyKind::Slash {
+                    increase_weighted_stake_diff(
+                        self.weighted_stake_diffs,
+                        stake_diff.date,
+                        stake_diff.diff,
+                    )?;
+                }
+                continue;
+            }
 
-            let diff_record = self
-                .weighted_stake_diffs
-                .get_mut(&beginning_of_the_stake_expiration_date)
-                .ok_or(MplxRewardsError::NoWeightedStakeModifiersAtADate)?;
-            *diff_record = diff_record.safe_sub(diff_by_expiration_date)?;
+            let index_to = find_max_value_limited_by_key(self.cumulative_index, stake_diff.date)
+                .unwrap_or(0)
+                .max(index_from);
+            lost_rewards = lost_rewards.safe_add(Mining::calculate_rewards(
+                index_from,
+                index_to,
+                lost_weighted_stake,
+            )?)?;
+            index_from = index_to;
+            lost_weighted_stake = lost_weighted_stake.safe_sub(stake_diff.diff)?;
         }
 
-        Ok(())
+        let index_to = find_max_value_limited_by_key(self.cumulative_index, curr_ts)
+            .unwrap_or(0)
+            .max(index_from);
+        lost_rewards = lost_rewards.safe_add(Mining::calculate_rewards(
+            index_from,
+            index_to,
+            lost_weighted_stake,
+        )?)?;
+
+        let restored_weighted_stake = match record.deposit_id() {
+            Some(deposit_id) => {
+                let (weighted_amount, stake_diff) = mining
+                    .add_to_deposit(deposit_id, record.native_amount, beginning_of_the_epoch)
+                    .map_err(|err| match err {
+                        // the whole deposit has been withdrawn or moved to another mining
+                        MplxRewardsError::DepositIsNotFound => {
+                            MplxRewardsError::PenaltyNotRestorable
+                        }
+                        err => err,
+                    })?;
+                if let Some(stake_diff) = stake_diff {
+                    increase_weighted_stake_diff(
+                        self.weighted_stake_diffs,
+                        stake_diff.date,
+                        stake_diff.diff,
+                    )?;
+                }
+                weighted_amount
+            }
+            None => {
+                mining.mining.share = mining.mining.share.safe_add(lost_weighted_stake)?;
+                lost_weighted_stake
+            }
+        };
+        if record.kind()? == PenaltyKind::Slash {
+            // unlike slash, rewards decrease doesn't change the total share
+            self.pool.total_share = self.pool.total_share.safe_add(restored_weighted_stake)?;
+        }
+
+        if !credit_lost_rewards {
+            return Ok(0);
+        }
+
+        if record.kind()? == PenaltyKind::Slash {
+            // slashed stake has been removed from the total share, so its rewards were
+            // distributed to the others and have to be taken from the undistributed ones
+            self.pool.tokens_available_for_distribution = self
+                .pool
+                .tokens_available_for_distribution
+                .safe_sub(lost_rewards)?;
+            self.pool.total_rewards_indexed =
+                self.pool.total_rewards_indexed.safe_add(lost_rewards)?;
+        }
+        // decreased stake has stayed in the total share, so its rewards are already
+        // indexed, but nobody could claim them
+        mining.mining.unclaimed_rewards = mining.mining.unclaimed_rewards.safe_add(lost_rewards)?;
+
+        Ok(lost_rewards)
     }
 
-    /// Process extend stake
-    #[allow(clippy::too_many_arguments)]
-    pub fn extend(
+    /// Applies temporary weighted stake boost to the mining.
+    /// Boost is a part of the mining's weighted stake which is added to both
+    /// mining's share and total share, and removed by the weighted stake modifier
+    /// on the expiration date.
+    pub fn apply_boost(
         &mut self,
         mining: &mut WrappedMining,
-        old_lockup_period: LockupPeriod,
-        new_lockup_period: LockupPeriod,
-        deposit_start_ts: u64,
-        base_amount: u64,
-        additional_amount: u64,
-        delegate_mining: Option<&AccountInfo>,
+        boost_bps: u16,
+        expires_at: u64,
+        curr_ts: u64,
     ) -> ProgramResult {
-        mining.refresh_rewards(self.cumulative_index)?;
+        self.pool.assert_not_sunset()?;
+        mining.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
+
+        let expiration_date = self.pool.beginning_of_the_epoch(expires_at);
+        // the boost cannot outlast the longest lockup started now
+        let latest_expiration_date =
+            LockupPeriod::OneYear.end_timestamp(curr_ts, self.pool.epoch_length())?;
+        if boost_bps == 0 || expiration_date <= curr_ts || expiration_date > latest_expiration_date
+        {
+            return Err(MplxRewardsError::InvalidBoost.into());
+        }
+        if mining.mining.boost_expires_at > curr_ts {
+            return Err(MplxRewardsError::BoostIsAlreadyActive.into());
+        }
 
-        let curr_ts = get_curr_unix_ts();
+        let boost = mining
+            .mining
+            .share
+            .safe_mul(u64::from(boost_bps))?
+            .safe_div(BASIS_POINTS)?;
 
-        let deposit_old_expiration_ts = if old_lockup_period == LockupPeriod::Flex {
-            0 // it's expired, so the date is in the past
-        } else {
-            old_lockup_period.end_timestamp(deposit_start_ts)?
-        };
+        mining.mining.share = mining.mining.share.safe_add(boost)?;
+        self.pool.total_share = self.pool.total_share.safe_add(boost)?;
+        increase_weighted_stake_diff(mining.weighted_stake_diffs, expiration_date, boost)?;
+        increase_weighted_stake_diff(self.weighted_stake_diffs, expiration_date, boost)?;
 
-        // curr_part_of_weighted_stake_for_flex = old_base_amount * flex_multipler
-        let curr_part_of_weighted_stake_for_flex =
-            base_amount.safe_mul(LockupPeriod::Flex.multiplier())?;
+        mining.mining.boost = boost;
+        mining.mining.boost_expires_at = expiration_date;
 
-        // if current date is lower than stake expiration date, we need to
-        // remove stake modifier from the date of expiration
-        if curr_ts < deposit_old_expiration_ts {
-            // current_part_of_weighted_stake = base_amount * lockup_period_multiplier
-            let curr_part_of_weighted_stake =
-                base_amount.safe_mul(old_lockup_period.multiplier())?;
+        Ok(())
+    }
 
-            // weighted_stake_modifier_to_remove = old_base_amount * lockup_period_multiplier - amount_times_flex
-            let weighted_stake_diff =
-                curr_part_of_weighted_stake.safe_sub(curr_part_of_weighted_stake_for_flex)?;
+    /// Moves the whole position of the source mining into the destination one.
+    /// Weighted stake modifiers are joined date by date, so the pool's total share
+    /// and its modifiers stay the same. Positions of the source deposits are recorded
+    /// under `deposit_ids`, which are ordered by the source deposit ids.
+    /// The source mining must not have stake from others.
+    pub fn merge_mining(
+        &self,
+        source: &mut WrappedMining,
+        destination: &mut WrappedMining,
+        deposit_ids: &[u64],
+        curr_ts: u64,
+    ) -> ProgramResult {
+        source.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
+        destination.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
 
-            RewardPool::modify_weighted_stake_diffs(
-                mining.weighted_stake_diffs,
-                deposit_old_expiration_ts,
-                weighted_stake_diff,
-            )?;
+        // delegators refer the source mining by its address, so their stake cannot be moved
+        if source.mining.stake_from_others > 0 {
+            return Err(MplxRewardsError::StakeFromOthersMustBeZero.into());
+        }
 
-            // also, we need to reduce staking power because we want to extend stake from "scratch"
-            mining.mining.share = mining.mining.share.safe_sub(curr_part_of_weighted_stake)?;
+        destination.mining.share = destination.mining.share.safe_add(source.mining.share)?;
+        destination.mining.unclaimed_rewards = destination
+            .mining
+            .unclaimed_rewards
+            .safe_add(source.mining.unclaimed_rewards)?;
 
-            self.pool.total_share = self
-                .pool
-                .total_share
-                .safe_sub(curr_part_of_weighted_stake)?;
-        } else {
-            // otherwise, we want to substract flex multiplier, becase deposit has expired already
-            mining.mining.share = mining
-                .mining
-                .share
-                .safe_sub(curr_part_of_weighted_stake_for_flex)?;
+        for (date, diff) in source.weighted_stake_diffs.iter() {
+            increase_weighted_stake_diff(destination.weighted_stake_diffs, *date, *diff)?;
+        }
 
-            self.pool.total_share = self
-                .pool
-                .total_share
-                .safe_sub(curr_part_of_weighted_stake_for_flex)?;
+        if deposit_ids.len() != source.positions.len() {
+            return Err(MplxRewardsError::InvalidDepositIds.into());
+        }
+        for ((source_deposit_id, position), deposit_id) in source.positions.iter().zip(deposit_ids)
+        {
+            destination.record_deposit(*deposit_id, *position)?;
+            if let Some(recorded) = source.delegated_stakes.get(source_deposit_id) {
+                destination.record_delegated_stakes(*deposit_id, recorded.stakes())?;
+            }
         }
 
-        // do actions like it's a regular deposit
-        let amount_to_restake = base_amount.safe_add(additional_amount)?;
+        // the boost which lasts longer is kept, so that a new boost
+        // cannot be applied while any of the merged ones is active
+        if source.mining.boost_expires_at > destination.mining.boost_expires_at {
+            destination.mining.boost = source.mining.boost;
+            destination.mining.boost_expires_at = source.mining.boost_expires_at;
+        }
 
-        let delegate_mining = match delegate_mining {
-            Some(delegate_mining_acc) => {
-                let delegate_mining_data = &mut delegate_mining_acc.data.borrow_mut();
-                let mut delegate_mining = WrappedMining::from_bytes_mut(delegate_mining_data)?;
+        source.mining.share = 0;
+        source.mining.unclaimed_rewards = 0;
 
-                delegate_mining.mining.stake_from_others = delegate_mining
-                    .mining
-                    .stake_from_others
-                    .safe_sub(base_amount)?;
-                self.pool.total_share = self.pool.total_share.safe_sub(base_amount)?;
-                delegate_mining.refresh_rewards(self.cumulative_index)?;
+        self.pool.assert_share_limits(destination.mining)
+    }
 
-                Some(delegate_mining_acc)
-            }
-            None => None,
-        };
+    /// Moves the part of the source mining's deposit to the destination mining,
+    /// where it's recorded as the deposit with `destination_deposit_id`.
+    /// The weighted stake of the part is derived from the position.
+    pub fn split_mining(
+        &self,
+        source: &mut WrappedMining,
+        destination: &mut WrappedMining,
+        deposit_id: u64,
+        destination_deposit_id: u64,
+        amount: u64,
+        curr_ts: u64,
+    ) -> ProgramResult {
+        source.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
+        destination.refresh_rewards(self.cumulative_index, self.pool.epoch_length(), curr_ts)?;
+
+        // the stake the part has delegated moves along with it
+        let delegated_stakes = source
+            .delegated_stakes
+            .get_mut(&deposit_id)
+            .map(|recorded| recorded.take(amount))
+            .transpose()?;
+        let position = source.take_from_deposit(deposit_id, amount)?;
+        // the unbonding tokens stay in the source mining
+        destination.record_deposit(
+            destination_deposit_id,
+            Position {
+                amount,
+                unbonding_amount: 0,
+                unbonding_stake: 0,
+                unbonding_ends_at: 0,
+                ..position
+            },
+        )?;
+        if let Some(delegated_stakes) = delegated_stakes {
+            destination.record_delegated_stakes(destination_deposit_id, &delegated_stakes)?;
+        }
+
+        let (weighted_amount, stake_diff) = source.remove_position_stake(
+            &position,
+            amount,
+            self.pool.beginning_of_the_epoch(curr_ts),
+        )?;
+        destination.mining.share = destination.mining.share.safe_add(weighted_amount)?;
+        if let Some(stake_diff) = stake_diff {
+            increase_weighted_stake_diff(
+                destination.weighted_stake_diffs,
+                stake_diff.date,
+                stake_diff.diff,
+            )?;
+        }
+
+        self.pool.assert_share_limits(destination.mining)
+    }
+
+    /// Process extend stake of the deposit. The deposit is restaked from scratch
+    /// with the new lockup period, while the old one is taken from the position.
+    pub fn extend(
+        &mut self,
+        mining: &mut WrappedMining,
+        deposit_id: u64,
+        new_lockup_period: LockupPeriod,
+        additional_amount: u64,
+        delegates: &[DelegateAccounts],
+        curr_ts: u64,
+    ) -> ProgramResult {
+        self.pool.assert_not_sunset()?;
+        if additional_amount > 0 {
+            self.pool.assert_deposit_amount(additional_amount)?;
+        }
 
-        self.deposit(
+        let position = *mining.position(deposit_id)?;
+        let weights = delegate_weights(delegates);
+        let delegated_stakes = mining.undelegate(deposit_id, position.amount, &weights)?;
+        self.remove_stake(mining, &position, position.amount, curr_ts)?;
+        self.remove_delegated_stake(delegates, &delegated_stakes, curr_ts)?;
+
+        // do actions like it's a regular deposit
+        let amount_to_restake = position.amount.safe_add(additional_amount)?;
+        let (restaked, restaked_stakes) = self.add_stake(
             mining,
             amount_to_restake,
             new_lockup_period,
-            delegate_mining,
+            delegates,
+            curr_ts,
         )?;
-
-        Ok(())
+        // the unbonding tokens aren't restaked, but they're still delegated
+        *mining.position(deposit_id)? = Position {
+            unbonding_amount: position.unbonding_amount,
+            unbonding_stake: position.unbonding_stake,
+            unbonding_ends_at: position.unbonding_ends_at,
+            ..restaked
+        };
+        let unbonding_stakes = match mining.delegated_stakes.get(&deposit_id) {
+            Some(recorded) => recorded.stakes().to_vec(),
+            None => split_by_weights(position.unbonding_amount, &weights)?,
+        };
+        let delegated_stakes = restaked_stakes
+            .iter()
+            .zip(unbonding_stakes)
+            .map(|(stake, unbonding_stake)| stake.safe_add(unbonding_stake))
+            .collect::<Result<Vec<_>, _>>()?;
+        mining.record_delegated_stakes(deposit_id, &delegated_stakes)?;
+
+        self.pool.assert_share_limits(mining.mining)
     }
 }
 
+/// Returns the weights the stake is split between the delegates by
+fn delegate_weights(delegates: &[DelegateAccounts]) -> Vec<u16> {
+    delegates.iter().map(|delegate| delegate.weight).collect()
+}
+
 /// Reward pool
 #[repr(C)]
 #[derive(Debug, Default, Clone, Copy, Pod, Zeroable, ShankAccount)]
@@ -450,15 +913,58 @@ pub struct RewardPool {
     pub token_account_bump: u8,
     /// Account type - Mining. This discriminator should exist in order to prevent
     /// shenanigans with customly modified accounts and their fields.
-    /// 1: account type
-    /// 2-7: unused
+    /// 0: account type
+    /// 1: layout version
+    /// 2-6: unused
     pub data: [u8; 7],
+    /// The minimal number of tokens a single deposit can be made with. Zero means no limit.
+    pub min_deposit: u64,
+    /// The maximal weighted stake a single mining account can have. Zero means no limit.
+    pub max_weighted_share_per_mining: u64,
+    /// The maximal total share of the pool. Zero means no limit.
+    pub max_total_share: u64,
+    /// The length of the distribution epoch in seconds. Distributions, weighted stake modifiers
+    /// and rewards calculations are bucketed by epochs. Zero means the default one day epoch.
+    pub epoch_length_secs: u64,
+    /// The collection whose NFT holders are eligible for the weighted stake boost.
+    /// Default pubkey means boosts by collection are disabled.
+    pub boost_collection: Pubkey,
+    /// The address which may attest mining owners for the weighted stake boost.
+    /// Default pubkey means boosts by attestation are disabled.
+    pub boost_attester: Pubkey,
+    /// The time the pool has been sunset at. Zero means the pool is active.
+    /// Sunset pool doesn't accept new deposits and distributions.
+    pub sunset_at: u64,
+    /// The period after the sunset during which rewards still can be claimed.
+    /// Once it's elapsed, remaining funds can be swept with the ClosePool instruction.
+    pub sunset_grace_period_secs: u64,
+    /// The total amount of rewards which have been indexed and thus became claimable by miners.
+    /// Pools migrated from the layouts without it count the rewards left unclaimed
+    /// by the migration, i.e. the vault balance exceeding the tokens available for distribution.
+    pub total_rewards_indexed: u64,
+    /// The total amount of rewards which have been claimed by miners
+    pub total_rewards_claimed: u64,
+    /// The period tokens requested for withdrawal stay unbonding for.
+    /// Zero means the tokens may be withdrawn without the request.
+    pub unbonding_period_secs: u64,
+    /// Weighted stake of the unbonding tokens in basis points of their number.
+    /// Zero means the unbonding tokens don't earn rewards.
+    pub unbonding_multiplier_bps: u64,
 }
 
 impl ZeroCopy for RewardPool {}
 
 impl RewardPool {
     pub const LEN: usize = std::mem::size_of::<RewardPool>();
+    /// Version of the current layout
+    pub const VERSION: u8 = 3;
+    /// Header lengths of the layouts pools had been created with before
+    /// the version byte was introduced
+    pub const UNVERSIONED_HEADER_LENS: [usize; 4] = [176, 208, 272, 288];
+    /// Header lengths of the outdated versioned layouts along with their versions
+    pub const VERSIONED_HEADER_LENS: [(u8, usize); 2] = [(1, 288), (2, 304)];
+    /// Header length of the first layout tracking indexed and claimed rewards
+    pub const REWARDS_TRACKING_HEADER_LEN: usize = 304;
 
     /// Init reward pool
     pub fn initialize(
@@ -471,6 +977,7 @@ impl RewardPool {
         let account_type = AccountType::RewardPool.into();
         let mut data = [0; 7];
         data[0] = account_type;
+        data[ACCOUNT_VERSION_BYTE] = Self::VERSION;
         RewardPool {
             data,
             token_account_bump,
@@ -482,28 +989,205 @@ impl RewardPool {
         }
     }
 
+    pub fn version(&self) -> u8 {
+        self.data[ACCOUNT_VERSION_BYTE]
+    }
+
+    /// Checks the pool is stored in the current layout
+    pub fn assert_version(&self) -> Result<(), MplxRewardsError> {
+        assert_account_version(self.data[0], self.version(), Self::VERSION)
+    }
+
+    /// Checks the account holds an initialized pool
+    pub fn assert_account_type(&self) -> Result<(), MplxRewardsError> {
+        assert_account_type(self.data[0], AccountType::RewardPool)
+    }
+
+    pub fn is_sunset(&self) -> bool {
+        self.sunset_at != 0
+    }
+
+    /// Checks the pool accepts new deposits and distributions
+    pub fn assert_not_sunset(&self) -> ProgramResult {
+        if self.is_sunset() {
+            return Err(MplxRewardsError::PoolIsSunset.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the pool has been sunset and its grace period has elapsed
+    pub fn is_sunset_grace_period_elapsed(&self, curr_ts: u64) -> Result<bool, ProgramError> {
+        Ok(
+            self.is_sunset()
+                && curr_ts >= self.sunset_at.safe_add(self.sunset_grace_period_secs)?,
+        )
+    }
+
+    /// Checks the deposit isn't lower than the pool's minimal deposit
+    pub fn assert_deposit_amount(&self, amount: u64) -> ProgramResult {
+        if amount < self.min_deposit {
+            return Err(MplxRewardsError::DepositIsTooSmall.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks both the mining's share and the pool's total share are within the pool limits
+    pub fn assert_share_limits(&self, mining: &Mining) -> ProgramResult {
+        if self.max_weighted_share_per_mining > 0
+            && mining.share > self.max_weighted_share_per_mining
+        {
+            return Err(MplxRewardsError::MiningShareLimitExceeded.into());
+        }
+
+        if self.max_total_share > 0 && self.total_share > self.max_total_share {
+            return Err(MplxRewardsError::PoolShareLimitExceeded.into());
+        }
+
+        Ok(())
+    }
+
+    /// Sets the unbonding period and the multiplier of the unbonding tokens.
+    /// The unbonding tokens cannot earn more than the Flex ones.
+    pub fn set_unbonding_config(
+        &mut self,
+        unbonding_period_secs: u64,
+        unbonding_multiplier_bps: u16,
+    ) -> ProgramResult {
+        let unbonding_multiplier_bps = u64::from(unbonding_multiplier_bps);
+        if unbonding_multiplier_bps > LockupPeriod::Flex.multiplier().safe_mul(BASIS_POINTS)? {
+            return Err(MplxRewardsError::InvalidUnbondingMultiplier.into());
+        }
+
+        self.unbonding_period_secs = unbonding_period_secs;
+        self.unbonding_multiplier_bps = unbonding_multiplier_bps;
+
+        Ok(())
+    }
+
+    /// The weighted stake the number of tokens has while unbonding
+    pub fn unbonding_stake(&self, amount: u64) -> Result<u64, MplxRewardsError> {
+        u64::try_from(
+            u128::from(amount)
+                .safe_mul(u128::from(self.unbonding_multiplier_bps))?
+                .safe_div(u128::from(BASIS_POINTS))?,
+        )
+        .map_err(|_| MplxRewardsError::InvalidPrimitiveTypesConversion)
+    }
+
+    /// The length of the distribution epoch in seconds
+    pub fn epoch_length(&self) -> u64 {
+        if self.epoch_length_secs == 0 {
+            SECONDS_PER_DAY
+        } else {
+            self.epoch_length_secs
+        }
+    }
+
+    /// Returns the beginning of the epoch the timestamp belongs to
+    pub fn beginning_of_the_epoch(&self, timestamp: u64) -> u64 {
+        timestamp - (timestamp % self.epoch_length())
+    }
+
+    /// Makes the rewards received by the vault available for distribution.
+    /// Distribution end is moved to the beginning of the epoch `distribution_ends_at` belongs to.
+    pub fn fill(&mut self, rewards: u64, distribution_ends_at: u64, curr_ts: u64) -> ProgramResult {
+        let distribution_ends_at_epoch_start = self.beginning_of_the_epoch(distribution_ends_at);
+        if distribution_ends_at_epoch_start < self.beginning_of_the_epoch(curr_ts) {
+            return Err(MplxRewardsError::DistributionInThePast.into());
+        }
+
+        let epochs_diff = distribution_ends_at_epoch_start.safe_sub(self.distribution_ends_at)?;
+        self.distribution_ends_at = self.distribution_ends_at.safe_add(epochs_diff)?;
+        self.tokens_available_for_distribution =
+            self.tokens_available_for_distribution.safe_add(rewards)?;
+
+        Ok(())
+    }
+
+    /// Checks the account is the vault of the pool
+    pub fn assert_vault(
+        &self,
+        program_id: &Pubkey,
+        reward_pool: &Pubkey,
+        vault: &AccountInfo,
+    ) -> ProgramResult {
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            reward_pool.as_ref(),
+            self.reward_mint.as_ref(),
+            &[self.token_account_bump],
+        ];
+        assert_account_key(
+            vault,
+            &Pubkey::create_program_address(vault_seeds, program_id)?,
+        )
+    }
+
+    /// Rewards which have been indexed but haven't been claimed yet
+    pub fn unclaimed_rewards(&self) -> u64 {
+        self.total_rewards_indexed
+            .saturating_sub(self.total_rewards_claimed)
+    }
+
+    /// The amount of tokens the vault must hold: tokens which are going to be distributed
+    /// and rewards which have already been distributed but haven't been claimed yet
+    pub fn liabilities(&self) -> Result<u64, MplxRewardsError> {
+        self.tokens_available_for_distribution
+            .safe_add(self.unclaimed_rewards())
+    }
+
+    /// Compares the vault balance with the pool's liabilities
+    pub fn solvency_report(&self, vault_balance: u64) -> Result<SolvencyReport, MplxRewardsError> {
+        let liabilities = self.liabilities()?;
+
+        Ok(SolvencyReport {
+            vault_balance,
+            liabilities,
+            surplus: vault_balance.saturating_sub(liabilities),
+            deficit: liabilities.saturating_sub(vault_balance),
+        })
+    }
+
+    /// Checks the vault balance covers the pool's liabilities
+    pub fn assert_solvency(&self, vault_balance: u64) -> ProgramResult {
+        let report = self.solvency_report(vault_balance)?;
+        if report.deficit > 0 {
+            msg!(
+                "Vault balance {} doesn't cover liabilities {}, deficit {}",
+                report.vault_balance,
+                report.liabilities,
+                report.deficit
+            );
+            return Err(MplxRewardsError::VaultIsInsolvent.into());
+        }
+
+        Ok(())
+    }
+
     /// Defines the amount of money that will be distributed
     /// The formula is vault_tokens_are_available_for_distribution / (distrtribution_period_ends_at - curr_time)
-    pub fn rewards_to_distribute(&self) -> Result<u64, ProgramError> {
-        let distribution_days_left: u128 =
-            (self.distribution_ends_at.saturating_sub(get_curr_unix_ts()) / SECONDS_PER_DAY).into();
+    pub fn rewards_to_distribute(&self, curr_ts: u64) -> Result<u64, ProgramError> {
+        let distribution_epochs_left: u128 =
+            (self.distribution_ends_at.saturating_sub(curr_ts) / self.epoch_length()).into();
 
-        if distribution_days_left == 0 {
+        if distribution_epochs_left == 0 {
             return Ok(self.tokens_available_for_distribution);
         }
 
-        // ((tokens_available_for_distribution * precision) / days_left) / precision
+        // ((tokens_available_for_distribution * precision) / epochs_left) / precision
         Ok(u64::try_from(
             (u128::from(self.tokens_available_for_distribution))
                 .safe_mul(PRECISION)?
-                .safe_div(distribution_days_left)?
+                .safe_div(distribution_epochs_left)?
                 .safe_div(PRECISION)?,
         )
         .map_err(|_| MplxRewardsError::InvalidPrimitiveTypesConversion)?)
     }
 
-    fn modify_weighted_stake_diffs(
-        diffs: &mut MiningWeightedStakeDiffs,
+    fn modify_weighted_stake_diffs<const CAP: usize>(
+        diffs: &mut RedBlackTree<u64, u64, CAP>,
         timestamp: u64,
         weighted_stake_diff: u64,
     ) -> Result<(), MplxRewardsError> {
@@ -517,6 +1201,16 @@ impl RewardPool {
     }
 }
 
+/// Comparison of the vault balance with the pool's liabilities.
+/// Either surplus or deficit is zero.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct SolvencyReport {
+    pub vault_balance: u64,
+    pub liabilities: u64,
+    pub surplus: u64,
+    pub deficit: u64,
+}
+
 impl IsInitialized for RewardPool {
     fn is_initialized(&self) -> bool {
         self.data[0] == <u8>::from(AccountType::RewardPool)
@@ -524,6 +1218,42 @@ impl IsInitialized for RewardPool {
 }
 
 mod test {
+    #[test]
+    fn distribution_is_rejected_once_index_history_is_full() {
+        let mut bytes = vec![0; std::mem::size_of::<super::CumulativeIndex>()];
+        let cumulative_index =
+            <super::CumulativeIndex as sokoban::ZeroCopy>::load_mut_bytes(&mut bytes).unwrap();
+        cumulative_index.initialize();
+
+        let mut index_with_precision = 0;
+        for date in 0..super::super::INDEX_HISTORY_MAX_SIZE as u64 {
+            super::WrappedRewardPool::update_index(
+                cumulative_index,
+                &mut index_with_precision,
+                100,
+                100,
+                date,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            super::WrappedRewardPool::update_index(
+                cumulative_index,
+                &mut index_with_precision,
+                100,
+                100,
+                super::super::INDEX_HISTORY_MAX_SIZE as u64,
+            ),
+            Err(crate::error::MplxRewardsError::IndexHistoryIsFull.into())
+        );
+        // the index isn't advanced by the rejected distribution
+        assert_eq!(
+            index_with_precision,
+            super::super::INDEX_HISTORY_MAX_SIZE as u128 * super::PRECISION
+        );
+    }
+
     #[test]
     fn test_wrapped_immutable_reward_pool_is_same_size_as_wrapped_reward_pool() {
         assert_eq!(
@@ -536,7 +1266,15 @@ mod test {
     fn test_can_deserialize_wrapped_immutable_reward_pool_from_bytes_initialized_with_wrapped_reward_pool(
     ) {
         let mut bytes = vec![0; super::WrappedRewardPool::LEN];
-        let wrapped_reward_pool = super::WrappedRewardPool::from_bytes_mut(&mut bytes).unwrap();
+        let wrapped_reward_pool =
+            super::WrappedRewardPool::from_uninitialized_bytes_mut(&mut bytes).unwrap();
+        *wrapped_reward_pool.pool = super::RewardPool::initialize(
+            0,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        );
         let deposit_authority = solana_program::pubkey::Pubkey::new_unique();
         let distribute_authority = solana_program::pubkey::Pubkey::new_unique();
         let fill_authority = solana_program::pubkey::Pubkey::new_unique();
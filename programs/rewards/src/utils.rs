@@ -16,6 +16,13 @@ use solana_program::{
     system_instruction,
     sysvar::Sysvar,
 };
+use spl_token_2022::{
+    extension::{
+        transfer_fee::TransferFeeAmount, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    state::{Account as TokenAccount, Mint},
+};
 
 /// Generates mining address
 pub fn find_mining_program_address(
@@ -49,11 +56,47 @@ pub fn find_vault_program_address(
     )
 }
 
+/// Generates penalty history address
+pub fn find_penalty_history_program_address(program_id: &Pubkey, mining: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &["penalty_history".as_bytes(), &mining.to_bytes()],
+        program_id,
+    )
+}
+
+/// Generates delegate registry address
+pub fn find_delegate_registry_program_address(
+    program_id: &Pubkey,
+    mining: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &["delegate_registry".as_bytes(), &mining.to_bytes()],
+        program_id,
+    )
+}
+
+/// Generates boost record address
+pub fn find_boost_record_program_address(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    nft_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            "boost_record".as_bytes(),
+            &reward_pool.to_bytes(),
+            &nft_mint.to_bytes(),
+        ],
+        program_id,
+    )
+}
+
 /// Create account
-pub fn create_account<'a, S: Pack>(
+pub fn create_account<'a>(
     program_id: &Pubkey,
     from: AccountInfo<'a>,
     to: AccountInfo<'a>,
+    space: usize,
     signers_seeds: &[&[&[u8]]],
 ) -> ProgramResult {
     let rent = Rent::get()?;
@@ -61,14 +104,75 @@ pub fn create_account<'a, S: Pack>(
     let ix = system_instruction::create_account(
         from.key,
         to.key,
-        rent.minimum_balance(S::LEN),
-        S::LEN as u64,
+        rent.minimum_balance(space),
+        space as u64,
         program_id,
     );
 
     invoke_signed(&ix, &[from, to], signers_seeds)
 }
 
+/// Checks that the token program is either SPL Token or Token-2022
+pub fn assert_token_program(token_program: &Pubkey) -> ProgramResult {
+    if token_program != &spl_token::id() && token_program != &spl_token_2022::id() {
+        msg!("Unsupported token program {}", token_program);
+        return Err(MplxRewardsError::UnsupportedTokenProgram.into());
+    }
+
+    Ok(())
+}
+
+/// Token-2022 extensions the reward mint may have. The others either let somebody
+/// else move or freeze the pool's tokens, or change the amounts the pool accounts for.
+pub const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+/// Checks the reward mint has no other extensions than the supported ones
+pub fn assert_mint_extensions(mint: &AccountInfo) -> ProgramResult {
+    if mint.owner == &spl_token::id() {
+        return Ok(());
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_extensions = StateWithExtensions::<Mint>::unpack(&mint_data)?.get_extension_types()?;
+    if let Some(extension) = mint_extensions
+        .iter()
+        .find(|extension| !SUPPORTED_MINT_EXTENSIONS.contains(extension))
+    {
+        msg!("Unsupported mint extension {:?}", extension);
+        return Err(MplxRewardsError::UnsupportedMintExtension.into());
+    }
+
+    Ok(())
+}
+
+/// Unpacks the mint owned by either SPL Token or Token-2022, extensions are skipped
+pub fn unpack_mint(mint: &AccountInfo) -> Result<Mint, ProgramError> {
+    Ok(StateWithExtensions::<Mint>::unpack(&mint.try_borrow_data()?)?.base)
+}
+
+/// Unpacks the token account owned by either SPL Token or Token-2022, extensions are skipped
+pub fn unpack_token_account(account: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+    Ok(StateWithExtensions::<TokenAccount>::unpack(&account.try_borrow_data()?)?.base)
+}
+
+/// Size of the token account for the mint, including the extensions
+/// the mint requires its token accounts to have
+pub fn get_token_account_len(mint: &AccountInfo) -> Result<usize, ProgramError> {
+    if mint.owner == &spl_token::id() {
+        return Ok(TokenAccount::LEN);
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_extensions = StateWithExtensions::<Mint>::unpack(&mint_data)?.get_extension_types()?;
+    let account_extensions = ExtensionType::get_required_init_account_extensions(&mint_extensions);
+
+    ExtensionType::try_calculate_account_len::<TokenAccount>(&account_extensions)
+}
+
 /// Initialize SPL account instruction.
 pub fn initialize_account<'a>(
     account: AccountInfo<'a>,
@@ -76,8 +180,8 @@ pub fn initialize_account<'a>(
     authority: AccountInfo<'a>,
     rent: AccountInfo<'a>,
 ) -> ProgramResult {
-    let ix = spl_token::instruction::initialize_account(
-        &spl_token::id(),
+    let ix = spl_token_2022::instruction::initialize_account(
+        mint.owner,
         account.key,
         mint.key,
         authority.key,
@@ -87,23 +191,223 @@ pub fn initialize_account<'a>(
 }
 
 /// SPL transfer instruction.
+/// Transfers are always checked, so both SPL Token and Token-2022 mints are supported.
 pub fn spl_transfer<'a>(
     source: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
     destination: AccountInfo<'a>,
     authority: AccountInfo<'a>,
     amount: u64,
     signers_seeds: &[&[&[u8]]],
 ) -> Result<(), ProgramError> {
-    let ix = spl_token::instruction::transfer(
-        &spl_token::id(),
+    let decimals = unpack_mint(&mint)?.decimals;
+    let ix = spl_token_2022::instruction::transfer_checked(
+        mint.owner,
         source.key,
+        mint.key,
         destination.key,
         authority.key,
         &[],
         amount,
+        decimals,
+    )?;
+
+    invoke_signed(&ix, &[source, mint, destination, authority], signers_seeds)
+}
+
+/// Moves transfer fees withheld in the token account to the mint.
+/// Token-2022 doesn't allow to close accounts with withheld fees.
+pub fn spl_harvest_withheld_tokens<'a>(
+    account: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+) -> Result<(), ProgramError> {
+    let withheld_amount = {
+        let account_data = account.try_borrow_data()?;
+        let account_state = StateWithExtensions::<TokenAccount>::unpack(&account_data)?;
+        account_state
+            .get_extension::<TransferFeeAmount>()
+            .map(|transfer_fee_amount| u64::from(transfer_fee_amount.withheld_amount))
+            .unwrap_or_default()
+    };
+    if withheld_amount == 0 {
+        return Ok(());
+    }
+
+    let ix = spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+        mint.owner,
+        mint.key,
+        &[account.key],
+    )?;
+
+    invoke(&ix, &[mint, account])
+}
+
+/// SPL close account instruction.
+pub fn spl_close_account<'a>(
+    account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    let ix = spl_token_2022::instruction::close_account(
+        account.owner,
+        account.key,
+        destination.key,
+        authority.key,
+        &[],
     )?;
 
-    invoke_signed(&ix, &[source, destination, authority], signers_seeds)
+    invoke_signed(&ix, &[account, destination, authority], signers_seeds)
+}
+
+/// Reallocates the account, topping up its balance from the payer to keep it rent exempt.
+pub fn realloc_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let lamports_required = rent
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+
+    if lamports_required > 0 {
+        let ix = system_instruction::transfer(payer.key, account.key, lamports_required);
+        invoke(&ix, &[payer.clone(), account.clone()])?;
+    }
+
+    account.realloc(new_len, true)
+}
+
+/// Maximal number of delegates the delegated stake might be split between
+pub const MAX_DELEGATES: usize = 8;
+
+/// Mining account of the delegate along with its registry
+/// and the weight the delegated stake is split by
+#[derive(Clone, Copy)]
+pub struct DelegateAccounts<'a, 'b> {
+    pub mining: &'a AccountInfo<'b>,
+    /// Registry is only required when the stake is delegated, not when it's taken back
+    pub registry: Option<&'a AccountInfo<'b>>,
+    pub weight: u16,
+}
+
+/// Splits the amount between delegates proportionally to their weights.
+/// Parts are rounded down and the remainder goes to the last delegate,
+/// so the parts always sum up to the amount.
+pub fn split_by_weights(amount: u64, weights: &[u16]) -> Result<Vec<u64>, ProgramError> {
+    let Some((_, leading_weights)) = weights.split_last() else {
+        return Ok(vec![]);
+    };
+
+    let total_weight = weights.iter().map(|weight| *weight as u128).sum::<u128>();
+    if total_weight == 0 {
+        return Err(MplxRewardsError::InvalidDelegateWeights.into());
+    }
+
+    let mut parts = Vec::with_capacity(weights.len());
+    let mut remainder = amount;
+    for weight in leading_weights {
+        let part = (amount as u128)
+            .safe_mul(*weight as u128)?
+            .safe_div(total_weight)? as u64;
+        remainder = remainder.safe_sub(part)?;
+        parts.push(part);
+    }
+    parts.push(remainder);
+
+    Ok(parts)
+}
+
+/// Loads the delegates the stake of the mining is delegated to.
+/// With no `delegate_weights` the whole stake is delegated to `delegate_mining`, unless it's
+/// the mining itself. Otherwise `delegate_mining` must be the mining itself, and the stake is split
+/// between the delegate minings passed in the remaining accounts, each one followed by its registry
+/// if `with_registries` is set.
+#[allow(clippy::too_many_arguments)]
+pub fn next_delegates<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut Enumerate<I>,
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &'a AccountInfo<'b>,
+    delegate_mining: &'a AccountInfo<'b>,
+    delegate: &Pubkey,
+    delegate_weights: &[u16],
+    with_registries: bool,
+) -> Result<Vec<DelegateAccounts<'a, 'b>>, ProgramError> {
+    if delegate_weights.is_empty() {
+        let Some(delegate_mining) = get_delegate_mining(delegate_mining, mining)? else {
+            return Ok(vec![]);
+        };
+        verify_delegate_mining_address(program_id, delegate_mining, delegate, reward_pool)?;
+        let registry = if with_registries {
+            Some(AccountLoader::next_with_owner(iter, program_id)?)
+        } else {
+            None
+        };
+
+        return Ok(vec![DelegateAccounts {
+            mining: delegate_mining,
+            registry,
+            weight: 1,
+        }]);
+    }
+
+    next_split_delegates(
+        iter,
+        program_id,
+        reward_pool,
+        mining,
+        delegate_mining,
+        delegate_weights,
+        with_registries,
+    )
+}
+
+/// Loads the delegates the stake of the mining is split between. They are passed
+/// in the remaining accounts, each one followed by its registry if `with_registries` is set.
+/// `delegate_mining` must be the mining itself.
+pub fn next_split_delegates<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut Enumerate<I>,
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &'a AccountInfo<'b>,
+    delegate_mining: &'a AccountInfo<'b>,
+    delegate_weights: &[u16],
+    with_registries: bool,
+) -> Result<Vec<DelegateAccounts<'a, 'b>>, ProgramError> {
+    if delegate_mining.key != mining.key
+        || delegate_weights.len() > MAX_DELEGATES
+        || delegate_weights.contains(&0)
+    {
+        return Err(MplxRewardsError::InvalidDelegateWeights.into());
+    }
+
+    let mut delegates: Vec<DelegateAccounts> = Vec::with_capacity(delegate_weights.len());
+    for weight in delegate_weights {
+        let delegate_mining = AccountLoader::next_with_owner(iter, program_id)?;
+        if delegate_mining.key == mining.key
+            || delegates
+                .iter()
+                .any(|d| d.mining.key == delegate_mining.key)
+        {
+            return Err(MplxRewardsError::DelegateIsDuplicated.into());
+        }
+        verify_delegate_mining_pool(delegate_mining, reward_pool)?;
+        let registry = if with_registries {
+            Some(AccountLoader::next_with_owner(iter, program_id)?)
+        } else {
+            None
+        };
+
+        delegates.push(DelegateAccounts {
+            mining: delegate_mining,
+            registry,
+            weight: *weight,
+        });
+    }
+
+    Ok(delegates)
 }
 
 pub fn get_delegate_mining<'a, 'b>(
@@ -129,7 +433,7 @@ pub fn verify_delegate_mining_address(
             program_id,
             delegate,
             reward_pool_key,
-            WrappedImmutableMining::from_bytes(&delegate_mining.data.borrow())?
+            WrappedImmutableMining::from_bytes(&delegate_mining.try_borrow_data()?)?
                 .mining
                 .bump,
         )
@@ -141,6 +445,20 @@ pub fn verify_delegate_mining_address(
     Ok(())
 }
 
+/// Checks the delegate mining is an initialized mining of the reward pool
+pub fn verify_delegate_mining_pool(
+    delegate_mining: &AccountInfo<'_>,
+    reward_pool_key: &Pubkey,
+) -> ProgramResult {
+    let delegate_mining_data = delegate_mining.try_borrow_data()?;
+    let wrapped_mining = WrappedImmutableMining::from_bytes(&delegate_mining_data)?;
+    if wrapped_mining.mining.reward_pool != *reward_pool_key {
+        return Err(MplxRewardsError::InvalidMining.into());
+    }
+
+    Ok(())
+}
+
 /// Helper for parsing accounts with arbitrary input conditions
 pub struct AccountLoader {}
 
@@ -214,6 +532,33 @@ impl AccountLoader {
         Err(ProgramError::MissingRequiredSignature)
     }
 
+    /// Loads the optional account owned by the specified address. None means the account
+    /// is omitted: either the accounts are over, the owner itself is passed in its place,
+    /// or the account hasn't been created yet.
+    pub fn next_optional_with_owner<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+        iter: &mut Enumerate<I>,
+        owner: &Pubkey,
+    ) -> Result<Option<I::Item>, ProgramError> {
+        let Some((idx, acc)) = iter.next() else {
+            return Ok(None);
+        };
+        if acc.key == owner || (acc.lamports() == 0 && acc.data_is_empty()) {
+            return Ok(None);
+        }
+        if acc.owner.eq(owner) {
+            return Ok(Some(acc));
+        }
+
+        msg!(
+            "Account #{}:{} owner error. Got {} Expected {}",
+            idx,
+            acc.key,
+            acc.owner,
+            owner
+        );
+        Err(MplxRewardsError::InvalidAccountOwner.into())
+    }
+
     /// Load the account without any checks
     pub fn next_unchecked<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
         iter: &mut Enumerate<I>,
@@ -229,6 +574,17 @@ impl AccountLoader {
     }
 }
 
+/// Kind of the proof the weighted stake boost is granted with
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BoostProof {
+    /// Mining owner holds an NFT which belongs to the pool's boost collection.
+    /// Proof accounts: NFT token account and NFT metadata account.
+    Collection,
+    /// Pool's boost attester has signed the transaction.
+    /// Proof accounts: attester.
+    Attester,
+}
+
 /// LockupPeriod is used to define the time during which the lockup will recieve full reward
 #[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
 pub enum LockupPeriod {
@@ -244,6 +600,32 @@ pub enum LockupPeriod {
     OneYear,
 }
 
+impl From<LockupPeriod> for u8 {
+    fn from(value: LockupPeriod) -> Self {
+        match value {
+            LockupPeriod::None => 0,
+            LockupPeriod::Flex => 1,
+            LockupPeriod::ThreeMonths => 2,
+            LockupPeriod::SixMonths => 3,
+            LockupPeriod::OneYear => 4,
+        }
+    }
+}
+
+impl TryFrom<u8> for LockupPeriod {
+    type Error = MplxRewardsError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(LockupPeriod::Flex),
+            2 => Ok(LockupPeriod::ThreeMonths),
+            3 => Ok(LockupPeriod::SixMonths),
+            4 => Ok(LockupPeriod::OneYear),
+            _ => Err(MplxRewardsError::InvalidLockupPeriod),
+        }
+    }
+}
+
 impl LockupPeriod {
     /// Converts LockupPeriod into the Multiplier
     /// which will be used in rewards calculations
@@ -257,18 +639,13 @@ impl LockupPeriod {
         }
     }
 
-    /// Calculates the time when a lockup should expire
-    pub fn end_timestamp(&self, start_ts: u64) -> Result<u64, MplxRewardsError> {
+    /// Calculates the time when a lockup should expire.
+    /// The lockup starts at the beginning of the epoch it has been made within.
+    pub fn end_timestamp(&self, start_ts: u64, epoch_length: u64) -> Result<u64, MplxRewardsError> {
         // conversion should be unfailable because negative timestamp means the ts is earlier than 1970y
-        let beginning_of_the_day = start_ts - (start_ts % SECONDS_PER_DAY);
+        let beginning_of_the_epoch = start_ts - (start_ts % epoch_length);
 
-        match self {
-            LockupPeriod::None => Err(MplxRewardsError::InvalidLockupPeriod),
-            LockupPeriod::ThreeMonths => Ok(beginning_of_the_day + SECONDS_PER_DAY * 90),
-            LockupPeriod::SixMonths => Ok(beginning_of_the_day + SECONDS_PER_DAY * 180),
-            LockupPeriod::OneYear => Ok(beginning_of_the_day + SECONDS_PER_DAY * 365),
-            LockupPeriod::Flex => Ok(beginning_of_the_day + SECONDS_PER_DAY * 5),
-        }
+        beginning_of_the_epoch.safe_add(SECONDS_PER_DAY * self.days()?)
     }
 
     /// Return number of days plain numbers to make them appliable for the self.weighted_stake_diff
@@ -361,3 +738,14 @@ pub fn create_mining_address(
         program_id,
     )
 }
+
+pub fn create_penalty_history_address(
+    program_id: &Pubkey,
+    mining: &Pubkey,
+    bump: u8,
+) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        &["penalty_history".as_bytes(), &mining.to_bytes(), &[bump]],
+        program_id,
+    )
+}
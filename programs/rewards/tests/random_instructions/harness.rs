@@ -0,0 +1,838 @@
+//! Fixture accounts, syscall stubs and instruction generators shared by the randomized
+//! property test and the fuzz targets. The program runs in-process: the accounts are
+//! serialized the way the runtime passes them to the entrypoint, the clock and the CPIs
+//! are served by syscall stubs.
+//!
+//! Program-owned accounts only ever hold data of one of the program's account types or
+//! zeroes, since no one but the program can write them. So the trees the program reads are
+//! corrupted by passing accounts of another type, e.g. a pool as a mining, while arbitrary
+//! bytes are only fed through the accounts of other programs and the instruction data.
+
+use mplx_rewards::{
+    instruction::*,
+    instructions::process_instruction,
+    state::{
+        DelegateRegistry, Mining, PenaltyHistory, RewardPool, WrappedMining, WrappedRewardPool,
+    },
+    utils::{
+        find_delegate_registry_program_address, find_mining_program_address,
+        find_penalty_history_program_address, find_vault_program_address, BoostProof, LockupPeriod,
+    },
+};
+use rand::{seq::SliceRandom, Rng};
+use solana_program::{
+    account_info::AccountInfo,
+    bpf_loader,
+    clock::Clock,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    program_stubs::SyscallStubs,
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::{SystemError, SystemInstruction},
+    system_program,
+};
+use solana_sdk::native_loader;
+use std::{
+    collections::BTreeMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::atomic::{AtomicI64, Ordering},
+};
+
+const DAY: u64 = 86_400;
+/// Time the fixture accounts are created at
+const START: u64 = 1_700_006_400;
+
+/// Timestamp the clock stub returns
+static NOW: AtomicI64 = AtomicI64::new(START as i64);
+
+/// Serves the clock and the rent, swallows the logs and pretends every CPI succeeds.
+/// Accounts the program asks the system program to create are created, since the program
+/// goes on writing them.
+pub struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, _data: &[&[u8]]) {}
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            unix_timestamp: NOW.load(Ordering::Relaxed),
+            ..Default::default()
+        };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id != system_program::id() {
+            return Ok(());
+        }
+        let Ok(SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        }) = limited_deserialize(&instruction.data, 1024)
+        else {
+            return Ok(());
+        };
+        let account = account_infos
+            .iter()
+            .find(|info| info.key == &instruction.accounts[1].pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if account.lamports() > 0 || !account.data_is_empty() {
+            return Err(ProgramError::Custom(
+                SystemError::AccountAlreadyInUse as u32,
+            ));
+        }
+        account.realloc(space as usize, true)?;
+        account.assign(&owner);
+        **account.lamports.borrow_mut() = lamports;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct Account {
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    executable: bool,
+}
+
+impl Account {
+    fn new(owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            owner,
+            lamports: 1_000_000_000,
+            data,
+            executable: false,
+        }
+    }
+}
+
+/// Pool with a couple of minings along with all the accounts the instructions refer to
+#[derive(Clone)]
+pub struct Fixture {
+    accounts: BTreeMap<Pubkey, Account>,
+    pool: Pubkey,
+    mint: Pubkey,
+    vault: Pubkey,
+    deposit_authority: Pubkey,
+    distribute_authority: Pubkey,
+    fill_authority: Pubkey,
+    payer: Pubkey,
+    owner: Pubkey,
+    owner_token: Pubkey,
+    mining: Pubkey,
+    penalty_history: Pubkey,
+    delegate: Pubkey,
+    delegate_mining: Pubkey,
+    new_owner: Pubkey,
+    new_mining: Pubkey,
+    new_pool: Pubkey,
+    new_vault: Pubkey,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let id = mplx_rewards::ID;
+        let pool = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (vault, vault_bump) = find_vault_program_address(&id, &pool, &mint);
+        let deposit_authority = Pubkey::new_unique();
+        let distribute_authority = Pubkey::new_unique();
+        let fill_authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let owner_token = Pubkey::new_unique();
+        let (mining, mining_bump) = find_mining_program_address(&id, &owner, &pool);
+        let (penalty_history, penalty_history_bump) =
+            find_penalty_history_program_address(&id, &mining);
+        let delegate = Pubkey::new_unique();
+        let (delegate_mining, delegate_mining_bump) =
+            find_mining_program_address(&id, &delegate, &pool);
+        let (registry, registry_bump) =
+            find_delegate_registry_program_address(&id, &delegate_mining);
+        let new_owner = Pubkey::new_unique();
+        let (new_mining, _) = find_mining_program_address(&id, &new_owner, &pool);
+        let new_pool = Pubkey::new_unique();
+        let (new_vault, _) = find_vault_program_address(&id, &new_pool, &mint);
+
+        let mut pool_data = vec![0; WrappedRewardPool::LEN];
+        let mut mining_data = vec![0; WrappedMining::LEN];
+        let mut delegate_mining_data = vec![0; WrappedMining::LEN];
+        {
+            let mut wrapped_pool =
+                WrappedRewardPool::from_uninitialized_bytes_mut(&mut pool_data).unwrap();
+            *wrapped_pool.pool = RewardPool::initialize(
+                vault_bump,
+                deposit_authority,
+                distribute_authority,
+                fill_authority,
+                mint,
+            );
+            wrapped_pool.weighted_stake_diffs.initialize();
+            wrapped_pool.cumulative_index.initialize();
+
+            let mut wrapped_mining =
+                WrappedMining::from_uninitialized_bytes_mut(&mut mining_data).unwrap();
+            *wrapped_mining.mining = Mining::initialize(pool, owner, mint, mining_bump);
+            wrapped_mining.weighted_stake_diffs.initialize();
+            wrapped_mining.positions.initialize();
+            wrapped_mining.delegated_stakes.initialize();
+
+            let mut wrapped_delegate_mining =
+                WrappedMining::from_uninitialized_bytes_mut(&mut delegate_mining_data).unwrap();
+            *wrapped_delegate_mining.mining =
+                Mining::initialize(pool, delegate, mint, delegate_mining_bump);
+            wrapped_delegate_mining.weighted_stake_diffs.initialize();
+            wrapped_delegate_mining.positions.initialize();
+            wrapped_delegate_mining.delegated_stakes.initialize();
+
+            wrapped_pool
+                .pool
+                .fill(1_000_000, START + 30 * DAY, START)
+                .unwrap();
+            let mut ts = START;
+            for (deposit_id, lockup_period) in [
+                LockupPeriod::Flex,
+                LockupPeriod::ThreeMonths,
+                LockupPeriod::OneYear,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let deposit_id = deposit_id as u64;
+                wrapped_pool
+                    .deposit(
+                        &mut wrapped_mining,
+                        deposit_id,
+                        1_000,
+                        lockup_period,
+                        &[],
+                        ts,
+                    )
+                    .unwrap();
+                wrapped_pool
+                    .deposit(
+                        &mut wrapped_delegate_mining,
+                        deposit_id,
+                        5_000,
+                        lockup_period,
+                        &[],
+                        ts,
+                    )
+                    .unwrap();
+                ts += DAY;
+                let rewards = wrapped_pool.pool.rewards_to_distribute(ts).unwrap();
+                wrapped_pool.distribute(rewards, ts).unwrap();
+            }
+        }
+
+        let mut penalty_history_data = vec![0; PenaltyHistory::LEN];
+        *PenaltyHistory::from_uninitialized_bytes_mut(&mut penalty_history_data).unwrap() =
+            PenaltyHistory::initialize(mining, penalty_history_bump);
+        let mut registry_data = vec![0; DelegateRegistry::LEN];
+        *DelegateRegistry::from_uninitialized_bytes_mut(&mut registry_data).unwrap() =
+            DelegateRegistry::initialize(delegate_mining, registry_bump);
+
+        let mut accounts = BTreeMap::new();
+        accounts.insert(pool, Account::new(id, pool_data));
+        accounts.insert(mining, Account::new(id, mining_data));
+        accounts.insert(delegate_mining, Account::new(id, delegate_mining_data));
+        accounts.insert(penalty_history, Account::new(id, penalty_history_data));
+        accounts.insert(registry, Account::new(id, registry_data));
+        accounts.insert(new_pool, Account::new(id, vec![0; WrappedRewardPool::LEN]));
+        accounts.insert(
+            Pubkey::new_unique(),
+            Account::new(id, vec![0; WrappedMining::LEN]),
+        );
+
+        accounts.insert(mint, Account::new(spl_token::id(), token_mint(&payer)));
+        accounts.insert(
+            vault,
+            Account::new(spl_token::id(), token_account(&mint, &vault, 1_000_000)),
+        );
+        accounts.insert(
+            owner_token,
+            Account::new(spl_token::id(), token_account(&mint, &owner, 0)),
+        );
+
+        for key in [
+            deposit_authority,
+            distribute_authority,
+            fill_authority,
+            payer,
+            owner,
+            delegate,
+            new_owner,
+        ] {
+            accounts.insert(key, Account::new(system_program::id(), vec![]));
+        }
+        for (key, loader) in [
+            (spl_token::id(), bpf_loader::id()),
+            (system_program::id(), native_loader::id()),
+        ] {
+            accounts.insert(
+                key,
+                Account {
+                    executable: true,
+                    ..Account::new(loader, vec![])
+                },
+            );
+        }
+
+        Self {
+            accounts,
+            pool,
+            mint,
+            vault,
+            deposit_authority,
+            distribute_authority,
+            fill_authority,
+            payer,
+            owner,
+            owner_token,
+            mining,
+            penalty_history,
+            delegate,
+            delegate_mining,
+            new_owner,
+            new_mining,
+            new_pool,
+            new_vault,
+        }
+    }
+
+    fn random_key(&self, rng: &mut impl Rng) -> Pubkey {
+        *self
+            .accounts
+            .keys()
+            .nth(rng.gen_range(0..self.accounts.len()))
+            .unwrap()
+    }
+}
+
+fn token_mint(authority: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::Some(*authority),
+        supply: 1_000_000_000,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+fn amount(rng: &mut impl Rng) -> u64 {
+    *[0, 1, 500, 1_000, 5_000, 1_000_000, u64::MAX, rng.gen()]
+        .choose(rng)
+        .unwrap()
+}
+
+/// Ids of the fixture deposits along with one that isn't there
+fn deposit_id(rng: &mut impl Rng) -> u64 {
+    rng.gen_range(0..4)
+}
+
+fn timestamp(rng: &mut impl Rng) -> u64 {
+    *[
+        0,
+        START,
+        START + DAY,
+        START + 2 * DAY,
+        START + 90 * DAY,
+        START + 365 * DAY,
+        START + rng.gen_range(0..400 * DAY),
+        u64::MAX,
+    ]
+    .choose(rng)
+    .unwrap()
+}
+
+fn lockup_period(rng: &mut impl Rng) -> LockupPeriod {
+    *[
+        LockupPeriod::None,
+        LockupPeriod::Flex,
+        LockupPeriod::ThreeMonths,
+        LockupPeriod::SixMonths,
+        LockupPeriod::OneYear,
+    ]
+    .choose(rng)
+    .unwrap()
+}
+
+fn split_delegates(rng: &mut impl Rng, f: &Fixture) -> Vec<(Pubkey, u16)> {
+    (0..rng.gen_range(0..3))
+        .map(|_| {
+            let mining = *[f.delegate_mining, f.mining, f.new_mining]
+                .choose(rng)
+                .unwrap();
+            (mining, rng.gen_range(0..3))
+        })
+        .collect()
+}
+
+/// Builds one of the program's instructions with the fixture accounts and random arguments
+fn instruction(rng: &mut impl Rng, f: &Fixture) -> Instruction {
+    let id = &mplx_rewards::ID;
+    let token_program = &spl_token::id();
+    let delegate_mining = *[f.mining, f.delegate_mining].choose(rng).unwrap();
+    let delegate = if delegate_mining == f.mining {
+        f.owner
+    } else {
+        f.delegate
+    };
+    let keys: Vec<Pubkey> = (0..rng.gen_range(0..5))
+        .map(|_| f.random_key(rng))
+        .collect();
+
+    match rng.gen_range(0..31) {
+        0 => initialize_pool(
+            id,
+            &f.new_pool,
+            &f.mint,
+            &f.new_vault,
+            &f.payer,
+            &f.deposit_authority,
+            &f.fill_authority,
+            &f.distribute_authority,
+            token_program,
+        ),
+        1 => fill_vault(
+            id,
+            &f.pool,
+            &f.mint,
+            &f.vault,
+            &f.fill_authority,
+            &f.owner_token,
+            amount(rng),
+            timestamp(rng),
+            token_program,
+        ),
+        2 => initialize_mining(id, &f.pool, &f.new_mining, &f.payer, &f.new_owner),
+        3 => deposit_mining(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.deposit_authority,
+            &delegate_mining,
+            deposit_id(rng),
+            amount(rng),
+            lockup_period(rng),
+            &f.owner,
+            &delegate,
+            &split_delegates(rng, f),
+        ),
+        4 => withdraw_mining(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.deposit_authority,
+            &delegate_mining,
+            deposit_id(rng),
+            amount(rng),
+            &f.owner,
+            &delegate,
+            &split_delegates(rng, f),
+        ),
+        5 => claim(
+            id,
+            &f.pool,
+            &f.mint,
+            &f.vault,
+            &f.mining,
+            &f.owner,
+            &f.deposit_authority,
+            &f.owner_token,
+            token_program,
+        ),
+        6 => extend_stake(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.deposit_authority,
+            &delegate_mining,
+            deposit_id(rng),
+            lockup_period(rng),
+            amount(rng),
+            &f.owner,
+            &delegate,
+            &split_delegates(rng, f),
+        ),
+        7 => distribute_rewards(id, &f.pool, &f.distribute_authority, &f.vault),
+        8 => close_mining(
+            id,
+            &f.mining,
+            &f.owner,
+            &f.payer,
+            &f.deposit_authority,
+            &f.pool,
+        ),
+        9 => change_delegate(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.deposit_authority,
+            &f.owner,
+            &delegate_mining,
+            &f.delegate_mining,
+            &f.delegate,
+            deposit_id(rng),
+            amount(rng),
+            &split_delegates(rng, f),
+            &split_delegates(rng, f),
+        ),
+        10 => slash(
+            id,
+            &f.deposit_authority,
+            &f.pool,
+            &f.mining,
+            &f.penalty_history,
+            &f.owner,
+            deposit_id(rng),
+            amount(rng),
+            rng.gen(),
+        ),
+        11 => decrease_rewards(
+            id,
+            &f.deposit_authority,
+            &f.pool,
+            &f.mining,
+            &f.penalty_history,
+            &f.owner,
+            amount(rng),
+            rng.gen(),
+        ),
+        12 => initialize_penalty_history(id, &f.mining, &f.penalty_history, &f.payer),
+        13 => restore_penalty(
+            id,
+            &f.deposit_authority,
+            &f.pool,
+            &f.mining,
+            &f.penalty_history,
+            &f.owner,
+            rng.gen_range(0..3),
+            rng.gen(),
+        ),
+        14 => set_pool_limits(
+            id,
+            &f.pool,
+            &f.deposit_authority,
+            amount(rng),
+            amount(rng),
+            amount(rng),
+        ),
+        15 => set_boost_config(
+            id,
+            &f.pool,
+            &f.deposit_authority,
+            &f.mint,
+            &f.deposit_authority,
+        ),
+        16 => apply_boost(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.deposit_authority,
+            &keys,
+            &f.owner,
+            rng.gen(),
+            timestamp(rng),
+            *[BoostProof::Collection, BoostProof::Attester]
+                .choose(rng)
+                .unwrap(),
+        ),
+        17 => set_epoch_length(id, &f.pool, &f.deposit_authority, amount(rng)),
+        18 => close_pool(
+            id,
+            &f.pool,
+            &f.mint,
+            &f.vault,
+            &f.deposit_authority,
+            &f.owner_token,
+            &f.payer,
+            token_program,
+        ),
+        19 => sunset_pool(id, &f.pool, &f.deposit_authority, amount(rng)),
+        20 => migrate_pool(id, &f.pool, &f.payer, &f.vault),
+        21 => migrate_mining(id, &f.pool, &f.mining, &f.payer),
+        22 => transfer_mining(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.new_mining,
+            &f.owner,
+            &f.new_owner,
+            &f.deposit_authority,
+            &f.payer,
+            &f.payer,
+        ),
+        23 => merge_mining(
+            id,
+            &f.pool,
+            &f.delegate_mining,
+            &f.mining,
+            &f.delegate,
+            &f.deposit_authority,
+            &f.payer,
+            (0..rng.gen_range(0..5)).map(|_| deposit_id(rng)).collect(),
+        ),
+        24 => split_mining(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.delegate_mining,
+            &f.owner,
+            &f.deposit_authority,
+            deposit_id(rng),
+            deposit_id(rng),
+            amount(rng),
+        ),
+        25 => register_delegate(
+            id,
+            &f.delegate_mining,
+            &f.delegate,
+            &f.payer,
+            amount(rng),
+            keys.clone(),
+            keys,
+        ),
+        26 => update_delegate_registry(
+            id,
+            &f.delegate_mining,
+            &f.delegate,
+            amount(rng),
+            keys.clone(),
+            vec![],
+        ),
+        27 => record_deposit(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.deposit_authority,
+            &f.owner,
+            deposit_id(rng),
+            amount(rng),
+            lockup_period(rng),
+            timestamp(rng),
+        ),
+        28 => set_unbonding_config(id, &f.pool, &f.deposit_authority, timestamp(rng), rng.gen()),
+        29 => request_withdraw(
+            id,
+            &f.pool,
+            &f.mining,
+            &f.owner,
+            deposit_id(rng),
+            amount(rng),
+        ),
+        _ => report_solvency(id, &f.pool, &f.vault),
+    }
+}
+
+/// Makes the fixture accounts wrong in the ways an attacker could pass them
+fn mutate_accounts(rng: &mut impl Rng, f: &mut Fixture, ix: &mut Instruction) {
+    for meta in ix.accounts.iter_mut() {
+        if rng.gen_bool(0.05) {
+            meta.pubkey = f.random_key(rng);
+        }
+        if rng.gen_bool(0.03) {
+            meta.is_signer = !meta.is_signer;
+        }
+        if rng.gen_bool(0.03) {
+            meta.is_writable = !meta.is_writable;
+        }
+    }
+
+    // the data any account of the program might hold
+    let program_data: Vec<Vec<u8>> = f
+        .accounts
+        .values()
+        .filter(|account| account.owner == mplx_rewards::ID)
+        .map(|account| account.data.clone())
+        .collect();
+
+    for meta in &ix.accounts {
+        let Some(account) = f.accounts.get_mut(&meta.pubkey) else {
+            continue;
+        };
+
+        if rng.gen_bool(0.03) {
+            account.owner = *[
+                mplx_rewards::ID,
+                spl_token::id(),
+                system_program::id(),
+                Pubkey::new_unique(),
+            ]
+            .choose(rng)
+            .unwrap();
+        }
+
+        if account.owner == mplx_rewards::ID {
+            if rng.gen_bool(0.05) {
+                account.data = program_data.choose(rng).unwrap().clone();
+            }
+            if rng.gen_bool(0.03) {
+                let len = rng.gen_range(0..account.data.len() + 64);
+                account.data.resize(len, 0);
+            }
+        } else if rng.gen_bool(0.05) {
+            let len = rng.gen_range(0..account.data.len() + 64);
+            account.data.resize(len, 0);
+            for _ in 0..rng.gen_range(1..8) {
+                if let Some(byte) = account.data.choose_mut(rng) {
+                    *byte = rng.gen();
+                }
+            }
+        }
+    }
+}
+
+fn mutate_data(rng: &mut impl Rng, data: &mut Vec<u8>) {
+    match rng.gen_range(0..3) {
+        0 => {
+            for _ in 0..rng.gen_range(1..4) {
+                if let Some(byte) = data.choose_mut(rng) {
+                    *byte = rng.gen();
+                }
+            }
+        }
+        1 => data.truncate(rng.gen_range(0..=data.len())),
+        _ => data.extend((0..rng.gen_range(1..16)).map(|_| rng.gen::<u8>())),
+    }
+}
+
+/// Builds the random instruction over the mutated fixture accounts
+/// and moves the clock to a random date
+pub fn random_instruction(rng: &mut impl Rng, f: &mut Fixture) -> Instruction {
+    let mut ix = instruction(rng, f);
+
+    if rng.gen_bool(0.05) {
+        // anything at all
+        ix.data = (0..rng.gen_range(0..64)).map(|_| rng.gen()).collect();
+        ix.accounts.shuffle(rng);
+    }
+    if rng.gen_bool(0.2) {
+        mutate_data(rng, &mut ix.data);
+    }
+    mutate_accounts(rng, f, &mut ix);
+    NOW.store(
+        timestamp(rng).min(i64::MAX as u64) as i64,
+        Ordering::Relaxed,
+    );
+
+    ix
+}
+
+/// Lays out the accounts and the instruction data the way the runtime passes them to the program
+fn serialize(f: &Fixture, ix: &Instruction) -> Vec<u8> {
+    let mut input = vec![];
+    input.extend_from_slice(&(ix.accounts.len() as u64).to_le_bytes());
+
+    for (i, meta) in ix.accounts.iter().enumerate() {
+        if let Some(first) = ix.accounts[..i]
+            .iter()
+            .position(|m| m.pubkey == meta.pubkey)
+        {
+            input.push(first as u8);
+            input.extend_from_slice(&[0; 7]);
+            continue;
+        }
+
+        let account = f
+            .accounts
+            .get(&meta.pubkey)
+            .cloned()
+            .unwrap_or_else(|| Account {
+                lamports: 0,
+                ..Account::new(system_program::id(), vec![])
+            });
+        input.push(u8::MAX);
+        input.push(meta.is_signer.into());
+        input.push(meta.is_writable.into());
+        input.push(account.executable.into());
+        input.extend_from_slice(&[0; 4]);
+        input.extend_from_slice(meta.pubkey.as_ref());
+        input.extend_from_slice(account.owner.as_ref());
+        input.extend_from_slice(&account.lamports.to_le_bytes());
+        input.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        input.extend_from_slice(&account.data);
+        input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        input.resize(input.len().next_multiple_of(8), 0);
+        input.extend_from_slice(&0u64.to_le_bytes());
+    }
+
+    input.extend_from_slice(&(ix.data.len() as u64).to_le_bytes());
+    input.extend_from_slice(&ix.data);
+    input.extend_from_slice(ix.program_id.as_ref());
+    input
+}
+
+/// Runs the instruction the way the entrypoint does and reports whether the program panicked.
+/// The accounts the instruction has succeeded with are written back to the fixture,
+/// so that the instructions run one after another see the changes of the previous ones.
+pub fn panics(f: &mut Fixture, ix: &Instruction) -> bool {
+    let input = serialize(f, ix);
+    // the account data is aligned the way the runtime aligns it
+    let mut buffer = vec![0u128; input.len().div_ceil(16)];
+    let ptr = buffer.as_mut_ptr() as *mut u8;
+    unsafe { std::ptr::copy_nonoverlapping(input.as_ptr(), ptr, input.len()) };
+
+    let (program_id, accounts, data) = unsafe { solana_program::entrypoint::deserialize(ptr) };
+    let Ok(result) = catch_unwind(AssertUnwindSafe(|| {
+        process_instruction(program_id, &accounts, data)
+    })) else {
+        return true;
+    };
+
+    // failed instructions leave the accounts untouched, as the runtime rolls them back
+    if result.is_ok() {
+        for info in &accounts {
+            if !f.accounts.contains_key(info.key) && info.lamports() == 0 {
+                continue;
+            }
+            f.accounts.insert(
+                *info.key,
+                Account {
+                    owner: *info.owner,
+                    lamports: info.lamports(),
+                    data: info.data.borrow().to_vec(),
+                    executable: info.executable,
+                },
+            );
+        }
+    }
+
+    false
+}
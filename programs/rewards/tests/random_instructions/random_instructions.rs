@@ -0,0 +1,45 @@
+//! Randomized property test: feeds the program seeded random instructions over mutated
+//! account sets and checks it never panics, failing with a `ProgramError` instead.
+//! It isn't coverage guided, the seeds only make every run reproducible,
+//! the coverage guided runs are made by the fuzz targets sharing its harness.
+//!
+//! Set RANDOM_SEED to reproduce a single run and RANDOM_ITERATIONS to run longer.
+
+mod harness;
+
+use harness::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Seeds the test runs with, unless RANDOM_SEED is set
+const SEEDS: [u64; 2] = [0xf022, 0xbad5eed];
+const ITERATIONS: usize = 3_000;
+
+fn run(seed: u64, iterations: usize) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let fixture = Fixture::new();
+
+    for i in 0..iterations {
+        let mut f = fixture.clone();
+        let ix = random_instruction(&mut rng, &mut f);
+
+        assert!(
+            !panics(&mut f, &ix),
+            "program panicked, seed {seed} iteration {i}\ninstruction data: {:?}\naccounts: {:#?}",
+            ix.data,
+            ix.accounts
+        );
+    }
+}
+
+#[test]
+fn program_never_panics() {
+    solana_program::program_stubs::set_syscall_stubs(Box::new(Stubs));
+
+    let iterations = std::env::var("RANDOM_ITERATIONS")
+        .map(|iterations| iterations.parse().unwrap())
+        .unwrap_or(ITERATIONS);
+    match std::env::var("RANDOM_SEED") {
+        Ok(seed) => run(seed.parse().unwrap(), iterations),
+        Err(_) => SEEDS.iter().for_each(|seed| run(*seed, iterations)),
+    }
+}
@@ -0,0 +1,204 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{
+    error::MplxRewardsError,
+    state::{AccountType, WrappedMining, WrappedRewardPool},
+    utils::LockupPeriod,
+};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account, clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer,
+    transaction::Transaction,
+};
+
+async fn setup() -> (ProgramTestContext, TestRewards, Keypair, Keypair, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    let (user, user_reward, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    (context, test_rewards, user, user_reward, user_mining)
+}
+
+async fn claim_from_pool(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    reward_pool: &Pubkey,
+    user: &Keypair,
+    mining: &Pubkey,
+    user_reward: &Pubkey,
+) -> BanksClientResult<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[mplx_rewards::instruction::claim(
+            &mplx_rewards::id(),
+            reward_pool,
+            &test_rewards.token_mint_pubkey,
+            &test_rewards.vault_pubkey,
+            mining,
+            &user.pubkey(),
+            &test_rewards.deposit_authority.pubkey(),
+            user_reward,
+            &spl_token::id(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, user, &test_rewards.deposit_authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn mining_passed_as_pool() {
+    let (mut context, test_rewards, user, user_reward, user_mining) = setup().await;
+
+    claim_from_pool(
+        &mut context,
+        &test_rewards,
+        &user_mining,
+        &user,
+        &user_mining,
+        &user_reward.pubkey(),
+    )
+    .await
+    .assert_on_chain_err(MplxRewardsError::InvalidAccountLength);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[mplx_rewards::instruction::distribute_rewards(
+            &mplx_rewards::id(),
+            &user_mining,
+            &test_rewards.distribution_authority.pubkey(),
+            &test_rewards.vault_pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &test_rewards.distribution_authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidAccountLength);
+}
+
+#[tokio::test]
+async fn pool_passed_as_mining() {
+    let (mut context, test_rewards, user, user_reward, _) = setup().await;
+
+    let other_rewards = TestRewards::new(test_rewards.token_mint_pubkey);
+    other_rewards.initialize_pool(&mut context).await.unwrap();
+
+    test_rewards
+        .claim(
+            &mut context,
+            &user,
+            &other_rewards.reward_pool.pubkey(),
+            &user_reward.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidAccountLength);
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &other_rewards.reward_pool.pubkey(),
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &other_rewards.reward_pool.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidAccountLength);
+}
+
+#[tokio::test]
+async fn uninitialized_pool() {
+    let (mut context, test_rewards, _, _, _) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    // the account is created for the pool, but the pool is never initialized in it
+    let uninitialized_rewards = TestRewards::new(test_rewards.token_mint_pubkey);
+    let account = Account {
+        lamports: rent.minimum_balance(WrappedRewardPool::LEN),
+        data: vec![0; WrappedRewardPool::LEN],
+        owner: mplx_rewards::id(),
+        ..Default::default()
+    };
+    context.set_account(&uninitialized_rewards.reward_pool.pubkey(), &account.into());
+
+    uninitialized_rewards
+        .distribute_rewards(&uninitialized_rewards.distribution_authority, &mut context)
+        .await
+        .assert_on_chain_err(MplxRewardsError::AccountIsNotInitialized);
+
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &uninitialized_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    // the vault of the initialized pool passes the token account checks
+    let tx = Transaction::new_signed_with_payer(
+        &[mplx_rewards::instruction::fill_vault(
+            &mplx_rewards::id(),
+            &uninitialized_rewards.reward_pool.pubkey(),
+            &test_rewards.token_mint_pubkey,
+            &test_rewards.vault_pubkey,
+            &uninitialized_rewards.fill_authority.pubkey(),
+            &rewarder.pubkey(),
+            1_000,
+            SECONDS_PER_DAY * 10,
+            &spl_token::id(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &uninitialized_rewards.fill_authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .assert_on_chain_err(MplxRewardsError::AccountIsNotInitialized);
+}
+
+#[tokio::test]
+async fn account_of_another_type() {
+    let (mut context, test_rewards, user, user_reward, user_mining) = setup().await;
+
+    let mut account = get_account(&mut context, &user_mining).await;
+    WrappedMining::from_bytes_mut(&mut account.data)
+        .unwrap()
+        .mining
+        .data[0] = AccountType::DelegateRegistry.into();
+    context.set_account(&user_mining, &account.into());
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward.pubkey())
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidAccountType);
+}
@@ -0,0 +1,374 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{
+    error::MplxRewardsError,
+    state::{BoostRecord, WrappedMining, WrappedRewardPool},
+    utils::{find_boost_record_program_address, BoostProof, LockupPeriod},
+};
+use sokoban::NodeAllocatorMap;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Keypair, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_rewards.initialize_mining(&mut context, &user).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    (context, test_rewards, user, user_mining)
+}
+
+async fn boost_expiration_date(context: &mut ProgramTestContext, days: u64) -> u64 {
+    let expires_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * days;
+    expires_at - expires_at % SECONDS_PER_DAY
+}
+
+async fn assert_shares(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    mining_addr: &Pubkey,
+    share: u64,
+    total_share: u64,
+) {
+    let mut reward_pool_account = get_account(context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    assert_eq!(wrapped_reward_pool.pool.total_share, total_share);
+
+    let mut mining_account = get_account(context, mining_addr).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.share, share);
+}
+
+#[tokio::test]
+async fn attester_boost() {
+    let (mut context, test_rewards, user, mining_addr) = setup().await;
+    let attester = Keypair::new();
+    test_rewards
+        .set_boost_config(&mut context, &Pubkey::default(), &attester.pubkey())
+        .await
+        .unwrap();
+
+    let expires_at = boost_expiration_date(&mut context, 10).await;
+    let stranger = Keypair::new();
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            5_000,
+            expires_at,
+            BoostProof::Attester,
+            &[stranger.pubkey()],
+            &[&stranger],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidBoostProof);
+
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            5_000,
+            expires_at,
+            BoostProof::Attester,
+            &[attester.pubkey()],
+            &[&attester],
+        )
+        .await
+        .unwrap();
+
+    // 100 * 2 + 50% boost
+    assert_shares(&mut context, &test_rewards, &mining_addr, 300, 300).await;
+
+    let mut reward_pool_account =
+        get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    assert_eq!(
+        *wrapped_reward_pool
+            .weighted_stake_diffs
+            .get(&expires_at)
+            .unwrap(),
+        100
+    );
+
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            1_000,
+            expires_at,
+            BoostProof::Attester,
+            &[attester.pubkey()],
+            &[&attester],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::BoostIsAlreadyActive);
+}
+
+#[tokio::test]
+async fn boost_expires() {
+    let (mut context, test_rewards, user, mining_addr) = setup().await;
+    let attester = Keypair::new();
+    test_rewards
+        .set_boost_config(&mut context, &Pubkey::default(), &attester.pubkey())
+        .await
+        .unwrap();
+
+    let expires_at = boost_expiration_date(&mut context, 10).await;
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            5_000,
+            expires_at,
+            BoostProof::Attester,
+            &[attester.pubkey()],
+            &[&attester],
+        )
+        .await
+        .unwrap();
+
+    advance_clock_by_ts(&mut context, (SECONDS_PER_DAY * 11).try_into().unwrap()).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            10,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &mining_addr,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let mut mining_account = get_account(&mut context, &mining_addr).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.share, 210);
+}
+
+#[tokio::test]
+async fn boost_cannot_outlast_the_longest_lockup() {
+    let (mut context, test_rewards, user, mining_addr) = setup().await;
+    let attester = Keypair::new();
+    test_rewards
+        .set_boost_config(&mut context, &Pubkey::default(), &attester.pubkey())
+        .await
+        .unwrap();
+
+    let expires_at = boost_expiration_date(&mut context, 366).await;
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            5_000,
+            expires_at,
+            BoostProof::Attester,
+            &[attester.pubkey()],
+            &[&attester],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidBoost);
+
+    let expires_at = boost_expiration_date(&mut context, 365).await;
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            5_000,
+            expires_at,
+            BoostProof::Attester,
+            &[attester.pubkey()],
+            &[&attester],
+        )
+        .await
+        .unwrap();
+
+    assert_shares(&mut context, &test_rewards, &mining_addr, 300, 300).await;
+}
+
+#[tokio::test]
+async fn collection_boost() {
+    let (mut context, test_rewards, user, mining_addr) = setup().await;
+    let collection = Pubkey::new_unique();
+    test_rewards
+        .set_boost_config(&mut context, &collection, &Pubkey::default())
+        .await
+        .unwrap();
+
+    let payer = context.payer.pubkey();
+    let nft_mint = Keypair::new();
+    create_mint(&mut context, &nft_mint, &payer).await.unwrap();
+    let nft_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &nft_account,
+        &nft_mint.pubkey(),
+        &user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(&mut context, &nft_mint.pubkey(), &nft_account.pubkey(), 1)
+        .await
+        .unwrap();
+
+    let expires_at = boost_expiration_date(&mut context, 30).await;
+    let (boost_record, _) = find_boost_record_program_address(
+        &mplx_rewards::id(),
+        &test_rewards.reward_pool.pubkey(),
+        &nft_mint.pubkey(),
+    );
+
+    let metadata = create_nft_metadata(&mut context, &nft_mint.pubkey(), &collection, false);
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            2_500,
+            expires_at,
+            BoostProof::Collection,
+            &[nft_account.pubkey(), metadata, boost_record, payer],
+            &[],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidBoostProof);
+
+    let metadata = create_nft_metadata(&mut context, &nft_mint.pubkey(), &collection, true);
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            2_500,
+            expires_at,
+            BoostProof::Collection,
+            &[nft_account.pubkey(), metadata, boost_record, payer],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    // 100 * 2 + 25% boost
+    assert_shares(&mut context, &test_rewards, &mining_addr, 250, 250).await;
+
+    // the same NFT cannot boost another mining until the boost expires
+    let other_user = Keypair::new();
+    let other_mining_addr = test_rewards
+        .initialize_mining(&mut context, &other_user)
+        .await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &other_mining_addr,
+            100,
+            LockupPeriod::ThreeMonths,
+            &other_user.pubkey(),
+            &other_mining_addr,
+            &other_user.pubkey(),
+        )
+        .await
+        .unwrap();
+    let other_nft_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &other_nft_account,
+        &nft_mint.pubkey(),
+        &other_user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &nft_mint.pubkey(),
+        &other_nft_account.pubkey(),
+        1,
+    )
+    .await
+    .unwrap();
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &other_mining_addr,
+            &other_user.pubkey(),
+            2_500,
+            expires_at,
+            BoostProof::Collection,
+            &[other_nft_account.pubkey(), metadata, boost_record, payer],
+            &[],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::NftIsAlreadyUsedForBoost);
+
+    // the clock is moved without warping, since the metadata accounts are set out of thin air
+    let mut clock = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap();
+    clock.unix_timestamp += (SECONDS_PER_DAY * 31) as i64;
+    context.set_sysvar(&clock);
+    let expires_at = boost_expiration_date(&mut context, 30).await;
+    test_rewards
+        .apply_boost(
+            &mut context,
+            &other_mining_addr,
+            &other_user.pubkey(),
+            2_500,
+            expires_at,
+            BoostProof::Collection,
+            &[other_nft_account.pubkey(), metadata, boost_record, payer],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let mut boost_record_account = get_account(&mut context, &boost_record).await;
+    let boost_record_data = &mut boost_record_account.data.borrow_mut();
+    let record = BoostRecord::from_bytes_mut(boost_record_data).unwrap();
+    assert_eq!(record.nft_mint, nft_mint.pubkey());
+    assert_eq!(record.mining, other_mining_addr);
+    assert_eq!(record.expires_at, expires_at);
+}
@@ -51,7 +51,7 @@ async fn change_delegate_to_the_same() {
     let (mut context, test_rewards, _) = setup().await;
 
     let (user_a, _, user_mining_a) = create_end_user(&mut context, &test_rewards).await;
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &user_mining_a,
@@ -71,6 +71,7 @@ async fn change_delegate_to_the_same() {
             &user_mining_a,
             &user_mining_a,
             &user_a.pubkey(),
+            deposit_id,
             6_000_000,
         )
         .await
@@ -95,6 +96,10 @@ async fn change_delegate_then_claim() {
         )
         .await
         .unwrap();
+    test_rewards
+        .register_delegate(&mut context, &delegate_mining, &delegate, 0, vec![], vec![])
+        .await
+        .unwrap();
     let mut delegate_mining_account = get_account(&mut context, &delegate_mining).await;
     let d_mining_data = &mut delegate_mining_account.data.borrow_mut();
     let d_wrapped_mining = WrappedMining::from_bytes_mut(d_mining_data).unwrap();
@@ -103,7 +108,7 @@ async fn change_delegate_then_claim() {
 
     let (user_a, user_rewards_a, user_mining_a) =
         create_end_user(&mut context, &test_rewards).await;
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &user_mining_a,
@@ -123,6 +128,7 @@ async fn change_delegate_then_claim() {
             &delegate_mining,
             &user_mining_a,
             &delegate.pubkey(),
+            deposit_id,
             1_000_000,
         )
         .await
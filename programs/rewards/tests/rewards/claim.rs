@@ -1,11 +1,14 @@
-use crate::utils::*;
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
 use mplx_rewards::{
+    error::MplxRewardsError,
     state::{WrappedMining, WrappedRewardPool},
     utils::LockupPeriod,
 };
 use solana_program::{program_pack::Pack, pubkey::Pubkey};
 use solana_program_test::*;
-use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use solana_sdk::{
+    clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer, transaction::Transaction,
+};
 use spl_token::state::Account;
 use std::borrow::{Borrow, BorrowMut};
 
@@ -757,7 +760,7 @@ async fn claim_after_withdraw_is_correct() {
         .await
         .unwrap();
     // D3
-    test_rewards
+    let d3_deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &user_mining_b,
@@ -864,6 +867,7 @@ async fn claim_after_withdraw_is_correct() {
             &mut context,
             &user_mining_b,
             &user_mining_b,
+            d3_deposit_id,
             150,
             &user_b.pubkey(),
             &user_b.pubkey(),
@@ -1022,6 +1026,10 @@ async fn claim_with_delegate() {
         )
         .await
         .unwrap();
+    test_rewards
+        .register_delegate(&mut context, &delegate_mining, &delegate, 0, vec![], vec![])
+        .await
+        .unwrap();
     let mut delegate_mining_account = get_account(&mut context, &delegate_mining).await;
     let d_mining_data = &mut delegate_mining_account.data.borrow_mut();
     let d_wrapped_mining = WrappedMining::from_bytes_mut(d_mining_data).unwrap();
@@ -1115,3 +1123,117 @@ async fn claim_with_delegate() {
 
     assert_eq!(delegate_rewards.amount, 760_000);
 }
+
+async fn claim_with_accounts(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    reward_mint: &Pubkey,
+    vault: &Pubkey,
+    user: &Keypair,
+    mining: &Pubkey,
+    user_reward: &Pubkey,
+) -> BanksClientResult<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[mplx_rewards::instruction::claim(
+            &mplx_rewards::id(),
+            &test_rewards.reward_pool.pubkey(),
+            reward_mint,
+            vault,
+            mining,
+            &user.pubkey(),
+            &test_rewards.deposit_authority.pubkey(),
+            user_reward,
+            &spl_token::id(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, user, &test_rewards.deposit_authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn claim_with_another_reward_mint_fails() {
+    let (mut context, test_rewards, _) = setup().await;
+
+    let (user, user_reward, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    // the pool of another mint, whose vault is a valid token account as well
+    let payer = context.payer.pubkey();
+    let other_mint = Keypair::new();
+    create_mint(&mut context, &other_mint, &payer)
+        .await
+        .unwrap();
+    let other_rewards = TestRewards::new(other_mint.pubkey());
+    other_rewards.initialize_pool(&mut context).await.unwrap();
+    let other_user_reward = Keypair::new();
+    create_token_account(
+        &mut context,
+        &other_user_reward,
+        &other_mint.pubkey(),
+        &user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    claim_with_accounts(
+        &mut context,
+        &test_rewards,
+        &other_mint.pubkey(),
+        &other_rewards.vault_pubkey,
+        &user,
+        &user_mining,
+        &user_reward.pubkey(),
+    )
+    .await
+    .assert_on_chain_err(MplxRewardsError::InvalidRewardMint);
+
+    claim_with_accounts(
+        &mut context,
+        &test_rewards,
+        &test_rewards.token_mint_pubkey,
+        &other_rewards.vault_pubkey,
+        &user,
+        &user_mining,
+        &user_reward.pubkey(),
+    )
+    .await
+    .assert_on_chain_err(MplxRewardsError::InvalidRewardMint);
+
+    test_rewards
+        .claim(
+            &mut context,
+            &user,
+            &user_mining,
+            &other_user_reward.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidRewardMint);
+
+    // the mining recorded with another mint can't claim from the pool
+    let mut mining_account = get_account(&mut context, &user_mining).await;
+    WrappedMining::from_bytes_mut(&mut mining_account.data)
+        .unwrap()
+        .mining
+        .reward_mint = other_mint.pubkey();
+    context.set_account(&user_mining, &mining_account.into());
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward.pubkey())
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidRewardMint);
+}
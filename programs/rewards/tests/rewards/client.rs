@@ -0,0 +1,712 @@
+use mplx_rewards::{
+    instruction,
+    utils::{
+        find_delegate_registry_program_address, find_penalty_history_program_address, BoostProof,
+        LockupPeriod,
+    },
+};
+use num_traits::FromPrimitive;
+use rewards::{instructions::*, types};
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_program};
+
+/// Every instruction the generated client builds must be the one the program's builder does,
+/// so that the client cannot fall behind the program.
+fn assert_same(program: Instruction, client: Instruction) {
+    assert_eq!(program.program_id, client.program_id);
+    assert_eq!(program.accounts, client.accounts);
+    assert_eq!(program.data, client.data);
+}
+
+struct Keys {
+    reward_pool: Pubkey,
+    reward_mint: Pubkey,
+    vault: Pubkey,
+    mining: Pubkey,
+    mining_owner: Pubkey,
+    delegate_mining: Pubkey,
+    delegate: Pubkey,
+    deposit_authority: Pubkey,
+    payer: Pubkey,
+    token_account: Pubkey,
+    target_account: Pubkey,
+}
+
+impl Keys {
+    fn new() -> Self {
+        Self {
+            reward_pool: Pubkey::new_unique(),
+            reward_mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            mining: Pubkey::new_unique(),
+            mining_owner: Pubkey::new_unique(),
+            delegate_mining: Pubkey::new_unique(),
+            delegate: Pubkey::new_unique(),
+            deposit_authority: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            target_account: Pubkey::new_unique(),
+        }
+    }
+
+    fn delegate_registry(&self) -> Pubkey {
+        find_delegate_registry_program_address(&mplx_rewards::ID, &self.delegate_mining).0
+    }
+
+    fn penalty_history(&self) -> Pubkey {
+        find_penalty_history_program_address(&mplx_rewards::ID, &self.mining).0
+    }
+}
+
+#[test]
+fn pool_instructions() {
+    let k = Keys::new();
+    let fill_authority = Pubkey::new_unique();
+    let distribute_authority = Pubkey::new_unique();
+
+    assert_same(
+        instruction::initialize_pool(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.reward_mint,
+            &k.vault,
+            &k.payer,
+            &k.deposit_authority,
+            &fill_authority,
+            &distribute_authority,
+            &spl_token::id(),
+        ),
+        InitializePoolBuilder::new()
+            .reward_pool(k.reward_pool)
+            .reward_mint(k.reward_mint)
+            .vault(k.vault)
+            .payer(k.payer)
+            .deposit_authority(k.deposit_authority)
+            .fill_authority(fill_authority)
+            .distribute_authority(distribute_authority)
+            .instruction(),
+    );
+    assert_same(
+        instruction::fill_vault(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.reward_mint,
+            &k.vault,
+            &fill_authority,
+            &k.token_account,
+            1_000,
+            1_700_000_000,
+            &spl_token::id(),
+        ),
+        FillVaultBuilder::new()
+            .reward_pool(k.reward_pool)
+            .reward_mint(k.reward_mint)
+            .vault(k.vault)
+            .fill_authority(fill_authority)
+            .source_token_account(k.token_account)
+            .rewards(1_000)
+            .distribution_ends_at(1_700_000_000)
+            .instruction(),
+    );
+    assert_same(
+        instruction::distribute_rewards(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &distribute_authority,
+            &k.vault,
+        ),
+        DistributeRewardsBuilder::new()
+            .reward_pool(k.reward_pool)
+            .distribute_authority(distribute_authority)
+            .vault(k.vault)
+            .instruction(),
+    );
+    assert_same(
+        instruction::set_pool_limits(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.deposit_authority,
+            1,
+            2,
+            3,
+        ),
+        SetPoolLimitsBuilder::new()
+            .reward_pool(k.reward_pool)
+            .deposit_authority(k.deposit_authority)
+            .min_deposit(1)
+            .max_weighted_share_per_mining(2)
+            .max_total_share(3)
+            .instruction(),
+    );
+    let boost_collection = Pubkey::new_unique();
+    let boost_attester = Pubkey::new_unique();
+    assert_same(
+        instruction::set_boost_config(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.deposit_authority,
+            &boost_collection,
+            &boost_attester,
+        ),
+        SetBoostConfigBuilder::new()
+            .reward_pool(k.reward_pool)
+            .deposit_authority(k.deposit_authority)
+            .boost_collection(boost_collection)
+            .boost_attester(boost_attester)
+            .instruction(),
+    );
+    assert_same(
+        instruction::set_epoch_length(&mplx_rewards::ID, &k.reward_pool, &k.deposit_authority, 7),
+        SetEpochLengthBuilder::new()
+            .reward_pool(k.reward_pool)
+            .deposit_authority(k.deposit_authority)
+            .epoch_length_secs(7)
+            .instruction(),
+    );
+    assert_same(
+        instruction::set_unbonding_config(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.deposit_authority,
+            7,
+            5_000,
+        ),
+        SetUnbondingConfigBuilder::new()
+            .reward_pool(k.reward_pool)
+            .deposit_authority(k.deposit_authority)
+            .unbonding_period_secs(7)
+            .unbonding_multiplier_bps(5_000)
+            .instruction(),
+    );
+    assert_same(
+        instruction::sunset_pool(&mplx_rewards::ID, &k.reward_pool, &k.deposit_authority, 7),
+        SunsetPoolBuilder::new()
+            .reward_pool(k.reward_pool)
+            .deposit_authority(k.deposit_authority)
+            .grace_period_secs(7)
+            .instruction(),
+    );
+    assert_same(
+        instruction::close_pool(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.reward_mint,
+            &k.vault,
+            &k.deposit_authority,
+            &k.token_account,
+            &k.target_account,
+            &spl_token::id(),
+        ),
+        ClosePoolBuilder::new()
+            .reward_pool(k.reward_pool)
+            .reward_mint(k.reward_mint)
+            .vault(k.vault)
+            .deposit_authority(k.deposit_authority)
+            .recipient_token_account(k.token_account)
+            .target_account(k.target_account)
+            .instruction(),
+    );
+    assert_same(
+        instruction::migrate_pool(&mplx_rewards::ID, &k.reward_pool, &k.payer, &k.vault),
+        MigratePoolBuilder::new()
+            .reward_pool(k.reward_pool)
+            .payer(k.payer)
+            .vault(k.vault)
+            .instruction(),
+    );
+    assert_same(
+        instruction::report_solvency(&mplx_rewards::ID, &k.reward_pool, &k.vault),
+        ReportSolvencyBuilder::new()
+            .reward_pool(k.reward_pool)
+            .vault(k.vault)
+            .instruction(),
+    );
+}
+
+#[test]
+fn stake_instructions() {
+    let k = Keys::new();
+
+    assert_same(
+        instruction::deposit_mining(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.deposit_authority,
+            &k.delegate_mining,
+            1,
+            100,
+            LockupPeriod::ThreeMonths,
+            &k.mining_owner,
+            &k.delegate,
+            &[],
+        ),
+        DepositMiningBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .deposit_authority(k.deposit_authority)
+            .delegate_mining(k.delegate_mining)
+            .delegate_registry(Some(k.delegate_registry()))
+            .deposit_id(1)
+            .amount(100)
+            .lockup_period(types::LockupPeriod::ThreeMonths)
+            .mining_owner(k.mining_owner)
+            .delegate(k.delegate)
+            .delegate_weights(vec![])
+            .instruction(),
+    );
+    assert_same(
+        instruction::withdraw_mining(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.deposit_authority,
+            &k.delegate_mining,
+            1,
+            100,
+            &k.mining_owner,
+            &k.delegate,
+            &[],
+        ),
+        WithdrawMiningBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .deposit_authority(k.deposit_authority)
+            .delegate_mining(k.delegate_mining)
+            .deposit_id(1)
+            .amount(100)
+            .mining_owner(k.mining_owner)
+            .delegate(k.delegate)
+            .delegate_weights(vec![])
+            .instruction(),
+    );
+    assert_same(
+        instruction::extend_stake(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.deposit_authority,
+            &k.delegate_mining,
+            1,
+            LockupPeriod::OneYear,
+            100,
+            &k.mining_owner,
+            &k.delegate,
+            &[],
+        ),
+        ExtendStakeBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .deposit_authority(k.deposit_authority)
+            .delegate_mining(k.delegate_mining)
+            .delegate_registry(Some(k.delegate_registry()))
+            .deposit_id(1)
+            .new_lockup_period(types::LockupPeriod::OneYear)
+            .additional_amount(100)
+            .mining_owner(k.mining_owner)
+            .delegate(k.delegate)
+            .delegate_weights(vec![])
+            .instruction(),
+    );
+    let old_delegate_mining = Pubkey::new_unique();
+    assert_same(
+        instruction::change_delegate(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.deposit_authority,
+            &k.mining_owner,
+            &old_delegate_mining,
+            &k.delegate_mining,
+            &k.delegate,
+            1,
+            100,
+            &[],
+            &[],
+        ),
+        ChangeDelegateBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .deposit_authority(k.deposit_authority)
+            .mining_owner(k.mining_owner)
+            .old_delegate_mining(old_delegate_mining)
+            .new_delegate_mining(k.delegate_mining)
+            .new_delegate_registry(Some(k.delegate_registry()))
+            .deposit_id(1)
+            .staked_amount(100)
+            .new_delegate(k.delegate)
+            .old_delegate_weights(vec![])
+            .new_delegate_weights(vec![])
+            .instruction(),
+    );
+    assert_same(
+        instruction::record_deposit(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.deposit_authority,
+            &k.mining_owner,
+            1,
+            100,
+            LockupPeriod::SixMonths,
+            1_700_000_000,
+        ),
+        RecordDepositBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .deposit_authority(k.deposit_authority)
+            .mining_owner(k.mining_owner)
+            .deposit_id(1)
+            .amount(100)
+            .lockup_period(types::LockupPeriod::SixMonths)
+            .deposit_start_ts(1_700_000_000)
+            .instruction(),
+    );
+    assert_same(
+        instruction::request_withdraw(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.mining_owner,
+            1,
+            100,
+        ),
+        RequestWithdrawBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .mining_owner(k.mining_owner)
+            .deposit_id(1)
+            .amount(100)
+            .instruction(),
+    );
+    let proof_accounts = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    assert_same(
+        instruction::apply_boost(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.deposit_authority,
+            &proof_accounts,
+            &k.mining_owner,
+            1_000,
+            1_700_000_000,
+            BoostProof::Collection,
+        ),
+        ApplyBoostBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .deposit_authority(k.deposit_authority)
+            .proof(proof_accounts[0])
+            .proof_metadata(Some(proof_accounts[1]))
+            .boost_record(Some(proof_accounts[2]))
+            .payer(Some(proof_accounts[3]))
+            .system_program(Some(system_program::id()))
+            .mining_owner(k.mining_owner)
+            .boost_bps(1_000)
+            .expires_at(1_700_000_000)
+            .proof_kind(types::BoostProof::Collection)
+            .instruction(),
+    );
+}
+
+#[test]
+fn mining_instructions() {
+    let k = Keys::new();
+
+    assert_same(
+        instruction::initialize_mining(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.payer,
+            &k.mining_owner,
+        ),
+        InitializeMiningBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .payer(k.payer)
+            .mining_owner(k.mining_owner)
+            .instruction(),
+    );
+    assert_same(
+        instruction::claim(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.reward_mint,
+            &k.vault,
+            &k.mining,
+            &k.mining_owner,
+            &k.deposit_authority,
+            &k.token_account,
+            &spl_token::id(),
+        ),
+        ClaimBuilder::new()
+            .reward_pool(k.reward_pool)
+            .reward_mint(k.reward_mint)
+            .vault(k.vault)
+            .mining(k.mining)
+            .mining_owner(k.mining_owner)
+            .deposit_authority(k.deposit_authority)
+            .mining_owner_reward_token_account(k.token_account)
+            .instruction(),
+    );
+    assert_same(
+        instruction::close_mining(
+            &mplx_rewards::ID,
+            &k.mining,
+            &k.mining_owner,
+            &k.target_account,
+            &k.deposit_authority,
+            &k.reward_pool,
+        ),
+        CloseMiningBuilder::new()
+            .mining(k.mining)
+            .mining_owner(k.mining_owner)
+            .target_account(k.target_account)
+            .deposit_authority(k.deposit_authority)
+            .reward_pool(k.reward_pool)
+            .instruction(),
+    );
+    assert_same(
+        instruction::migrate_mining(&mplx_rewards::ID, &k.reward_pool, &k.mining, &k.payer),
+        MigrateMiningBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .payer(k.payer)
+            .instruction(),
+    );
+    let new_mining = Pubkey::new_unique();
+    let new_owner = Pubkey::new_unique();
+    assert_same(
+        instruction::transfer_mining(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &new_mining,
+            &k.mining_owner,
+            &new_owner,
+            &k.deposit_authority,
+            &k.payer,
+            &k.target_account,
+        ),
+        TransferMiningBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .new_mining(new_mining)
+            .mining_owner(k.mining_owner)
+            .deposit_authority(k.deposit_authority)
+            .payer(k.payer)
+            .target_account(k.target_account)
+            .system_program(system_program::id())
+            .new_owner(new_owner)
+            .instruction(),
+    );
+    assert_same(
+        instruction::merge_mining(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.delegate_mining,
+            &k.mining_owner,
+            &k.deposit_authority,
+            &k.target_account,
+            vec![1, 2],
+        ),
+        MergeMiningBuilder::new()
+            .reward_pool(k.reward_pool)
+            .source_mining(k.mining)
+            .mining(k.delegate_mining)
+            .source_mining_owner(k.mining_owner)
+            .deposit_authority(k.deposit_authority)
+            .target_account(k.target_account)
+            .source_penalty_history(k.penalty_history())
+            .source_delegate_registry(
+                find_delegate_registry_program_address(&mplx_rewards::ID, &k.mining).0,
+            )
+            .deposit_ids(vec![1, 2])
+            .instruction(),
+    );
+    assert_same(
+        instruction::split_mining(
+            &mplx_rewards::ID,
+            &k.reward_pool,
+            &k.mining,
+            &k.delegate_mining,
+            &k.mining_owner,
+            &k.deposit_authority,
+            1,
+            2,
+            100,
+        ),
+        SplitMiningBuilder::new()
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .destination_mining(k.delegate_mining)
+            .mining_owner(k.mining_owner)
+            .deposit_authority(k.deposit_authority)
+            .deposit_id(1)
+            .destination_deposit_id(2)
+            .amount(100)
+            .instruction(),
+    );
+}
+
+#[test]
+fn penalty_instructions() {
+    let k = Keys::new();
+
+    assert_same(
+        instruction::initialize_penalty_history(
+            &mplx_rewards::ID,
+            &k.mining,
+            &k.penalty_history(),
+            &k.payer,
+        ),
+        InitializePenaltyHistoryBuilder::new()
+            .mining(k.mining)
+            .penalty_history(k.penalty_history())
+            .payer(k.payer)
+            .instruction(),
+    );
+    assert_same(
+        instruction::slash(
+            &mplx_rewards::ID,
+            &k.deposit_authority,
+            &k.reward_pool,
+            &k.mining,
+            &k.penalty_history(),
+            &k.mining_owner,
+            1,
+            100,
+            7,
+        ),
+        SlashBuilder::new()
+            .deposit_authority(k.deposit_authority)
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .penalty_history(Some(k.penalty_history()))
+            .mining_owner(k.mining_owner)
+            .deposit_id(1)
+            .slash_amount_in_native(100)
+            .reason_code(7)
+            .instruction(),
+    );
+    assert_same(
+        instruction::decrease_rewards(
+            &mplx_rewards::ID,
+            &k.deposit_authority,
+            &k.reward_pool,
+            &k.mining,
+            &k.penalty_history(),
+            &k.mining_owner,
+            100,
+            7,
+        ),
+        DecreaseRewardsBuilder::new()
+            .deposit_authority(k.deposit_authority)
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .penalty_history(Some(k.penalty_history()))
+            .mining_owner(k.mining_owner)
+            .decreased_weighted_stake_number(100)
+            .reason_code(7)
+            .instruction(),
+    );
+    assert_same(
+        instruction::restore_penalty(
+            &mplx_rewards::ID,
+            &k.deposit_authority,
+            &k.reward_pool,
+            &k.mining,
+            &k.penalty_history(),
+            &k.mining_owner,
+            3,
+            true,
+        ),
+        RestorePenaltyBuilder::new()
+            .deposit_authority(k.deposit_authority)
+            .reward_pool(k.reward_pool)
+            .mining(k.mining)
+            .penalty_history(k.penalty_history())
+            .mining_owner(k.mining_owner)
+            .penalty_id(3)
+            .credit_lost_rewards(true)
+            .instruction(),
+    );
+}
+
+#[test]
+fn delegate_registry_instructions() {
+    let k = Keys::new();
+    let allow_list = vec![Pubkey::new_unique()];
+    let deny_list = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+    assert_same(
+        instruction::register_delegate(
+            &mplx_rewards::ID,
+            &k.delegate_mining,
+            &k.delegate,
+            &k.payer,
+            100,
+            allow_list.clone(),
+            deny_list.clone(),
+        ),
+        RegisterDelegateBuilder::new()
+            .mining(k.delegate_mining)
+            .delegate_registry(k.delegate_registry())
+            .mining_owner(k.delegate)
+            .payer(k.payer)
+            .max_stake_from_others(100)
+            .allow_list(allow_list.clone())
+            .deny_list(deny_list.clone())
+            .instruction(),
+    );
+    assert_same(
+        instruction::update_delegate_registry(
+            &mplx_rewards::ID,
+            &k.delegate_mining,
+            &k.delegate,
+            100,
+            allow_list.clone(),
+            deny_list.clone(),
+        ),
+        UpdateDelegateRegistryBuilder::new()
+            .mining(k.delegate_mining)
+            .delegate_registry(k.delegate_registry())
+            .mining_owner(k.delegate)
+            .max_stake_from_others(100)
+            .allow_list(allow_list)
+            .deny_list(deny_list)
+            .instruction(),
+    );
+    assert_same(
+        instruction::unregister_delegate(
+            &mplx_rewards::ID,
+            &k.delegate_mining,
+            &k.delegate,
+            &k.target_account,
+        ),
+        UnregisterDelegateBuilder::new()
+            .mining(k.delegate_mining)
+            .delegate_registry(k.delegate_registry())
+            .mining_owner(k.delegate)
+            .target_account(k.target_account)
+            .instruction(),
+    );
+}
+
+#[test]
+fn errors() {
+    let mut code = 0;
+    while let Some(error) = mplx_rewards::error::MplxRewardsError::from_u32(code) {
+        let client_error = rewards::errors::MplxRewardsError::from_u32(code)
+            .unwrap_or_else(|| panic!("client has no error {code}"));
+        assert_eq!(error.to_string(), client_error.to_string());
+        code += 1;
+    }
+    assert_eq!(rewards::errors::MplxRewardsError::from_u32(code), None);
+}
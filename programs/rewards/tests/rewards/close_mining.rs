@@ -87,6 +87,10 @@ async fn close_when_has_stake_from_others() {
         )
         .await
         .unwrap();
+    test_rewards
+        .register_delegate(&mut context, &delegate_mining, &delegate, 0, vec![], vec![])
+        .await
+        .unwrap();
     let mut delegate_mining_account = get_account(&mut context, &delegate_mining).await;
     let d_mining_data = &mut delegate_mining_account.data.borrow_mut();
     let d_wrapped_mining = WrappedMining::from_bytes_mut(d_mining_data).unwrap();
@@ -0,0 +1,197 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, utils::LockupPeriod};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+
+async fn setup() -> (ProgramTestContext, TestRewards, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 10;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            1_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+
+    (context, test_rewards, rewarder.pubkey())
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_rewards, rewarder) = setup().await;
+    assert_tokens(&mut context, &rewarder, 999_000).await;
+
+    let target = Keypair::new();
+    test_rewards
+        .close_pool(&mut context, &rewarder, &target.pubkey())
+        .await
+        .unwrap();
+
+    assert_tokens(&mut context, &rewarder, 1_000_000).await;
+
+    let vault_after = context
+        .banks_client
+        .get_account(test_rewards.vault_pubkey)
+        .await
+        .unwrap();
+    assert_eq!(None, vault_after);
+    let reward_pool_after = context
+        .banks_client
+        .get_account(test_rewards.reward_pool.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(None, reward_pool_after);
+
+    let target = get_account(&mut context, &target.pubkey()).await;
+    assert!(target.lamports > 0);
+}
+
+#[tokio::test]
+async fn close_when_has_stakes() {
+    let (mut context, test_rewards, rewarder) = setup().await;
+
+    let (user, _, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .close_pool(&mut context, &rewarder, &Keypair::new().pubkey())
+        .await
+        .assert_on_chain_err(MplxRewardsError::PoolHasStakes);
+}
+
+#[tokio::test]
+async fn close_when_has_unclaimed_rewards() {
+    let (mut context, test_rewards, rewarder) = setup().await;
+
+    let (user, user_rewards, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &user_mining,
+            &user_mining,
+            deposit_id,
+            100,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    // the staker has left, but the distributed rewards are still owed to them
+    let target = Keypair::new();
+    test_rewards
+        .close_pool(&mut context, &rewarder, &target.pubkey())
+        .await
+        .assert_on_chain_err(MplxRewardsError::PoolHasUnclaimedRewards);
+
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &user,
+        &user_mining,
+        &user_rewards.pubkey(),
+        111,
+    )
+    .await;
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+    test_rewards
+        .close_pool(&mut context, &rewarder, &target.pubkey())
+        .await
+        .unwrap();
+    assert_tokens(&mut context, &rewarder, 999_889).await;
+}
+
+#[tokio::test]
+async fn sweep_to_foreign_account_fails() {
+    let (mut context, test_rewards, _) = setup().await;
+
+    let stranger_account = Keypair::new();
+    create_token_account(
+        &mut context,
+        &stranger_account,
+        &test_rewards.token_mint_pubkey,
+        &Keypair::new().pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    let res = test_rewards
+        .close_pool(
+            &mut context,
+            &stranger_account.pubkey(),
+            &Keypair::new().pubkey(),
+        )
+        .await;
+    assert!(res.is_err());
+}
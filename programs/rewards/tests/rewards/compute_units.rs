@@ -0,0 +1,398 @@
+use crate::{simulator::Simulator, utils::*};
+use mplx_rewards::{
+    state::{INDEX_HISTORY_MAX_SIZE, MINING_MODIFIERS_TREE_CAPACITY, POOL_MODIFIERS_TREE_CAPACITY},
+    utils::LockupPeriod,
+};
+use sokoban::NodeAllocatorMap;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    clock::{Clock, SECONDS_PER_DAY},
+    compute_budget::ComputeBudgetInstruction,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Compute units every benchmark may consume, one `name units` per line, `#` starts a comment.
+/// Rerun the benchmarks against the SBF build with `RECORD_COMPUTE_UNITS=1` to record it again,
+/// the measured units are recorded along with the margin.
+const BASELINE_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/rewards/fixtures/compute_units.txt"
+);
+
+/// Percentage of the measured units recorded on top of them, so that changes of the runtime
+/// and the toolchain don't fail the benchmarks
+const BASELINE_MARGIN_PERCENT: u64 = 10;
+
+const HOUR: u64 = 3_600;
+const REWARDS: u64 = 1_000_000_000;
+/// The pool is distributed daily until a single free slot is left in the cumulative index,
+/// so the benchmarked distribution fills it up.
+const DISTRIBUTED_DAYS: u64 = INDEX_HISTORY_MAX_SIZE as u64 - 1;
+/// Three months deposits made since this day expire after the last distribution,
+/// so their modifiers stay in the trees.
+const FIRST_DEPOSIT_DAY: u64 = DISTRIBUTED_DAYS - 90;
+/// A deposit is made every day until the pool modifiers tree is full
+const HISTORY_DAYS: u64 = FIRST_DEPOSIT_DAY + POOL_MODIFIERS_TREE_CAPACITY as u64;
+
+struct Bench {
+    context: ProgramTestContext,
+    test_rewards: TestRewards,
+    user: Keypair,
+    user_reward: Pubkey,
+    user_mining: Pubkey,
+    first_deposit_id: u64,
+}
+
+/// Creates the pool and the mining of the user with the years of history replayed,
+/// so that the cumulative index, the pool modifiers and the mining modifiers are full
+/// and all of the modifiers are expired.
+async fn setup() -> Bench {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    let (user, user_reward, user_mining) = create_end_user(&mut context, &test_rewards).await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let now = clock.unix_timestamp as u64;
+    let day0 = now - now % SECONDS_PER_DAY - HISTORY_DAYS * SECONDS_PER_DAY;
+
+    let pool = get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let mining = get_account(&mut context, &user_mining).await;
+    let mut simulator = Simulator::from_accounts(pool.data, vec![mining.data], day0 + HOUR);
+    let user_index = 0;
+
+    simulator.fill(REWARDS, now + 365 * SECONDS_PER_DAY);
+    let staker = simulator.add_mining();
+    simulator.deposit(staker, 1_000_000, LockupPeriod::Flex);
+
+    let mut depositor = user_index;
+    let mut first_deposit_id = None;
+    for day in 0..HISTORY_DAYS {
+        simulator.now = day0 + day * SECONDS_PER_DAY + HOUR;
+        if day < DISTRIBUTED_DAYS {
+            simulator.distribute();
+        }
+
+        if day >= FIRST_DEPOSIT_DAY {
+            // the user fills its own tree first, the others fill the rest of the pool tree
+            let deposits = day - FIRST_DEPOSIT_DAY;
+            if deposits > 0 && deposits.is_multiple_of(MINING_MODIFIERS_TREE_CAPACITY as u64) {
+                depositor = simulator.add_mining();
+            }
+            let deposit_id = simulator.deposit(depositor, 1_000, LockupPeriod::ThreeMonths);
+            first_deposit_id.get_or_insert(deposit_id);
+        }
+    }
+
+    let pool = simulator.pool();
+    assert_eq!(pool.cumulative_index.len(), INDEX_HISTORY_MAX_SIZE - 1);
+    assert_eq!(
+        pool.weighted_stake_diffs.len(),
+        POOL_MODIFIERS_TREE_CAPACITY
+    );
+    let mining = simulator.mining(user_index);
+    assert_eq!(
+        mining.weighted_stake_diffs.len(),
+        MINING_MODIFIERS_TREE_CAPACITY
+    );
+
+    let (pool, minings) = simulator.into_accounts();
+    store(&mut context, &test_rewards.reward_pool.pubkey(), pool).await;
+    store(&mut context, &user_mining, minings[user_index].clone()).await;
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.vault_pubkey,
+        REWARDS,
+    )
+    .await
+    .unwrap();
+
+    Bench {
+        context,
+        test_rewards,
+        user,
+        user_reward: user_reward.pubkey(),
+        user_mining,
+        first_deposit_id: first_deposit_id.unwrap(),
+    }
+}
+
+async fn store(context: &mut ProgramTestContext, pubkey: &Pubkey, data: Vec<u8>) {
+    let mut account = get_account(context, pubkey).await;
+    account.data = data;
+    context.set_account(pubkey, &account.into());
+}
+
+/// Processes the instruction with the maximal compute budget and returns the units it consumed
+async fn compute_units(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> u64 {
+    let tx = {
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+        Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                instruction,
+            ],
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            context.last_blockhash,
+        )
+    };
+
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    result.result.unwrap();
+    result.metadata.unwrap().compute_units_consumed
+}
+
+/// Consumes all of the pool modifiers and adds the last entry to the cumulative index
+async fn distribute_rewards() -> u64 {
+    let Bench {
+        mut context,
+        test_rewards,
+        ..
+    } = setup().await;
+
+    let ix = mplx_rewards::instruction::distribute_rewards(
+        &mplx_rewards::ID,
+        &test_rewards.reward_pool.pubkey(),
+        &test_rewards.distribution_authority.pubkey(),
+        &test_rewards.vault_pubkey,
+    );
+    compute_units(&mut context, ix, &[&test_rewards.distribution_authority]).await
+}
+
+/// Consumes all of the mining modifiers, looking up the cumulative index for each of them
+async fn claim() -> u64 {
+    let mut bench = setup().await;
+
+    let ix = mplx_rewards::instruction::claim(
+        &mplx_rewards::ID,
+        &bench.test_rewards.reward_pool.pubkey(),
+        &bench.test_rewards.token_mint_pubkey,
+        &bench.test_rewards.vault_pubkey,
+        &bench.user_mining,
+        &bench.user.pubkey(),
+        &bench.test_rewards.deposit_authority.pubkey(),
+        &bench.user_reward,
+        &bench.test_rewards.token_program_id,
+    );
+    compute_units(
+        &mut bench.context,
+        ix,
+        &[&bench.user, &bench.test_rewards.deposit_authority],
+    )
+    .await
+}
+
+/// Consumes all of the mining modifiers and all of the pool modifiers
+async fn withdraw_mining() -> u64 {
+    let mut bench = setup().await;
+
+    let ix = mplx_rewards::instruction::withdraw_mining(
+        &mplx_rewards::ID,
+        &bench.test_rewards.reward_pool.pubkey(),
+        &bench.user_mining,
+        &bench.test_rewards.deposit_authority.pubkey(),
+        &bench.user_mining,
+        bench.first_deposit_id,
+        1_000,
+        &bench.user.pubkey(),
+        &bench.user.pubkey(),
+        &[],
+    );
+    compute_units(
+        &mut bench.context,
+        ix,
+        &[&bench.test_rewards.deposit_authority],
+    )
+    .await
+}
+
+/// Consumes all of the mining modifiers and all of the pool modifiers
+async fn slash() -> u64 {
+    let mut bench = setup().await;
+    let penalty_history = bench
+        .test_rewards
+        .initialize_penalty_history(&mut bench.context, &bench.user_mining)
+        .await;
+
+    let ix = mplx_rewards::instruction::slash(
+        &mplx_rewards::ID,
+        &bench.test_rewards.deposit_authority.pubkey(),
+        &bench.test_rewards.reward_pool.pubkey(),
+        &bench.user_mining,
+        &penalty_history,
+        &bench.user.pubkey(),
+        bench.first_deposit_id,
+        1_000,
+        0,
+    );
+    compute_units(
+        &mut bench.context,
+        ix,
+        &[&bench.test_rewards.deposit_authority],
+    )
+    .await
+}
+
+/// Consumes all of the mining modifiers and adds a new one.
+/// The pool is distributed first, since there's no room for a new modifier in the full pool tree.
+async fn deposit_mining() -> u64 {
+    let mut bench = setup().await;
+    bench
+        .test_rewards
+        .distribute_rewards(
+            &bench.test_rewards.distribution_authority,
+            &mut bench.context,
+        )
+        .await
+        .unwrap();
+
+    let ix = mplx_rewards::instruction::deposit_mining(
+        &mplx_rewards::ID,
+        &bench.test_rewards.reward_pool.pubkey(),
+        &bench.user_mining,
+        &bench.test_rewards.deposit_authority.pubkey(),
+        &bench.user_mining,
+        // no deposit of the history has it
+        u64::MAX,
+        1_000,
+        LockupPeriod::ThreeMonths,
+        &bench.user.pubkey(),
+        &bench.user.pubkey(),
+        &[],
+    );
+    compute_units(
+        &mut bench.context,
+        ix,
+        &[&bench.test_rewards.deposit_authority],
+    )
+    .await
+}
+
+/// Consumes all of the mining modifiers and restakes the first deposit of the user.
+/// The pool is distributed first, since there's no room for a new modifier in the full pool tree.
+async fn extend_stake() -> u64 {
+    let mut bench = setup().await;
+    bench
+        .test_rewards
+        .distribute_rewards(
+            &bench.test_rewards.distribution_authority,
+            &mut bench.context,
+        )
+        .await
+        .unwrap();
+
+    let ix = mplx_rewards::instruction::extend_stake(
+        &mplx_rewards::ID,
+        &bench.test_rewards.reward_pool.pubkey(),
+        &bench.user_mining,
+        &bench.test_rewards.deposit_authority.pubkey(),
+        &bench.user_mining,
+        bench.first_deposit_id,
+        LockupPeriod::ThreeMonths,
+        0,
+        &bench.user.pubkey(),
+        &bench.user.pubkey(),
+        &[],
+    );
+    compute_units(
+        &mut bench.context,
+        ix,
+        &[&bench.test_rewards.deposit_authority],
+    )
+    .await
+}
+
+/// Measured units along with the margin to record
+fn with_margin(units: u64) -> u64 {
+    units + units * BASELINE_MARGIN_PERCENT / 100
+}
+
+fn read_baseline() -> BTreeMap<String, u64> {
+    let baseline = fs::read_to_string(BASELINE_PATH)
+        .unwrap_or_else(|e| panic!("cannot read the baseline {BASELINE_PATH}: {e}"));
+
+    baseline
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, units) = line
+                .split_once(' ')
+                .unwrap_or_else(|| panic!("malformed baseline line: {line}"));
+            let units = units
+                .trim()
+                .parse()
+                .unwrap_or_else(|e| panic!("malformed baseline line: {line}: {e}"));
+            (name.to_string(), units)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn with_full_trees() {
+    let measured = [
+        ("distribute_rewards", distribute_rewards().await),
+        ("claim", claim().await),
+        ("withdraw_mining", withdraw_mining().await),
+        ("slash", slash().await),
+        ("deposit_mining", deposit_mining().await),
+        ("extend_stake", extend_stake().await),
+    ];
+
+    if std::env::var("RECORD_COMPUTE_UNITS").as_deref() == Ok("1") {
+        let baseline: String = measured
+            .iter()
+            .map(|(name, units)| format!("{name} {}\n", with_margin(*units)))
+            .collect();
+        let baseline = format!(
+            "# Compute units the instructions consume with full trees\n\
+             # Recorded from the SBF build with {BASELINE_MARGIN_PERCENT}% on top of the measured units\n\
+             {baseline}"
+        );
+        let path = Path::new(BASELINE_PATH);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, baseline).unwrap();
+        return;
+    }
+
+    let baseline = read_baseline();
+    let mut regressions = vec![];
+    for (name, units) in measured {
+        match baseline.get(name) {
+            Some(recorded) if units > *recorded => {
+                regressions.push(format!("{name}: {units} CU, {recorded} CU recorded"));
+            }
+            Some(_) => {}
+            None => regressions.push(format!("{name}: {units} CU, no baseline recorded")),
+        }
+    }
+
+    assert!(
+        regressions.is_empty(),
+        "compute units usage has grown or isn't recorded, rerun with RECORD_COMPUTE_UNITS=1 if it's expected:\n{}",
+        regressions.join("\n")
+    );
+}
@@ -0,0 +1,388 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{
+    error::MplxRewardsError,
+    state::{DelegateRegistry, WrappedMining},
+    utils::{find_delegate_registry_program_address, LockupPeriod},
+};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use std::borrow::{Borrow, BorrowMut};
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Keypair,
+    Pubkey,
+    Keypair,
+    Pubkey,
+) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+    let deposit_token_mint = Keypair::new();
+    let payer = &context.payer.pubkey();
+    create_mint(&mut context, &deposit_token_mint, payer)
+        .await
+        .unwrap();
+
+    let test_reward_pool = TestRewards::new(deposit_token_mint.pubkey());
+
+    test_reward_pool
+        .initialize_pool(&mut context)
+        .await
+        .unwrap();
+
+    let delegate = Keypair::new();
+    let delegate_mining = test_reward_pool
+        .initialize_mining(&mut context, &delegate)
+        .await;
+    test_reward_pool
+        .deposit_mining(
+            &mut context,
+            &delegate_mining,
+            3_000_000,
+            LockupPeriod::OneYear,
+            &delegate.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_reward_pool
+        .initialize_mining(&mut context, &user)
+        .await;
+
+    (
+        context,
+        test_reward_pool,
+        delegate,
+        delegate_mining,
+        user,
+        user_mining,
+    )
+}
+
+#[tokio::test]
+async fn register_delegate() {
+    let (mut context, test_rewards, delegate, delegate_mining, user, _) = setup().await;
+
+    let denied = Pubkey::new_unique();
+    test_rewards
+        .register_delegate(
+            &mut context,
+            &delegate_mining,
+            &delegate,
+            1_000,
+            vec![user.pubkey()],
+            vec![denied],
+        )
+        .await
+        .unwrap();
+
+    let (registry_key, bump) =
+        find_delegate_registry_program_address(&mplx_rewards::id(), &delegate_mining);
+    let registry_account = get_account(&mut context, &registry_key).await;
+    let registry = DelegateRegistry::from_bytes(registry_account.data.borrow()).unwrap();
+    assert_eq!(registry.mining, delegate_mining);
+    assert_eq!(registry.bump, bump);
+    assert_eq!(registry.max_stake_from_others, 1_000);
+    assert_eq!(registry.allow_list(), &[user.pubkey()]);
+    assert_eq!(registry.deny_list(), &[denied]);
+}
+
+#[tokio::test]
+async fn register_by_not_an_owner() {
+    let (mut context, test_rewards, _, delegate_mining, user, _) = setup().await;
+
+    let res = test_rewards
+        .register_delegate(&mut context, &delegate_mining, &user, 0, vec![], vec![])
+        .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn register_with_too_long_list() {
+    let (mut context, test_rewards, delegate, delegate_mining, _, _) = setup().await;
+
+    let allow_list = (0..17).map(|_| Pubkey::new_unique()).collect();
+    test_rewards
+        .register_delegate(
+            &mut context,
+            &delegate_mining,
+            &delegate,
+            0,
+            allow_list,
+            vec![],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DelegateListIsTooLong);
+}
+
+#[tokio::test]
+async fn delegating_to_unregistered_delegate() {
+    let (mut context, test_rewards, delegate, delegate_mining, user, user_mining) = setup().await;
+
+    let res = test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn delegating_when_denied() {
+    let (mut context, test_rewards, delegate, delegate_mining, user, user_mining) = setup().await;
+
+    test_rewards
+        .register_delegate(
+            &mut context,
+            &delegate_mining,
+            &delegate,
+            0,
+            vec![],
+            vec![user.pubkey()],
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DelegationIsNotAllowed);
+}
+
+#[tokio::test]
+async fn delegating_when_not_in_allow_list() {
+    let (mut context, test_rewards, delegate, delegate_mining, user, user_mining) = setup().await;
+
+    test_rewards
+        .register_delegate(
+            &mut context,
+            &delegate_mining,
+            &delegate,
+            0,
+            vec![Pubkey::new_unique()],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DelegationIsNotAllowed);
+
+    test_rewards
+        .update_delegate_registry(
+            &mut context,
+            &delegate_mining,
+            &delegate,
+            0,
+            vec![user.pubkey()],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let mut delegate_mining_account = get_account(&mut context, &delegate_mining).await;
+    let d_mining_data = &mut delegate_mining_account.data.borrow_mut();
+    let d_wrapped_mining = WrappedMining::from_bytes_mut(d_mining_data).unwrap();
+    assert_eq!(d_wrapped_mining.mining.stake_from_others, 100);
+}
+
+#[tokio::test]
+async fn delegating_over_the_limit() {
+    let (mut context, test_rewards, delegate, delegate_mining, user, user_mining) = setup().await;
+
+    test_rewards
+        .register_delegate(
+            &mut context,
+            &delegate_mining,
+            &delegate,
+            150,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DelegateStakeLimitExceeded);
+}
+
+#[tokio::test]
+async fn change_delegate_to_denying_delegate() {
+    let (mut context, test_rewards, delegate, delegate_mining, user, user_mining) = setup().await;
+
+    test_rewards
+        .register_delegate(
+            &mut context,
+            &delegate_mining,
+            &delegate,
+            0,
+            vec![],
+            vec![user.pubkey()],
+        )
+        .await
+        .unwrap();
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .change_delegate(
+            &mut context,
+            &user_mining,
+            &user,
+            &delegate_mining,
+            &user_mining,
+            &delegate.pubkey(),
+            deposit_id,
+            100,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DelegationIsNotAllowed);
+}
+
+#[tokio::test]
+async fn unregister_delegate() {
+    let (mut context, test_rewards, delegate, delegate_mining, user, user_mining) = setup().await;
+
+    test_rewards
+        .register_delegate(&mut context, &delegate_mining, &delegate, 0, vec![], vec![])
+        .await
+        .unwrap();
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .unregister_delegate(
+            &mut context,
+            &delegate_mining,
+            &delegate,
+            &delegate.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let (registry_key, _) =
+        find_delegate_registry_program_address(&mplx_rewards::id(), &delegate_mining);
+    let registry_account = context
+        .banks_client
+        .get_account(registry_key)
+        .await
+        .unwrap();
+    assert_eq!(None, registry_account);
+
+    // the stake which is already delegated stays untouched
+    let mut delegate_mining_account = get_account(&mut context, &delegate_mining).await;
+    let d_mining_data = &mut delegate_mining_account.data.borrow_mut();
+    let d_wrapped_mining = WrappedMining::from_bytes_mut(d_mining_data).unwrap();
+    assert_eq!(d_wrapped_mining.mining.stake_from_others, 100);
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    let res = test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await;
+    assert!(res.is_err());
+}
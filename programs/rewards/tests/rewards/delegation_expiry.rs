@@ -0,0 +1,159 @@
+use crate::utils::*;
+use mplx_rewards::{
+    state::{WrappedMining, WrappedRewardPool},
+    utils::LockupPeriod,
+};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Keypair,
+    Pubkey,
+    Keypair,
+    Pubkey,
+    u64,
+) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+    let deposit_token_mint = Keypair::new();
+    let payer = &context.payer.pubkey();
+    create_mint(&mut context, &deposit_token_mint, payer)
+        .await
+        .unwrap();
+
+    let test_reward_pool = TestRewards::new(deposit_token_mint.pubkey());
+
+    test_reward_pool
+        .initialize_pool(&mut context)
+        .await
+        .unwrap();
+
+    let delegate = Keypair::new();
+    let delegate_mining = test_reward_pool
+        .initialize_mining(&mut context, &delegate)
+        .await;
+    test_reward_pool
+        .deposit_mining(
+            &mut context,
+            &delegate_mining,
+            100,
+            LockupPeriod::Flex,
+            &delegate.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .unwrap();
+    test_reward_pool
+        .register_delegate(&mut context, &delegate_mining, &delegate, 0, vec![], vec![])
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_reward_pool
+        .initialize_mining(&mut context, &user)
+        .await;
+    let deposit_id = test_reward_pool
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            1_000,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &delegate_mining,
+            &delegate.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_reward_pool,
+        delegate,
+        delegate_mining,
+        user,
+        user_mining,
+        deposit_id,
+    )
+}
+
+async fn total_share(context: &mut ProgramTestContext, test_rewards: &TestRewards) -> u64 {
+    let mut reward_pool_account = get_account(context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    wrapped_reward_pool.pool.total_share
+}
+
+async fn share_and_stake_from_others(
+    context: &mut ProgramTestContext,
+    mining: &Pubkey,
+) -> (u64, u64) {
+    let mut mining_account = get_account(context, mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    (
+        wrapped_mining.mining.share,
+        wrapped_mining.mining.stake_from_others,
+    )
+}
+
+#[tokio::test]
+async fn delegated_stake_stays_after_lockup_expiration() {
+    let (mut context, test_rewards, _, delegate_mining, _, _, _) = setup().await;
+
+    // delegate's own stake, user's weighted stake and the stake delegated by user
+    assert_eq!(
+        total_share(&mut context, &test_rewards).await,
+        100 + 2_000 + 1_000
+    );
+
+    advance_clock_by_ts(&mut context, (SECONDS_PER_DAY * 100).try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    // only user's own stake decays to the flex one
+    assert_eq!(
+        total_share(&mut context, &test_rewards).await,
+        100 + 1_000 + 1_000
+    );
+    assert_eq!(
+        share_and_stake_from_others(&mut context, &delegate_mining).await,
+        (100, 1_000)
+    );
+}
+
+#[tokio::test]
+async fn withdraw_delegated_stake_after_lockup_expiration() {
+    let (mut context, test_rewards, delegate, delegate_mining, user, user_mining, deposit_id) =
+        setup().await;
+
+    advance_clock_by_ts(&mut context, (SECONDS_PER_DAY * 100).try_into().unwrap()).await;
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &user_mining,
+            &delegate_mining,
+            deposit_id,
+            1_000,
+            &user.pubkey(),
+            &delegate.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        share_and_stake_from_others(&mut context, &user_mining).await,
+        (0, 0)
+    );
+    assert_eq!(
+        share_and_stake_from_others(&mut context, &delegate_mining).await,
+        (100, 0)
+    );
+    assert_eq!(total_share(&mut context, &test_rewards).await, 100);
+}
@@ -1,11 +1,12 @@
-use crate::utils::*;
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
 use mplx_rewards::{
+    error::MplxRewardsError,
     state::{WrappedMining, WrappedRewardPool},
     utils::LockupPeriod,
 };
 use solana_program::pubkey::Pubkey;
 use solana_program_test::*;
-use solana_sdk::{signature::Keypair, signer::Signer};
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
 use std::borrow::BorrowMut;
 
 async fn setup() -> (ProgramTestContext, TestRewards, Pubkey, Pubkey) {
@@ -36,7 +37,13 @@ async fn setup() -> (ProgramTestContext, TestRewards, Pubkey, Pubkey) {
 async fn success() {
     let (mut context, test_rewards, user, mining) = setup().await;
 
-    test_rewards
+    let curr_ts = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64;
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
@@ -59,8 +66,20 @@ async fn success() {
 
     let mut mining_account = get_account(&mut context, &mining).await;
     let mining_data = &mut mining_account.data.borrow_mut();
-    let wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
     assert_eq!(wrapped_mining.mining.share, 200);
+
+    // the position keeps the tier and the start of the lockup
+    let position = *wrapped_mining.position(deposit_id).unwrap();
+    assert_eq!(position.amount, 100);
+    assert_eq!(position.lockup_period().unwrap(), LockupPeriod::ThreeMonths);
+    assert_eq!(position.start_ts, curr_ts);
+    assert_eq!(
+        position.stake_expiration_date,
+        LockupPeriod::ThreeMonths
+            .end_timestamp(curr_ts, SECONDS_PER_DAY)
+            .unwrap()
+    );
 }
 
 #[tokio::test]
@@ -114,6 +133,10 @@ async fn delegating_success() {
         )
         .await
         .unwrap();
+    test_rewards
+        .register_delegate(&mut context, &delegate_mining, &delegate, 0, vec![], vec![])
+        .await
+        .unwrap();
     let mut delegate_mining_account = get_account(&mut context, &delegate_mining).await;
     let d_mining_data = &mut delegate_mining_account.data.borrow_mut();
     let d_wrapped_mining = WrappedMining::from_bytes_mut(d_mining_data).unwrap();
@@ -152,3 +175,41 @@ async fn delegating_success() {
     let wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
     assert_eq!(wrapped_mining.mining.share, 100);
 }
+
+#[tokio::test]
+async fn deposit_is_applied_once() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            100,
+            LockupPeriod::ThreeMonths,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .unwrap();
+    // a retry of the same deposit, even with another amount
+    test_rewards
+        .deposit_mining_with_id(
+            &mut context,
+            deposit_id,
+            &mining,
+            200,
+            LockupPeriod::ThreeMonths,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DepositIsAlreadyApplied);
+
+    let mut mining_account = get_account(&mut context, &mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(wrapped_mining.mining.share, 200);
+    assert_eq!(wrapped_mining.position(deposit_id).unwrap().amount, 100);
+}
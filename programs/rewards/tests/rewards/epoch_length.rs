@@ -0,0 +1,183 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, state::MIN_EPOCH_LENGTH_SECS, utils::LockupPeriod};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const SECONDS_PER_HOUR: u64 = 3600;
+const EPOCH_LENGTH: u64 = 8 * SECONDS_PER_HOUR;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    (context, test_rewards, rewarder.pubkey())
+}
+
+#[tokio::test]
+async fn distribution_per_epoch() {
+    let (mut context, test_rewards, rewarder) = setup().await;
+    test_rewards
+        .set_epoch_length(&mut context, EPOCH_LENGTH)
+        .await
+        .unwrap();
+
+    let (user_a, user_rewards_a, user_mining_a) =
+        create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining_a,
+            100,
+            LockupPeriod::Flex,
+            &user_a.pubkey(),
+            &user_mining_a,
+            &user_a.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    // three distributions are left
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + EPOCH_LENGTH * 4;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder,
+            &test_rewards.fill_authority,
+            300,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    advance_clock_by_ts(&mut context, EPOCH_LENGTH as i64).await;
+
+    let (user_b, user_rewards_b, user_mining_b) =
+        create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining_b,
+            100,
+            LockupPeriod::Flex,
+            &user_b.pubkey(),
+            &user_mining_b,
+            &user_b.pubkey(),
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &user_a,
+        &user_mining_a,
+        &user_rewards_a.pubkey(),
+        150,
+    )
+    .await;
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &user_b,
+        &user_mining_b,
+        &user_rewards_b.pubkey(),
+        50,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn epoch_length_must_divide_a_day() {
+    let (mut context, test_rewards, _) = setup().await;
+
+    test_rewards
+        .set_epoch_length(&mut context, 7 * SECONDS_PER_HOUR)
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidEpochLength);
+    test_rewards
+        .set_epoch_length(&mut context, 0)
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidEpochLength);
+}
+
+#[tokio::test]
+async fn epoch_length_must_keep_the_index_of_the_longest_lockup() {
+    let (mut context, test_rewards, _) = setup().await;
+
+    // hourly epochs would fill the index history in 45 days
+    test_rewards
+        .set_epoch_length(&mut context, SECONDS_PER_HOUR)
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidEpochLength);
+    test_rewards
+        .set_epoch_length(&mut context, MIN_EPOCH_LENGTH_SECS)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn epoch_length_cannot_be_changed_after_deposit() {
+    let (mut context, test_rewards, _) = setup().await;
+
+    let (user, _, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .set_epoch_length(&mut context, EPOCH_LENGTH)
+        .await
+        .assert_on_chain_err(MplxRewardsError::EpochLengthCannotBeChanged);
+}
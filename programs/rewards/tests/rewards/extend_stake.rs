@@ -1,5 +1,6 @@
-use crate::utils::*;
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
 use mplx_rewards::{
+    error::MplxRewardsError,
     state::{WrappedMining, WrappedRewardPool},
     utils::LockupPeriod,
 };
@@ -44,12 +45,11 @@ async fn restake_before_its_expired() {
         .await
         .unwrap()
         .unix_timestamp as u64;
-    let base_amount = 100;
     let additional_amount = 100;
     let old_lockup_period = LockupPeriod::ThreeMonths;
     let new_lockup_period = LockupPeriod::ThreeMonths;
 
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
@@ -71,10 +71,8 @@ async fn restake_before_its_expired() {
             &mut context,
             &mining,
             &mining,
-            old_lockup_period,
+            deposit_id,
             new_lockup_period,
-            deposit_start_ts,
-            base_amount,
             additional_amount,
             &mining_owner,
             &mining_owner,
@@ -84,13 +82,16 @@ async fn restake_before_its_expired() {
 
     // new expiration date modifier added
     let beginning_of_the_old_expiration_day = LockupPeriod::ThreeMonths
-        .end_timestamp(deposit_start_ts - (deposit_start_ts % SECONDS_PER_DAY))
+        .end_timestamp(
+            deposit_start_ts - (deposit_start_ts % SECONDS_PER_DAY),
+            SECONDS_PER_DAY,
+        )
         .unwrap();
     check_modifier_at_a_day(&mut context, mining, 0, beginning_of_the_old_expiration_day).await;
 
     // new expiration date modifier added
     let beginning_of_the_expiration_day = LockupPeriod::ThreeMonths
-        .end_timestamp(curr_ts as u64)
+        .end_timestamp(curr_ts as u64, SECONDS_PER_DAY)
         .unwrap();
     check_modifier_at_a_day(&mut context, mining, 200, beginning_of_the_expiration_day).await;
 
@@ -102,16 +103,9 @@ async fn restake_before_its_expired() {
 async fn restake_for_another_period_after_old_is_expired() {
     let (mut context, test_rewards, mining_owner, mining) = setup().await;
 
-    let deposit_start_ts = context
-        .banks_client
-        .get_sysvar::<solana_program::clock::Clock>()
-        .await
-        .unwrap()
-        .unix_timestamp as u64;
-    let base_amount = 100;
     let additional_amount = 100;
 
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
@@ -127,8 +121,7 @@ async fn restake_for_another_period_after_old_is_expired() {
     let curr_ts =
         advance_clock_by_ts(&mut context, (91 * SECONDS_PER_DAY).try_into().unwrap()).await;
 
-    // we set it to Flex and not to ThreeMonth because it's expired
-    let old_lockup_period = LockupPeriod::Flex;
+    // the expired deposit is restaked from Flex and not from ThreeMonths
     let new_lockup_period = LockupPeriod::OneYear;
 
     test_rewards
@@ -136,10 +129,8 @@ async fn restake_for_another_period_after_old_is_expired() {
             &mut context,
             &mining,
             &mining,
-            old_lockup_period,
+            deposit_id,
             new_lockup_period,
-            deposit_start_ts,
-            base_amount,
             additional_amount,
             &mining_owner,
             &mining_owner,
@@ -148,8 +139,9 @@ async fn restake_for_another_period_after_old_is_expired() {
         .unwrap();
 
     // new expiration date modifier added
-    let beginning_of_the_expiration_day =
-        LockupPeriod::OneYear.end_timestamp(curr_ts as u64).unwrap();
+    let beginning_of_the_expiration_day = LockupPeriod::OneYear
+        .end_timestamp(curr_ts as u64, SECONDS_PER_DAY)
+        .unwrap();
     check_modifier_at_a_day(&mut context, mining, 1000, beginning_of_the_expiration_day).await;
 
     // and power is multiplied twice
@@ -166,12 +158,11 @@ async fn just_prolong_without_adding_tokes() {
         .await
         .unwrap()
         .unix_timestamp as u64;
-    let base_amount = 100;
     let additional_amount = 0;
     let old_lockup_period = LockupPeriod::ThreeMonths;
     let new_lockup_period = LockupPeriod::ThreeMonths;
 
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
@@ -193,10 +184,8 @@ async fn just_prolong_without_adding_tokes() {
             &mut context,
             &mining,
             &mining,
-            old_lockup_period,
+            deposit_id,
             new_lockup_period,
-            deposit_start_ts,
-            base_amount,
             additional_amount,
             &mining_owner,
             &mining_owner,
@@ -206,13 +195,16 @@ async fn just_prolong_without_adding_tokes() {
 
     // new expiration date modifier added
     let beginning_of_the_old_expiration_day = LockupPeriod::ThreeMonths
-        .end_timestamp(deposit_start_ts - (deposit_start_ts % SECONDS_PER_DAY))
+        .end_timestamp(
+            deposit_start_ts - (deposit_start_ts % SECONDS_PER_DAY),
+            SECONDS_PER_DAY,
+        )
         .unwrap();
     check_modifier_at_a_day(&mut context, mining, 0, beginning_of_the_old_expiration_day).await;
 
     // new expiration date modifier added
     let beginning_of_the_expiration_day = LockupPeriod::ThreeMonths
-        .end_timestamp(curr_ts as u64)
+        .end_timestamp(curr_ts as u64, SECONDS_PER_DAY)
         .unwrap();
     check_modifier_at_a_day(&mut context, mining, 100, beginning_of_the_expiration_day).await;
 
@@ -224,16 +216,9 @@ async fn just_prolong_without_adding_tokes() {
 async fn restake_after_its_expired_with_no_additional_tokens() {
     let (mut context, test_rewards, mining_owner, mining) = setup().await;
 
-    let deposit_start_ts = context
-        .banks_client
-        .get_sysvar::<solana_program::clock::Clock>()
-        .await
-        .unwrap()
-        .unix_timestamp as u64;
-    let base_amount = 100;
     let additional_amount = 0;
 
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
@@ -249,8 +234,7 @@ async fn restake_after_its_expired_with_no_additional_tokens() {
     let curr_ts =
         advance_clock_by_ts(&mut context, (91 * SECONDS_PER_DAY).try_into().unwrap()).await;
 
-    // we set it to Flex and not to ThreeMonth because it's expired
-    let old_lockup_period = LockupPeriod::Flex;
+    // the expired deposit is restaked from Flex and not from ThreeMonths
     let new_lockup_period = LockupPeriod::ThreeMonths;
 
     test_rewards
@@ -258,10 +242,8 @@ async fn restake_after_its_expired_with_no_additional_tokens() {
             &mut context,
             &mining,
             &mining,
-            old_lockup_period,
+            deposit_id,
             new_lockup_period,
-            deposit_start_ts,
-            base_amount,
             additional_amount,
             &mining_owner,
             &mining_owner,
@@ -271,7 +253,7 @@ async fn restake_after_its_expired_with_no_additional_tokens() {
 
     // new expiration date modifier added
     let beginning_of_the_expiration_day = LockupPeriod::ThreeMonths
-        .end_timestamp(curr_ts as u64)
+        .end_timestamp(curr_ts as u64, SECONDS_PER_DAY)
         .unwrap();
     check_modifier_at_a_day(&mut context, mining, 100, beginning_of_the_expiration_day).await;
 
@@ -283,16 +265,9 @@ async fn restake_after_its_expired_with_no_additional_tokens() {
 async fn restake_in_expiration_day() {
     let (mut context, test_rewards, mining_owner, mining) = setup().await;
 
-    let deposit_start_ts = context
-        .banks_client
-        .get_sysvar::<solana_program::clock::Clock>()
-        .await
-        .unwrap()
-        .unix_timestamp as u64;
-    let base_amount = 100;
     let additional_amount = 0;
 
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
@@ -308,8 +283,7 @@ async fn restake_in_expiration_day() {
     let curr_ts =
         advance_clock_by_ts(&mut context, (90 * SECONDS_PER_DAY).try_into().unwrap()).await;
 
-    // we set it to Flex and not to ThreeMonth because it's expired
-    let old_lockup_period = LockupPeriod::Flex;
+    // the expired deposit is restaked from Flex and not from ThreeMonths
     let new_lockup_period = LockupPeriod::ThreeMonths;
 
     test_rewards
@@ -317,10 +291,8 @@ async fn restake_in_expiration_day() {
             &mut context,
             &mining,
             &mining,
-            old_lockup_period,
+            deposit_id,
             new_lockup_period,
-            deposit_start_ts,
-            base_amount,
             additional_amount,
             &mining_owner,
             &mining_owner,
@@ -330,7 +302,7 @@ async fn restake_in_expiration_day() {
 
     // new expiration date modifier added
     let beginning_of_the_expiration_day = LockupPeriod::ThreeMonths
-        .end_timestamp(curr_ts as u64)
+        .end_timestamp(curr_ts as u64, SECONDS_PER_DAY)
         .unwrap();
     check_modifier_at_a_day(&mut context, mining, 100, beginning_of_the_expiration_day).await;
 
@@ -358,6 +330,10 @@ async fn prolong_with_delegate() {
         )
         .await
         .unwrap();
+    test_rewards
+        .register_delegate(&mut context, &delegate_mining, &delegate, 0, vec![], vec![])
+        .await
+        .unwrap();
 
     let mut delegate_mining_account = get_account(&mut context, &delegate_mining).await;
     let d_mining_data = &mut delegate_mining_account.data.borrow_mut();
@@ -371,16 +347,15 @@ async fn prolong_with_delegate() {
         .await
         .unwrap()
         .unix_timestamp as u64;
-    let base_amount = 100;
     let additional_amount = 0;
     let old_lockup_period = LockupPeriod::ThreeMonths;
     let new_lockup_period = LockupPeriod::ThreeMonths;
 
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
-            base_amount,
+            100,
             old_lockup_period,
             &mining_owner,
             &delegate_mining,
@@ -417,10 +392,8 @@ async fn prolong_with_delegate() {
             &mut context,
             &mining,
             &delegate_mining,
-            old_lockup_period,
+            deposit_id,
             new_lockup_period,
-            deposit_start_ts,
-            base_amount,
             additional_amount,
             &mining_owner,
             &delegate.pubkey(),
@@ -430,13 +403,16 @@ async fn prolong_with_delegate() {
 
     // new expiration date modifier added
     let beginning_of_the_old_expiration_day = LockupPeriod::ThreeMonths
-        .end_timestamp(deposit_start_ts - (deposit_start_ts % SECONDS_PER_DAY))
+        .end_timestamp(
+            deposit_start_ts - (deposit_start_ts % SECONDS_PER_DAY),
+            SECONDS_PER_DAY,
+        )
         .unwrap();
     check_modifier_at_a_day(&mut context, mining, 0, beginning_of_the_old_expiration_day).await;
 
     // new expiration date modifier added
     let beginning_of_the_expiration_day = LockupPeriod::ThreeMonths
-        .end_timestamp(curr_ts as u64)
+        .end_timestamp(curr_ts as u64, SECONDS_PER_DAY)
         .unwrap();
     check_modifier_at_a_day(&mut context, mining, 100, beginning_of_the_expiration_day).await;
 
@@ -444,6 +420,68 @@ async fn prolong_with_delegate() {
     check_weighted_stake(&mut context, mining, 200).await;
 }
 
+#[tokio::test]
+async fn restake_is_bound_to_the_deposit() {
+    let (mut context, test_rewards, mining_owner, mining) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            100,
+            LockupPeriod::ThreeMonths,
+            &mining_owner,
+            &mining,
+            &mining_owner,
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .extend_stake(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id + 1,
+            LockupPeriod::OneYear,
+            0,
+            &mining_owner,
+            &mining_owner,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DepositIsNotFound);
+
+    let curr_ts =
+        advance_clock_by_ts(&mut context, (10 * SECONDS_PER_DAY).try_into().unwrap()).await;
+    test_rewards
+        .extend_stake(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id,
+            LockupPeriod::OneYear,
+            50,
+            &mining_owner,
+            &mining_owner,
+        )
+        .await
+        .unwrap();
+
+    let beginning_of_the_expiration_day = LockupPeriod::OneYear
+        .end_timestamp(curr_ts as u64, SECONDS_PER_DAY)
+        .unwrap();
+    let mut mining_account = get_account(&mut context, &mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    let position = *wrapped_mining.position(deposit_id).unwrap();
+    assert_eq!(position.amount, 150);
+    assert_eq!(
+        position.stake_expiration_date,
+        beginning_of_the_expiration_day
+    );
+    assert_eq!(wrapped_mining.mining.share, 900);
+}
+
 pub async fn check_weighted_stake(
     context: &mut ProgramTestContext,
     mining_account: Pubkey,
@@ -38,4 +38,8 @@ async fn success() {
         test_rewards.reward_pool.pubkey()
     );
     assert_eq!(wrapped_mining.mining.owner, user.pubkey());
+    assert_eq!(
+        wrapped_mining.mining.reward_mint,
+        test_rewards.token_mint_pubkey
+    );
 }
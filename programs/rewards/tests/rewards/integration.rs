@@ -1,369 +1,657 @@
-// use crate::utils::*;
-// use mplx_rewards::utils::LockupPeriod;
-// use solana_program::pubkey::Pubkey;
-// use solana_program_test::*;
-// use solana_sdk::clock::SECONDS_PER_DAY;
-// use solana_sdk::signature::Keypair;
-// use solana_sdk::signer::Signer;
-
-// async fn setup() -> (ProgramTestContext, TestRewards, Pubkey, Keypair) {
-//     let test = ProgramTest::new(
-//         "mplx_rewards",
-//         mplx_rewards::id(),
-//         processor!(mplx_rewards::processor::process_instruction),
-//     );
-
-//     let mut context = test.start_with_context().await;
-
-//     let owner = &context.payer.pubkey();
-
-//     let deposit_token_mint = Keypair::new();
-//     create_mint(&mut context, &deposit_token_mint, owner)
-//         .await
-//         .unwrap();
-
-//     let test_reward_pool = TestRewards::new(deposit_token_mint.pubkey());
-//     test_reward_pool
-//         .initialize_pool(&mut context)
-//         .await
-//         .unwrap();
-
-//     let rewarder = Keypair::new();
-//     create_token_account(
-//         &mut context,
-//         &rewarder,
-//         &deposit_token_mint.pubkey(),
-//         owner,
-//         0,
-//     )
-//     .await
-//     .unwrap();
-//     mint_tokens(
-//         &mut context,
-//         &deposit_token_mint.pubkey(),
-//         &rewarder.pubkey(),
-//         1_000_000,
-//     )
-//     .await
-//     .unwrap();
-
-//     test_reward_pool.add_vault(&mut context).await;
-
-//     (
-//         context,
-//         test_reward_pool,
-//         rewarder.pubkey(),
-//         deposit_token_mint,
-//     )
-// }
-
-// #[tokio::test]
-// async fn success() {
-//     let (mut context, test_rewards_pool, rewarder, mint) = setup().await;
-
-//     let (user_a, user_reward_a, user_mining_a) =
-//         create_user(&mut context, &test_rewards_pool).await;
-//     let (user_b, user_reward_b, user_mining_b) =
-//         create_user(&mut context, &test_rewards_pool).await;
-//     let (user_c, user_reward_c, user_mining_c) =
-//         create_user(&mut context, &test_rewards_pool).await;
-
-//     // User C - deposit (D0) 100 tokens for 1 year
-//     test_rewards_pool
-//         .deposit_mining(
-//             &mut context,
-//             &user_mining_c,
-//             100,
-//             LockupPeriod::OneYear,
-//             &mint.pubkey(),
-//             &user_c.pubkey(),
-//         )
-//         .await
-//         .unwrap();
-//     // 1 distribuiton happens
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-
-//     // User A - deposit(D1) 1000 tokens for 1 year.
-//     test_rewards_pool
-//         .deposit_mining(
-//             &mut context,
-//             &user_mining_a,
-//             1000,
-//             LockupPeriod::OneYear,
-//             &mint.pubkey(),
-//             &user_a.pubkey(),
-//         )
-//         .await
-//         .unwrap();
-//     // 3 distributions happen
-//     for _ in 0..3 {
-//         advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
-//         test_rewards_pool
-//             .fill_vault(&mut context, &rewarder, 100)
-//             .await
-//             .unwrap();
-//     }
-
-//     // User A - deposit(D2) 2000 tokens for 1 year.
-//     test_rewards_pool
-//         .deposit_mining(
-//             &mut context,
-//             &user_mining_a,
-//             2000,
-//             LockupPeriod::OneYear,
-//             &mint.pubkey(),
-//             &user_a.pubkey(),
-//         )
-//         .await
-//         .unwrap();
-//     // 1 distribution happens
-//     advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-
-//     // User B deposit(D3) 100k tokens 3 month after the User A for half a year
-//     advance_clock_by_ts(&mut context, (SECONDS_PER_DAY * 90).try_into().unwrap()).await;
-//     test_rewards_pool
-//         .deposit_mining(
-//             &mut context,
-//             &user_mining_b,
-//             100_000,
-//             LockupPeriod::SixMonths,
-//             &mint.pubkey(),
-//             &user_b.pubkey(),
-//         )
-//         .await
-//         .unwrap();
-//     // 1 distribution happens
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-
-//     // User B deposit(D4) 100k tokens for half a year - his unclaimed balance is calculated
-//     advance_clock_by_ts(&mut context, (5 * SECONDS_PER_DAY).try_into().unwrap()).await;
-//     test_rewards_pool
-//         .deposit_mining(
-//             &mut context,
-//             &user_mining_b,
-//             100_000,
-//             LockupPeriod::SixMonths,
-//             &mint.pubkey(),
-//             &user_b.pubkey(),
-//         )
-//         .await
-//         .unwrap();
-
-//     // 6 distributions happen
-//     for _ in 0..6 {
-//         advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
-//         test_rewards_pool
-//             .fill_vault(&mut context, &rewarder, 100)
-//             .await
-//             .unwrap();
-//     }
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_a,
-//         &user_mining_a,
-//         &user_reward_a.pubkey(),
-//         386,
-//     )
-//     .await;
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_b,
-//         &user_mining_b,
-//         &user_reward_b.pubkey(),
-//         681,
-//     )
-//     .await;
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_c,
-//         &user_mining_c,
-//         &user_reward_c.pubkey(),
-//         131,
-//     )
-//     .await;
-
-//     // D3 expires (therefore turns into Flex)
-//     advance_clock_by_ts(&mut context, (171 * SECONDS_PER_DAY).try_into().unwrap()).await;
-
-//     // User B unstakes and claims D3
-//     test_rewards_pool
-//         .withdraw_mining(&mut context, &user_mining_b, 100_000, &user_b.pubkey())
-//         .await
-//         .unwrap();
-
-//     // 1 distribution happens
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_a,
-//         &user_mining_a,
-//         &user_reward_a.pubkey(),
-//         390,
-//     )
-//     .await;
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_b,
-//         &user_mining_b,
-//         &user_reward_b.pubkey(),
-//         776,
-//     )
-//     .await;
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_c,
-//         &user_mining_c,
-//         &user_reward_c.pubkey(),
-//         131,
-//     )
-//     .await;
-
-//     // 1 distribution happens
-//     advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_a,
-//         &user_mining_a,
-//         &user_reward_a.pubkey(),
-//         394,
-//     )
-//     .await;
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_b,
-//         &user_mining_b,
-//         &user_reward_b.pubkey(),
-//         871,
-//     )
-//     .await;
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_c,
-//         &user_mining_c,
-//         &user_reward_c.pubkey(),
-//         131,
-//     )
-//     .await;
-
-//     // D4 expires.
-//     advance_clock_by_ts(&mut context, (5 * SECONDS_PER_DAY).try_into().unwrap()).await;
-//     // 2 distributions happen
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_a,
-//         &user_mining_a,
-//         &user_reward_a.pubkey(),
-//         409,
-//     )
-//     .await;
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_c,
-//         &user_mining_c,
-//         &user_reward_c.pubkey(),
-//         131,
-//     )
-//     .await;
-
-//     advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-
-//     // D0 expires
-//     // D1 expires
-//     advance_clock_by_ts(&mut context, (90 * SECONDS_PER_DAY).try_into().unwrap()).await;
-//     // 1 distribution happens
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-//     // D2 expires
-//     advance_clock_by_ts(&mut context, (3 * SECONDS_PER_DAY).try_into().unwrap()).await;
-//     // 5 distributions happen
-//     for _ in 0..5 {
-//         test_rewards_pool
-//             .fill_vault(&mut context, &rewarder, 100)
-//             .await
-//             .unwrap();
-//         advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
-//     }
-
-//     // User A unstakes and claims D1 and D2
-//     test_rewards_pool
-//         .withdraw_mining(&mut context, &user_mining_a, 3000, &user_a.pubkey())
-//         .await
-//         .unwrap();
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_a,
-//         &user_mining_a,
-//         &user_reward_a.pubkey(),
-//         441,
-//     )
-//     .await;
-//     // Usr B unstakes and claims D4
-//     test_rewards_pool
-//         .withdraw_mining(&mut context, &user_mining_b, 100_000, &user_b.pubkey())
-//         .await
-//         .unwrap();
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_b,
-//         &user_mining_b,
-//         &user_reward_b.pubkey(),
-//         1621,
-//     )
-//     .await;
-//     // 1 distribution happens
-//     test_rewards_pool
-//         .fill_vault(&mut context, &rewarder, 100)
-//         .await
-//         .unwrap();
-//     // User C claims his rewards
-//     claim_and_assert(
-//         &test_rewards_pool,
-//         &mut context,
-//         &user_c,
-//         &user_mining_c,
-//         &user_reward_c.pubkey(),
-//         231,
-//     )
-//     .await;
-// }
+use crate::utils::*;
+use mplx_rewards::utils::{
+    find_delegate_registry_program_address, find_mining_program_address,
+    find_penalty_history_program_address,
+};
+use rewards::{
+    accounts::{Mining, RewardPool},
+    instructions::{
+        ChangeDelegateBuilder, ClaimBuilder, CloseMiningBuilder, DepositMiningBuilder,
+        DistributeRewardsBuilder, ExtendStakeBuilder, FillVaultBuilder, InitializeMiningBuilder,
+        InitializePenaltyHistoryBuilder, RegisterDelegateBuilder, SlashBuilder,
+        WithdrawMiningBuilder,
+    },
+    types::LockupPeriod,
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    clock::{Clock, SECONDS_PER_DAY},
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+/// Weighted stake changes of the scenario participants, written down from the lockup rules
+/// rather than taken from the program, and the rewards they are expected to get out of them.
+#[derive(Default)]
+struct Schedule {
+    /// (first distribution day the change takes part in, participant, weighted stake diff)
+    changes: Vec<(u64, usize, i64)>,
+}
+
+impl Schedule {
+    fn change(&mut self, day: u64, participant: usize, weighted_stake_diff: i64) {
+        self.changes.push((day, participant, weighted_stake_diff));
+    }
+
+    fn weighted_stake(&self, participant: usize, day: u64) -> u64 {
+        let stake: i64 = self
+            .changes
+            .iter()
+            .filter(|(from, p, _)| *from <= day && *p == participant)
+            .map(|(_, _, diff)| diff)
+            .sum();
+        stake.try_into().unwrap()
+    }
+
+    fn total_share(&self, day: u64) -> u64 {
+        let stake: i64 = self
+            .changes
+            .iter()
+            .filter(|(from, _, _)| *from <= day)
+            .map(|(_, _, diff)| diff)
+            .sum();
+        stake.try_into().unwrap()
+    }
+
+    /// Rewards of the participant from the distributions of days `1..=day`.
+    /// The scenarios pick the daily rewards divisible by every total share they go through,
+    /// so nothing is lost to rounding and the expected amounts are exact.
+    fn rewards(&self, participant: usize, day: u64, daily_rewards: u64) -> u64 {
+        (1..=day)
+            .map(|d| {
+                let total_share = self.total_share(d);
+                assert_eq!(
+                    daily_rewards % total_share,
+                    0,
+                    "total share {total_share} on day {d} doesn't divide the daily rewards"
+                );
+                daily_rewards / total_share * self.weighted_stake(participant, d)
+            })
+            .sum()
+    }
+}
+
+struct Participant {
+    owner: Keypair,
+    mining: Pubkey,
+    reward_account: Pubkey,
+}
+
+/// Creates the pool and moves the clock to the beginning of an epoch,
+/// so the scenario days match the epochs. Returns the beginning of the first day.
+async fn setup() -> (ProgramTestContext, TestRewards, Pubkey, u64) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let day = SECONDS_PER_DAY as i64;
+    let day0 = advance_clock_by_ts(&mut context, day - clock.unix_timestamp % day).await;
+
+    (context, test_rewards, rewarder.pubkey(), day0 as u64)
+}
+
+/// Moves the clock to the given moment
+async fn warp_to(context: &mut ProgramTestContext, ts: u64) {
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    advance_clock_by_ts(context, ts as i64 - clock.unix_timestamp).await;
+}
+
+async fn process(context: &mut ProgramTestContext, instruction: Instruction, signers: &[&Keypair]) {
+    let tx = {
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            context.last_blockhash,
+        )
+    };
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+async fn join(context: &mut ProgramTestContext, test_rewards: &TestRewards) -> Participant {
+    let owner = Keypair::new();
+    let reward_account = Keypair::new();
+    create_token_account(
+        context,
+        &reward_account,
+        &test_rewards.token_mint_pubkey,
+        &owner.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    let (mining, _) = find_mining_program_address(
+        &mplx_rewards::ID,
+        &owner.pubkey(),
+        &test_rewards.reward_pool.pubkey(),
+    );
+    let ix = InitializeMiningBuilder::new()
+        .reward_pool(test_rewards.reward_pool.pubkey())
+        .mining(mining)
+        .payer(context.payer.pubkey())
+        .mining_owner(owner.pubkey())
+        .instruction();
+    process(context, ix, &[]).await;
+
+    Participant {
+        owner,
+        mining,
+        reward_account: reward_account.pubkey(),
+    }
+}
+
+/// Registry of the delegate, which isn't needed when the participant stakes on its own
+fn delegate_registry(participant: &Participant, delegate: &Participant) -> Option<Pubkey> {
+    (participant.mining != delegate.mining)
+        .then(|| find_delegate_registry_program_address(&mplx_rewards::ID, &delegate.mining).0)
+}
+
+async fn register_delegate(context: &mut ProgramTestContext, delegate: &Participant) {
+    let ix = RegisterDelegateBuilder::new()
+        .mining(delegate.mining)
+        .delegate_registry(
+            find_delegate_registry_program_address(&mplx_rewards::ID, &delegate.mining).0,
+        )
+        .mining_owner(delegate.owner.pubkey())
+        .payer(context.payer.pubkey())
+        .max_stake_from_others(0)
+        .allow_list(vec![])
+        .deny_list(vec![])
+        .instruction();
+    process(context, ix, &[&delegate.owner]).await;
+}
+
+async fn deposit(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    participant: &Participant,
+    amount: u64,
+    lockup_period: LockupPeriod,
+    delegate: &Participant,
+) -> u64 {
+    let deposit_id = test_rewards.next_deposit_id();
+    let ix = DepositMiningBuilder::new()
+        .reward_pool(test_rewards.reward_pool.pubkey())
+        .mining(participant.mining)
+        .deposit_authority(test_rewards.deposit_authority.pubkey())
+        .delegate_mining(delegate.mining)
+        .delegate_registry(delegate_registry(participant, delegate))
+        .deposit_id(deposit_id)
+        .amount(amount)
+        .lockup_period(lockup_period)
+        .mining_owner(participant.owner.pubkey())
+        .delegate(delegate.owner.pubkey())
+        .delegate_weights(vec![])
+        .instruction();
+    process(context, ix, &[&test_rewards.deposit_authority]).await;
+
+    deposit_id
+}
+
+async fn withdraw(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    participant: &Participant,
+    deposit_id: u64,
+    amount: u64,
+    delegate: &Participant,
+) {
+    let ix = WithdrawMiningBuilder::new()
+        .reward_pool(test_rewards.reward_pool.pubkey())
+        .mining(participant.mining)
+        .deposit_authority(test_rewards.deposit_authority.pubkey())
+        .delegate_mining(delegate.mining)
+        .deposit_id(deposit_id)
+        .amount(amount)
+        .mining_owner(participant.owner.pubkey())
+        .delegate(delegate.owner.pubkey())
+        .delegate_weights(vec![])
+        .instruction();
+    process(context, ix, &[&test_rewards.deposit_authority]).await;
+}
+
+async fn fill(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    rewarder: &Pubkey,
+    rewards: u64,
+    distribution_ends_at: u64,
+) {
+    mint_tokens(context, &test_rewards.token_mint_pubkey, rewarder, rewards)
+        .await
+        .unwrap();
+
+    let ix = FillVaultBuilder::new()
+        .reward_pool(test_rewards.reward_pool.pubkey())
+        .reward_mint(test_rewards.token_mint_pubkey)
+        .vault(test_rewards.vault_pubkey)
+        .fill_authority(test_rewards.fill_authority.pubkey())
+        .source_token_account(*rewarder)
+        .token_program(test_rewards.token_program_id)
+        .rewards(rewards)
+        .distribution_ends_at(distribution_ends_at)
+        .instruction();
+    process(context, ix, &[&test_rewards.fill_authority]).await;
+}
+
+async fn distribute(context: &mut ProgramTestContext, test_rewards: &TestRewards) {
+    let ix = DistributeRewardsBuilder::new()
+        .reward_pool(test_rewards.reward_pool.pubkey())
+        .distribute_authority(test_rewards.distribution_authority.pubkey())
+        .vault(test_rewards.vault_pubkey)
+        .instruction();
+    process(context, ix, &[&test_rewards.distribution_authority]).await;
+}
+
+async fn claim(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    participant: &Participant,
+) {
+    let ix = ClaimBuilder::new()
+        .reward_pool(test_rewards.reward_pool.pubkey())
+        .reward_mint(test_rewards.token_mint_pubkey)
+        .vault(test_rewards.vault_pubkey)
+        .mining(participant.mining)
+        .mining_owner(participant.owner.pubkey())
+        .deposit_authority(test_rewards.deposit_authority.pubkey())
+        .mining_owner_reward_token_account(participant.reward_account)
+        .token_program(test_rewards.token_program_id)
+        .instruction();
+    process(
+        context,
+        ix,
+        &[&participant.owner, &test_rewards.deposit_authority],
+    )
+    .await;
+}
+
+async fn close(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    participant: &Participant,
+) {
+    let ix = CloseMiningBuilder::new()
+        .mining(participant.mining)
+        .mining_owner(participant.owner.pubkey())
+        .target_account(participant.owner.pubkey())
+        .deposit_authority(test_rewards.deposit_authority.pubkey())
+        .reward_pool(test_rewards.reward_pool.pubkey())
+        .instruction();
+    process(
+        context,
+        ix,
+        &[&participant.owner, &test_rewards.deposit_authority],
+    )
+    .await;
+}
+
+async fn reward_pool(context: &mut ProgramTestContext, test_rewards: &TestRewards) -> RewardPool {
+    let account = get_account(context, &test_rewards.reward_pool.pubkey()).await;
+    RewardPool::from_bytes(&account.data).unwrap()
+}
+
+async fn mining(context: &mut ProgramTestContext, participant: &Participant) -> Mining {
+    let account = get_account(context, &participant.mining).await;
+    Mining::from_bytes(&account.data).unwrap()
+}
+
+const HOUR: u64 = 3_600;
+
+const DAVE: usize = 0;
+const ALICE: usize = 1;
+const BOB: usize = 2;
+const CAROL: usize = 3;
+const ERIN: usize = 4;
+
+/// Five stakers over 200 days:
+/// - Dave stakes Flex, is Bob's delegate until day 60 and leaves on day 150
+/// - Alice stakes for a year, gets slashed on day 45 and is Bob's delegate from day 60
+/// - Bob stakes for three months, which expire on day 90, and leaves on day 120
+/// - Carol stakes for six months and extends it to a year on day 30
+/// - Erin joins on day 100 with three months and Flex stakes
+#[tokio::test]
+async fn every_lockup_tier_with_delegation_slash_extend_and_close() {
+    const DAYS: u64 = 200;
+    // the least common multiple of the total shares the scenario goes through
+    const DAILY_REWARDS: u64 = 2_184_000;
+
+    let (mut context, test_rewards, rewarder, day0) = setup().await;
+    let day = |n: u64| day0 + n * SECONDS_PER_DAY;
+
+    let mut participants = vec![];
+    for _ in [DAVE, ALICE, BOB, CAROL, ERIN] {
+        participants.push(join(&mut context, &test_rewards).await);
+    }
+    let [dave, alice, bob, carol, erin] = &participants[..] else {
+        unreachable!()
+    };
+    let mut schedule = Schedule::default();
+
+    let dave_deposit = deposit(
+        &mut context,
+        &test_rewards,
+        dave,
+        500,
+        LockupPeriod::Flex,
+        dave,
+    )
+    .await;
+    register_delegate(&mut context, dave).await;
+    schedule.change(1, DAVE, 500);
+
+    let alice_deposit = deposit(
+        &mut context,
+        &test_rewards,
+        alice,
+        500,
+        LockupPeriod::OneYear,
+        alice,
+    )
+    .await;
+    register_delegate(&mut context, alice).await;
+    let ix = InitializePenaltyHistoryBuilder::new()
+        .mining(alice.mining)
+        .penalty_history(find_penalty_history_program_address(&mplx_rewards::ID, &alice.mining).0)
+        .payer(context.payer.pubkey())
+        .instruction();
+    process(&mut context, ix, &[]).await;
+    schedule.change(1, ALICE, 6 * 500);
+
+    let bob_deposit = deposit(
+        &mut context,
+        &test_rewards,
+        bob,
+        500,
+        LockupPeriod::ThreeMonths,
+        dave,
+    )
+    .await;
+    schedule.change(1, BOB, 2 * 500);
+    schedule.change(90, BOB, -500);
+    schedule.change(1, DAVE, 500);
+
+    let carol_deposit = deposit(
+        &mut context,
+        &test_rewards,
+        carol,
+        250,
+        LockupPeriod::SixMonths,
+        carol,
+    )
+    .await;
+    schedule.change(1, CAROL, 4 * 250);
+
+    fill(
+        &mut context,
+        &test_rewards,
+        &rewarder,
+        DAILY_REWARDS * DAYS,
+        day(DAYS + 1),
+    )
+    .await;
+
+    for d in 1..=DAYS {
+        // rewards are distributed at the beginning of the day and the stakers act an hour later
+        warp_to(&mut context, day(d)).await;
+        distribute(&mut context, &test_rewards).await;
+        assert_eq!(
+            reward_pool(&mut context, &test_rewards).await.total_share,
+            schedule.total_share(d),
+            "total share on day {d}"
+        );
+        warp_to(&mut context, day(d) + HOUR).await;
+
+        match d {
+            30 => {
+                let ix = ExtendStakeBuilder::new()
+                    .reward_pool(test_rewards.reward_pool.pubkey())
+                    .mining(carol.mining)
+                    .deposit_authority(test_rewards.deposit_authority.pubkey())
+                    .delegate_mining(carol.mining)
+                    .deposit_id(carol_deposit)
+                    .new_lockup_period(LockupPeriod::OneYear)
+                    .additional_amount(250)
+                    .mining_owner(carol.owner.pubkey())
+                    .delegate(carol.owner.pubkey())
+                    .delegate_weights(vec![])
+                    .instruction();
+                process(&mut context, ix, &[&test_rewards.deposit_authority]).await;
+                schedule.change(d + 1, CAROL, -4 * 250 + 6 * 500);
+            }
+            45 => {
+                let ix = SlashBuilder::new()
+                    .deposit_authority(test_rewards.deposit_authority.pubkey())
+                    .reward_pool(test_rewards.reward_pool.pubkey())
+                    .mining(alice.mining)
+                    .penalty_history(Some(
+                        find_penalty_history_program_address(&mplx_rewards::ID, &alice.mining).0,
+                    ))
+                    .mining_owner(alice.owner.pubkey())
+                    .deposit_id(alice_deposit)
+                    .slash_amount_in_native(250)
+                    .reason_code(0)
+                    .instruction();
+                process(&mut context, ix, &[&test_rewards.deposit_authority]).await;
+                schedule.change(d + 1, ALICE, -6 * 250);
+            }
+            50 => {
+                claim(&mut context, &test_rewards, alice).await;
+                assert_tokens(
+                    &mut context,
+                    &alice.reward_account,
+                    schedule.rewards(ALICE, d, DAILY_REWARDS),
+                )
+                .await;
+            }
+            60 => {
+                let ix = ChangeDelegateBuilder::new()
+                    .reward_pool(test_rewards.reward_pool.pubkey())
+                    .mining(bob.mining)
+                    .deposit_authority(test_rewards.deposit_authority.pubkey())
+                    .mining_owner(bob.owner.pubkey())
+                    .old_delegate_mining(dave.mining)
+                    .new_delegate_mining(alice.mining)
+                    .new_delegate_registry(delegate_registry(bob, alice))
+                    .deposit_id(bob_deposit)
+                    .staked_amount(500)
+                    .new_delegate(alice.owner.pubkey())
+                    .old_delegate_weights(vec![])
+                    .new_delegate_weights(vec![])
+                    .instruction();
+                process(
+                    &mut context,
+                    ix,
+                    &[&test_rewards.deposit_authority, &bob.owner],
+                )
+                .await;
+                schedule.change(d + 1, DAVE, -500);
+                schedule.change(d + 1, ALICE, 500);
+            }
+            100 => {
+                claim(&mut context, &test_rewards, carol).await;
+                assert_tokens(
+                    &mut context,
+                    &carol.reward_account,
+                    schedule.rewards(CAROL, d, DAILY_REWARDS),
+                )
+                .await;
+
+                deposit(
+                    &mut context,
+                    &test_rewards,
+                    erin,
+                    500,
+                    LockupPeriod::ThreeMonths,
+                    erin,
+                )
+                .await;
+                deposit(
+                    &mut context,
+                    &test_rewards,
+                    erin,
+                    1_000,
+                    LockupPeriod::Flex,
+                    erin,
+                )
+                .await;
+                schedule.change(d + 1, ERIN, 2 * 500 + 1_000);
+                schedule.change(d + 90, ERIN, -500);
+            }
+            120 => {
+                withdraw(&mut context, &test_rewards, bob, bob_deposit, 500, alice).await;
+                schedule.change(d + 1, BOB, -500);
+                schedule.change(d + 1, ALICE, -500);
+
+                claim(&mut context, &test_rewards, bob).await;
+                assert_tokens(
+                    &mut context,
+                    &bob.reward_account,
+                    schedule.rewards(BOB, d, DAILY_REWARDS),
+                )
+                .await;
+                close(&mut context, &test_rewards, bob).await;
+            }
+            150 => {
+                let dave_mining = mining(&mut context, dave).await;
+                assert_eq!(dave_mining.stake_from_others, 0);
+
+                withdraw(&mut context, &test_rewards, dave, dave_deposit, 500, dave).await;
+                schedule.change(d + 1, DAVE, -500);
+
+                claim(&mut context, &test_rewards, dave).await;
+                assert_tokens(
+                    &mut context,
+                    &dave.reward_account,
+                    schedule.rewards(DAVE, d, DAILY_REWARDS),
+                )
+                .await;
+                close(&mut context, &test_rewards, dave).await;
+            }
+            _ => {}
+        }
+    }
+
+    for (i, participant) in [(ALICE, alice), (CAROL, carol), (ERIN, erin)] {
+        claim(&mut context, &test_rewards, participant).await;
+        assert_tokens(
+            &mut context,
+            &participant.reward_account,
+            schedule.rewards(i, DAYS, DAILY_REWARDS),
+        )
+        .await;
+    }
+    assert!(context
+        .banks_client
+        .get_account(bob.mining)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(context
+        .banks_client
+        .get_account(dave.mining)
+        .await
+        .unwrap()
+        .is_none());
+
+    // every token has been distributed and claimed without rounding leftovers
+    let pool = reward_pool(&mut context, &test_rewards).await;
+    assert_eq!(pool.tokens_available_for_distribution, 0);
+    assert_eq!(pool.total_rewards_indexed, DAILY_REWARDS * DAYS);
+    assert_eq!(pool.total_rewards_claimed, DAILY_REWARDS * DAYS);
+    assert_tokens(&mut context, &test_rewards.vault_pubkey, 0).await;
+}
+
+/// A staker of every lockup tier, each one claiming the day the lockup expires
+#[tokio::test]
+async fn lockups_of_every_tier_expire_on_schedule() {
+    const DAYS: u64 = 400;
+    // the least common multiple of the total shares the scenario goes through
+    const DAILY_REWARDS: u64 = 46_800;
+
+    let (mut context, test_rewards, rewarder, day0) = setup().await;
+    let day = |n: u64| day0 + n * SECONDS_PER_DAY;
+
+    let tiers = [
+        (LockupPeriod::Flex, 1, 5),
+        (LockupPeriod::ThreeMonths, 2, 90),
+        (LockupPeriod::SixMonths, 4, 180),
+        (LockupPeriod::OneYear, 6, 365),
+    ];
+    let mut schedule = Schedule::default();
+    let mut participants = vec![];
+    for (i, (lockup_period, multiplier, days)) in tiers.iter().enumerate() {
+        let participant = join(&mut context, &test_rewards).await;
+        deposit(
+            &mut context,
+            &test_rewards,
+            &participant,
+            100,
+            lockup_period.clone(),
+            &participant,
+        )
+        .await;
+        schedule.change(1, i, multiplier * 100);
+        schedule.change(*days, i, -(multiplier - 1) * 100);
+        participants.push(participant);
+    }
+
+    fill(
+        &mut context,
+        &test_rewards,
+        &rewarder,
+        DAILY_REWARDS * DAYS,
+        day(DAYS + 1),
+    )
+    .await;
+
+    for d in 1..=DAYS {
+        // rewards are distributed at the beginning of the day and the stakers act an hour later
+        warp_to(&mut context, day(d)).await;
+        distribute(&mut context, &test_rewards).await;
+        warp_to(&mut context, day(d) + HOUR).await;
+
+        for (i, (_, _, days)) in tiers.iter().enumerate() {
+            if d == *days || d == DAYS {
+                claim(&mut context, &test_rewards, &participants[i]).await;
+                assert_tokens(
+                    &mut context,
+                    &participants[i].reward_account,
+                    schedule.rewards(i, d, DAILY_REWARDS),
+                )
+                .await;
+            }
+        }
+    }
+
+    for (i, (_, multiplier, _)) in tiers.iter().enumerate() {
+        let mining = mining(&mut context, &participants[i]).await;
+        assert_eq!(mining.share, 100, "tier with multiplier {multiplier}");
+    }
+    assert_tokens(&mut context, &test_rewards.vault_pubkey, 0).await;
+}
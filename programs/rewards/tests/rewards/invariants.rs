@@ -0,0 +1,479 @@
+use crate::utils::*;
+use mplx_rewards::{
+    invariants::{check_invariants, MiningAccounts},
+    state::{PenaltyHistory, WrappedImmutableMining, WrappedImmutableRewardPool, WrappedMining},
+    utils::LockupPeriod,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{account::Account, clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+const USERS: usize = 4;
+const STEPS: usize = 80;
+/// Seeds the harness runs with, unless INVARIANTS_SEED is set
+const SEEDS: [u64; 2] = [0x5eed, 0xdecaf];
+/// The first user is the delegate others can delegate their stake to
+const DELEGATE: usize = 0;
+
+const LOCKUP_PERIODS: [LockupPeriod; 4] = [
+    LockupPeriod::Flex,
+    LockupPeriod::ThreeMonths,
+    LockupPeriod::SixMonths,
+    LockupPeriod::OneYear,
+];
+
+/// Deposit as it's tracked by the staking contract
+#[derive(Debug)]
+struct Deposit {
+    id: u64,
+    amount: u64,
+    lockup_period: LockupPeriod,
+    expires_at: u64,
+    /// The user the stake of the deposit is delegated to
+    delegate: usize,
+}
+
+struct User {
+    owner: Keypair,
+    reward_account: Keypair,
+    mining: Pubkey,
+    penalty_history: Pubkey,
+    deposits: Vec<Deposit>,
+}
+
+struct Harness {
+    context: ProgramTestContext,
+    test_rewards: TestRewards,
+    users: Vec<User>,
+    rng: StdRng,
+    log: Vec<String>,
+}
+
+async fn setup(seed: u64) -> Harness {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000_000,
+    )
+    .await
+    .unwrap();
+    let distribution_ends_at = now(&mut context).await + SECONDS_PER_DAY * 365;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            1_000_000_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+
+    let mut users = vec![];
+    for i in 0..USERS {
+        let (owner, reward_account, mining) = create_end_user(&mut context, &test_rewards).await;
+        let penalty_history = test_rewards
+            .initialize_penalty_history(&mut context, &mining)
+            .await;
+        if i == DELEGATE {
+            test_rewards
+                .register_delegate(&mut context, &mining, &owner, 0, vec![], vec![])
+                .await
+                .unwrap();
+        }
+        users.push(User {
+            owner,
+            reward_account,
+            mining,
+            penalty_history,
+            deposits: vec![],
+        });
+    }
+
+    Harness {
+        context,
+        test_rewards,
+        users,
+        rng: StdRng::seed_from_u64(seed),
+        log: vec![],
+    }
+}
+
+async fn now(context: &mut ProgramTestContext) -> u64 {
+    context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+}
+
+/// Moves the clock forward, warping to the next slot
+/// so that identical transactions aren't deduplicated
+async fn jump_clock(context: &mut ProgramTestContext, seconds: u64) {
+    let mut clock = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap();
+
+    let slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(slot + 1).unwrap();
+
+    clock.unix_timestamp += seconds as i64;
+    context.set_sysvar(&clock);
+}
+
+impl Harness {
+    async fn step(&mut self) {
+        let jump = match self.rng.gen_range(0..10) {
+            0 => self.rng.gen_range(30..=100) * SECONDS_PER_DAY,
+            1..=4 => self.rng.gen_range(1..=3) * SECONDS_PER_DAY,
+            _ => self.rng.gen_range(0..SECONDS_PER_DAY),
+        };
+        jump_clock(&mut self.context, jump).await;
+
+        let user = self.rng.gen_range(0..USERS);
+        let op = match self.rng.gen_range(0..12) {
+            0..=2 => self.deposit(user).await,
+            3 => self.withdraw(user).await,
+            4 => self.extend(user).await,
+            5 => self.slash(user).await,
+            6 => self.decrease(user).await,
+            7 => self.restore(user).await,
+            8 => self.change_delegate(user).await,
+            9 | 10 => self.distribute().await,
+            _ => self.claim(user).await,
+        };
+        self.log.push(format!("+{jump}s {op}"));
+    }
+
+    async fn deposit(&mut self, i: usize) -> String {
+        let amount = self.rng.gen_range(1..=10_000);
+        let lockup_period = LOCKUP_PERIODS[self.rng.gen_range(0..LOCKUP_PERIODS.len())];
+        let start_ts = now(&mut self.context).await;
+
+        // new deposits aren't delegated until the delegate is changed
+        let user = &self.users[i];
+        let res = self
+            .test_rewards
+            .deposit_mining(
+                &mut self.context,
+                &user.mining,
+                amount,
+                lockup_period,
+                &user.owner.pubkey(),
+                &user.mining,
+                &user.owner.pubkey(),
+            )
+            .await;
+
+        if let Ok(id) = res {
+            self.users[i].deposits.push(Deposit {
+                id,
+                amount,
+                lockup_period,
+                expires_at: lockup_period
+                    .end_timestamp(start_ts, SECONDS_PER_DAY)
+                    .unwrap(),
+                delegate: i,
+            });
+        }
+        format!("deposit user {i} {amount} {lockup_period:?}: {res:?}")
+    }
+
+    /// Withdraws the expired deposit, as the staking contract only allows that
+    async fn withdraw(&mut self, i: usize) -> String {
+        let curr_ts = now(&mut self.context).await;
+        let Some(d) = self.users[i]
+            .deposits
+            .iter()
+            .position(|d| d.expires_at <= curr_ts)
+        else {
+            return format!("withdraw user {i}: no expired deposits");
+        };
+
+        let user = &self.users[i];
+        let delegate = &self.users[user.deposits[d].delegate];
+        let amount = user.deposits[d].amount;
+        let res = self
+            .test_rewards
+            .withdraw_mining(
+                &mut self.context,
+                &user.mining,
+                &delegate.mining,
+                user.deposits[d].id,
+                amount,
+                &user.owner.pubkey(),
+                &delegate.owner.pubkey(),
+            )
+            .await;
+
+        if res.is_ok() {
+            self.users[i].deposits.remove(d);
+        }
+        format!("withdraw user {i} {amount}: {res:?}")
+    }
+
+    async fn extend(&mut self, i: usize) -> String {
+        if self.users[i].deposits.is_empty() {
+            return format!("extend user {i}: no deposits");
+        }
+        let d = self.rng.gen_range(0..self.users[i].deposits.len());
+        let new_lockup_period = LOCKUP_PERIODS[self.rng.gen_range(1..LOCKUP_PERIODS.len())];
+        let additional_amount = self.rng.gen_range(0..=1_000);
+        let curr_ts = now(&mut self.context).await;
+
+        let user = &self.users[i];
+        let deposit = &user.deposits[d];
+        let delegate = &self.users[deposit.delegate];
+        let res = self
+            .test_rewards
+            .extend_stake(
+                &mut self.context,
+                &user.mining,
+                &delegate.mining,
+                deposit.id,
+                new_lockup_period,
+                additional_amount,
+                &user.owner.pubkey(),
+                &delegate.owner.pubkey(),
+            )
+            .await;
+
+        let msg = format!(
+            "extend user {i} {deposit:?} to {new_lockup_period:?} +{additional_amount}: {res:?}"
+        );
+        if res.is_ok() {
+            let deposit = &mut self.users[i].deposits[d];
+            deposit.amount += additional_amount;
+            deposit.lockup_period = new_lockup_period;
+            deposit.expires_at = new_lockup_period
+                .end_timestamp(curr_ts, SECONDS_PER_DAY)
+                .unwrap();
+        }
+        msg
+    }
+
+    async fn slash(&mut self, i: usize) -> String {
+        if self.users[i].deposits.is_empty() {
+            return format!("slash user {i}: no deposits");
+        }
+        let d = self.rng.gen_range(0..self.users[i].deposits.len());
+        let curr_ts = now(&mut self.context).await;
+
+        let user = &self.users[i];
+        let deposit = &user.deposits[d];
+        let deposit_id = deposit.id;
+        let slashed = self.rng.gen_range(1..=deposit.amount);
+        let weighted = if curr_ts < deposit.expires_at {
+            slashed * deposit.lockup_period.multiplier()
+        } else {
+            slashed * LockupPeriod::Flex.multiplier()
+        };
+        let res = self
+            .test_rewards
+            .slash(
+                &mut self.context,
+                &user.mining,
+                &user.owner.pubkey(),
+                deposit_id,
+                slashed,
+                0,
+            )
+            .await;
+
+        if res.is_ok() {
+            // slash doesn't take the stake back from the delegate
+            let deposits = &mut self.users[i].deposits;
+            deposits[d].amount -= slashed;
+            if deposits[d].amount == 0 {
+                deposits.remove(d);
+            }
+        }
+        format!("slash user {i} {slashed} ({weighted}) of deposit {deposit_id}: {res:?}")
+    }
+
+    async fn decrease(&mut self, i: usize) -> String {
+        let share = self.mining(i).await.share;
+        if share == 0 {
+            return format!("decrease user {i}: no share");
+        }
+        let decreased = self.rng.gen_range(1..=share);
+
+        let user = &self.users[i];
+        let res = self
+            .test_rewards
+            .decrease_rewards(
+                &mut self.context,
+                &user.mining,
+                &user.owner.pubkey(),
+                decreased,
+                0,
+            )
+            .await;
+        format!("decrease user {i} {decreased}: {res:?}")
+    }
+
+    async fn restore(&mut self, i: usize) -> String {
+        let history_account = get_account(&mut self.context, &self.users[i].penalty_history).await;
+        let history = PenaltyHistory::from_bytes(&history_account.data).unwrap();
+        let ids: Vec<_> = history
+            .records()
+            .filter(|r| !r.is_restored())
+            .map(|r| r.id)
+            .collect();
+        if ids.is_empty() {
+            return format!("restore user {i}: no penalties");
+        }
+        let id = ids[self.rng.gen_range(0..ids.len())];
+        let credit_lost_rewards = self.rng.gen_bool(0.5);
+
+        let user = &self.users[i];
+        let res = self
+            .test_rewards
+            .restore_penalty(
+                &mut self.context,
+                &user.mining,
+                &user.owner.pubkey(),
+                id,
+                credit_lost_rewards,
+            )
+            .await;
+        format!("restore user {i} penalty {id} credit {credit_lost_rewards}: {res:?}")
+    }
+
+    /// Delegates the stake of the deposit to the delegate or takes it back
+    async fn change_delegate(&mut self, i: usize) -> String {
+        if i == DELEGATE {
+            return format!("change delegate user {i}: delegate itself");
+        }
+        if self.users[i].deposits.is_empty() {
+            return format!("change delegate user {i}: no deposits");
+        }
+        let d = self.rng.gen_range(0..self.users[i].deposits.len());
+
+        let user = &self.users[i];
+        let deposit = &user.deposits[d];
+        let new_delegate = if deposit.delegate == i { DELEGATE } else { i };
+        let res = self
+            .test_rewards
+            .change_delegate(
+                &mut self.context,
+                &user.mining,
+                &user.owner,
+                &self.users[new_delegate].mining,
+                &self.users[deposit.delegate].mining,
+                &self.users[new_delegate].owner.pubkey(),
+                deposit.id,
+                deposit.amount,
+            )
+            .await;
+
+        let msg = format!("change delegate user {i} {deposit:?} to {new_delegate}: {res:?}");
+        if res.is_ok() {
+            self.users[i].deposits[d].delegate = new_delegate;
+        }
+        msg
+    }
+
+    async fn distribute(&mut self) -> String {
+        let res = self
+            .test_rewards
+            .distribute_rewards(&self.test_rewards.distribution_authority, &mut self.context)
+            .await;
+        format!("distribute: {res:?}")
+    }
+
+    async fn claim(&mut self, i: usize) -> String {
+        let user = &self.users[i];
+        let res = self
+            .test_rewards
+            .claim(
+                &mut self.context,
+                &user.owner,
+                &user.mining,
+                &user.reward_account.pubkey(),
+            )
+            .await;
+        format!("claim user {i}: {res:?}")
+    }
+
+    async fn mining(&mut self, i: usize) -> mplx_rewards::state::Mining {
+        let mut mining_account = get_account(&mut self.context, &self.users[i].mining).await;
+        let mining_data = &mut mining_account.data.borrow_mut();
+        *WrappedMining::from_bytes_mut(mining_data).unwrap().mining
+    }
+
+    async fn assert_invariants(&mut self, seed: u64) {
+        let curr_ts = now(&mut self.context).await;
+        let pool_account =
+            get_account(&mut self.context, &self.test_rewards.reward_pool.pubkey()).await;
+        let mut accounts: Vec<(Account, Account)> = vec![];
+        for user in &self.users {
+            accounts.push((
+                get_account(&mut self.context, &user.mining).await,
+                get_account(&mut self.context, &user.penalty_history).await,
+            ));
+        }
+
+        let pool = WrappedImmutableRewardPool::from_bytes(&pool_account.data).unwrap();
+        let minings: Vec<_> = accounts
+            .iter()
+            .map(|(mining, penalty_history)| MiningAccounts {
+                mining: WrappedImmutableMining::from_bytes(&mining.data).unwrap(),
+                penalty_history: Some(PenaltyHistory::from_bytes(&penalty_history.data).unwrap()),
+            })
+            .collect();
+
+        if let Err(e) = check_invariants(&pool, &minings, curr_ts) {
+            panic!(
+                "invariant is broken with seed {seed}: {e}\nsteps:\n{}",
+                self.log.join("\n")
+            );
+        }
+    }
+}
+
+async fn run(seed: u64) {
+    let mut harness = setup(seed).await;
+    for _ in 0..STEPS {
+        harness.step().await;
+        harness.assert_invariants(seed).await;
+    }
+}
+
+#[tokio::test]
+async fn random_operations_keep_invariants() {
+    let seeds = match std::env::var("INVARIANTS_SEED") {
+        Ok(seed) => vec![seed.parse().unwrap()],
+        Err(_) => SEEDS.to_vec(),
+    };
+
+    for seed in seeds {
+        run(seed).await;
+    }
+}
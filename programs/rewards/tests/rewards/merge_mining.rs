@@ -0,0 +1,262 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, state::WrappedMining, utils::LockupPeriod};
+use sokoban::NodeAllocatorMap;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Keypair, Pubkey, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let (user_a, _, user_mining_a) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining_a,
+            100,
+            LockupPeriod::ThreeMonths,
+            &user_a.pubkey(),
+            &user_mining_a,
+            &user_a.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let (user_b, _, user_mining_b) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining_b,
+            50,
+            LockupPeriod::SixMonths,
+            &user_b.pubkey(),
+            &user_mining_b,
+            &user_b.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 10;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            1_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+
+    advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    (context, test_rewards, user_a, user_mining_a, user_mining_b)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_rewards, user_a, user_mining_a, user_mining_b) = setup().await;
+
+    let reward_pool_before = get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+
+    let target = Keypair::new();
+    let deposit_ids = test_rewards
+        .merge_mining(
+            &mut context,
+            &user_mining_a,
+            &user_mining_b,
+            &user_a,
+            &target.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let source_mining = context
+        .banks_client
+        .get_account(user_mining_a)
+        .await
+        .unwrap();
+    assert_eq!(None, source_mining);
+
+    // pool's total share and modifiers stay the same
+    let reward_pool_after = get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    assert_eq!(reward_pool_before.data, reward_pool_after.data);
+
+    let mut mining_account = get_account(&mut context, &user_mining_b).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    // 100 x2 + 50 x4
+    assert_eq!(mining.mining.share, 400);
+    // both stakes have the same weight, so they have got the same rewards
+    assert!(mining.mining.unclaimed_rewards > 0);
+    assert_eq!(mining.mining.unclaimed_rewards % 2, 0);
+    let diffs: Vec<_> = mining
+        .weighted_stake_diffs
+        .iter()
+        .map(|(_, diff)| *diff)
+        .collect();
+    assert_eq!(diffs, vec![100, 150]);
+    // the deposit is moved under the id it has been given
+    assert_eq!(mining.positions.len(), 2);
+    assert_eq!(mining.position(deposit_ids[0]).unwrap().amount, 100);
+}
+
+#[tokio::test]
+async fn merge_into_itself_fails() {
+    let (mut context, test_rewards, user_a, user_mining_a, _) = setup().await;
+
+    test_rewards
+        .merge_mining(
+            &mut context,
+            &user_mining_a,
+            &user_mining_a,
+            &user_a,
+            &Keypair::new().pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::MiningsAreTheSame);
+}
+
+#[tokio::test]
+async fn merge_by_not_an_owner_fails() {
+    let (mut context, test_rewards, _, user_mining_a, user_mining_b) = setup().await;
+
+    let res = test_rewards
+        .merge_mining(
+            &mut context,
+            &user_mining_a,
+            &user_mining_b,
+            &Keypair::new(),
+            &Keypair::new().pubkey(),
+        )
+        .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn merge_with_stake_from_others_fails() {
+    let (mut context, test_rewards, user_a, user_mining_a, user_mining_b) = setup().await;
+
+    test_rewards
+        .register_delegate(&mut context, &user_mining_a, &user_a, 0, vec![], vec![])
+        .await
+        .unwrap();
+    let (delegator, _, delegator_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &delegator_mining,
+            50,
+            LockupPeriod::Flex,
+            &delegator.pubkey(),
+            &user_mining_a,
+            &user_a.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .merge_mining(
+            &mut context,
+            &user_mining_a,
+            &user_mining_b,
+            &user_a,
+            &Keypair::new().pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::StakeFromOthersMustBeZero);
+}
+
+#[tokio::test]
+async fn merge_with_delegate_registry_waits_for_unregistering() {
+    let (mut context, test_rewards, user_a, user_mining_a, user_mining_b) = setup().await;
+
+    test_rewards
+        .register_delegate(&mut context, &user_mining_a, &user_a, 0, vec![], vec![])
+        .await
+        .unwrap();
+    test_rewards
+        .merge_mining(
+            &mut context,
+            &user_mining_a,
+            &user_mining_b,
+            &user_a,
+            &Keypair::new().pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::MiningHasLinkedAccounts);
+
+    test_rewards
+        .unregister_delegate(&mut context, &user_mining_a, &user_a, &user_a.pubkey())
+        .await
+        .unwrap();
+    test_rewards
+        .merge_mining(
+            &mut context,
+            &user_mining_a,
+            &user_mining_b,
+            &user_a,
+            &Keypair::new().pubkey(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn merge_with_penalty_history_fails() {
+    let (mut context, test_rewards, user_a, user_mining_a, user_mining_b) = setup().await;
+
+    test_rewards
+        .initialize_penalty_history(&mut context, &user_mining_a)
+        .await;
+    test_rewards
+        .merge_mining(
+            &mut context,
+            &user_mining_a,
+            &user_mining_b,
+            &user_a,
+            &Keypair::new().pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::MiningHasLinkedAccounts);
+}
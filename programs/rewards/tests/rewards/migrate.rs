@@ -0,0 +1,628 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{
+    error::MplxRewardsError,
+    state::{
+        Mining, MiningWeightedStakeDiffs, RewardPool, WrappedMining, WrappedRewardPool,
+        ACCOUNT_VERSION_BYTE,
+    },
+    utils::LockupPeriod,
+};
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{account::Account, clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use spl_token::state::Account as SplTokenAccount;
+
+/// Header lengths of the reward pool layouts deployed before the version byte:
+/// the original one, with pool limits, with boosts and epoch length, with sunset.
+const POOL_FIXTURE_HEADER_LENS: [usize; 4] = [176, 208, 272, 288];
+/// Header lengths of the mining layouts deployed before the version byte:
+/// the original one and with boosts.
+const MINING_FIXTURE_HEADER_LENS: [usize; 2] = [144, 160];
+
+/// Deposit made in the setup
+struct Deposit {
+    id: u64,
+    start_ts: u64,
+}
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Keypair,
+    Pubkey,
+    Pubkey,
+    Deposit,
+) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    // both stakes and distributions are made, so the trees aren't empty
+    let (user, user_reward, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    let start_ts = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64;
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 10;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            1_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+
+    advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_rewards,
+        user,
+        user_reward.pubkey(),
+        user_mining,
+        Deposit {
+            id: deposit_id,
+            start_ts,
+        },
+    )
+}
+
+/// Builds the account as it would be stored in the unversioned layout with the given header length.
+/// The pool trees have never changed, so they immediately follow the truncated header.
+fn historic_layout_fixture(
+    account: &Account,
+    header: &[u8],
+    header_len: usize,
+    rent: &solana_sdk::rent::Rent,
+) -> Account {
+    let mut data = header[..header_len].to_vec();
+    data.extend_from_slice(&account.data[header.len()..]);
+
+    Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        ..account.clone()
+    }
+}
+
+/// Builds the mining as it would be stored in the outdated layout with the given header length.
+/// Positions are only stored since the layout version 3, so the weighted stake diffs
+/// are the only tree following the truncated header.
+fn historic_mining_fixture(
+    account: &Account,
+    header: &[u8],
+    header_len: usize,
+    rent: &solana_sdk::rent::Rent,
+) -> Account {
+    let mut data = header[..header_len].to_vec();
+    data.extend_from_slice(
+        &account.data[header.len()..header.len() + std::mem::size_of::<MiningWeightedStakeDiffs>()],
+    );
+
+    Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        ..account.clone()
+    }
+}
+
+/// Builds the mining as it would be stored in the layout version 3,
+/// which had the positions, but didn't record the delegated stakes yet
+fn v3_mining_fixture(account: &Account, rent: &solana_sdk::rent::Rent) -> Account {
+    let mut data = versioned_mining_header(account, 3);
+    data.extend_from_slice(&account.data[Mining::LEN..WrappedMining::LEN_V3]);
+
+    Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        ..account.clone()
+    }
+}
+
+fn unversioned_pool_header(account: &Account) -> Vec<u8> {
+    versioned_pool_header(account, 0)
+}
+
+fn versioned_pool_header(account: &Account, version: u8) -> Vec<u8> {
+    let mut data = account.data.clone();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(&mut data).unwrap();
+    wrapped_reward_pool.pool.data[ACCOUNT_VERSION_BYTE] = version;
+
+    bytemuck::bytes_of(wrapped_reward_pool.pool).to_vec()
+}
+
+async fn get_vault_balance(context: &mut ProgramTestContext, test_rewards: &TestRewards) -> u64 {
+    let vault = get_account(context, &test_rewards.vault_pubkey).await;
+    SplTokenAccount::unpack(&vault.data).unwrap().amount
+}
+
+fn unversioned_mining_header(account: &Account) -> Vec<u8> {
+    versioned_mining_header(account, 0)
+}
+
+fn versioned_mining_header(account: &Account, version: u8) -> Vec<u8> {
+    let mut data = account.data.clone();
+    let wrapped_mining = WrappedMining::from_bytes_mut(&mut data).unwrap();
+    wrapped_mining.mining.data[ACCOUNT_VERSION_BYTE] = version;
+
+    bytemuck::bytes_of(wrapped_mining.mining).to_vec()
+}
+
+/// The reward mint is only recorded since the layout version 2,
+/// so minings of the outdated layouts get it from the pool on the migration
+fn mining_without_reward_mint(account: &Account) -> Account {
+    let mut account = account.clone();
+    let wrapped_mining = WrappedMining::from_bytes_mut(&mut account.data).unwrap();
+    wrapped_mining.mining.reward_mint = Pubkey::default();
+
+    account
+}
+
+/// Minings migrated from outdated layouts have no positions
+/// until the deposit authority records them
+fn mining_without_positions(account: &Account) -> Account {
+    let mut account = account.clone();
+    let wrapped_mining = WrappedMining::from_bytes_mut(&mut account.data).unwrap();
+    *wrapped_mining.positions = bytemuck::Zeroable::zeroed();
+    wrapped_mining.positions.initialize();
+
+    account
+}
+
+#[tokio::test]
+async fn pool_round_trip_over_historic_layouts() {
+    let (mut context, test_rewards, user, user_reward, user_mining, _) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let reward_pool = test_rewards.reward_pool.pubkey();
+    // no rewards have been claimed yet, so the backfilled emissions match the tracked ones
+    let current = get_account(&mut context, &reward_pool).await;
+    let header = unversioned_pool_header(&current);
+    assert_eq!(header.len(), RewardPool::LEN);
+
+    for header_len in POOL_FIXTURE_HEADER_LENS {
+        // AVOID CACHING FOR IDENTICAL OPERATIONS
+        let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+        context.warp_to_slot(initial_slot + 1).unwrap();
+
+        let fixture = historic_layout_fixture(&current, &header, header_len, &rent);
+        context.set_account(&reward_pool, &fixture.into());
+
+        test_rewards
+            .claim(&mut context, &user, &user_mining, &user_reward)
+            .await
+            .assert_on_chain_err(MplxRewardsError::AccountNeedsMigration);
+
+        test_rewards.migrate_pool(&mut context).await.unwrap();
+
+        let migrated = get_account(&mut context, &reward_pool).await;
+        assert_eq!(migrated.data, current.data);
+        assert!(migrated.lamports >= rent.minimum_balance(WrappedRewardPool::LEN));
+    }
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn pool_round_trip_from_first_versioned_layout() {
+    let (mut context, test_rewards, user, user_reward, user_mining, _) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let reward_pool = test_rewards.reward_pool.pubkey();
+    // no rewards have been claimed yet, so the backfilled emissions match the tracked ones
+    let current = get_account(&mut context, &reward_pool).await;
+    let header = versioned_pool_header(&current, 1);
+
+    let fixture = historic_layout_fixture(&current, &header, 288, &rent);
+    context.set_account(&reward_pool, &fixture.into());
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .assert_on_chain_err(MplxRewardsError::AccountNeedsMigration);
+
+    test_rewards.migrate_pool(&mut context).await.unwrap();
+
+    let migrated = get_account(&mut context, &reward_pool).await;
+    assert_eq!(migrated.data, current.data);
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn pool_round_trip_from_second_versioned_layout() {
+    let (mut context, test_rewards, user, user_reward, user_mining, _) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    // the unbonding config is only stored since the layout version 3,
+    // so the pool must not have it configured for the round trip
+    let reward_pool = test_rewards.reward_pool.pubkey();
+    let current = get_account(&mut context, &reward_pool).await;
+    let header = versioned_pool_header(&current, 2);
+
+    let fixture = historic_layout_fixture(&current, &header, 304, &rent);
+    context.set_account(&reward_pool, &fixture.into());
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .assert_on_chain_err(MplxRewardsError::AccountNeedsMigration);
+
+    test_rewards.migrate_pool(&mut context).await.unwrap();
+
+    let migrated = get_account(&mut context, &reward_pool).await;
+    assert_eq!(migrated.data, current.data);
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn migrated_pool_accounts_for_rewards_indexed_before_migration() {
+    let (mut context, test_rewards, user, user_reward, user_mining, _) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let reward_pool = test_rewards.reward_pool.pubkey();
+    let current = get_account(&mut context, &reward_pool).await;
+    let header = versioned_pool_header(&current, 1);
+    let fixture = historic_layout_fixture(&current, &header, 288, &rent);
+    context.set_account(&reward_pool, &fixture.into());
+
+    test_rewards.migrate_pool(&mut context).await.unwrap();
+
+    let vault_balance = get_vault_balance(&mut context, &test_rewards).await;
+    let mut reward_pool_account = get_account(&mut context, &reward_pool).await;
+    let wrapped_reward_pool =
+        WrappedRewardPool::from_bytes_mut(&mut reward_pool_account.data).unwrap();
+    let unclaimed_rewards = wrapped_reward_pool.pool.unclaimed_rewards();
+    assert!(unclaimed_rewards > 0);
+    assert_eq!(
+        unclaimed_rewards,
+        vault_balance - wrapped_reward_pool.pool.tokens_available_for_distribution
+    );
+    assert_eq!(
+        wrapped_reward_pool.pool.liabilities().unwrap(),
+        vault_balance
+    );
+
+    // rewards indexed before the migration are claimed without zeroing the liabilities
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+
+    let vault_balance = get_vault_balance(&mut context, &test_rewards).await;
+    let mut reward_pool_account = get_account(&mut context, &reward_pool).await;
+    let wrapped_reward_pool =
+        WrappedRewardPool::from_bytes_mut(&mut reward_pool_account.data).unwrap();
+    assert_eq!(
+        wrapped_reward_pool.pool.total_rewards_claimed,
+        unclaimed_rewards
+    );
+    assert_eq!(wrapped_reward_pool.pool.unclaimed_rewards(), 0);
+    assert_eq!(
+        wrapped_reward_pool.pool.liabilities().unwrap(),
+        vault_balance
+    );
+}
+
+#[tokio::test]
+async fn mining_round_trip_over_historic_layouts() {
+    let (mut context, test_rewards, user, user_reward, user_mining, _) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let current = get_account(&mut context, &user_mining).await;
+    let header = unversioned_mining_header(&mining_without_reward_mint(&current));
+    assert_eq!(header.len(), Mining::LEN);
+    let expected = mining_without_positions(&current);
+
+    for header_len in MINING_FIXTURE_HEADER_LENS {
+        // AVOID CACHING FOR IDENTICAL OPERATIONS
+        let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+        context.warp_to_slot(initial_slot + 1).unwrap();
+
+        let fixture = historic_mining_fixture(&current, &header, header_len, &rent);
+        context.set_account(&user_mining, &fixture.into());
+
+        test_rewards
+            .claim(&mut context, &user, &user_mining, &user_reward)
+            .await
+            .assert_on_chain_err(MplxRewardsError::AccountNeedsMigration);
+
+        test_rewards
+            .migrate_mining(&mut context, &user_mining)
+            .await
+            .unwrap();
+
+        let migrated = get_account(&mut context, &user_mining).await;
+        assert_eq!(migrated.data, expected.data);
+        assert!(migrated.lamports >= rent.minimum_balance(WrappedMining::LEN));
+    }
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn mining_round_trip_from_first_versioned_layout() {
+    let (mut context, test_rewards, user, user_reward, user_mining, _) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let current = get_account(&mut context, &user_mining).await;
+    let header = versioned_mining_header(&mining_without_reward_mint(&current), 1);
+
+    let fixture = historic_mining_fixture(&current, &header, Mining::LEN, &rent);
+    context.set_account(&user_mining, &fixture.into());
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .assert_on_chain_err(MplxRewardsError::AccountNeedsMigration);
+
+    // the reward mint is taken from the pool the mining belongs to only
+    let other_rewards = TestRewards::new(test_rewards.token_mint_pubkey);
+    other_rewards.initialize_pool(&mut context).await.unwrap();
+    let res = other_rewards
+        .migrate_mining(&mut context, &user_mining)
+        .await;
+    assert!(res.is_err());
+
+    test_rewards
+        .migrate_mining(&mut context, &user_mining)
+        .await
+        .unwrap();
+
+    let migrated = get_account(&mut context, &user_mining).await;
+    assert_eq!(migrated.data, mining_without_positions(&current).data);
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn mining_round_trip_from_third_versioned_layout() {
+    let (mut context, test_rewards, user, user_reward, user_mining, deposit) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    // the deposit isn't split between delegates, so it has no delegated stakes to be recorded
+    let current = get_account(&mut context, &user_mining).await;
+    let fixture = v3_mining_fixture(&current, &rent);
+    assert_eq!(fixture.data.len(), WrappedMining::LEN_V3);
+    context.set_account(&user_mining, &fixture.into());
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .assert_on_chain_err(MplxRewardsError::AccountNeedsMigration);
+
+    test_rewards
+        .migrate_mining(&mut context, &user_mining)
+        .await
+        .unwrap();
+
+    let migrated = get_account(&mut context, &user_mining).await;
+    assert_eq!(migrated.data, current.data);
+    assert!(migrated.lamports >= rent.minimum_balance(WrappedMining::LEN));
+
+    // positions are kept, so the deposit is withdrawn without recording it again
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &user_mining,
+            &user_mining,
+            deposit.id,
+            100,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn deposits_are_recorded_after_migration() {
+    let (mut context, test_rewards, user, _, user_mining, deposit) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let current = get_account(&mut context, &user_mining).await;
+    let header = versioned_mining_header(&current, 2);
+    let fixture = historic_mining_fixture(&current, &header, Mining::LEN, &rent);
+    context.set_account(&user_mining, &fixture.into());
+
+    test_rewards
+        .migrate_mining(&mut context, &user_mining)
+        .await
+        .unwrap();
+
+    // the deposit cannot be withdrawn until it's recorded
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &user_mining,
+            &user_mining,
+            deposit.id,
+            100,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DepositIsNotFound);
+
+    // the lockup of the deposit must match the scheduled modifiers
+    test_rewards
+        .record_deposit(
+            &mut context,
+            &user_mining,
+            &user.pubkey(),
+            deposit.id,
+            100,
+            LockupPeriod::OneYear,
+            deposit.start_ts,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::NoWeightedStakeModifiersAtADate);
+
+    test_rewards
+        .record_deposit(
+            &mut context,
+            &user_mining,
+            &user.pubkey(),
+            deposit.id,
+            100,
+            LockupPeriod::ThreeMonths,
+            deposit.start_ts,
+        )
+        .await
+        .unwrap();
+
+    let mut migrated = get_account(&mut context, &user_mining).await;
+    let mut expected = current.clone();
+    assert_eq!(
+        WrappedMining::from_bytes_mut(&mut migrated.data)
+            .unwrap()
+            .position(deposit.id),
+        WrappedMining::from_bytes_mut(&mut expected.data)
+            .unwrap()
+            .position(deposit.id)
+    );
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .record_deposit(
+            &mut context,
+            &user_mining,
+            &user.pubkey(),
+            deposit.id,
+            100,
+            LockupPeriod::ThreeMonths,
+            deposit.start_ts,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DepositIsAlreadyApplied);
+}
+
+#[tokio::test]
+async fn current_layout_is_left_untouched() {
+    let (mut context, test_rewards, _, _, user_mining, _) = setup().await;
+
+    let reward_pool = test_rewards.reward_pool.pubkey();
+    let pool_before = get_account(&mut context, &reward_pool).await;
+    let mining_before = get_account(&mut context, &user_mining).await;
+
+    test_rewards.migrate_pool(&mut context).await.unwrap();
+    test_rewards
+        .migrate_mining(&mut context, &user_mining)
+        .await
+        .unwrap();
+
+    assert_eq!(get_account(&mut context, &reward_pool).await, pool_before);
+    assert_eq!(get_account(&mut context, &user_mining).await, mining_before);
+}
+
+#[tokio::test]
+async fn unknown_layout_is_rejected() {
+    let (mut context, test_rewards, _, _, _, _) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let reward_pool = test_rewards.reward_pool.pubkey();
+    let current = get_account(&mut context, &reward_pool).await;
+    let header = unversioned_pool_header(&current);
+
+    let fixture = historic_layout_fixture(&current, &header, 192, &rent);
+    context.set_account(&reward_pool, &fixture.into());
+
+    test_rewards
+        .migrate_pool(&mut context)
+        .await
+        .assert_on_chain_err(MplxRewardsError::UnsupportedAccountVersion);
+}
@@ -1 +1,3 @@
+mod penalty_history;
+mod restore_penalty;
 mod slash;
@@ -0,0 +1,141 @@
+use crate::utils::*;
+use mplx_rewards::{
+    state::{PenaltyHistory, PenaltyKind, WrappedMining},
+    utils::{find_penalty_history_program_address, LockupPeriod},
+};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+
+async fn setup() -> (ProgramTestContext, TestRewards, Pubkey, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_rewards.initialize_mining(&mut context, &user).await;
+
+    (context, test_rewards, user.pubkey(), user_mining)
+}
+
+#[tokio::test]
+async fn penalties_are_recorded() {
+    let (mut context, test_rewards, user, mining_addr) = setup().await;
+    let penalty_history = test_rewards
+        .initialize_penalty_history(&mut context, &mining_addr)
+        .await;
+
+    let curr_ts = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            1_000,
+            LockupPeriod::ThreeMonths,
+            &user,
+            &mining_addr,
+            &user,
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .slash(&mut context, &mining_addr, &user, deposit_id, 100, 42)
+        .await
+        .unwrap();
+    test_rewards
+        .decrease_rewards(&mut context, &mining_addr, &user, 300, 43)
+        .await
+        .unwrap();
+
+    let penalty_history_account = get_account(&mut context, &penalty_history).await;
+    let history = PenaltyHistory::from_bytes(&penalty_history_account.data).unwrap();
+    assert_eq!(history.mining, mining_addr);
+    assert_eq!(history.records_count, 2);
+
+    let records: Vec<_> = history.records().collect();
+    assert_eq!(records[0].id, 0);
+    assert_eq!(records[0].kind(), Ok(PenaltyKind::Slash));
+    assert_eq!(records[0].native_amount, 100);
+    assert_eq!(records[0].weighted_amount, 200);
+    assert_eq!(records[0].reason_code, 42);
+    assert_eq!(records[0].timestamp, curr_ts);
+    assert_eq!(records[0].deposit_id(), Some(deposit_id));
+
+    assert_eq!(records[1].id, 1);
+    assert_eq!(records[1].kind(), Ok(PenaltyKind::DecreaseRewards));
+    assert_eq!(records[1].native_amount, 0);
+    assert_eq!(records[1].weighted_amount, 300);
+    assert_eq!(records[1].reason_code, 43);
+    assert_eq!(records[1].deposit_id(), None);
+}
+
+#[tokio::test]
+async fn penalties_are_applied_without_history() {
+    let (mut context, test_rewards, user, mining_addr) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            1_000,
+            LockupPeriod::Flex,
+            &user,
+            &mining_addr,
+            &user,
+        )
+        .await
+        .unwrap();
+
+    // the history address is passed, but the history hasn't been created
+    test_rewards
+        .decrease_rewards(&mut context, &mining_addr, &user, 300, 0)
+        .await
+        .unwrap();
+
+    // the history isn't passed at all, as the staking program used to call it
+    let (penalty_history, _) =
+        find_penalty_history_program_address(&mplx_rewards::id(), &mining_addr);
+    let mut slash = mplx_rewards::instruction::slash(
+        &mplx_rewards::id(),
+        &test_rewards.deposit_authority.pubkey(),
+        &test_rewards.reward_pool.pubkey(),
+        &mining_addr,
+        &penalty_history,
+        &user,
+        deposit_id,
+        100,
+        0,
+    );
+    slash.accounts.pop();
+    let tx = Transaction::new_signed_with_payer(
+        &[slash],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &test_rewards.deposit_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let mut mining_account = get_account(&mut context, &mining_addr).await;
+    let mining = WrappedMining::from_bytes_mut(&mut mining_account.data).unwrap();
+    assert_eq!(mining.mining.share, 600);
+    assert!(context
+        .banks_client
+        .get_account(penalty_history)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,533 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{
+    error::MplxRewardsError,
+    state::{PenaltyHistory, WrappedMining, WrappedRewardPool},
+    utils::LockupPeriod,
+};
+use sokoban::NodeAllocatorMap;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    (context, test_rewards, rewarder.pubkey())
+}
+
+async fn stake_expiration_date(context: &mut ProgramTestContext, days: u64) -> u64 {
+    let stake_expiration_date = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * days;
+    stake_expiration_date - stake_expiration_date % SECONDS_PER_DAY
+}
+
+#[tokio::test]
+async fn slash_is_restored() {
+    let (mut context, test_rewards, _) = setup().await;
+    let (user, _, mining_addr) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .initialize_penalty_history(&mut context, &mining_addr)
+        .await;
+
+    let stake_expiration_date = stake_expiration_date(&mut context, 90).await;
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            1_000,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &mining_addr,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .slash(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            deposit_id,
+            100,
+            0,
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .restore_penalty(&mut context, &mining_addr, &user.pubkey(), 0, false)
+        .await
+        .unwrap();
+
+    let mut reward_pool_account =
+        get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    assert_eq!(wrapped_reward_pool.pool.total_share, 2_000);
+    assert_eq!(
+        *wrapped_reward_pool
+            .weighted_stake_diffs
+            .get(&stake_expiration_date)
+            .unwrap(),
+        1_000
+    );
+
+    let mut mining_account = get_account(&mut context, &mining_addr).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.share, 2_000);
+    assert_eq!(
+        *mining
+            .weighted_stake_diffs
+            .get(&stake_expiration_date)
+            .unwrap(),
+        1_000
+    );
+    // slashed tokens are back in the deposit
+    assert_eq!(mining.position(deposit_id).unwrap().amount, 1_000);
+
+    let (penalty_history, _) = mplx_rewards::utils::find_penalty_history_program_address(
+        &mplx_rewards::id(),
+        &mining_addr,
+    );
+    let penalty_history_account = get_account(&mut context, &penalty_history).await;
+    let history = PenaltyHistory::from_bytes(&penalty_history_account.data).unwrap();
+    assert!(history.records().next().unwrap().is_restored());
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+    test_rewards
+        .restore_penalty(&mut context, &mining_addr, &user.pubkey(), 0, false)
+        .await
+        .assert_on_chain_err(MplxRewardsError::PenaltyAlreadyRestored);
+    test_rewards
+        .restore_penalty(&mut context, &mining_addr, &user.pubkey(), 1, false)
+        .await
+        .assert_on_chain_err(MplxRewardsError::PenaltyNotFound);
+}
+
+#[tokio::test]
+async fn decreased_rewards_are_restored() {
+    let (mut context, test_rewards, _) = setup().await;
+    let (user, _, mining_addr) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .initialize_penalty_history(&mut context, &mining_addr)
+        .await;
+
+    let stake_expiration_date = stake_expiration_date(&mut context, 90).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            1_000,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &mining_addr,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .decrease_rewards(&mut context, &mining_addr, &user.pubkey(), 1_500, 0)
+        .await
+        .unwrap();
+    test_rewards
+        .restore_penalty(&mut context, &mining_addr, &user.pubkey(), 0, false)
+        .await
+        .unwrap();
+
+    let mut mining_account = get_account(&mut context, &mining_addr).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.share, 2_000);
+    assert_eq!(
+        *mining
+            .weighted_stake_diffs
+            .get(&stake_expiration_date)
+            .unwrap(),
+        1_000
+    );
+}
+
+#[tokio::test]
+async fn lost_rewards_are_credited() {
+    let (mut context, test_rewards, rewarder) = setup().await;
+
+    let (user_a, user_rewards_a, user_mining_a) =
+        create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .initialize_penalty_history(&mut context, &user_mining_a)
+        .await;
+    let (user_b, user_rewards_b, user_mining_b) =
+        create_end_user(&mut context, &test_rewards).await;
+
+    let mut deposit_ids = vec![];
+    for (user, mining) in [(&user_a, &user_mining_a), (&user_b, &user_mining_b)] {
+        let deposit_id = test_rewards
+            .deposit_mining(
+                &mut context,
+                mining,
+                100,
+                LockupPeriod::ThreeMonths,
+                &user.pubkey(),
+                mining,
+                &user.pubkey(),
+            )
+            .await
+            .unwrap();
+        deposit_ids.push(deposit_id);
+    }
+    test_rewards
+        .slash(
+            &mut context,
+            &user_mining_a,
+            &user_a.pubkey(),
+            deposit_ids[0],
+            100,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 3;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder,
+            &test_rewards.fill_authority,
+            200,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    // the first day distribution went to the user B only, the user A lost the half of it,
+    // which is credited from the second day distribution
+    test_rewards
+        .restore_penalty(&mut context, &user_mining_a, &user_a.pubkey(), 0, true)
+        .await
+        .unwrap();
+
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &user_a,
+        &user_mining_a,
+        &user_rewards_a.pubkey(),
+        100,
+    )
+    .await;
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &user_b,
+        &user_mining_b,
+        &user_rewards_b.pubkey(),
+        100,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn decreased_rewards_are_credited_from_the_indexed_rewards() {
+    let (mut context, test_rewards, rewarder) = setup().await;
+
+    let (user_a, user_rewards_a, user_mining_a) =
+        create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .initialize_penalty_history(&mut context, &user_mining_a)
+        .await;
+    let (user_b, user_rewards_b, user_mining_b) =
+        create_end_user(&mut context, &test_rewards).await;
+
+    for (user, mining) in [(&user_a, &user_mining_a), (&user_b, &user_mining_b)] {
+        test_rewards
+            .deposit_mining(
+                &mut context,
+                mining,
+                100,
+                LockupPeriod::ThreeMonths,
+                &user.pubkey(),
+                mining,
+                &user.pubkey(),
+            )
+            .await
+            .unwrap();
+    }
+    test_rewards
+        .decrease_rewards(&mut context, &user_mining_a, &user_a.pubkey(), 200, 0)
+        .await
+        .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 3;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder,
+            &test_rewards.fill_authority,
+            200,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    // the decreased stake has stayed in the total share, so the half of the first day
+    // distribution is indexed already and nobody can claim it until it's restored
+    test_rewards
+        .restore_penalty(&mut context, &user_mining_a, &user_a.pubkey(), 0, true)
+        .await
+        .unwrap();
+
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &user_a,
+        &user_mining_a,
+        &user_rewards_a.pubkey(),
+        50,
+    )
+    .await;
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &user_b,
+        &user_mining_b,
+        &user_rewards_b.pubkey(),
+        50,
+    )
+    .await;
+
+    let mut reward_pool_account =
+        get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    // the second day distribution is untouched and nothing is left unclaimed
+    assert_eq!(
+        wrapped_reward_pool.pool.tokens_available_for_distribution,
+        100
+    );
+    assert_eq!(wrapped_reward_pool.pool.unclaimed_rewards(), 0);
+    assert_eq!(wrapped_reward_pool.pool.liabilities().unwrap(), 100);
+}
+
+#[tokio::test]
+async fn slash_is_restored_with_the_lockup_of_the_restaked_deposit() {
+    let (mut context, test_rewards, _) = setup().await;
+    let (user, _, mining_addr) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .initialize_penalty_history(&mut context, &mining_addr)
+        .await;
+
+    let old_expiration_date = stake_expiration_date(&mut context, 90).await;
+    let new_expiration_date = stake_expiration_date(&mut context, 365).await;
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            1_000,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &mining_addr,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .slash(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            deposit_id,
+            100,
+            0,
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .extend_stake(
+            &mut context,
+            &mining_addr,
+            &mining_addr,
+            deposit_id,
+            LockupPeriod::OneYear,
+            0,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .restore_penalty(&mut context, &mining_addr, &user.pubkey(), 0, false)
+        .await
+        .unwrap();
+
+    // the returned tokens have the weighted stake of the one year lockup
+    let mut mining_account = get_account(&mut context, &mining_addr).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.share, 6_000);
+    assert_eq!(
+        *mining
+            .weighted_stake_diffs
+            .get(&new_expiration_date)
+            .unwrap(),
+        5_000
+    );
+    assert_eq!(
+        mining
+            .weighted_stake_diffs
+            .get(&old_expiration_date)
+            .copied()
+            .unwrap_or_default(),
+        0
+    );
+    assert_eq!(mining.position(deposit_id).unwrap().amount, 1_000);
+
+    // the whole deposit leaves the mining without touching the stake of others
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &mining_addr,
+            &mining_addr,
+            deposit_id,
+            1_000,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let mut reward_pool_account =
+        get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    assert_eq!(wrapped_reward_pool.pool.total_share, 0);
+    assert_eq!(
+        wrapped_reward_pool
+            .weighted_stake_diffs
+            .get(&new_expiration_date)
+            .copied()
+            .unwrap_or_default(),
+        0
+    );
+
+    let mut mining_account = get_account(&mut context, &mining_addr).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.share, 0);
+    assert_eq!(
+        mining
+            .weighted_stake_diffs
+            .get(&new_expiration_date)
+            .copied()
+            .unwrap_or_default(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn slash_of_the_withdrawn_deposit_is_not_restorable() {
+    let (mut context, test_rewards, _) = setup().await;
+    let (user, _, mining_addr) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .initialize_penalty_history(&mut context, &mining_addr)
+        .await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            1_000,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &mining_addr,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .slash(
+            &mut context,
+            &mining_addr,
+            &user.pubkey(),
+            deposit_id,
+            100,
+            0,
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &mining_addr,
+            &mining_addr,
+            deposit_id,
+            900,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .restore_penalty(&mut context, &mining_addr, &user.pubkey(), 0, false)
+        .await
+        .assert_on_chain_err(MplxRewardsError::PenaltyNotRestorable);
+}
@@ -1,5 +1,6 @@
-use crate::utils::*;
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
 use mplx_rewards::{
+    error::MplxRewardsError,
     state::{WrappedMining, WrappedRewardPool},
     utils::LockupPeriod,
 };
@@ -23,6 +24,9 @@ async fn setup() -> (ProgramTestContext, TestRewards, Pubkey, Pubkey) {
 
     let user = Keypair::new();
     let user_mining = test_rewards.initialize_mining(&mut context, &user).await;
+    test_rewards
+        .initialize_penalty_history(&mut context, &user_mining)
+        .await;
 
     (context, test_rewards, user.pubkey(), user_mining)
 }
@@ -41,7 +45,7 @@ async fn one_stake_for_a_date() {
     let stake_expiration_date = stake_expiration_date - stake_expiration_date % SECONDS_PER_DAY;
 
     let lockup_period = LockupPeriod::SixMonths;
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining_addr,
@@ -81,14 +85,7 @@ async fn one_stake_for_a_date() {
     );
 
     test_rewards
-        .slash(
-            &mut context,
-            &mining_addr,
-            &user,
-            50,
-            200,
-            Some(stake_expiration_date),
-        )
+        .slash(&mut context, &mining_addr, &user, deposit_id, 50, 0)
         .await
         .unwrap();
 
@@ -132,7 +129,7 @@ async fn another_one_stake_for_a_date() {
     let stake_expiration_date = stake_expiration_date - stake_expiration_date % SECONDS_PER_DAY;
 
     let lockup_period = LockupPeriod::ThreeMonths;
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining_addr,
@@ -172,14 +169,7 @@ async fn another_one_stake_for_a_date() {
     );
 
     test_rewards
-        .slash(
-            &mut context,
-            &mining_addr,
-            &user,
-            5_000,
-            10_000,
-            Some(stake_expiration_date),
-        )
+        .slash(&mut context, &mining_addr, &user, deposit_id, 5_000, 0)
         .await
         .unwrap();
 
@@ -223,7 +213,7 @@ async fn multiple_stakes_for_a_date_but_one_slashed() {
     let stake_expiration_date = stake_expiration_date - stake_expiration_date % SECONDS_PER_DAY;
 
     let lockup_period = LockupPeriod::OneYear;
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining_addr,
@@ -296,39 +286,95 @@ async fn multiple_stakes_for_a_date_but_one_slashed() {
     );
 
     test_rewards
-        .slash(
-            &mut context,
-            &mining_addr,
-            &user,
-            50,
-            200,
-            Some(stake_expiration_date),
-        )
+        .slash(&mut context, &mining_addr, &user, deposit_id, 50, 0)
         .await
         .unwrap();
 
+    // the slashed tokens of the one year deposit: 50 x6
+    // weighted stake = 1900 - 300 = 1600
+    // diff = 1450 - 250 = 1200
+
     let mut reward_pool_account =
         get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
     let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
     let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
     let reward_pool = wrapped_reward_pool.pool;
-    assert_eq!(reward_pool.total_share, 1700);
+    assert_eq!(reward_pool.total_share, 1600);
     assert_eq!(
         *wrapped_reward_pool
             .weighted_stake_diffs
             .get(&stake_expiration_date)
             .unwrap(),
-        1300
+        1200
     );
     let mut mining_account = get_account(&mut context, &mining_addr).await;
     let mining_data = &mut mining_account.data.borrow_mut();
     let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
-    assert_eq!(mining.mining.share, 1700);
+    assert_eq!(mining.mining.share, 1600);
     assert_eq!(
         *mining
             .weighted_stake_diffs
             .get(&stake_expiration_date)
             .unwrap(),
-        1300
+        1200
+    );
+}
+
+#[tokio::test]
+async fn slash_is_bound_to_the_deposit() {
+    let (mut context, test_rewards, user, mining_addr) = setup().await;
+
+    let locked_deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            200, // 200 x6
+            LockupPeriod::OneYear,
+            &user,
+            &mining_addr,
+            &user,
+        )
+        .await
+        .unwrap();
+    let flex_deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining_addr,
+            100,
+            LockupPeriod::Flex,
+            &user,
+            &mining_addr,
+            &user,
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .slash(&mut context, &mining_addr, &user, flex_deposit_id, 150, 0)
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidDepositAmount);
+    test_rewards
+        .slash(&mut context, &mining_addr, &user, 42, 50, 0)
+        .await
+        .assert_on_chain_err(MplxRewardsError::DepositIsNotFound);
+
+    // the flex deposit has no modifier to change
+    test_rewards
+        .slash(&mut context, &mining_addr, &user, flex_deposit_id, 100, 0)
+        .await
+        .unwrap();
+
+    let mut mining_account = get_account(&mut context, &mining_addr).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.share, 1_200);
+    let locked_position = *mining.position(locked_deposit_id).unwrap();
+    assert_eq!(locked_position.amount, 200);
+    assert_eq!(
+        mining
+            .weighted_stake_diffs
+            .get(&locked_position.stake_expiration_date),
+        Some(&1_000)
     );
+    assert_eq!(mining.position(flex_deposit_id).unwrap().amount, 0);
 }
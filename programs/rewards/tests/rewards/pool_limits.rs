@@ -0,0 +1,200 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, state::WrappedRewardPool, utils::LockupPeriod};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Pubkey, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+    test_rewards
+        .set_pool_limits(&mut context, 10, 1_000, 1_500)
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_rewards.initialize_mining(&mut context, &user).await;
+
+    (context, test_rewards, user.pubkey(), user_mining)
+}
+
+#[tokio::test]
+async fn limits_are_set() {
+    let (mut context, test_rewards, _, _) = setup().await;
+
+    let mut reward_pool_account =
+        get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    assert_eq!(wrapped_reward_pool.pool.min_deposit, 10);
+    assert_eq!(
+        wrapped_reward_pool.pool.max_weighted_share_per_mining,
+        1_000
+    );
+    assert_eq!(wrapped_reward_pool.pool.max_total_share, 1_500);
+}
+
+#[tokio::test]
+async fn dust_deposit_is_rejected() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            9,
+            LockupPeriod::Flex,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DepositIsTooSmall);
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            10,
+            LockupPeriod::Flex,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn mining_share_limit_is_enforced() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    // 500 * 2 = 1000 is exactly the limit
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            500,
+            LockupPeriod::ThreeMonths,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            10,
+            LockupPeriod::Flex,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::MiningShareLimitExceeded);
+}
+
+#[tokio::test]
+async fn extend_stake_respects_limits() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            300,
+            LockupPeriod::ThreeMonths,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .extend_stake(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id,
+            LockupPeriod::ThreeMonths,
+            5,
+            &user,
+            &user,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DepositIsTooSmall);
+
+    // 300 * 4 = 1200 exceeds the limit per mining
+    test_rewards
+        .extend_stake(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id,
+            LockupPeriod::SixMonths,
+            0,
+            &user,
+            &user,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::MiningShareLimitExceeded);
+}
+
+#[tokio::test]
+async fn pool_cap_is_enforced() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            1_000,
+            LockupPeriod::Flex,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .unwrap();
+
+    let (another_user, _, another_mining) = create_end_user(&mut context, &test_rewards).await;
+    let another_user = another_user.pubkey();
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &another_mining,
+            600,
+            LockupPeriod::Flex,
+            &another_user,
+            &another_mining,
+            &another_user,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::PoolShareLimitExceeded);
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &another_mining,
+            500,
+            LockupPeriod::Flex,
+            &another_user,
+            &another_mining,
+            &another_user,
+        )
+        .await
+        .unwrap();
+}
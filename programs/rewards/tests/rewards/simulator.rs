@@ -0,0 +1,398 @@
+use crate::utils::*;
+use mplx_rewards::{
+    error::MplxRewardsError,
+    invariants::{check_invariants, MiningAccounts},
+    state::{
+        Mining, RewardPool, WrappedImmutableMining, WrappedImmutableRewardPool, WrappedMining,
+        WrappedRewardPool, POOL_MODIFIERS_TREE_CAPACITY,
+    },
+    utils::LockupPeriod,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+/// In-process model of the pool and its minings. It drives the same accounting
+/// the program does, but without transactions and with the clock under control.
+pub struct Simulator {
+    pool: Vec<u8>,
+    minings: Vec<Vec<u8>>,
+    pub now: u64,
+    next_deposit_id: u64,
+}
+
+impl Simulator {
+    pub fn new(now: u64) -> Self {
+        let mut pool = vec![0; WrappedRewardPool::LEN];
+        let wrapped_reward_pool =
+            WrappedRewardPool::from_uninitialized_bytes_mut(&mut pool).unwrap();
+        *wrapped_reward_pool.pool = RewardPool::initialize(
+            0,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        wrapped_reward_pool.weighted_stake_diffs.initialize();
+        wrapped_reward_pool.cumulative_index.initialize();
+
+        Self {
+            pool,
+            minings: vec![],
+            now,
+            next_deposit_id: 0,
+        }
+    }
+
+    /// Continues the accounting of the pool and the minings stored in the given accounts
+    pub fn from_accounts(pool: Vec<u8>, minings: Vec<Vec<u8>>, now: u64) -> Self {
+        Self {
+            pool,
+            minings,
+            now,
+            next_deposit_id: 0,
+        }
+    }
+
+    /// Returns the data of the pool and the minings, the added ones included
+    pub fn into_accounts(self) -> (Vec<u8>, Vec<Vec<u8>>) {
+        (self.pool, self.minings)
+    }
+
+    pub fn add_mining(&mut self) -> usize {
+        let mut mining = vec![0; WrappedMining::LEN];
+        let wrapped_mining = WrappedMining::from_uninitialized_bytes_mut(&mut mining).unwrap();
+        *wrapped_mining.mining = Mining::initialize(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+        );
+        wrapped_mining.weighted_stake_diffs.initialize();
+        wrapped_mining.positions.initialize();
+
+        self.minings.push(mining);
+        self.minings.len() - 1
+    }
+
+    pub fn pool(&mut self) -> WrappedRewardPool<'_> {
+        WrappedRewardPool::from_bytes_mut(&mut self.pool).unwrap()
+    }
+
+    pub fn mining(&mut self, i: usize) -> WrappedMining<'_> {
+        WrappedMining::from_bytes_mut(&mut self.minings[i]).unwrap()
+    }
+
+    fn pool_and_mining(&mut self, i: usize) -> (WrappedRewardPool<'_>, WrappedMining<'_>) {
+        (
+            WrappedRewardPool::from_bytes_mut(&mut self.pool).unwrap(),
+            WrappedMining::from_bytes_mut(&mut self.minings[i]).unwrap(),
+        )
+    }
+
+    pub fn advance(&mut self, seconds: u64) {
+        self.now += seconds;
+    }
+
+    pub fn fill(&mut self, rewards: u64, distribution_ends_at: u64) {
+        let now = self.now;
+        self.pool()
+            .pool
+            .fill(rewards, distribution_ends_at, now)
+            .unwrap();
+    }
+
+    /// Returns the id of the deposit
+    pub fn deposit(&mut self, i: usize, amount: u64, lockup_period: LockupPeriod) -> u64 {
+        let now = self.now;
+        let deposit_id = self.next_deposit_id;
+        self.next_deposit_id += 1;
+        let (mut pool, mut mining) = self.pool_and_mining(i);
+        pool.deposit(&mut mining, deposit_id, amount, lockup_period, &[], now)
+            .unwrap();
+        deposit_id
+    }
+
+    pub fn withdraw(&mut self, i: usize, deposit_id: u64, amount: u64) {
+        let now = self.now;
+        let (mut pool, mut mining) = self.pool_and_mining(i);
+        pool.withdraw(&mut mining, deposit_id, amount, &[], now)
+            .unwrap();
+    }
+
+    pub fn distribute(&mut self) {
+        let now = self.now;
+        let mut pool = self.pool();
+        let rewards = pool.pool.rewards_to_distribute(now).unwrap();
+        pool.distribute(rewards, now).unwrap();
+    }
+
+    pub fn claim(&mut self, i: usize) -> u64 {
+        let now = self.now;
+        let (mut pool, mut mining) = self.pool_and_mining(i);
+        pool.claim(&mut mining, now).unwrap()
+    }
+
+    pub fn assert_invariants(&self) {
+        let pool = WrappedImmutableRewardPool::from_bytes(&self.pool).unwrap();
+        let minings: Vec<_> = self
+            .minings
+            .iter()
+            .map(|mining| MiningAccounts {
+                mining: WrappedImmutableMining::from_bytes(mining).unwrap(),
+                penalty_history: None,
+            })
+            .collect();
+
+        check_invariants(&pool, &minings, self.now).unwrap();
+    }
+}
+
+/// Deposit as it's tracked by the staking contract
+struct Deposit {
+    mining: usize,
+    id: u64,
+    amount: u64,
+    expires_at: u64,
+}
+
+const LOCKUP_PERIODS: [LockupPeriod; 4] = [
+    LockupPeriod::Flex,
+    LockupPeriod::ThreeMonths,
+    LockupPeriod::SixMonths,
+    LockupPeriod::OneYear,
+];
+
+#[test]
+fn two_years_of_daily_activity() {
+    const MININGS: usize = 2_000;
+    const DAYS: u64 = 730;
+    const REWARDS: u64 = 1_000_000_000_000;
+
+    let mut rng = StdRng::seed_from_u64(730);
+    let mut sim = Simulator::new(1_700_000_000);
+    for _ in 0..MININGS {
+        sim.add_mining();
+    }
+    sim.fill(REWARDS, sim.now + DAYS * SECONDS_PER_DAY);
+
+    let mut deposits: Vec<Deposit> = vec![];
+    let mut claimed = 0;
+    for day in 0..DAYS {
+        for _ in 0..20 {
+            let mining = rng.gen_range(0..MININGS);
+            let amount = rng.gen_range(1..=1_000_000);
+            let lockup_period = LOCKUP_PERIODS[rng.gen_range(0..LOCKUP_PERIODS.len())];
+            let id = sim.deposit(mining, amount, lockup_period);
+            deposits.push(Deposit {
+                mining,
+                id,
+                amount,
+                expires_at: lockup_period
+                    .end_timestamp(sim.now, SECONDS_PER_DAY)
+                    .unwrap(),
+            });
+        }
+
+        let now = sim.now;
+        let (expired, active): (Vec<_>, Vec<_>) = deposits
+            .into_iter()
+            .partition(|d| d.expires_at <= now && rng.gen_bool(0.1));
+        for deposit in expired {
+            sim.withdraw(deposit.mining, deposit.id, deposit.amount);
+        }
+        deposits = active;
+
+        for _ in 0..20 {
+            claimed += sim.claim(rng.gen_range(0..MININGS));
+        }
+
+        // distribution goes first in the epoch, so the modifiers of the day are consumed
+        sim.advance(SECONDS_PER_DAY);
+        sim.distribute();
+        if day % 30 == 0 {
+            sim.assert_invariants();
+        }
+    }
+
+    for mining in 0..MININGS {
+        claimed += sim.claim(mining);
+    }
+    sim.assert_invariants();
+
+    let pool = sim.pool();
+    assert_eq!(pool.pool.tokens_available_for_distribution, 0);
+    assert_eq!(pool.pool.total_rewards_indexed, REWARDS);
+    assert_eq!(pool.pool.total_rewards_claimed, claimed);
+    // every mining loses less than a token on each refresh because of rounding
+    assert!(REWARDS - claimed < (MININGS as u64) * DAYS);
+}
+
+#[test]
+fn modifier_is_not_lost_when_the_tree_is_full() {
+    let mut sim = Simulator::new(1_700_000_000);
+    for _ in 0..POOL_MODIFIERS_TREE_CAPACITY {
+        let mining = sim.add_mining();
+        sim.deposit(mining, 100, LockupPeriod::OneYear);
+        sim.advance(SECONDS_PER_DAY);
+    }
+
+    let mining = sim.add_mining();
+    let now = sim.now;
+    let (mut pool, mut mining) = sim.pool_and_mining(mining);
+    assert_eq!(
+        pool.deposit(&mut mining, 0, 100, LockupPeriod::OneYear, &[], now),
+        Err(MplxRewardsError::WeightedStakeDiffsAreFull.into())
+    );
+}
+
+#[test]
+fn single_staker_gets_everything_over_three_years() {
+    const DAYS: u64 = 1_095;
+    const REWARDS: u64 = 1_095_000_000;
+
+    let mut sim = Simulator::new(1_700_000_000);
+    let mining = sim.add_mining();
+    sim.fill(REWARDS, sim.now + DAYS * SECONDS_PER_DAY);
+    sim.deposit(mining, 3, LockupPeriod::OneYear);
+
+    let mut claimed = 0;
+    for _ in 0..DAYS {
+        sim.advance(SECONDS_PER_DAY);
+        sim.distribute();
+        claimed += sim.claim(mining);
+    }
+
+    // less than a token is lost on each refresh because of rounding
+    assert!(REWARDS - claimed < DAYS);
+    assert_eq!(sim.pool().pool.tokens_available_for_distribution, 0);
+}
+
+#[tokio::test]
+async fn simulator_matches_the_program() {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let now = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64;
+    let mut sim = Simulator::new(now);
+    let distribution_ends_at = now + SECONDS_PER_DAY * 100;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            1_000_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+    sim.fill(1_000_000, distribution_ends_at);
+
+    let mut users = vec![];
+    for (amount, lockup_period) in [
+        (1_000, LockupPeriod::Flex),
+        (700, LockupPeriod::ThreeMonths),
+        (300, LockupPeriod::OneYear),
+    ] {
+        let (user, user_reward, user_mining) = create_end_user(&mut context, &test_rewards).await;
+        test_rewards
+            .deposit_mining(
+                &mut context,
+                &user_mining,
+                amount,
+                lockup_period,
+                &user.pubkey(),
+                &user_mining,
+                &user.pubkey(),
+            )
+            .await
+            .unwrap();
+        let mining = sim.add_mining();
+        sim.deposit(mining, amount, lockup_period);
+        users.push((user, user_reward, user_mining, mining));
+    }
+
+    for day in 1..=120 {
+        let now = advance_clock_by_ts(&mut context, SECONDS_PER_DAY as i64).await as u64;
+        sim.now = now;
+
+        test_rewards
+            .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+            .await
+            .unwrap();
+        sim.distribute();
+
+        if day % 7 == 0 {
+            let (user, user_reward, user_mining, mining) = &users[day % users.len()];
+            test_rewards
+                .claim(&mut context, user, user_mining, &user_reward.pubkey())
+                .await
+                .unwrap();
+            sim.claim(*mining);
+        }
+    }
+
+    for (_, _, user_mining, mining) in &users {
+        let mut mining_account = get_account(&mut context, user_mining).await;
+        let mining_data = &mut mining_account.data.borrow_mut();
+        let program_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+        let sim_mining = sim.mining(*mining);
+
+        assert_eq!(program_mining.mining.share, sim_mining.mining.share);
+        assert_eq!(
+            program_mining.mining.unclaimed_rewards,
+            sim_mining.mining.unclaimed_rewards
+        );
+        assert_eq!(
+            program_mining.mining.index_with_precision,
+            sim_mining.mining.index_with_precision
+        );
+    }
+
+    let mut reward_pool_account =
+        get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let program_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    let sim_pool = sim.pool();
+    assert_eq!(program_pool.pool.total_share, sim_pool.pool.total_share);
+    assert_eq!(
+        program_pool.pool.index_with_precision,
+        sim_pool.pool.index_with_precision
+    );
+    assert_eq!(
+        program_pool.pool.total_rewards_claimed,
+        sim_pool.pool.total_rewards_claimed
+    );
+}
@@ -0,0 +1,226 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, state::SolvencyReport, utils::LockupPeriod};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer, transaction::Transaction,
+};
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Pubkey,
+    Keypair,
+    Keypair,
+    Pubkey,
+) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let (user, user_reward, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 10;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            1_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_rewards,
+        rewarder.pubkey(),
+        user,
+        user_reward,
+        user_mining,
+    )
+}
+
+/// Moves tokens out of the vault bypassing the pool accounting,
+/// which is possible for the deposit authority as the owner of the vault
+async fn withdraw_from_vault(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    destination: &Pubkey,
+    amount: u64,
+) {
+    let tx = Transaction::new_signed_with_payer(
+        &[spl_token::instruction::transfer(
+            &spl_token::id(),
+            &test_rewards.vault_pubkey,
+            destination,
+            &test_rewards.deposit_authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &test_rewards.deposit_authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn report_surplus() {
+    let (mut context, test_rewards, _, _, _, _) = setup().await;
+
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.vault_pubkey,
+        500,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        test_rewards.report_solvency(&mut context).await.unwrap(),
+        SolvencyReport {
+            vault_balance: 1_500,
+            liabilities: 1_000,
+            surplus: 500,
+            deficit: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn distribute_and_claim_keep_liabilities() {
+    let (mut context, test_rewards, _, user, user_reward, user_mining) = setup().await;
+
+    advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    // distributed rewards are owed to miners until they're claimed
+    assert_eq!(
+        test_rewards.report_solvency(&mut context).await.unwrap(),
+        SolvencyReport {
+            vault_balance: 1_000,
+            liabilities: 1_000,
+            surplus: 0,
+            deficit: 0,
+        }
+    );
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward.pubkey())
+        .await
+        .unwrap();
+
+    // the claimed amount depends on how many days the clock has advanced,
+    // but whatever is left in the vault is still owed to the pool
+    let report = test_rewards.report_solvency(&mut context).await.unwrap();
+    assert!(report.vault_balance < 1_000);
+    assert_eq!(report.liabilities, report.vault_balance);
+    assert_eq!(report.surplus, 0);
+    assert_eq!(report.deficit, 0);
+}
+
+#[tokio::test]
+async fn insolvent_vault() {
+    let (mut context, test_rewards, rewarder, user, user_reward, user_mining) = setup().await;
+
+    advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    withdraw_from_vault(&mut context, &test_rewards, &rewarder, 950).await;
+
+    assert_eq!(
+        test_rewards.report_solvency(&mut context).await.unwrap(),
+        SolvencyReport {
+            vault_balance: 50,
+            liabilities: 1_000,
+            surplus: 0,
+            deficit: 950,
+        }
+    );
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward.pubkey())
+        .await
+        .assert_on_chain_err(MplxRewardsError::VaultIsInsolvent);
+
+    advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .assert_on_chain_err(MplxRewardsError::VaultIsInsolvent);
+
+    // filling the vault doesn't cover the deficit, since filled tokens become liabilities too
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 10;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder,
+            &test_rewards.fill_authority,
+            1_000,
+            distribution_ends_at,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::VaultIsInsolvent);
+}
@@ -0,0 +1,369 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{
+    error::MplxRewardsError,
+    state::{WrappedMining, WrappedRewardPool},
+    utils::LockupPeriod,
+};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Vec<Pubkey>,
+    Keypair,
+    Pubkey,
+) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+    let deposit_token_mint = Keypair::new();
+    let payer = &context.payer.pubkey();
+    create_mint(&mut context, &deposit_token_mint, payer)
+        .await
+        .unwrap();
+
+    let test_reward_pool = TestRewards::new(deposit_token_mint.pubkey());
+
+    test_reward_pool
+        .initialize_pool(&mut context)
+        .await
+        .unwrap();
+
+    let mut delegate_minings = vec![];
+    for _ in 0..3 {
+        let delegate = Keypair::new();
+        let delegate_mining = test_reward_pool
+            .initialize_mining(&mut context, &delegate)
+            .await;
+        test_reward_pool
+            .register_delegate(&mut context, &delegate_mining, &delegate, 0, vec![], vec![])
+            .await
+            .unwrap();
+        delegate_minings.push(delegate_mining);
+    }
+
+    let user = Keypair::new();
+    let user_mining = test_reward_pool
+        .initialize_mining(&mut context, &user)
+        .await;
+
+    (
+        context,
+        test_reward_pool,
+        delegate_minings,
+        user,
+        user_mining,
+    )
+}
+
+async fn stake_from_others(context: &mut ProgramTestContext, mining: &Pubkey) -> u64 {
+    let mut mining_account = get_account(context, mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    wrapped_mining.mining.stake_from_others
+}
+
+async fn total_share(context: &mut ProgramTestContext, test_rewards: &TestRewards) -> u64 {
+    let mut reward_pool_account = get_account(context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    wrapped_reward_pool.pool.total_share
+}
+
+#[tokio::test]
+async fn deposit_and_withdraw_split_between_delegates() {
+    let (mut context, test_rewards, delegate_minings, user, user_mining) = setup().await;
+    let split_delegates = [
+        (delegate_minings[0], 50),
+        (delegate_minings[1], 30),
+        (delegate_minings[2], 20),
+    ];
+
+    let deposit_id = test_rewards
+        .deposit_mining_split(
+            &mut context,
+            &user_mining,
+            1_000,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &split_delegates,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[0]).await,
+        500
+    );
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[1]).await,
+        300
+    );
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[2]).await,
+        200
+    );
+    // user's own weighted stake and the stake delegated to others
+    assert_eq!(total_share(&mut context, &test_rewards).await, 2_000);
+
+    test_rewards
+        .withdraw_mining_split(
+            &mut context,
+            &user_mining,
+            deposit_id,
+            1_000,
+            &user.pubkey(),
+            &split_delegates,
+        )
+        .await
+        .unwrap();
+
+    for delegate_mining in &delegate_minings {
+        assert_eq!(stake_from_others(&mut context, delegate_mining).await, 0);
+    }
+    assert_eq!(total_share(&mut context, &test_rewards).await, 0);
+}
+
+#[tokio::test]
+async fn remainder_goes_to_the_last_delegate() {
+    let (mut context, test_rewards, delegate_minings, user, user_mining) = setup().await;
+    let split_delegates = [
+        (delegate_minings[0], 1),
+        (delegate_minings[1], 1),
+        (delegate_minings[2], 1),
+    ];
+
+    test_rewards
+        .deposit_mining_split(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &split_delegates,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[0]).await,
+        33
+    );
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[1]).await,
+        33
+    );
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[2]).await,
+        34
+    );
+    assert_eq!(total_share(&mut context, &test_rewards).await, 200);
+}
+
+#[tokio::test]
+async fn partial_withdrawals_take_back_the_delegated_stake() {
+    let (mut context, test_rewards, delegate_minings, user, user_mining) = setup().await;
+    let split_delegates = [
+        (delegate_minings[0], 1),
+        (delegate_minings[1], 1),
+        (delegate_minings[2], 1),
+    ];
+
+    // 33, 33 and 34 are delegated
+    let deposit_id = test_rewards
+        .deposit_mining_split(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &split_delegates,
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .withdraw_mining_split(
+            &mut context,
+            &user_mining,
+            deposit_id,
+            50,
+            &user.pubkey(),
+            &split_delegates,
+        )
+        .await
+        .unwrap();
+
+    // delegated stakes are halved, the remainder is taken from the first rounded one
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[0]).await,
+        17
+    );
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[1]).await,
+        16
+    );
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[2]).await,
+        17
+    );
+    assert_eq!(total_share(&mut context, &test_rewards).await, 100);
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .withdraw_mining_split(
+            &mut context,
+            &user_mining,
+            deposit_id,
+            50,
+            &user.pubkey(),
+            &split_delegates,
+        )
+        .await
+        .unwrap();
+
+    for delegate_mining in &delegate_minings {
+        assert_eq!(stake_from_others(&mut context, delegate_mining).await, 0);
+    }
+    assert_eq!(total_share(&mut context, &test_rewards).await, 0);
+}
+
+#[tokio::test]
+async fn withdrawal_from_other_delegates_is_rejected() {
+    let (mut context, test_rewards, delegate_minings, user, user_mining) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining_split(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &[(delegate_minings[0], 1), (delegate_minings[1], 1)],
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .withdraw_mining_split(
+            &mut context,
+            &user_mining,
+            deposit_id,
+            50,
+            &user.pubkey(),
+            &[
+                (delegate_minings[0], 1),
+                (delegate_minings[1], 1),
+                (delegate_minings[2], 1),
+            ],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DelegatesDontMatchDeposit);
+}
+
+#[tokio::test]
+async fn change_split_delegates() {
+    let (mut context, test_rewards, delegate_minings, user, user_mining) = setup().await;
+    let old_split_delegates = [(delegate_minings[0], 1), (delegate_minings[1], 1)];
+    let new_split_delegates = [(delegate_minings[1], 3), (delegate_minings[2], 1)];
+
+    let deposit_id = test_rewards
+        .deposit_mining_split(
+            &mut context,
+            &user_mining,
+            1_000,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &old_split_delegates,
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .change_delegate_split(
+            &mut context,
+            &user_mining,
+            &user,
+            &old_split_delegates,
+            &new_split_delegates,
+            deposit_id,
+            1_000,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[0]).await,
+        0
+    );
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[1]).await,
+        750
+    );
+    assert_eq!(
+        stake_from_others(&mut context, &delegate_minings[2]).await,
+        250
+    );
+    assert_eq!(total_share(&mut context, &test_rewards).await, 2_000);
+}
+
+#[tokio::test]
+async fn split_with_zero_weight() {
+    let (mut context, test_rewards, delegate_minings, user, user_mining) = setup().await;
+
+    test_rewards
+        .deposit_mining_split(
+            &mut context,
+            &user_mining,
+            1_000,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &[(delegate_minings[0], 1), (delegate_minings[1], 0)],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidDelegateWeights);
+}
+
+#[tokio::test]
+async fn split_with_duplicated_delegate() {
+    let (mut context, test_rewards, delegate_minings, user, user_mining) = setup().await;
+
+    test_rewards
+        .deposit_mining_split(
+            &mut context,
+            &user_mining,
+            1_000,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &[(delegate_minings[0], 1), (delegate_minings[0], 1)],
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::DelegateIsDuplicated);
+}
+
+#[tokio::test]
+async fn split_to_unregistered_delegate() {
+    let (mut context, test_rewards, delegate_minings, user, user_mining) = setup().await;
+
+    let delegate = Keypair::new();
+    let unregistered_delegate_mining = test_rewards
+        .initialize_mining(&mut context, &delegate)
+        .await;
+
+    let res = test_rewards
+        .deposit_mining_split(
+            &mut context,
+            &user_mining,
+            1_000,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &[(delegate_minings[0], 1), (unregistered_delegate_mining, 1)],
+        )
+        .await;
+    assert!(res.is_err());
+}
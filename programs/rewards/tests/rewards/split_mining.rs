@@ -0,0 +1,159 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, state::WrappedMining, utils::LockupPeriod};
+use sokoban::NodeAllocatorMap;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Keypair,
+    Pubkey,
+    Pubkey,
+    u64,
+    u64,
+) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    let stake_expiration_date = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 30 * 3;
+    let stake_expiration_date = stake_expiration_date - stake_expiration_date % SECONDS_PER_DAY;
+
+    let (user, _, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let (_, _, destination_mining) = create_end_user(&mut context, &test_rewards).await;
+
+    (
+        context,
+        test_rewards,
+        user,
+        user_mining,
+        destination_mining,
+        deposit_id,
+        stake_expiration_date,
+    )
+}
+
+async fn assert_mining(
+    context: &mut ProgramTestContext,
+    mining_account: &Pubkey,
+    share: u64,
+    stake_expiration_date: u64,
+    diff: u64,
+) {
+    let mut mining_account = get_account(context, mining_account).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.share, share);
+    assert_eq!(
+        *mining
+            .weighted_stake_diffs
+            .get(&stake_expiration_date)
+            .unwrap(),
+        diff
+    );
+}
+
+#[tokio::test]
+async fn success() {
+    let (
+        mut context,
+        test_rewards,
+        user,
+        user_mining,
+        destination_mining,
+        deposit_id,
+        stake_expiration_date,
+    ) = setup().await;
+
+    let reward_pool_before = get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+
+    test_rewards
+        .split_mining(
+            &mut context,
+            &user_mining,
+            &destination_mining,
+            &user,
+            deposit_id,
+            40,
+        )
+        .await
+        .unwrap();
+
+    // pool's total share and modifiers stay the same
+    let reward_pool_after = get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    assert_eq!(reward_pool_before.data, reward_pool_after.data);
+
+    assert_mining(&mut context, &user_mining, 120, stake_expiration_date, 60).await;
+    assert_mining(
+        &mut context,
+        &destination_mining,
+        80,
+        stake_expiration_date,
+        40,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn split_more_than_staked_fails() {
+    let (mut context, test_rewards, user, user_mining, destination_mining, deposit_id, _) =
+        setup().await;
+
+    test_rewards
+        .split_mining(
+            &mut context,
+            &user_mining,
+            &destination_mining,
+            &user,
+            deposit_id,
+            150,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidDepositAmount);
+}
+
+#[tokio::test]
+async fn split_into_itself_fails() {
+    let (mut context, test_rewards, user, user_mining, _, deposit_id, _) = setup().await;
+
+    test_rewards
+        .split_mining(
+            &mut context,
+            &user_mining,
+            &user_mining,
+            &user,
+            deposit_id,
+            40,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::MiningsAreTheSame);
+}
@@ -0,0 +1,232 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, state::WrappedRewardPool, utils::LockupPeriod};
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use spl_token::state::Account;
+use std::borrow::{Borrow, BorrowMut};
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Pubkey,
+    Keypair,
+    Pubkey,
+    Pubkey,
+    u64,
+) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let (user, user_reward, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 10;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            1_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+
+    advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_rewards,
+        rewarder.pubkey(),
+        user,
+        user_reward.pubkey(),
+        user_mining,
+        deposit_id,
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_rewards, _, _, _, _, _) = setup().await;
+
+    test_rewards
+        .sunset_pool(&mut context, SECONDS_PER_DAY)
+        .await
+        .unwrap();
+
+    let mut reward_pool_account =
+        get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    assert!(wrapped_reward_pool.pool.is_sunset());
+    assert_eq!(
+        wrapped_reward_pool.pool.sunset_grace_period_secs,
+        SECONDS_PER_DAY
+    );
+
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+
+    test_rewards
+        .sunset_pool(&mut context, SECONDS_PER_DAY)
+        .await
+        .assert_on_chain_err(MplxRewardsError::PoolIsSunset);
+}
+
+#[tokio::test]
+async fn deposits_and_distributions_are_rejected() {
+    let (mut context, test_rewards, rewarder, user, _, user_mining, _) = setup().await;
+
+    test_rewards
+        .sunset_pool(&mut context, SECONDS_PER_DAY)
+        .await
+        .unwrap();
+
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::PoolIsSunset);
+
+    advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .assert_on_chain_err(MplxRewardsError::PoolIsSunset);
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 10;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder,
+            &test_rewards.fill_authority,
+            1_000,
+            distribution_ends_at,
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::PoolIsSunset);
+}
+
+#[tokio::test]
+async fn claim_and_withdraw_during_grace_period() {
+    let (mut context, test_rewards, _, user, user_reward, user_mining, deposit_id) = setup().await;
+
+    test_rewards
+        .sunset_pool(&mut context, SECONDS_PER_DAY * 2)
+        .await
+        .unwrap();
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+    let user_reward_account = get_account(&mut context, &user_reward).await;
+    let user_rewards = Account::unpack(user_reward_account.data.borrow()).unwrap();
+    assert!(user_rewards.amount > 0);
+
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &user_mining,
+            &user_mining,
+            deposit_id,
+            100,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn claim_after_grace_period_fails_and_pool_is_swept() {
+    let (mut context, test_rewards, rewarder, user, user_reward, user_mining, _) = setup().await;
+
+    test_rewards
+        .sunset_pool(&mut context, SECONDS_PER_DAY)
+        .await
+        .unwrap();
+
+    // the pool still has stakes until the grace period is elapsed
+    test_rewards
+        .close_pool(&mut context, &rewarder, &Keypair::new().pubkey())
+        .await
+        .assert_on_chain_err(MplxRewardsError::PoolHasStakes);
+
+    advance_clock_by_ts(&mut context, (SECONDS_PER_DAY * 2).try_into().unwrap()).await;
+
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .assert_on_chain_err(MplxRewardsError::SunsetGracePeriodElapsed);
+
+    test_rewards
+        .close_pool(&mut context, &rewarder, &Keypair::new().pubkey())
+        .await
+        .unwrap();
+    assert_tokens(&mut context, &rewarder, 1_000_000).await;
+}
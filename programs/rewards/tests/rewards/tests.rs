@@ -1,13 +1,33 @@
+mod account_validation;
+mod boost;
 mod change_delegate;
 mod claim;
+mod client;
 mod close_mining;
+mod close_pool;
+mod compute_units;
+mod delegate_registry;
+mod delegation_expiry;
 mod deposit_mining;
 mod distribute_rewards;
+mod epoch_length;
 mod fill_vault;
 mod initialize_mining;
 mod initialize_pool;
 mod integration;
+mod invariants;
+mod merge_mining;
+mod migrate;
+mod pool_limits;
 mod precision;
+mod simulator;
+mod solvency;
+mod split_delegation;
+mod split_mining;
+mod sunset_pool;
+mod token_2022;
+mod transfer_mining;
+mod unbonding;
 mod utils;
 mod withdraw_mining;
 
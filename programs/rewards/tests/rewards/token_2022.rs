@@ -0,0 +1,199 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, state::WrappedRewardPool, utils::LockupPeriod};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{account::Account, clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+use std::borrow::BorrowMut;
+
+// 1% transfer fee
+const TRANSFER_FEE_BASIS_POINTS: u16 = 100;
+const MAXIMUM_FEE: u64 = 1_000_000;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint_with_transfer_fee(
+        &mut context,
+        &mint,
+        owner,
+        TRANSFER_FEE_BASIS_POINTS,
+        MAXIMUM_FEE,
+    )
+    .await
+    .unwrap();
+
+    let test_rewards = TestRewards::new_with_token_program(mint.pubkey(), spl_token_2022::id());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            100_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+
+    (context, test_rewards, rewarder.pubkey())
+}
+
+#[tokio::test]
+async fn fill_vault_with_transfer_fee() {
+    let (mut context, test_rewards, rewarder) = setup().await;
+
+    assert_tokens(&mut context, &rewarder, 900_000).await;
+    // 1_000 is withheld by the mint as the transfer fee
+    assert_tokens(&mut context, &test_rewards.vault_pubkey, 99_000).await;
+
+    let mut reward_pool_account =
+        get_account(&mut context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+    assert_eq!(
+        wrapped_reward_pool.pool.tokens_available_for_distribution,
+        99_000
+    );
+}
+
+#[tokio::test]
+async fn claim_with_transfer_fee() {
+    let (mut context, test_rewards, _) = setup().await;
+
+    let (user, user_reward, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    // 990 out of claimed 99_000 is withheld by the mint as the transfer fee
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &user,
+        &user_mining,
+        &user_reward.pubkey(),
+        98_010,
+    )
+    .await;
+    assert_tokens(&mut context, &test_rewards.vault_pubkey, 0).await;
+}
+
+#[tokio::test]
+async fn close_pool_harvests_withheld_fees() {
+    let (mut context, test_rewards, rewarder) = setup().await;
+
+    let target = Keypair::new();
+    test_rewards
+        .close_pool(&mut context, &rewarder, &target.pubkey())
+        .await
+        .unwrap();
+
+    // 990 out of swept 99_000 is withheld by the mint as the transfer fee
+    assert_tokens(&mut context, &rewarder, 998_010).await;
+
+    let vault_after = context
+        .banks_client
+        .get_account(test_rewards.vault_pubkey)
+        .await
+        .unwrap();
+    assert_eq!(None, vault_after);
+
+    let mint_account = get_account(&mut context, &test_rewards.token_mint_pubkey).await;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_account.data).unwrap();
+    let transfer_fee_config = mint.get_extension::<TransferFeeConfig>().unwrap();
+    assert_eq!(u64::from(transfer_fee_config.withheld_amount), 1_000);
+}
+
+#[tokio::test]
+async fn initialize_pool_with_unsupported_token_program() {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let mint = Keypair::new();
+    let token_program = Pubkey::new_unique();
+    context.set_account(
+        &mint.pubkey(),
+        &Account {
+            lamports: 1_000_000_000,
+            data: vec![0; 82],
+            owner: token_program,
+            ..Default::default()
+        }
+        .into(),
+    );
+
+    let test_rewards = TestRewards::new_with_token_program(mint.pubkey(), token_program);
+    test_rewards
+        .initialize_pool(&mut context)
+        .await
+        .assert_on_chain_err(MplxRewardsError::UnsupportedTokenProgram);
+}
+
+#[tokio::test]
+async fn initialize_pool_with_unsupported_mint_extension() {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint_with_permanent_delegate(&mut context, &mint, owner)
+        .await
+        .unwrap();
+
+    let test_rewards = TestRewards::new_with_token_program(mint.pubkey(), spl_token_2022::id());
+    test_rewards
+        .initialize_pool(&mut context)
+        .await
+        .assert_on_chain_err(MplxRewardsError::UnsupportedMintExtension);
+}
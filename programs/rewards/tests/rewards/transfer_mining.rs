@@ -0,0 +1,207 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{error::MplxRewardsError, state::WrappedMining, utils::LockupPeriod};
+use sokoban::NodeAllocatorMap;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
+use std::borrow::BorrowMut;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Keypair, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+
+    // mint token for fill_authority aka wallet who will fill the vault with tokens
+    let rewarder = Keypair::new();
+    create_token_account(
+        &mut context,
+        &rewarder,
+        &test_rewards.token_mint_pubkey,
+        &test_rewards.fill_authority.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut context,
+        &test_rewards.token_mint_pubkey,
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let (user, _, user_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &user_mining,
+            100,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let distribution_ends_at = context
+        .banks_client
+        .get_sysvar::<solana_program::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+        + SECONDS_PER_DAY * 10;
+    test_rewards
+        .fill_vault(
+            &mut context,
+            &rewarder.pubkey(),
+            &test_rewards.fill_authority,
+            1_000,
+            distribution_ends_at,
+        )
+        .await
+        .unwrap();
+
+    advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .distribute_rewards(&test_rewards.distribution_authority, &mut context)
+        .await
+        .unwrap();
+
+    (context, test_rewards, user, user_mining)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_rewards, user, user_mining) = setup().await;
+
+    let new_owner = Keypair::new();
+    let new_owner_reward = Keypair::new();
+    create_token_account(
+        &mut context,
+        &new_owner_reward,
+        &test_rewards.token_mint_pubkey,
+        &new_owner.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    let target = Keypair::new();
+    let new_mining = test_rewards
+        .transfer_mining(
+            &mut context,
+            &user_mining,
+            &user,
+            &new_owner.pubkey(),
+            &target.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let old_mining_account = context.banks_client.get_account(user_mining).await.unwrap();
+    assert_eq!(None, old_mining_account);
+    let target = get_account(&mut context, &target.pubkey()).await;
+    assert!(target.lamports > 0);
+
+    let mut mining_account = get_account(&mut context, &new_mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.owner, new_owner.pubkey());
+    assert_eq!(mining.mining.share, 200);
+    let unclaimed_rewards = mining.mining.unclaimed_rewards;
+    assert!(unclaimed_rewards > 0);
+    assert_eq!(mining.weighted_stake_diffs.len(), 1);
+
+    claim_and_assert(
+        &test_rewards,
+        &mut context,
+        &new_owner,
+        &new_mining,
+        &new_owner_reward.pubkey(),
+        unclaimed_rewards,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn transfer_by_not_an_owner_fails() {
+    let (mut context, test_rewards, _, user_mining) = setup().await;
+
+    let stranger = Keypair::new();
+    let res = test_rewards
+        .transfer_mining(
+            &mut context,
+            &user_mining,
+            &stranger,
+            &stranger.pubkey(),
+            &Keypair::new().pubkey(),
+        )
+        .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn transfer_to_owner_with_mining_fails() {
+    let (mut context, test_rewards, user, user_mining) = setup().await;
+
+    let (another_user, _, _) = create_end_user(&mut context, &test_rewards).await;
+    let res = test_rewards
+        .transfer_mining(
+            &mut context,
+            &user_mining,
+            &user,
+            &another_user.pubkey(),
+            &Keypair::new().pubkey(),
+        )
+        .await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn transfer_with_stake_from_others_fails() {
+    let (mut context, test_rewards, user, user_mining) = setup().await;
+
+    test_rewards
+        .register_delegate(&mut context, &user_mining, &user, 0, vec![], vec![])
+        .await
+        .unwrap();
+    let (delegator, _, delegator_mining) = create_end_user(&mut context, &test_rewards).await;
+    test_rewards
+        .deposit_mining(
+            &mut context,
+            &delegator_mining,
+            50,
+            LockupPeriod::Flex,
+            &delegator.pubkey(),
+            &user_mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .transfer_mining(
+            &mut context,
+            &user_mining,
+            &user,
+            &Keypair::new().pubkey(),
+            &Keypair::new().pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::StakeFromOthersMustBeZero);
+
+    let mut mining_account = get_account(&mut context, &user_mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(mining.mining.owner, user.pubkey());
+    assert_eq!(mining.mining.stake_from_others, 50);
+}
@@ -0,0 +1,291 @@
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
+use mplx_rewards::{
+    error::MplxRewardsError,
+    state::{WrappedMining, WrappedRewardPool},
+    utils::LockupPeriod,
+};
+use sokoban::NodeAllocatorMap;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer, transaction::Transaction,
+};
+use std::borrow::BorrowMut;
+
+const UNBONDING_PERIOD: u64 = 5 * SECONDS_PER_DAY;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Keypair, Pubkey) {
+    let test = ProgramTest::new("mplx_rewards", mplx_rewards::ID, None);
+    let mut context = test.start_with_context().await;
+
+    let owner = &context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut context, &mint, owner).await.unwrap();
+
+    let test_rewards = TestRewards::new(mint.pubkey());
+    test_rewards.initialize_pool(&mut context).await.unwrap();
+    // unbonding tokens earn half of the Flex ones
+    test_rewards
+        .set_unbonding_config(&mut context, UNBONDING_PERIOD, 5_000)
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_rewards.initialize_mining(&mut context, &user).await;
+
+    (context, test_rewards, user, user_mining)
+}
+
+async fn shares(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    mining: &Pubkey,
+) -> (u64, u64) {
+    let mut reward_pool_account = get_account(context, &test_rewards.reward_pool.pubkey()).await;
+    let reward_pool_data = &mut reward_pool_account.data.borrow_mut();
+    let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
+
+    let mut mining_account = get_account(context, mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+
+    (
+        wrapped_reward_pool.pool.total_share,
+        wrapped_mining.mining.share,
+    )
+}
+
+#[tokio::test]
+async fn requested_tokens_earn_with_unbonding_multiplier() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let curr_ts = advance_clock_by_ts(&mut context, SECONDS_PER_DAY.try_into().unwrap()).await;
+    test_rewards
+        .request_withdraw(&mut context, &mining, &user, deposit_id, 40)
+        .await
+        .unwrap();
+
+    // 60 x1 + 40 x0.5
+    assert_eq!(shares(&mut context, &test_rewards, &mining).await, (80, 80));
+
+    let mut mining_account = get_account(&mut context, &mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    let position = *wrapped_mining.position(deposit_id).unwrap();
+    assert_eq!(position.amount, 60);
+    assert_eq!(position.unbonding_amount, 40);
+    assert_eq!(position.unbonding_stake, 20);
+    assert_eq!(
+        position.unbonding_ends_at,
+        curr_ts as u64 + UNBONDING_PERIOD
+    );
+}
+
+#[tokio::test]
+async fn locked_tokens_lose_their_lockup_straight_away() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+    test_rewards
+        .set_unbonding_config(&mut context, UNBONDING_PERIOD, 0)
+        .await
+        .unwrap();
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            100,
+            LockupPeriod::ThreeMonths,
+            &user.pubkey(),
+            &mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .request_withdraw(&mut context, &mining, &user, deposit_id, 100)
+        .await
+        .unwrap();
+
+    assert_eq!(shares(&mut context, &test_rewards, &mining).await, (0, 0));
+
+    // the modifier of the lockup has been taken back as well
+    let mut mining_account = get_account(&mut context, &mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    let stake_expiration_date = wrapped_mining
+        .position(deposit_id)
+        .unwrap()
+        .stake_expiration_date;
+    assert_eq!(
+        wrapped_mining
+            .weighted_stake_diffs
+            .get(&stake_expiration_date),
+        Some(&0)
+    );
+}
+
+#[tokio::test]
+async fn withdrawal_waits_for_the_unbonding_period() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id,
+            100,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::WithdrawalIsNotRequested);
+
+    test_rewards
+        .request_withdraw(&mut context, &mining, &user, deposit_id, 100)
+        .await
+        .unwrap();
+    // AVOID CACHING FOR IDENTICAL OPERATIONS
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id,
+            100,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .assert_on_chain_err(MplxRewardsError::UnbondingIsNotOver);
+
+    advance_clock_by_ts(&mut context, UNBONDING_PERIOD.try_into().unwrap()).await;
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id,
+            30,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    // 70 x0.5
+    assert_eq!(shares(&mut context, &test_rewards, &mining).await, (35, 35));
+
+    test_rewards
+        .withdraw_mining(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id,
+            70,
+            &user.pubkey(),
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(shares(&mut context, &test_rewards, &mining).await, (0, 0));
+
+    let mut mining_account = get_account(&mut context, &mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let mut wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(
+        wrapped_mining.position(deposit_id).unwrap_err(),
+        MplxRewardsError::DepositIsNotFound
+    );
+}
+
+#[tokio::test]
+async fn unbonding_multiplier_cannot_exceed_flex() {
+    let (mut context, test_rewards, _, _) = setup().await;
+
+    test_rewards
+        .set_unbonding_config(&mut context, UNBONDING_PERIOD, 10_001)
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidUnbondingMultiplier);
+}
+
+#[tokio::test]
+async fn withdrawal_is_requested_by_the_owner_alone() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            100,
+            LockupPeriod::Flex,
+            &user.pubkey(),
+            &mining,
+            &user.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let stranger = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[mplx_rewards::instruction::request_withdraw(
+            &mplx_rewards::id(),
+            &test_rewards.reward_pool.pubkey(),
+            &mining,
+            &stranger.pubkey(),
+            deposit_id,
+            100,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &stranger],
+        context.last_blockhash,
+    );
+    assert!(context.banks_client.process_transaction(tx).await.is_err());
+
+    // neither the deposit authority nor the Staking program takes part in the request
+    let tx = Transaction::new_signed_with_payer(
+        &[mplx_rewards::instruction::request_withdraw(
+            &mplx_rewards::id(),
+            &test_rewards.reward_pool.pubkey(),
+            &mining,
+            &user.pubkey(),
+            deposit_id,
+            100,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &user],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    assert_eq!(shares(&mut context, &test_rewards, &mining).await, (50, 50));
+}
@@ -1,6 +1,16 @@
-use std::borrow::{Borrow, BorrowMut};
+use std::{
+    borrow::BorrowMut,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
-use mplx_rewards::{error::MplxRewardsError, state::WrappedRewardPool, utils::LockupPeriod};
+use borsh::BorshDeserialize;
+use mpl_token_metadata::accounts::Metadata;
+use mplx_rewards::{
+    error::MplxRewardsError,
+    state::{SolvencyReport, WrappedMining, WrappedRewardPool},
+    utils::{find_penalty_history_program_address, BoostProof, LockupPeriod},
+};
+use sokoban::NodeAllocatorMap;
 use solana_program::{instruction::InstructionError, pubkey::Pubkey};
 use solana_program_test::{BanksClientError, ProgramTestContext};
 use solana_sdk::{
@@ -10,7 +20,13 @@ use solana_sdk::{
     system_instruction::{self, create_account},
     transaction::{Transaction, TransactionError},
 };
-use spl_token::state::Account as SplTokenAccount;
+use spl_token_2022::{
+    extension::{
+        transfer_fee::instruction::initialize_transfer_fee_config, BaseStateWithExtensions,
+        ExtensionType, StateWithExtensions,
+    },
+    state::{Account as SplTokenAccount, Mint},
+};
 
 pub type BanksClientResult<T> = Result<T, BanksClientError>;
 
@@ -24,10 +40,17 @@ pub struct TestRewards {
     pub fill_authority: Keypair,
     pub reward_pool: Keypair,
     pub vault_pubkey: Pubkey,
+    pub token_program_id: Pubkey,
+    /// Ids are unique within the pool, so that deposits can be moved between minings
+    next_deposit_id: AtomicU64,
 }
 
 impl TestRewards {
     pub fn new(token_mint_pubkey: Pubkey) -> Self {
+        Self::new_with_token_program(token_mint_pubkey, spl_token::id())
+    }
+
+    pub fn new_with_token_program(token_mint_pubkey: Pubkey, token_program_id: Pubkey) -> Self {
         let deposit_authority = Keypair::new();
         let fill_authority = Keypair::new();
         let distribution_authority = Keypair::new();
@@ -49,9 +72,15 @@ impl TestRewards {
             reward_pool,
             vault_pubkey,
             distribution_authority,
+            token_program_id,
+            next_deposit_id: AtomicU64::new(0),
         }
     }
 
+    pub fn next_deposit_id(&self) -> u64 {
+        self.next_deposit_id.fetch_add(1, Ordering::Relaxed)
+    }
+
     pub async fn initialize_pool(&self, context: &mut ProgramTestContext) -> BanksClientResult<()> {
         let rent = context.banks_client.get_rent().await.unwrap();
         let lamports = rent.minimum_balance(WrappedRewardPool::LEN);
@@ -77,6 +106,7 @@ impl TestRewards {
                     &self.deposit_authority.pubkey(),
                     &self.fill_authority.pubkey(),
                     &self.distribution_authority.pubkey(),
+                    &self.token_program_id,
                 ),
             ],
             Some(&context.payer.pubkey()),
@@ -128,6 +158,7 @@ impl TestRewards {
         new_delegate_mining: &Pubkey,
         old_delegate_mining: &Pubkey,
         new_delegate: &Pubkey,
+        deposit_id: u64,
         amount: u64,
     ) -> BanksClientResult<()> {
         let tx = Transaction::new_signed_with_payer(
@@ -140,7 +171,10 @@ impl TestRewards {
                 old_delegate_mining,
                 new_delegate_mining,
                 new_delegate,
+                deposit_id,
                 amount,
+                &[],
+                &[],
             )],
             Some(&context.payer.pubkey()),
             &[&self.deposit_authority, mining_owner, &context.payer],
@@ -150,6 +184,7 @@ impl TestRewards {
         context.banks_client.process_transaction(tx).await
     }
 
+    /// Deposits under the next deposit id. Returns the id of the deposit.
     #[allow(clippy::too_many_arguments)]
     pub async fn deposit_mining(
         &self,
@@ -160,6 +195,33 @@ impl TestRewards {
         owner: &Pubkey,
         delegate_mining: &Pubkey,
         delegate_wallet_addr: &Pubkey,
+    ) -> BanksClientResult<u64> {
+        let deposit_id = self.next_deposit_id();
+        self.deposit_mining_with_id(
+            context,
+            deposit_id,
+            mining_account,
+            amount,
+            lockup_period,
+            owner,
+            delegate_mining,
+            delegate_wallet_addr,
+        )
+        .await
+        .map(|_| deposit_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn deposit_mining_with_id(
+        &self,
+        context: &mut ProgramTestContext,
+        deposit_id: u64,
+        mining_account: &Pubkey,
+        amount: u64,
+        lockup_period: LockupPeriod,
+        owner: &Pubkey,
+        delegate_mining: &Pubkey,
+        delegate_wallet_addr: &Pubkey,
     ) -> BanksClientResult<()> {
         let tx = Transaction::new_signed_with_payer(
             &[mplx_rewards::instruction::deposit_mining(
@@ -168,10 +230,12 @@ impl TestRewards {
                 mining_account,
                 &self.deposit_authority.pubkey(),
                 delegate_mining,
+                deposit_id,
                 amount,
                 lockup_period,
                 owner,
                 delegate_wallet_addr,
+                &[],
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, &self.deposit_authority],
@@ -181,25 +245,55 @@ impl TestRewards {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn initialize_penalty_history(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+    ) -> Pubkey {
+        let (penalty_history, _) =
+            find_penalty_history_program_address(&mplx_rewards::id(), mining_account);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::initialize_penalty_history(
+                &mplx_rewards::id(),
+                mining_account,
+                &penalty_history,
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        penalty_history
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn slash(
         &self,
         context: &mut ProgramTestContext,
         mining_account: &Pubkey,
         mining_owner: &Pubkey,
+        deposit_id: u64,
         slash_amount_in_native: u64,
-        slash_amount_multiplied_by_period: u64,
-        stake_expiration_date: Option<u64>,
+        reason_code: u32,
     ) -> BanksClientResult<()> {
+        let (penalty_history, _) =
+            find_penalty_history_program_address(&mplx_rewards::id(), mining_account);
+
         let tx = Transaction::new_signed_with_payer(
             &[mplx_rewards::instruction::slash(
                 &mplx_rewards::id(),
                 &self.deposit_authority.pubkey(),
                 &self.reward_pool.pubkey(),
                 mining_account,
+                &penalty_history,
                 mining_owner,
+                deposit_id,
                 slash_amount_in_native,
-                slash_amount_multiplied_by_period,
-                stake_expiration_date,
+                reason_code,
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, &self.deposit_authority],
@@ -209,11 +303,13 @@ impl TestRewards {
         context.banks_client.process_transaction(tx).await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn withdraw_mining(
         &self,
         context: &mut ProgramTestContext,
         mining_account: &Pubkey,
         delegate_mining: &Pubkey,
+        deposit_id: u64,
         amount: u64,
         owner: &Pubkey,
         delegate_wallet_addr: &Pubkey,
@@ -225,9 +321,11 @@ impl TestRewards {
                 mining_account,
                 &self.deposit_authority.pubkey(),
                 delegate_mining,
+                deposit_id,
                 amount,
                 owner,
                 delegate_wallet_addr,
+                &[],
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, &self.deposit_authority],
@@ -255,6 +353,7 @@ impl TestRewards {
                 from,
                 amount,
                 distribution_ends_at,
+                &self.token_program_id,
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, fill_authority],
@@ -281,6 +380,7 @@ impl TestRewards {
                 &user.pubkey(),
                 &self.deposit_authority.pubkey(),
                 user_reward_token,
+                &self.token_program_id,
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, user, &self.deposit_authority],
@@ -300,6 +400,7 @@ impl TestRewards {
                 &mplx_rewards::id(),
                 &self.reward_pool.pubkey(),
                 &authority.pubkey(),
+                &self.vault_pubkey,
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, authority],
@@ -315,10 +416,8 @@ impl TestRewards {
         context: &mut ProgramTestContext,
         mining_account: &Pubkey,
         delegate_mining: &Pubkey,
-        old_lockup_period: LockupPeriod,
+        deposit_id: u64,
         new_lockup_period: LockupPeriod,
-        deposit_start_ts: u64,
-        base_amount: u64,
         additional_amount: u64,
         mining_owner: &Pubkey,
         delegate_wallet_addr: &Pubkey,
@@ -330,13 +429,12 @@ impl TestRewards {
                 mining_account,
                 &self.deposit_authority.pubkey(),
                 delegate_mining,
-                old_lockup_period,
+                deposit_id,
                 new_lockup_period,
-                deposit_start_ts,
-                base_amount,
                 additional_amount,
                 mining_owner,
                 delegate_wallet_addr,
+                &[],
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, &self.deposit_authority],
@@ -370,22 +468,27 @@ impl TestRewards {
         context.banks_client.process_transaction(tx).await
     }
 
-    #[allow(dead_code)]
     pub async fn decrease_rewards(
         &self,
         context: &mut ProgramTestContext,
         mining_account: &Pubkey,
         mining_owner: &Pubkey,
         decreased_weighted_stake_number: u64,
+        reason_code: u32,
     ) -> BanksClientResult<()> {
+        let (penalty_history, _) =
+            find_penalty_history_program_address(&mplx_rewards::id(), mining_account);
+
         let tx = Transaction::new_signed_with_payer(
             &[mplx_rewards::instruction::decrease_rewards(
                 &mplx_rewards::id(),
                 &self.deposit_authority.pubkey(),
                 &self.reward_pool.pubkey(),
                 mining_account,
+                &penalty_history,
                 mining_owner,
                 decreased_weighted_stake_number,
+                reason_code,
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, &self.deposit_authority],
@@ -394,65 +497,721 @@ impl TestRewards {
 
         context.banks_client.process_transaction(tx).await
     }
-}
 
-pub async fn create_token_account(
-    context: &mut ProgramTestContext,
-    account: &Keypair,
-    mint: &Pubkey,
-    manager: &Pubkey,
-    lamports: u64,
-) -> BanksClientResult<()> {
-    let rent = context.banks_client.get_rent().await.unwrap();
+    pub async fn restore_penalty(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+        mining_owner: &Pubkey,
+        penalty_id: u64,
+        credit_lost_rewards: bool,
+    ) -> BanksClientResult<()> {
+        let (penalty_history, _) =
+            find_penalty_history_program_address(&mplx_rewards::id(), mining_account);
 
-    let tx = Transaction::new_signed_with_payer(
-        &[
-            system_instruction::create_account(
-                &context.payer.pubkey(),
-                &account.pubkey(),
-                rent.minimum_balance(spl_token::state::Account::LEN) + lamports,
-                spl_token::state::Account::LEN as u64,
-                &spl_token::id(),
-            ),
-            spl_token::instruction::initialize_account(
-                &spl_token::id(),
-                &account.pubkey(),
-                mint,
-                manager,
-            )
-            .unwrap(),
-        ],
-        Some(&context.payer.pubkey()),
-        &[&context.payer, account],
-        context.last_blockhash,
-    );
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::restore_penalty(
+                &mplx_rewards::id(),
+                &self.deposit_authority.pubkey(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                &penalty_history,
+                mining_owner,
+                penalty_id,
+                credit_lost_rewards,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
 
-    context.banks_client.process_transaction(tx).await
-}
+        context.banks_client.process_transaction(tx).await
+    }
 
-pub async fn get_account(context: &mut ProgramTestContext, pubkey: &Pubkey) -> Account {
-    context
-        .banks_client
-        .get_account(*pubkey)
-        .await
-        .expect("account not found")
-        .expect("account empty")
-}
+    pub async fn set_pool_limits(
+        &self,
+        context: &mut ProgramTestContext,
+        min_deposit: u64,
+        max_weighted_share_per_mining: u64,
+        max_total_share: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::set_pool_limits(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                &self.deposit_authority.pubkey(),
+                min_deposit,
+                max_weighted_share_per_mining,
+                max_total_share,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
 
-pub async fn create_mint(
-    context: &mut ProgramTestContext,
-    mint: &Keypair,
-    manager: &Pubkey,
-) -> BanksClientResult<()> {
-    let rent = context.banks_client.get_rent().await.unwrap();
+        context.banks_client.process_transaction(tx).await
+    }
 
-    let tx = Transaction::new_signed_with_payer(
-        &[
-            system_instruction::create_account(
+    pub async fn set_unbonding_config(
+        &self,
+        context: &mut ProgramTestContext,
+        unbonding_period_secs: u64,
+        unbonding_multiplier_bps: u16,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::set_unbonding_config(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                &self.deposit_authority.pubkey(),
+                unbonding_period_secs,
+                unbonding_multiplier_bps,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn request_withdraw(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+        mining_owner: &Keypair,
+        deposit_id: u64,
+        amount: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::request_withdraw(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                &mining_owner.pubkey(),
+                deposit_id,
+                amount,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, mining_owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_boost_config(
+        &self,
+        context: &mut ProgramTestContext,
+        boost_collection: &Pubkey,
+        boost_attester: &Pubkey,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::set_boost_config(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                &self.deposit_authority.pubkey(),
+                boost_collection,
+                boost_attester,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn apply_boost(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+        mining_owner: &Pubkey,
+        boost_bps: u16,
+        expires_at: u64,
+        proof: BoostProof,
+        proof_accounts: &[Pubkey],
+        proof_signers: &[&Keypair],
+    ) -> BanksClientResult<()> {
+        let mut signers = vec![&context.payer, &self.deposit_authority];
+        signers.extend_from_slice(proof_signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::apply_boost(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                &self.deposit_authority.pubkey(),
+                proof_accounts,
+                mining_owner,
+                boost_bps,
+                expires_at,
+                proof,
+            )],
+            Some(&context.payer.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_epoch_length(
+        &self,
+        context: &mut ProgramTestContext,
+        epoch_length_secs: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::set_epoch_length(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                &self.deposit_authority.pubkey(),
+                epoch_length_secs,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn close_pool(
+        &self,
+        context: &mut ProgramTestContext,
+        recipient_token_account: &Pubkey,
+        target_account: &Pubkey,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::close_pool(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                &self.token_mint_pubkey,
+                &self.vault_pubkey,
+                &self.deposit_authority.pubkey(),
+                recipient_token_account,
+                target_account,
+                &self.token_program_id,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn sunset_pool(
+        &self,
+        context: &mut ProgramTestContext,
+        grace_period_secs: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::sunset_pool(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                &self.deposit_authority.pubkey(),
+                grace_period_secs,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn migrate_pool(&self, context: &mut ProgramTestContext) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::migrate_pool(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
                 &context.payer.pubkey(),
-                &mint.pubkey(),
-                rent.minimum_balance(spl_token::state::Mint::LEN),
-                spl_token::state::Mint::LEN as u64,
+                &self.vault_pubkey,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn migrate_mining(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::migrate_mining(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn transfer_mining(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+        mining_owner: &Keypair,
+        new_owner: &Pubkey,
+        target_account: &Pubkey,
+    ) -> BanksClientResult<Pubkey> {
+        let (new_mining_account, _) = Pubkey::find_program_address(
+            &[
+                b"mining".as_ref(),
+                new_owner.as_ref(),
+                self.reward_pool.pubkey().as_ref(),
+            ],
+            &mplx_rewards::id(),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::transfer_mining(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                &new_mining_account,
+                &mining_owner.pubkey(),
+                new_owner,
+                &self.deposit_authority.pubkey(),
+                &context.payer.pubkey(),
+                target_account,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority, mining_owner],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map(|_| new_mining_account)
+    }
+
+    /// Merges the source mining, recording its deposits under the next deposit ids.
+    /// Returns the ids the source deposits have got.
+    pub async fn merge_mining(
+        &self,
+        context: &mut ProgramTestContext,
+        source_mining_account: &Pubkey,
+        mining_account: &Pubkey,
+        source_mining_owner: &Keypair,
+        target_account: &Pubkey,
+    ) -> BanksClientResult<Vec<u64>> {
+        let mut source_mining = get_account(context, source_mining_account).await;
+        let positions_count = WrappedMining::from_bytes_mut(&mut source_mining.data)
+            .map(|mining| mining.positions.len())
+            .unwrap_or_default();
+        let deposit_ids: Vec<_> = (0..positions_count)
+            .map(|_| self.next_deposit_id())
+            .collect();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::merge_mining(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                source_mining_account,
+                mining_account,
+                &source_mining_owner.pubkey(),
+                &self.deposit_authority.pubkey(),
+                target_account,
+                deposit_ids.clone(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority, source_mining_owner],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map(|_| deposit_ids)
+    }
+
+    /// Moves the part of the deposit, which gets the next deposit id in the destination mining.
+    /// Returns the id of the moved part.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn split_mining(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+        destination_mining_account: &Pubkey,
+        mining_owner: &Keypair,
+        deposit_id: u64,
+        amount: u64,
+    ) -> BanksClientResult<u64> {
+        let destination_deposit_id = self.next_deposit_id();
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::split_mining(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                destination_mining_account,
+                &mining_owner.pubkey(),
+                &self.deposit_authority.pubkey(),
+                deposit_id,
+                destination_deposit_id,
+                amount,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority, mining_owner],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map(|_| destination_deposit_id)
+    }
+
+    pub async fn register_delegate(
+        &self,
+        context: &mut ProgramTestContext,
+        mining: &Pubkey,
+        mining_owner: &Keypair,
+        max_stake_from_others: u64,
+        allow_list: Vec<Pubkey>,
+        deny_list: Vec<Pubkey>,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::register_delegate(
+                &mplx_rewards::id(),
+                mining,
+                &mining_owner.pubkey(),
+                &context.payer.pubkey(),
+                max_stake_from_others,
+                allow_list,
+                deny_list,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, mining_owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn update_delegate_registry(
+        &self,
+        context: &mut ProgramTestContext,
+        mining: &Pubkey,
+        mining_owner: &Keypair,
+        max_stake_from_others: u64,
+        allow_list: Vec<Pubkey>,
+        deny_list: Vec<Pubkey>,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::update_delegate_registry(
+                &mplx_rewards::id(),
+                mining,
+                &mining_owner.pubkey(),
+                max_stake_from_others,
+                allow_list,
+                deny_list,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, mining_owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn unregister_delegate(
+        &self,
+        context: &mut ProgramTestContext,
+        mining: &Pubkey,
+        mining_owner: &Keypair,
+        target_account: &Pubkey,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::unregister_delegate(
+                &mplx_rewards::id(),
+                mining,
+                &mining_owner.pubkey(),
+                target_account,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, mining_owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn deposit_mining_split(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+        amount: u64,
+        lockup_period: LockupPeriod,
+        owner: &Pubkey,
+        split_delegates: &[(Pubkey, u16)],
+    ) -> BanksClientResult<u64> {
+        let deposit_id = self.next_deposit_id();
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::deposit_mining(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                &self.deposit_authority.pubkey(),
+                mining_account,
+                deposit_id,
+                amount,
+                lockup_period,
+                owner,
+                owner,
+                split_delegates,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
+
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map(|_| deposit_id)
+    }
+
+    pub async fn withdraw_mining_split(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+        deposit_id: u64,
+        amount: u64,
+        owner: &Pubkey,
+        split_delegates: &[(Pubkey, u16)],
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::withdraw_mining(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                &self.deposit_authority.pubkey(),
+                mining_account,
+                deposit_id,
+                amount,
+                owner,
+                owner,
+                split_delegates,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn change_delegate_split(
+        &self,
+        context: &mut ProgramTestContext,
+        mining: &Pubkey,
+        mining_owner: &Keypair,
+        old_split_delegates: &[(Pubkey, u16)],
+        new_split_delegates: &[(Pubkey, u16)],
+        deposit_id: u64,
+        amount: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::change_delegate(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining,
+                &self.deposit_authority.pubkey(),
+                &mining_owner.pubkey(),
+                mining,
+                mining,
+                &mining_owner.pubkey(),
+                deposit_id,
+                amount,
+                old_split_delegates,
+                new_split_delegates,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&self.deposit_authority, mining_owner, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_deposit(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+        mining_owner: &Pubkey,
+        deposit_id: u64,
+        amount: u64,
+        lockup_period: LockupPeriod,
+        deposit_start_ts: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::record_deposit(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                mining_account,
+                &self.deposit_authority.pubkey(),
+                mining_owner,
+                deposit_id,
+                amount,
+                lockup_period,
+                deposit_start_ts,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.deposit_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn report_solvency(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> BanksClientResult<SolvencyReport> {
+        let tx = Transaction::new_signed_with_payer(
+            &[mplx_rewards::instruction::report_solvency(
+                &mplx_rewards::id(),
+                &self.reward_pool.pubkey(),
+                &self.vault_pubkey,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        let simulation = context.banks_client.simulate_transaction(tx).await?;
+        let return_data = simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .expect("no return data");
+
+        Ok(SolvencyReport::try_from_slice(&return_data.data).unwrap())
+    }
+}
+
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    account: &Keypair,
+    mint: &Pubkey,
+    manager: &Pubkey,
+    lamports: u64,
+) -> BanksClientResult<()> {
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mint_account = get_account(context, mint).await;
+    let account_len = if mint_account.owner == spl_token::id() {
+        SplTokenAccount::LEN
+    } else {
+        let mint_state = StateWithExtensions::<Mint>::unpack(&mint_account.data).unwrap();
+        let account_extensions = ExtensionType::get_required_init_account_extensions(
+            &mint_state.get_extension_types().unwrap(),
+        );
+        ExtensionType::try_calculate_account_len::<SplTokenAccount>(&account_extensions).unwrap()
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(account_len) + lamports,
+                account_len as u64,
+                &mint_account.owner,
+            ),
+            spl_token_2022::instruction::initialize_account(
+                &mint_account.owner,
+                &account.pubkey(),
+                mint,
+                manager,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, account],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Creates metadata account of the NFT which belongs to the given collection
+pub fn create_nft_metadata(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    collection: &Pubkey,
+    verified: bool,
+) -> Pubkey {
+    let (metadata, _) = Metadata::find_pda(mint);
+
+    // Borsh layout of the MetadataV1 account
+    let mut data = vec![4]; // key
+    data.extend_from_slice(&Pubkey::new_unique().to_bytes()); // update authority
+    data.extend_from_slice(&mint.to_bytes());
+    for field in ["NFT", "NFT", "https://example.com"] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field.as_bytes());
+    }
+    data.extend_from_slice(&0u16.to_le_bytes()); // seller fee basis points
+    data.extend_from_slice(&[0, 0, 1, 0, 0]); // creators, primary sale, mutable, nonce, standard
+    data.push(1); // collection
+    data.push(verified.into());
+    data.extend_from_slice(&collection.to_bytes());
+    data.extend_from_slice(&[0, 0, 0]); // uses, collection details, programmable config
+
+    context.set_account(
+        &metadata,
+        &Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: mpl_token_metadata::ID,
+            ..Default::default()
+        }
+        .into(),
+    );
+
+    metadata
+}
+
+pub async fn get_account(context: &mut ProgramTestContext, pubkey: &Pubkey) -> Account {
+    context
+        .banks_client
+        .get_account(*pubkey)
+        .await
+        .expect("account not found")
+        .expect("account empty")
+}
+
+pub async fn create_mint(
+    context: &mut ProgramTestContext,
+    mint: &Keypair,
+    manager: &Pubkey,
+) -> BanksClientResult<()> {
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
                 &spl_token::id(),
             ),
             spl_token::instruction::initialize_mint(
@@ -472,15 +1231,107 @@ pub async fn create_mint(
     context.banks_client.process_transaction(tx).await
 }
 
+/// Creates Token-2022 mint which charges the given transfer fee
+pub async fn create_mint_with_transfer_fee(
+    context: &mut ProgramTestContext,
+    mint: &Keypair,
+    manager: &Pubkey,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> BanksClientResult<()> {
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mint_len =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+            .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(mint_len),
+                mint_len as u64,
+                &spl_token_2022::id(),
+            ),
+            initialize_transfer_fee_config(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                Some(manager),
+                Some(manager),
+                transfer_fee_basis_points,
+                maximum_fee,
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                manager,
+                None,
+                TOKEN_DECIMALS,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, mint],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Creates Token-2022 mint whose tokens might be moved by the permanent delegate
+pub async fn create_mint_with_permanent_delegate(
+    context: &mut ProgramTestContext,
+    mint: &Keypair,
+    manager: &Pubkey,
+) -> BanksClientResult<()> {
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mint_len =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::PermanentDelegate])
+            .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(mint_len),
+                mint_len as u64,
+                &spl_token_2022::id(),
+            ),
+            spl_token_2022::instruction::initialize_permanent_delegate(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                manager,
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                manager,
+                None,
+                TOKEN_DECIMALS,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, mint],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
 pub async fn mint_tokens(
     context: &mut ProgramTestContext,
     mint: &Pubkey,
     account: &Pubkey,
     amount: u64,
 ) -> BanksClientResult<()> {
+    let token_program_id = get_account(context, mint).await.owner;
     let tx = Transaction::new_signed_with_payer(
-        &[spl_token::instruction::mint_to(
-            &spl_token::id(),
+        &[spl_token_2022::instruction::mint_to(
+            &token_program_id,
             mint,
             account,
             &context.payer.pubkey(),
@@ -536,8 +1387,9 @@ pub async fn create_end_user(
 
 pub async fn assert_tokens(context: &mut ProgramTestContext, reward_account: &Pubkey, amount: u64) {
     let user_reward_account: Account = get_account(context, reward_account).await;
-    let user_reward = SplTokenAccount::unpack(user_reward_account.data.borrow()).unwrap();
-    assert_eq!(user_reward.amount, amount);
+    let user_reward =
+        StateWithExtensions::<SplTokenAccount>::unpack(&user_reward_account.data).unwrap();
+    assert_eq!(user_reward.base.amount, amount);
 }
 
 pub async fn claim_and_assert(
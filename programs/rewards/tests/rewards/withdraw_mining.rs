@@ -1,8 +1,10 @@
-use crate::utils::*;
+use crate::utils::{assert_custom_on_chain_error::AssertCustomOnChainErr, *};
 use mplx_rewards::{
+    error::MplxRewardsError,
     state::{WrappedMining, WrappedRewardPool},
     utils::LockupPeriod,
 };
+use sokoban::NodeAllocatorMap;
 use solana_program::pubkey::Pubkey;
 use solana_program_test::*;
 use solana_sdk::{clock::SECONDS_PER_DAY, signature::Keypair, signer::Signer};
@@ -31,7 +33,7 @@ async fn success() {
     let (mut context, test_rewards, user, mining) = setup().await;
 
     let lockup_period = LockupPeriod::ThreeMonths;
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
@@ -45,7 +47,7 @@ async fn success() {
         .unwrap();
 
     test_rewards
-        .withdraw_mining(&mut context, &mining, &mining, 30, &user, &user)
+        .withdraw_mining(&mut context, &mining, &mining, deposit_id, 30, &user, &user)
         .await
         .unwrap();
 
@@ -55,12 +57,22 @@ async fn success() {
     let wrapped_reward_pool = WrappedRewardPool::from_bytes_mut(reward_pool_data).unwrap();
     let reward_pool = wrapped_reward_pool.pool;
 
-    assert_eq!(reward_pool.total_share, 170);
+    // the lockup hasn't expired, so the tokens are withdrawn with their multiplier
+    assert_eq!(reward_pool.total_share, 140);
 
     let mut mining_account = get_account(&mut context, &mining).await;
     let mining_data = &mut mining_account.data.borrow_mut();
     let mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
-    assert_eq!(mining.mining.share, 170);
+    assert_eq!(mining.mining.share, 140);
+    let position = *mining.positions.get(&deposit_id).unwrap();
+    assert_eq!(position.amount, 70);
+    // and the rest of the deposit is the only stake which expires
+    assert_eq!(
+        mining
+            .weighted_stake_diffs
+            .get(&position.stake_expiration_date),
+        Some(&70)
+    );
 }
 
 #[tokio::test]
@@ -68,7 +80,7 @@ async fn success_with_5kkk_after_expiring() {
     let (mut context, test_rewards, user, mining) = setup().await;
 
     let lockup_period = LockupPeriod::ThreeMonths;
-    test_rewards
+    let deposit_id = test_rewards
         .deposit_mining(
             &mut context,
             &mining,
@@ -84,7 +96,15 @@ async fn success_with_5kkk_after_expiring() {
     advance_clock_by_ts(&mut context, (100 * SECONDS_PER_DAY).try_into().unwrap()).await;
 
     test_rewards
-        .withdraw_mining(&mut context, &mining, &mining, 5000000000, &user, &user)
+        .withdraw_mining(
+            &mut context,
+            &mining,
+            &mining,
+            deposit_id,
+            5000000000,
+            &user,
+            &user,
+        )
         .await
         .unwrap();
 
@@ -101,3 +121,50 @@ async fn success_with_5kkk_after_expiring() {
     let wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
     assert_eq!(wrapped_mining.mining.share, 0);
 }
+
+#[tokio::test]
+async fn retried_withdrawal_is_rejected() {
+    let (mut context, test_rewards, user, mining) = setup().await;
+
+    let deposit_id = test_rewards
+        .deposit_mining(
+            &mut context,
+            &mining,
+            100,
+            LockupPeriod::Flex,
+            &user,
+            &mining,
+            &user,
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .withdraw_mining(&mut context, &mining, &mining, deposit_id, 60, &user, &user)
+        .await
+        .unwrap();
+
+    // only 40 tokens are left in the deposit
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+    test_rewards
+        .withdraw_mining(&mut context, &mining, &mining, deposit_id, 60, &user, &user)
+        .await
+        .assert_on_chain_err(MplxRewardsError::InvalidDepositAmount);
+
+    test_rewards
+        .withdraw_mining(&mut context, &mining, &mining, deposit_id, 40, &user, &user)
+        .await
+        .unwrap();
+    // the position is gone along with the deposit
+    let initial_slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(initial_slot + 1).unwrap();
+    test_rewards
+        .withdraw_mining(&mut context, &mining, &mining, deposit_id, 40, &user, &user)
+        .await
+        .assert_on_chain_err(MplxRewardsError::DepositIsNotFound);
+
+    let mut mining_account = get_account(&mut context, &mining).await;
+    let mining_data = &mut mining_account.data.borrow_mut();
+    let wrapped_mining = WrappedMining::from_bytes_mut(mining_data).unwrap();
+    assert_eq!(wrapped_mining.mining.share, 0);
+}